@@ -0,0 +1,29 @@
+use crate::node;
+use crate::pass;
+
+// The kind of a fixed amount of values of the same kind, stored
+// next to each other (ex. `[i32; 4]`).
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArrayKind {
+  pub element_kind: Box<node::AnyKindNode>,
+  pub length: u64,
+}
+
+impl std::fmt::Display for ArrayKind {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "[{}; {}]", self.element_kind, self.length)
+  }
+}
+
+impl node::Node for ArrayKind {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_array_kind(self)?;
+
+    Ok(())
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    vec![self.element_kind.as_node()]
+  }
+}
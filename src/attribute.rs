@@ -0,0 +1,40 @@
+use crate::{node, pass, span};
+
+// The attributes understood by the compiler.
+pub const NO_MANGLE: &str = "no_mangle";
+pub const EXPORT_NAME: &str = "export_name";
+
+// Metadata attached to the function following it, either as a
+// bare name (ex. `#[no_mangle]`) or as a name with a string value
+// (ex. `#[export_name = "foo"]`).
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Attribute {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  pub name: String,
+  pub value: Option<String>,
+}
+
+impl std::fmt::Display for Attribute {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match &self.value {
+      Some(value) => write!(f, "#[{} = \"{}\"]", self.name, value),
+      None => write!(f, "#[{}]", self.name),
+    }
+  }
+}
+
+impl node::Node for Attribute {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_children(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    vec![]
+  }
+}
@@ -1,31 +1,348 @@
-use crate::{node, pass};
+use crate::{node, pass, span};
 
-#[derive(Hash, Eq, PartialEq, Debug)]
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AnyStatementNode {
   ReturnStmt(ReturnStmt),
+  IfStmt(IfStmt),
+  WhileStmt(WhileStmt),
+  LoopStmt(LoopStmt),
+  ForStmt(ForStmt),
+  BreakStmt(BreakStmt),
+  ContinueStmt(ContinueStmt),
+  ExprStmt(ExprStmt),
+  LetStmt(LetStmt),
+  AssignStmt(AssignStmt),
 }
 
-#[derive(Hash, Eq, PartialEq, Debug)]
+impl AnyStatementNode {
+  pub fn as_node(&self) -> &dyn node::Node {
+    match self {
+      AnyStatementNode::ReturnStmt(return_stmt) => return_stmt,
+      AnyStatementNode::IfStmt(if_stmt) => if_stmt,
+      AnyStatementNode::WhileStmt(while_stmt) => while_stmt,
+      AnyStatementNode::LoopStmt(loop_stmt) => loop_stmt,
+      AnyStatementNode::ForStmt(for_stmt) => for_stmt,
+      AnyStatementNode::BreakStmt(break_stmt) => break_stmt,
+      AnyStatementNode::ContinueStmt(continue_stmt) => continue_stmt,
+      AnyStatementNode::ExprStmt(expr_stmt) => expr_stmt,
+      AnyStatementNode::LetStmt(let_stmt) => let_stmt,
+      AnyStatementNode::AssignStmt(assign_stmt) => assign_stmt,
+    }
+  }
+}
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Block {
+  pub id: node::NodeId,
+  pub span: span::Span,
   pub statements: Vec<AnyStatementNode>,
 }
 
 impl node::Node for Block {
-  fn accept(&mut self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
     pass.visit_block(self)?;
     Ok(())
   }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    self
+      .statements
+      .iter()
+      .map(|statement| statement.as_node())
+      .collect()
+  }
 }
 
-#[derive(Hash, Eq, PartialEq, Debug)]
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReturnStmt {
-  pub value: Option<node::AnyLiteralNode>,
+  pub id: node::NodeId,
+  pub span: span::Span,
+  pub value: Option<node::AnyExprNode>,
 }
 
 impl node::Node for ReturnStmt {
-  fn accept(&mut self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
     pass.visit_return_stmt(self)?;
 
     Ok(())
   }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    self.value.iter().map(|value| value.as_node()).collect()
+  }
+}
+
+// A condition paired with the block that is executed when
+// the condition holds.
+pub type IfBranch = (node::AnyExprNode, Block);
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IfStmt {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  pub condition: node::AnyExprNode,
+  pub then_block: Block,
+  // The `else if` branches, in the order they appear in the
+  // source. They are only evaluated if every preceding
+  // condition failed.
+  pub else_if_branches: Vec<IfBranch>,
+  pub else_block: Option<Block>,
+}
+
+impl node::Node for IfStmt {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_if_stmt(self)?;
+
+    Ok(())
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    let mut children: Vec<&dyn node::Node> = vec![self.condition.as_node(), &self.then_block];
+
+    for (condition, block) in &self.else_if_branches {
+      children.push(condition.as_node());
+      children.push(block);
+    }
+
+    if let Some(else_block) = &self.else_block {
+      children.push(else_block);
+    }
+
+    children
+  }
+}
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WhileStmt {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  pub label: Option<String>,
+  pub condition: node::AnyExprNode,
+  pub body: Block,
+}
+
+impl node::Node for WhileStmt {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_while_stmt(self)?;
+
+    Ok(())
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    vec![self.condition.as_node(), &self.body]
+  }
+}
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoopStmt {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  pub label: Option<String>,
+  pub body: Block,
+}
+
+impl node::Node for LoopStmt {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_loop_stmt(self)?;
+
+    Ok(())
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    vec![&self.body]
+  }
+}
+
+// A loop over a range of integers, such as `for i in 0..n { }`
+// or the inclusive `for i in 0..=n { }`. The iterator is an
+// immutable binding, which is only visible inside of the body.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForStmt {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  pub label: Option<String>,
+  pub iterator_name: String,
+  pub range_start: node::AnyExprNode,
+  pub range_end: node::AnyExprNode,
+  // Whether the range includes its end value (`..=`).
+  pub is_inclusive: bool,
+  pub body: Block,
+}
+
+impl node::Node for ForStmt {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_for_stmt(self)?;
+
+    Ok(())
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    vec![
+      self.range_start.as_node(),
+      self.range_end.as_node(),
+      &self.body,
+    ]
+  }
+}
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BreakStmt {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  // The label of the loop to break out of. If [`None`], the
+  // innermost loop is targeted.
+  pub label: Option<String>,
+}
+
+impl node::Node for BreakStmt {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_break_stmt(self)?;
+
+    Ok(())
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+}
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContinueStmt {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  // The label of the loop to continue. If [`None`], the
+  // innermost loop is targeted.
+  pub label: Option<String>,
+}
+
+impl node::Node for ContinueStmt {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_continue_stmt(self)?;
+
+    Ok(())
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+}
+
+// An expression evaluated only for its side effects, such as
+// a function call. Its resulting value (if any) is discarded.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExprStmt {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  pub expr: node::AnyExprNode,
+}
+
+impl node::Node for ExprStmt {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_expr_stmt(self)?;
+
+    Ok(())
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    vec![self.expr.as_node()]
+  }
+}
+
+// Binds a value to a name, which is visible until the end of the
+// enclosing block (ex. `let a: i32 = 1;`). The kind of the binding
+// is that of its value, unless it is given explicitly.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LetStmt {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  pub name: String,
+  pub is_mutable: bool,
+  pub kind_group: Option<node::KindGroup>,
+  pub value: node::AnyExprNode,
+}
+
+impl node::Node for LetStmt {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_let_stmt(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    let mut children: Vec<&dyn node::Node> = vec![];
+
+    if let Some(kind_group) = &self.kind_group {
+      children.push(kind_group);
+    }
+
+    children.push(self.value.as_node());
+
+    children
+  }
+}
+
+// Stores a value into a place, such as a variable or one of its
+// fields (ex. `a.b = 1;`).
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AssignStmt {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  pub target: node::AnyExprNode,
+  pub value: node::AnyExprNode,
+}
+
+impl node::Node for AssignStmt {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_assign_stmt(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    vec![self.target.as_node(), self.value.as_node()]
+  }
 }
@@ -0,0 +1,16 @@
+use crate::node;
+use crate::pass;
+
+#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoolKind {
+  //
+}
+
+impl node::Node for BoolKind {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_bool_kind(self)?;
+
+    Ok(())
+  }
+}
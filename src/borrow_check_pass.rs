@@ -0,0 +1,845 @@
+use crate::{block, diagnostic, function, namespace, node, pass, pass::Pass, pattern, span};
+
+// Ensures that references never outlive the variables they borrow,
+// and that a variable is not borrowed as mutable while another
+// borrow of it is still in use.
+//
+// Borrows are tracked as loans, which are held by the variables the
+// resulting references are stored in. A loan is in use for as long
+// as the variable holding it is mentioned by a later statement of
+// the block declaring that variable. Expects names to have been
+// resolved, and kinds to have been checked.
+pub struct BorrowCheckPass<'a> {
+  diagnostics: Vec<diagnostic::Diagnostic>,
+  // The kinds of all expressions, as determined by type checking.
+  expr_kinds: &'a node::NodeMap<node::AnyKindNode>,
+  // The kinds of the fields of all structs and enums (for enums,
+  // of all of their variants), by their path relative to the
+  // outermost namespace.
+  field_kinds: std::collections::HashMap<String, Vec<node::AnyKindNode>>,
+  // The number of namespaces enclosing the node being visited.
+  namespace_depth: usize,
+  // The scopes enclosing the statement being visited, with the
+  // innermost scope last.
+  scopes: Vec<Scope>,
+  // The loans taken by the statements being visited, which are in
+  // use until those statements end.
+  statement_loans: Vec<Loan>,
+  // The variables to declare within the next block's scope, such
+  // as function parameters or `for` iterators.
+  pending_variables: Vec<(String, span::Span, Vec<Loan>)>,
+}
+
+// Identifies a variable by the index of its scope, and its index
+// within that scope.
+type VariableId = (usize, usize);
+
+// A borrow of a variable, or of a temporary value if [`None`].
+#[derive(Clone)]
+struct Loan {
+  variable: Option<VariableId>,
+  is_mutable: bool,
+  span: span::Span,
+}
+
+struct Variable {
+  name: String,
+  span: span::Span,
+  // The index of the last statement of its scope mentioning the
+  // variable.
+  last_use: usize,
+  // The loans held by the variable's value.
+  loans: Vec<Loan>,
+}
+
+struct Scope {
+  // The names of the variables mentioned by each statement of the
+  // scope's block, including by nested blocks.
+  statement_mentions: Vec<std::collections::HashSet<String>>,
+  // The index of the statement being visited.
+  statement_index: usize,
+  variables: Vec<Variable>,
+}
+
+// What a borrow expression borrows.
+enum Borrowed<'b> {
+  Variable(VariableId),
+  Temporary,
+  // Whatever is referenced by the value of the expression (ex. when
+  // borrowing a field through a reference), which is reborrowed.
+  Reference(&'b node::AnyExprNode),
+}
+
+impl<'a> BorrowCheckPass<'a> {
+  pub fn new(expr_kinds: &'a node::NodeMap<node::AnyKindNode>) -> Self {
+    Self {
+      diagnostics: vec![],
+      expr_kinds,
+      field_kinds: std::collections::HashMap::new(),
+      namespace_depth: 0,
+      scopes: vec![],
+      statement_loans: vec![],
+      pending_variables: vec![],
+    }
+  }
+
+  fn collect_field_kinds(&mut self, namespace: &namespace::Namespace, prefix: &[String]) {
+    for (name, top_level_node) in namespace.symbol_table.iter() {
+      let mut path = prefix.to_vec();
+
+      path.push(name.clone());
+
+      match top_level_node {
+        namespace::TopLevelNode::Struct(struct_decl) => {
+          let kinds = struct_decl
+            .fields
+            .iter()
+            .map(|field| field.kind_group.to_value_kind())
+            .collect();
+
+          self.field_kinds.insert(path.join("::"), kinds);
+        }
+        namespace::TopLevelNode::Enum(enum_decl) => {
+          let kinds = enum_decl
+            .variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter())
+            .map(|field| field.to_value_kind())
+            .collect();
+
+          self.field_kinds.insert(path.join("::"), kinds);
+        }
+        namespace::TopLevelNode::Namespace(namespace) => self.collect_field_kinds(namespace, &path),
+        _ => {}
+      };
+    }
+  }
+
+  // Whether values of the given kind may contain references. The
+  // paths of the structs and enums being inspected are kept to stop
+  // at recursive declarations.
+  fn holds_references(&self, kind: &node::AnyKindNode, visited_paths: &mut Vec<String>) -> bool {
+    match kind {
+      node::AnyKindNode::ReferenceKind(_) => true,
+      node::AnyKindNode::ArrayKind(array_kind) => {
+        self.holds_references(&array_kind.element_kind, visited_paths)
+      }
+      node::AnyKindNode::StructKind(_) | node::AnyKindNode::EnumKind(_) => {
+        let path = kind.to_string();
+
+        if visited_paths.contains(&path) {
+          return false;
+        }
+
+        visited_paths.push(path.clone());
+
+        match self.field_kinds.get(&path) {
+          Some(field_kinds) => field_kinds
+            .iter()
+            .any(|field_kind| self.holds_references(field_kind, visited_paths)),
+          None => false,
+        }
+      }
+      _ => false,
+    }
+  }
+
+  fn get_variable(&self, id: VariableId) -> &Variable {
+    &self.scopes[id.0].variables[id.1]
+  }
+
+  fn find_variable(&self, name: &str) -> Option<VariableId> {
+    for (scope_index, scope) in self.scopes.iter().enumerate().rev() {
+      if let Some(index) = scope
+        .variables
+        .iter()
+        .rposition(|variable| variable.name == name)
+      {
+        return Some((scope_index, index));
+      }
+    }
+
+    None
+  }
+
+  // Declare a variable within the innermost scope, by the statement
+  // at the given index, or before all statements if [`None`].
+  fn declare(
+    &mut self,
+    name: String,
+    span: span::Span,
+    loans: Vec<Loan>,
+    statement_index: Option<usize>,
+  ) {
+    let scope = self.scopes.last_mut().unwrap();
+
+    let (first_index, default_index) = match statement_index {
+      Some(statement_index) => (statement_index + 1, statement_index),
+      None => (0, 0),
+    };
+
+    let last_use = (first_index..scope.statement_mentions.len())
+      .rev()
+      .find(|index| scope.statement_mentions[*index].contains(&name))
+      .unwrap_or(default_index);
+
+    scope.variables.push(Variable {
+      name,
+      span,
+      last_use,
+      loans,
+    });
+  }
+
+  // Whether the variable is mentioned by the statement being visited
+  // within its scope, or by a later one.
+  fn is_in_use(&self, id: VariableId) -> bool {
+    self.scopes[id.0].statement_index <= self.get_variable(id).last_use
+  }
+
+  // Collect the loans which are currently in use, along with the
+  // variables holding them (if any).
+  fn get_active_loans(&self) -> Vec<(Option<VariableId>, Loan)> {
+    let mut active_loans = vec![];
+
+    for (scope_index, scope) in self.scopes.iter().enumerate() {
+      for (index, variable) in scope.variables.iter().enumerate() {
+        if self.is_in_use((scope_index, index)) {
+          for loan in &variable.loans {
+            active_loans.push((Some((scope_index, index)), loan.clone()));
+          }
+        }
+      }
+    }
+
+    for loan in &self.statement_loans {
+      active_loans.push((None, loan.clone()));
+    }
+
+    active_loans
+  }
+
+  // Determine what is borrowed when borrowing the given expression.
+  fn find_borrowed<'b>(&self, expr: &'b node::AnyExprNode) -> Borrowed<'b> {
+    let base = match expr {
+      node::AnyExprNode::VariableRef(variable_ref) => {
+        return match self.find_variable(&variable_ref.name) {
+          Some(id) => Borrowed::Variable(id),
+          None => Borrowed::Temporary,
+        }
+      }
+      node::AnyExprNode::DerefExpr(deref_expr) => return Borrowed::Reference(&deref_expr.value),
+      node::AnyExprNode::FieldAccessExpr(field_access_expr) => &field_access_expr.base,
+      node::AnyExprNode::IndexExpr(index_expr) => &index_expr.base,
+      _ => return Borrowed::Temporary,
+    };
+
+    // Fields and elements are reached through references and slices
+    // without borrowing the variables holding them.
+    match self.expr_kinds.get(&base.get_id()) {
+      Some(node::AnyKindNode::ReferenceKind(_)) | Some(node::AnyKindNode::SliceKind(_)) => {
+        Borrowed::Reference(base)
+      }
+      _ => self.find_borrowed(base),
+    }
+  }
+
+  // Find the variable a place is rooted at, if any.
+  fn find_root(&self, place: &node::AnyExprNode) -> Option<VariableId> {
+    match place {
+      node::AnyExprNode::VariableRef(variable_ref) => self.find_variable(&variable_ref.name),
+      node::AnyExprNode::DerefExpr(deref_expr) => self.find_root(&deref_expr.value),
+      node::AnyExprNode::FieldAccessExpr(field_access_expr) => {
+        self.find_root(&field_access_expr.base)
+      }
+      node::AnyExprNode::IndexExpr(index_expr) => self.find_root(&index_expr.base),
+      _ => None,
+    }
+  }
+
+  // Collect the loans which the value of an expression may hold.
+  fn collect_loans(&self, expr: &node::AnyExprNode) -> Vec<Loan> {
+    let holds_references = match self.expr_kinds.get(&expr.get_id()) {
+      Some(kind) => self.holds_references(kind, &mut vec![]),
+      None => false,
+    };
+
+    if !holds_references {
+      return vec![];
+    }
+
+    match expr {
+      node::AnyExprNode::BorrowExpr(borrow_expr) => match self.find_borrowed(&borrow_expr.value) {
+        Borrowed::Variable(id) => vec![Loan {
+          variable: Some(id),
+          is_mutable: borrow_expr.is_mutable,
+          span: borrow_expr.span,
+        }],
+        Borrowed::Temporary => vec![Loan {
+          variable: None,
+          is_mutable: borrow_expr.is_mutable,
+          span: borrow_expr.span,
+        }],
+        Borrowed::Reference(reference) => self.collect_loans(reference),
+      },
+      node::AnyExprNode::VariableRef(variable_ref) => {
+        match self.find_variable(&variable_ref.name) {
+          Some(id) => self.get_variable(id).loans.clone(),
+          None => vec![],
+        }
+      }
+      node::AnyExprNode::DerefExpr(deref_expr) => self.collect_loans(&deref_expr.value),
+      node::AnyExprNode::FieldAccessExpr(field_access_expr) => {
+        self.collect_loans(&field_access_expr.base)
+      }
+      node::AnyExprNode::IndexExpr(index_expr) => self.collect_loans(&index_expr.base),
+      // The result of a call may reference anything its arguments
+      // reference.
+      node::AnyExprNode::CallExpr(call_expr) => call_expr
+        .arguments
+        .iter()
+        .flat_map(|argument| self.collect_loans(argument))
+        .collect(),
+      node::AnyExprNode::StructLiteral(struct_literal) => struct_literal
+        .fields
+        .iter()
+        .flat_map(|(_, value)| self.collect_loans(value))
+        .collect(),
+      node::AnyExprNode::VariantLiteral(variant_literal) => variant_literal
+        .arguments
+        .iter()
+        .flat_map(|argument| self.collect_loans(argument))
+        .collect(),
+      node::AnyExprNode::ArrayLiteral(array_literal) => array_literal
+        .elements
+        .iter()
+        .flat_map(|element| self.collect_loans(element))
+        .collect(),
+      node::AnyExprNode::MatchExpr(match_expr) => {
+        let mut loans = self.collect_loans(&match_expr.subject);
+
+        for arm in &match_expr.arms {
+          if let node::MatchArmBody::Expr(expr) = &arm.body {
+            loans.extend(self.collect_loans(expr));
+          }
+        }
+
+        loans
+      }
+      _ => vec![],
+    }
+  }
+
+  fn push_scope(&mut self, statement_mentions: Vec<std::collections::HashSet<String>>) {
+    self.scopes.push(Scope {
+      statement_mentions,
+      statement_index: 0,
+      variables: vec![],
+    });
+
+    for (name, span, loans) in std::mem::take(&mut self.pending_variables) {
+      self.declare(name, span, loans, None);
+    }
+  }
+
+  // Leave the innermost scope, whose variables go out of scope at
+  // the end of the given span. Variables of enclosing scopes which
+  // are still in use must no longer borrow them.
+  fn pop_scope(&mut self, span: span::Span, description: &str) {
+    let scope = self.scopes.pop().unwrap();
+    let scope_index = self.scopes.len();
+    let mut diagnostics = vec![];
+
+    for holder_scope in self.scopes.iter_mut() {
+      let statement_index = holder_scope.statement_index;
+
+      for holder in holder_scope.variables.iter_mut() {
+        let (dangling_loans, loans): (Vec<_>, Vec<_>) = std::mem::take(&mut holder.loans)
+          .into_iter()
+          .partition(|loan| matches!(loan.variable, Some((index, _)) if index >= scope_index));
+
+        holder.loans = loans;
+
+        // Using the holder within the statement being visited is
+        // fine, as the scope is nested within that statement.
+        if holder.last_use <= statement_index {
+          continue;
+        }
+
+        for loan in dangling_loans {
+          let name = &scope.variables[loan.variable.unwrap().1].name;
+
+          diagnostics.push(diagnostic::Diagnostic {
+            message: format!("`{}` does not live long enough", name),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(loan.span),
+            notes: vec![
+              diagnostic::DiagnosticNote {
+                message: format!(
+                  "the borrow is held by `{}`, which is used after `{}` goes out of scope",
+                  holder.name, name
+                ),
+                span: holder.span,
+              },
+              diagnostic::DiagnosticNote {
+                message: format!(
+                  "`{}` goes out of scope at the end of this {}",
+                  name, description
+                ),
+                span,
+              },
+            ],
+          });
+        }
+      }
+    }
+
+    self.diagnostics.extend(diagnostics);
+
+    self
+      .statement_loans
+      .retain(|loan| !matches!(loan.variable, Some((index, _)) if index >= scope_index));
+  }
+}
+
+// Collects the names of the variables mentioned within a node.
+struct MentionCollector {
+  names: std::collections::HashSet<String>,
+}
+
+impl pass::Pass<'_> for MentionCollector {
+  fn visit_variable_ref(&mut self, variable_ref: &node::VariableRef) -> pass::PassResult {
+    self.names.insert(variable_ref.name.clone());
+
+    Ok(())
+  }
+}
+
+fn collect_mentions(node: &dyn node::Node) -> std::collections::HashSet<String> {
+  let mut mention_collector = MentionCollector {
+    names: std::collections::HashSet::new(),
+  };
+
+  // Collecting mentions cannot fail.
+  let _ = mention_collector.visit(node);
+
+  mention_collector.names
+}
+
+// Collect the names bound by a pattern, along with their spans.
+fn collect_bindings(pattern: &pattern::AnyPatternNode, bindings: &mut Vec<(String, span::Span)>) {
+  match pattern {
+    pattern::AnyPatternNode::BindingPattern(binding_pattern) => {
+      bindings.push((binding_pattern.name.clone(), binding_pattern.span))
+    }
+    pattern::AnyPatternNode::VariantPattern(variant_pattern) => {
+      for field in &variant_pattern.fields {
+        collect_bindings(field, bindings);
+      }
+    }
+    _ => {}
+  };
+}
+
+impl pass::Pass<'_> for BorrowCheckPass<'_> {
+  fn get_diagnostics(&self) -> Vec<diagnostic::Diagnostic> {
+    self.diagnostics.clone()
+  }
+
+  fn visit_namespace(&mut self, namespace: &namespace::Namespace) -> pass::PassResult {
+    if self.namespace_depth == 0 {
+      self.collect_field_kinds(namespace, &[]);
+    }
+
+    self.namespace_depth += 1;
+
+    let result = self.visit_children(namespace);
+
+    self.namespace_depth -= 1;
+
+    result
+  }
+
+  fn visit_function(&mut self, function: &function::Function) -> pass::PassResult {
+    // Parameters are local variables of the function's body.
+    self.pending_variables = function
+      .prototype
+      .parameters
+      .iter()
+      .map(|(name, _)| (name.clone(), function.prototype.span, vec![]))
+      .collect();
+
+    self.visit(&function.body)
+  }
+
+  fn visit_block(&mut self, block: &block::Block) -> pass::PassResult {
+    self.push_scope(
+      block
+        .statements
+        .iter()
+        .map(|statement| collect_mentions(statement.as_node()))
+        .collect(),
+    );
+
+    // Loans taken by the enclosing statement remain in use within
+    // the block.
+    let statement_loan_count = self.statement_loans.len();
+
+    for (index, statement) in block.statements.iter().enumerate() {
+      self.scopes.last_mut().unwrap().statement_index = index;
+      self.statement_loans.truncate(statement_loan_count);
+      self.visit(statement.as_node())?;
+    }
+
+    self.statement_loans.truncate(statement_loan_count);
+    self.pop_scope(block.span, "block");
+
+    Ok(())
+  }
+
+  fn visit_for_stmt(&mut self, for_stmt: &block::ForStmt) -> pass::PassResult {
+    self.visit(for_stmt.range_start.as_node())?;
+    self.visit(for_stmt.range_end.as_node())?;
+
+    self.pending_variables = vec![(for_stmt.iterator_name.clone(), for_stmt.span, vec![])];
+
+    self.visit(&for_stmt.body)
+  }
+
+  fn visit_let_stmt(&mut self, let_stmt: &block::LetStmt) -> pass::PassResult {
+    self.visit(let_stmt.value.as_node())?;
+
+    let loans = self.collect_loans(&let_stmt.value);
+    let statement_index = self.scopes.last().unwrap().statement_index;
+
+    self.declare(
+      let_stmt.name.clone(),
+      let_stmt.span,
+      loans,
+      Some(statement_index),
+    );
+
+    Ok(())
+  }
+
+  fn visit_assign_stmt(&mut self, assign_stmt: &block::AssignStmt) -> pass::PassResult {
+    self.visit_children(assign_stmt)?;
+
+    let loans = self.collect_loans(&assign_stmt.value);
+
+    let id = match self.find_root(&assign_stmt.target) {
+      Some(id) => id,
+      None => return Ok(()),
+    };
+
+    let holder = &mut self.scopes[id.0].variables[id.1];
+
+    // Assigning to the whole variable replaces its value, whereas
+    // assigning to a part of it keeps the rest.
+    match &assign_stmt.target {
+      node::AnyExprNode::VariableRef(_) => holder.loans = loans,
+      _ => holder.loans.extend(loans),
+    };
+
+    Ok(())
+  }
+
+  fn visit_return_stmt(&mut self, return_stmt: &block::ReturnStmt) -> pass::PassResult {
+    self.visit_children(return_stmt)?;
+
+    let value = match &return_stmt.value {
+      Some(value) => value,
+      None => return Ok(()),
+    };
+
+    let mut reported_spans = vec![];
+
+    for loan in self.collect_loans(value) {
+      if reported_spans.contains(&loan.span) {
+        continue;
+      }
+
+      reported_spans.push(loan.span);
+
+      let (message, note) = match loan.variable {
+        Some(id) => {
+          let name = &self.get_variable(id).name;
+
+          (
+            format!("cannot return a reference to local variable `{}`", name),
+            format!("`{}` is borrowed here", name),
+          )
+        }
+        None => (
+          String::from("cannot return a reference to a temporary value"),
+          String::from("the temporary value is borrowed here"),
+        ),
+      };
+
+      self.diagnostics.push(diagnostic::Diagnostic {
+        message,
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(value.get_span()),
+        notes: vec![diagnostic::DiagnosticNote {
+          message: note,
+          span: loan.span,
+        }],
+      });
+    }
+
+    Ok(())
+  }
+
+  fn visit_match_expr(&mut self, match_expr: &node::MatchExpr) -> pass::PassResult {
+    self.visit(match_expr.subject.as_node())?;
+
+    // Bindings may hold whatever the subject references.
+    let subject_loans = self.collect_loans(&match_expr.subject);
+
+    for arm in &match_expr.arms {
+      let mut bindings = vec![];
+
+      collect_bindings(&arm.pattern, &mut bindings);
+
+      self.pending_variables = bindings
+        .into_iter()
+        .map(|(name, span)| (name, span, subject_loans.clone()))
+        .collect();
+
+      match &arm.body {
+        node::MatchArmBody::Block(block) => self.visit(block)?,
+        node::MatchArmBody::Expr(expr) => {
+          self.push_scope(vec![]);
+          self.visit(expr.as_node())?;
+          self.pop_scope(arm.span, "match arm");
+        }
+      };
+    }
+
+    Ok(())
+  }
+
+  fn visit_borrow_expr(&mut self, borrow_expr: &node::BorrowExpr) -> pass::PassResult {
+    self.visit_children(borrow_expr)?;
+
+    let id = match self.find_borrowed(&borrow_expr.value) {
+      Borrowed::Variable(id) => id,
+      _ => return Ok(()),
+    };
+
+    let conflict = self
+      .get_active_loans()
+      .into_iter()
+      .find(|(_, loan)| loan.variable == Some(id) && (loan.is_mutable || borrow_expr.is_mutable));
+
+    if let Some((holder, loan)) = conflict {
+      let name = &self.get_variable(id).name;
+
+      let describe = |is_mutable: bool| match is_mutable {
+        true => "mutable",
+        false => "immutable",
+      };
+
+      let message = match (borrow_expr.is_mutable, loan.is_mutable) {
+        (true, true) => format!(
+          "cannot borrow `{}` as mutable more than once at a time",
+          name
+        ),
+        _ => format!(
+          "cannot borrow `{}` as {} because it is also borrowed as {}",
+          name,
+          describe(borrow_expr.is_mutable),
+          describe(loan.is_mutable)
+        ),
+      };
+
+      let mut notes = vec![diagnostic::DiagnosticNote {
+        message: format!(
+          "{} borrow of `{}` starts here",
+          describe(loan.is_mutable),
+          name
+        ),
+        span: loan.span,
+      }];
+
+      if let Some(holder) = holder {
+        let holder = self.get_variable(holder);
+
+        notes.push(diagnostic::DiagnosticNote {
+          message: format!(
+            "the borrow is held by `{}`, which is used later",
+            holder.name
+          ),
+          span: holder.span,
+        });
+      }
+
+      self.diagnostics.push(diagnostic::Diagnostic {
+        message,
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(borrow_expr.span),
+        notes,
+      });
+    }
+
+    self.statement_loans.push(Loan {
+      variable: Some(id),
+      is_mutable: borrow_expr.is_mutable,
+      span: borrow_expr.span,
+    });
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::pipeline;
+
+  fn check_source(source: &str) -> Vec<diagnostic::Diagnostic> {
+    let (namespace, type_check_pass) = pipeline::type_check_source(source);
+
+    let mut borrow_check_pass = BorrowCheckPass::new(type_check_pass.get_expr_kinds());
+
+    assert_eq!(true, borrow_check_pass.visit(&namespace).is_ok());
+
+    borrow_check_pass.get_diagnostics()
+  }
+
+  fn get_messages(diagnostics: &[diagnostic::Diagnostic]) -> Vec<String> {
+    diagnostics
+      .iter()
+      .map(|diagnostic| diagnostic.message.clone())
+      .collect()
+  }
+
+  #[test]
+  fn borrow_check_pass_valid() {
+    let diagnostics = check_source(
+      "namespace foo {
+        struct Point { x: i32, y: i32 }
+        struct Pair { first: &i32, second: &i32 }
+        fn get(p: &Point) ~ &i32 {
+          return &p.x;
+        }
+        fn first(pair: Pair) ~ &i32 {
+          return pair.first;
+        }
+        fn f(p: &mut Point) ~ &i32 {
+          let mut a = 1;
+          let b = &mut a;
+          *b = 2;
+          let c = &a;
+          let d = &a;
+          let e = Pair { first: c, second: d };
+          let g = &mut p.y;
+          *g = *first(e);
+          return get(p);
+        }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+  }
+
+  #[test]
+  fn borrow_check_pass_conflicts() {
+    let diagnostics = check_source(
+      "namespace foo {
+        struct Point { x: i32, y: i32 }
+        fn set(a: &mut i32, b: &i32) ~ void {
+          *a = *b;
+        }
+        fn f() ~ i32 {
+          let mut a = Point { x: 1, y: 2 };
+          let b = &a.x;
+          let c = &mut a;
+          return *b;
+        }
+        fn g() ~ i32 {
+          let mut a = Point { x: 1, y: 2 };
+          let b = &mut a;
+          let c = &mut a.y;
+          return b.x;
+        }
+        fn h() ~ void {
+          let mut a = Point { x: 1, y: 2 };
+          set(&mut a.x, &a.y);
+        }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        "cannot borrow `a` as mutable because it is also borrowed as immutable",
+        "cannot borrow `a` as mutable more than once at a time",
+        "cannot borrow `a` as immutable because it is also borrowed as mutable",
+      ],
+      get_messages(&diagnostics)
+    );
+
+    assert_eq!(
+      vec![
+        "immutable borrow of `a` starts here",
+        "the borrow is held by `b`, which is used later"
+      ],
+      diagnostics[0]
+        .notes
+        .iter()
+        .map(|note| note.message.clone())
+        .collect::<Vec<_>>()
+    );
+  }
+
+  #[test]
+  fn borrow_check_pass_lifetimes() {
+    let diagnostics = check_source(
+      "namespace foo {
+        struct Point { x: i32, y: i32 }
+        fn local(p: Point) ~ &i32 {
+          let a = 1;
+          let b = &a;
+          if true {
+            return &p.x;
+          }
+          return b;
+        }
+        fn temporary() ~ &i32 {
+          return &5;
+        }
+        fn scope(c: bool) ~ i32 {
+          let a = 1;
+          let mut b = &a;
+          if c {
+            let d = 2;
+            b = &d;
+          }
+          return *b;
+        }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        "cannot return a reference to local variable `p`",
+        "cannot return a reference to local variable `a`",
+        "cannot return a reference to a temporary value",
+        "`d` does not live long enough",
+      ],
+      get_messages(&diagnostics)
+    );
+
+    assert_eq!(
+      vec![
+        "the borrow is held by `b`, which is used after `d` goes out of scope",
+        "`d` goes out of scope at the end of this block",
+      ],
+      diagnostics[3]
+        .notes
+        .iter()
+        .map(|note| note.message.clone())
+        .collect::<Vec<_>>()
+    );
+  }
+}
@@ -0,0 +1,568 @@
+use crate::{enum_decl, int_kind, node, struct_decl};
+
+// The size and alignment of a value (in bytes), as laid out by C
+// on x86-64.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Layout {
+  pub size: u64,
+  pub alignment: u64,
+}
+
+// How a value is passed to, or returned from, a C function,
+// following the x86-64 System V calling convention. Only the
+// integer class is considered, since there are no floating point
+// kinds.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum PassingMode {
+  // Passed as its own LLVM type (ex. integers).
+  Direct,
+  // Passed in one or two integer registers, as an integer of the
+  // size of the value (ex. `i24`), or as a pair of an `i64` and an
+  // integer of the remaining size (ex. `{ i64, i32 }`). Sizes are
+  // in bytes.
+  Coerced {
+    low_size: u64,
+    high_size: Option<u64>,
+  },
+  // Passed in memory: arguments are copied onto the stack (`byval`),
+  // and return values are written through a hidden pointer given
+  // by the caller (`sret`).
+  Indirect,
+}
+
+// How a value narrower than 32 bits is extended by C to fill its
+// register, which the other side of a call may rely on.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Extension {
+  // Extended with zeros (`zeroext`), as for `bool`.
+  Zero,
+  // Extended with copies of the sign bit (`signext`), as for `i8`.
+  Sign,
+}
+
+// The structs which may be referred to by kinds, by their path
+// relative to the outermost namespace.
+pub type StructMap = std::collections::HashMap<String, struct_decl::StructDecl>;
+
+// The enums which may be referred to by kinds, by their path
+// relative to the outermost namespace.
+pub type EnumMap = std::collections::HashMap<String, enum_decl::EnumDecl>;
+
+// The layout of the tag of enums, which is a C `int`.
+pub const ENUM_TAG_LAYOUT: Layout = Layout {
+  size: 4,
+  alignment: 4,
+};
+
+fn align_to(offset: u64, alignment: u64) -> u64 {
+  offset.div_ceil(alignment) * alignment
+}
+
+// Determine the layout of a struct with the given fields, which are
+// laid out in order, each aligned to its own alignment. The struct
+// is padded to a multiple of its alignment.
+pub fn find_fields_layout<'a>(
+  field_kind_groups: impl Iterator<Item = &'a node::KindGroup>,
+  structs: &StructMap,
+  enums: &EnumMap,
+) -> Option<Layout> {
+  let mut size = 0;
+  let mut alignment = 1;
+
+  for field_kind_group in field_kind_groups {
+    let field_layout = find_layout(field_kind_group, structs, enums)?;
+
+    size = align_to(size, field_layout.alignment) + field_layout.size;
+    alignment = alignment.max(field_layout.alignment);
+  }
+
+  Some(Layout {
+    size: align_to(size, alignment),
+    alignment,
+  })
+}
+
+// Determine the layout shared by the fields of all variants of an
+// enum, which is that of a C union of one struct per variant. The
+// union is padded to a multiple of its alignment.
+pub fn find_payload_layout(
+  enum_decl: &enum_decl::EnumDecl,
+  structs: &StructMap,
+  enums: &EnumMap,
+) -> Option<Layout> {
+  let mut size = 0;
+  let mut alignment = 1;
+
+  for variant in &enum_decl.variants {
+    let variant_layout = find_fields_layout(variant.fields.iter(), structs, enums)?;
+
+    size = size.max(variant_layout.size);
+    alignment = alignment.max(variant_layout.alignment);
+  }
+
+  Some(Layout {
+    size: align_to(size, alignment),
+    alignment,
+  })
+}
+
+// Determine the layout of a value of the given kind group. Fields
+// of structs are laid out in declaration order, each aligned to its
+// own alignment, and structs are padded to a multiple of their
+// alignment. Enums are laid out as a struct of their tag followed
+// by their payload. Arrays are laid out as their elements, one after
+// the other, slices as a struct of a pointer and an `i64`, and
+// references as pointers (or as a struct of two pointers, for
+// references to trait objects).
+//
+// Returns [`None`] for kinds which have no values (ex. `void`), trait
+// objects, which are of an unknown size, or structs and enums which
+// could not be found.
+pub fn find_layout(
+  kind_group: &node::KindGroup,
+  structs: &StructMap,
+  enums: &EnumMap,
+) -> Option<Layout> {
+  find_kind_layout(&kind_group.to_value_kind(), structs, enums)
+}
+
+fn find_kind_layout(
+  kind: &node::AnyKindNode,
+  structs: &StructMap,
+  enums: &EnumMap,
+) -> Option<Layout> {
+  match kind {
+    node::AnyKindNode::VoidKind(_) => None,
+    node::AnyKindNode::BoolKind(_) => Some(Layout {
+      size: 1,
+      alignment: 1,
+    }),
+    node::AnyKindNode::IntKind(int_kind) => {
+      let size = match int_kind.size {
+        int_kind::IntSize::Signed8 => 1,
+        int_kind::IntSize::Signed16 => 2,
+        int_kind::IntSize::Signed32 => 4,
+        int_kind::IntSize::Signed64 => 8,
+        int_kind::IntSize::Signed128 => 16,
+      };
+
+      Some(Layout {
+        size,
+        alignment: size,
+      })
+    }
+    node::AnyKindNode::StructKind(struct_kind) => {
+      let struct_decl = structs.get(&struct_kind.to_string())?;
+
+      find_fields_layout(
+        struct_decl.fields.iter().map(|field| &field.kind_group),
+        structs,
+        enums,
+      )
+    }
+    node::AnyKindNode::EnumKind(enum_kind) => {
+      let enum_decl = enums.get(&enum_kind.to_string())?;
+      let payload_layout = find_payload_layout(enum_decl, structs, enums)?;
+      let alignment = ENUM_TAG_LAYOUT.alignment.max(payload_layout.alignment);
+
+      Some(Layout {
+        size: align_to(
+          align_to(ENUM_TAG_LAYOUT.size, payload_layout.alignment) + payload_layout.size,
+          alignment,
+        ),
+        alignment,
+      })
+    }
+    node::AnyKindNode::ArrayKind(array_kind) => {
+      let element_layout = find_kind_layout(&array_kind.element_kind, structs, enums)?;
+
+      Some(Layout {
+        size: element_layout.size * array_kind.length,
+        alignment: element_layout.alignment,
+      })
+    }
+    node::AnyKindNode::SliceKind(_) => Some(Layout {
+      size: 16,
+      alignment: 8,
+    }),
+    node::AnyKindNode::ReferenceKind(reference_kind)
+      if matches!(
+        *reference_kind.referenced_kind,
+        node::AnyKindNode::DynKind(_)
+      ) =>
+    {
+      Some(Layout {
+        size: 16,
+        alignment: 8,
+      })
+    }
+    node::AnyKindNode::ReferenceKind(_) => Some(Layout {
+      size: 8,
+      alignment: 8,
+    }),
+    node::AnyKindNode::DynKind(_) => None,
+    // Generic kinds have no layout until they are substituted.
+    node::AnyKindNode::GenericKind(_) => None,
+  }
+}
+
+// Determine how a value of the given kind group is passed to, or
+// returned from, a C function. Structs (and enums, arrays and
+// slices) of up to 16 bytes are passed in registers, and larger
+// ones in memory.
+pub fn find_passing_mode(
+  kind_group: &node::KindGroup,
+  structs: &StructMap,
+  enums: &EnumMap,
+) -> PassingMode {
+  if kind_group.is_reference
+    || !matches!(
+      kind_group.kind,
+      node::AnyKindNode::StructKind(_)
+        | node::AnyKindNode::EnumKind(_)
+        | node::AnyKindNode::ArrayKind(_)
+        | node::AnyKindNode::SliceKind(_)
+    )
+  {
+    return PassingMode::Direct;
+  }
+
+  match find_layout(kind_group, structs, enums) {
+    // Empty structs occupy no registers.
+    None | Some(Layout { size: 0, .. }) => PassingMode::Direct,
+    Some(Layout { size, .. }) if size <= 8 => PassingMode::Coerced {
+      low_size: size,
+      high_size: None,
+    },
+    Some(Layout { size, .. }) if size <= 16 => PassingMode::Coerced {
+      low_size: 8,
+      high_size: Some(size - 8),
+    },
+    Some(_) => PassingMode::Indirect,
+  }
+}
+
+// Determine how a value of the given kind group is extended when
+// passed to, or returned from, a C function. Values of 32 bits or
+// more, and aggregates, are not extended.
+pub fn find_extension(kind_group: &node::KindGroup) -> Option<Extension> {
+  if kind_group.is_reference {
+    return None;
+  }
+
+  match &kind_group.kind {
+    node::AnyKindNode::BoolKind(_) => Some(Extension::Zero),
+    node::AnyKindNode::IntKind(int_kind) if int_kind.size.get_bit_width() < 32 => {
+      Some(Extension::Sign)
+    }
+    _ => None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{enum_kind, namespace, parser, span, struct_kind};
+
+  fn parse_namespace(source: &str) -> namespace::Namespace {
+    parser::parse_source(source).unwrap()
+  }
+
+  fn collect_structs(source: &str) -> StructMap {
+    parse_namespace(source)
+      .symbol_table
+      .iter()
+      .filter_map(|(name, top_level_node)| match top_level_node {
+        namespace::TopLevelNode::Struct(struct_decl) => Some((name.clone(), struct_decl.clone())),
+        _ => None,
+      })
+      .collect()
+  }
+
+  fn make_struct_kind_group(node_ids: &node::NodeIdAllocator, name: &str) -> node::KindGroup {
+    node::KindGroup {
+      id: node_ids.allocate(),
+      span: span::Span::default(),
+      kind: node::AnyKindNode::StructKind(struct_kind::StructKind {
+        path: vec![String::from(name)],
+      }),
+      is_reference: false,
+      is_mutable: false,
+    }
+  }
+
+  #[test]
+  fn c_abi_find_layout() {
+    let node_ids = node::NodeIdAllocator::new();
+
+    let structs = collect_structs(
+      "namespace test {
+        struct A { a: i8, b: i32, c: i16 }
+        struct B { a: bool, b: A, c: &B }
+        struct C { a: i128, b: i8 }
+        struct D {}
+      }",
+    );
+
+    for (name, size, alignment) in [("A", 12, 4), ("B", 24, 8), ("C", 32, 16), ("D", 0, 1)] {
+      assert_eq!(
+        Some(Layout { size, alignment }),
+        find_layout(
+          &make_struct_kind_group(&node_ids, name),
+          &structs,
+          &EnumMap::new()
+        ),
+        "{}",
+        name
+      );
+    }
+
+    assert_eq!(
+      None,
+      find_layout(
+        &make_struct_kind_group(&node_ids, "E"),
+        &structs,
+        &EnumMap::new()
+      )
+    );
+  }
+
+  #[test]
+  fn c_abi_find_passing_mode() {
+    let node_ids = node::NodeIdAllocator::new();
+
+    let structs = collect_structs(
+      "namespace test {
+        struct A { a: i8, b: i8, c: i8 }
+        struct B { a: i32, b: i32 }
+        struct C { a: i64, b: i32 }
+        struct D { a: i64, b: i64, c: i8 }
+        struct E {}
+      }",
+    );
+
+    assert_eq!(
+      PassingMode::Coerced {
+        low_size: 3,
+        high_size: None
+      },
+      find_passing_mode(
+        &make_struct_kind_group(&node_ids, "A"),
+        &structs,
+        &EnumMap::new()
+      )
+    );
+
+    assert_eq!(
+      PassingMode::Coerced {
+        low_size: 8,
+        high_size: None
+      },
+      find_passing_mode(
+        &make_struct_kind_group(&node_ids, "B"),
+        &structs,
+        &EnumMap::new()
+      )
+    );
+
+    assert_eq!(
+      PassingMode::Coerced {
+        low_size: 8,
+        high_size: Some(8)
+      },
+      find_passing_mode(
+        &make_struct_kind_group(&node_ids, "C"),
+        &structs,
+        &EnumMap::new()
+      )
+    );
+
+    assert_eq!(
+      PassingMode::Indirect,
+      find_passing_mode(
+        &make_struct_kind_group(&node_ids, "D"),
+        &structs,
+        &EnumMap::new()
+      )
+    );
+
+    assert_eq!(
+      PassingMode::Direct,
+      find_passing_mode(
+        &make_struct_kind_group(&node_ids, "E"),
+        &structs,
+        &EnumMap::new()
+      )
+    );
+
+    // References to structs are pointers, and are passed as is.
+    let mut kind_group = make_struct_kind_group(&node_ids, "D");
+
+    kind_group.is_reference = true;
+
+    assert_eq!(
+      PassingMode::Direct,
+      find_passing_mode(&kind_group, &structs, &EnumMap::new())
+    );
+  }
+
+  #[test]
+  fn c_abi_enums() {
+    let node_ids = node::NodeIdAllocator::new();
+
+    let namespace = parse_namespace(
+      "namespace test {
+        struct A { a: i8, b: i64 }
+        enum B { C, D }
+        enum E { F(i8, i8), G(i16) }
+        enum H { I(A), J(i32) }
+        enum K { L(i128) }
+      }",
+    );
+
+    let mut structs = StructMap::new();
+    let mut enums = EnumMap::new();
+
+    for (name, top_level_node) in namespace.symbol_table.iter() {
+      match top_level_node {
+        namespace::TopLevelNode::Struct(struct_decl) => {
+          structs.insert(name.clone(), struct_decl.clone());
+        }
+        namespace::TopLevelNode::Enum(enum_decl) => {
+          enums.insert(name.clone(), enum_decl.clone());
+        }
+        _ => {}
+      };
+    }
+
+    let make_enum_kind_group = |name: &str| node::KindGroup {
+      id: node_ids.allocate(),
+      span: span::Span::default(),
+      kind: node::AnyKindNode::EnumKind(enum_kind::EnumKind {
+        path: vec![String::from(name)],
+      }),
+      is_reference: false,
+      is_mutable: false,
+    };
+
+    // The payload follows the tag, aligned to its own alignment.
+    for (name, size, alignment) in [("B", 4, 4), ("E", 8, 4), ("H", 24, 8), ("K", 32, 16)] {
+      assert_eq!(
+        Some(Layout { size, alignment }),
+        find_layout(&make_enum_kind_group(name), &structs, &enums),
+        "{}",
+        name
+      );
+    }
+
+    assert_eq!(
+      PassingMode::Coerced {
+        low_size: 8,
+        high_size: None
+      },
+      find_passing_mode(&make_enum_kind_group("E"), &structs, &enums)
+    );
+
+    assert_eq!(
+      PassingMode::Indirect,
+      find_passing_mode(&make_enum_kind_group("H"), &structs, &enums)
+    );
+  }
+
+  #[test]
+  fn c_abi_arrays() {
+    let node_ids = node::NodeIdAllocator::new();
+
+    let structs = collect_structs(
+      "namespace test {
+        struct A { a: [i8; 3], b: [i32; 2] }
+        struct B { a: [i64], b: [A; 2] }
+      }",
+    );
+
+    for (name, size, alignment) in [("A", 12, 4), ("B", 40, 8)] {
+      assert_eq!(
+        Some(Layout { size, alignment }),
+        find_layout(
+          &make_struct_kind_group(&node_ids, name),
+          &structs,
+          &EnumMap::new()
+        ),
+        "{}",
+        name
+      );
+    }
+
+    // Arrays and slices are passed like structs of the same layout.
+    let fields = &structs["B"].fields;
+
+    assert_eq!(
+      PassingMode::Coerced {
+        low_size: 8,
+        high_size: Some(8)
+      },
+      find_passing_mode(&fields[0].kind_group, &structs, &EnumMap::new())
+    );
+
+    assert_eq!(
+      PassingMode::Indirect,
+      find_passing_mode(&fields[1].kind_group, &structs, &EnumMap::new())
+    );
+  }
+
+  #[test]
+  fn c_abi_trait_objects() {
+    let node_ids = node::NodeIdAllocator::new();
+
+    let structs = collect_structs(
+      "namespace test {
+        struct A { a: &dyn Shape, b: i8 }
+      }",
+    );
+
+    // References to trait objects are laid out as a pointer to the
+    // value, followed by a pointer to its vtable.
+    assert_eq!(
+      Some(Layout {
+        size: 24,
+        alignment: 8
+      }),
+      find_layout(
+        &make_struct_kind_group(&node_ids, "A"),
+        &structs,
+        &EnumMap::new()
+      )
+    );
+
+    let mut kind_group = structs["A"].fields[0].kind_group.clone();
+
+    kind_group.is_reference = false;
+
+    assert_eq!(None, find_layout(&kind_group, &structs, &EnumMap::new()));
+  }
+  #[test]
+  fn c_abi_find_extension() {
+    let structs = collect_structs(
+      "namespace test {
+        struct A { a: bool, b: i8, c: i16, d: i32, e: i64, f: &i8, g: [i8; 2] }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        Some(Extension::Zero),
+        Some(Extension::Sign),
+        Some(Extension::Sign),
+        None,
+        None,
+        None,
+        None
+      ],
+      structs["A"]
+        .fields
+        .iter()
+        .map(|field| find_extension(&field.kind_group))
+        .collect::<Vec<_>>()
+    );
+  }
+}
@@ -0,0 +1,120 @@
+use crate::{diagnostic, impl_decl, node, pass, span};
+
+// Ensures that each kind implements a trait at most once, so that
+// the method called through a trait is never ambiguous. Trait paths
+// and kinds are expected to have been resolved relative to the
+// outermost namespace, so that implementations within different
+// namespaces may be compared.
+pub struct CoherenceCheckPass {
+  diagnostics: Vec<diagnostic::Diagnostic>,
+  // The span of the first implementation of each trait for each
+  // kind, by the path of the trait and the kind.
+  implementations: std::collections::HashMap<(String, node::AnyKindNode), span::Span>,
+}
+
+impl CoherenceCheckPass {
+  pub fn new() -> Self {
+    Self {
+      diagnostics: vec![],
+      implementations: std::collections::HashMap::new(),
+    }
+  }
+}
+
+impl pass::Pass<'_> for CoherenceCheckPass {
+  fn get_diagnostics(&self) -> Vec<diagnostic::Diagnostic> {
+    self.diagnostics.clone()
+  }
+
+  fn visit_impl_decl(&mut self, impl_decl: &impl_decl::ImplDecl) -> pass::PassResult {
+    let trait_path = impl_decl.trait_path.to_string();
+    let kind = impl_decl.kind_group.to_value_kind();
+
+    match self
+      .implementations
+      .get(&(trait_path.clone(), kind.clone()))
+    {
+      Some(first_span) => self.diagnostics.push(diagnostic::Diagnostic {
+        message: format!(
+          "conflicting implementations of trait `{}` for kind `{}`",
+          trait_path, kind
+        ),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(impl_decl.span),
+        notes: vec![diagnostic::DiagnosticNote {
+          message: String::from("first implementation is here"),
+          span: *first_span,
+        }],
+      }),
+      None => {
+        self
+          .implementations
+          .insert((trait_path, kind), impl_decl.span);
+      }
+    };
+
+    // Implementations are never nested within methods, so these
+    // are left unvisited.
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{pass::Pass, pipeline};
+
+  fn check(source: &str) -> Vec<diagnostic::Diagnostic> {
+    let (namespace, _) = pipeline::type_check_source(source);
+
+    let mut coherence_check_pass = CoherenceCheckPass::new();
+
+    assert_eq!(
+      true,
+      coherence_check_pass.visit_namespace(&namespace).is_ok()
+    );
+
+    coherence_check_pass.get_diagnostics()
+  }
+
+  #[test]
+  fn coherence_check_pass_distinct_implementations() {
+    let diagnostics = check(
+      "namespace foo {
+        trait Shape { fn area(self: &Self) ~ i32; }
+        trait Named { fn id(self: &Self) ~ i32; }
+        struct Square { side: i32 }
+        impl Shape for Square { fn area(self: &Self) ~ i32 { return 1; } }
+        impl Named for Square { fn id(self: &Self) ~ i32 { return 2; } }
+        impl Shape for i32 { fn area(self: &Self) ~ i32 { return 3; } }
+        impl Shape for [Square; 2] { fn area(self: &Self) ~ i32 { return 4; } }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+  }
+
+  #[test]
+  fn coherence_check_pass_conflicting_implementations() {
+    let diagnostics = check(
+      "namespace foo {
+        trait Shape { fn area(self: &Self) ~ i32; }
+        struct Square { side: i32 }
+        impl Shape for Square { fn area(self: &Self) ~ i32 { return 1; } }
+        namespace bar {
+          impl Shape for Square { fn area(self: &Self) ~ i32 { return 2; } }
+        }
+      }",
+    );
+
+    assert_eq!(1, diagnostics.len());
+    assert_eq!(
+      "conflicting implementations of trait `Shape` for kind `Square`",
+      diagnostics[0].message
+    );
+    assert_eq!(
+      "first implementation is here",
+      diagnostics[0].notes[0].message
+    );
+  }
+}
@@ -1,3 +1,5 @@
+use crate::span;
+
 #[derive(Clone, Debug)]
 pub enum DiagnosticSeverity {
   Warning,
@@ -9,4 +11,22 @@ pub enum DiagnosticSeverity {
 pub struct Diagnostic {
   pub message: String,
   pub severity: DiagnosticSeverity,
+  // The location of the offending code, if known.
+  pub span: Option<span::Span>,
+  // Other locations relevant to the diagnostic (ex. the previous
+  // declaration of a redeclared name).
+  pub notes: Vec<DiagnosticNote>,
+}
+
+#[derive(Clone, Debug)]
+pub struct DiagnosticNote {
+  pub message: String,
+  pub span: span::Span,
+}
+
+impl Diagnostic {
+  // Whether the diagnostic prevents compilation, unlike warnings.
+  pub fn is_error(&self) -> bool {
+    !matches!(self.severity, DiagnosticSeverity::Warning)
+  }
 }
@@ -0,0 +1,28 @@
+use crate::node;
+use crate::pass;
+
+// The kind of the values of any kind implementing a trait (ex.
+// `dyn Shape`), whose methods are dispatched when called rather
+// than when compiled. Trait objects are of an unknown size, so they
+// may only be used behind a reference, which is made of a pointer
+// to the value, and a pointer to the trait's methods for the
+// value's kind (its vtable).
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DynKind {
+  pub trait_path: Vec<String>,
+}
+
+impl std::fmt::Display for DynKind {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "dyn {}", self.trait_path.join("::"))
+  }
+}
+
+impl node::Node for DynKind {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_dyn_kind(self)?;
+
+    Ok(())
+  }
+}
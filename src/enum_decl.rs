@@ -0,0 +1,73 @@
+use crate::{node, pass, span};
+
+// A variant of an enum, along with the kinds of the values it
+// carries, if any (ex. `Circle(i32)`).
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnumVariant {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  pub name: String,
+  pub fields: Vec<node::KindGroup>,
+}
+
+impl node::Node for EnumVariant {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_children(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    self
+      .fields
+      .iter()
+      .map(|field| field as &dyn node::Node)
+      .collect()
+  }
+}
+
+// A user-defined kind, whose values are one of its variants (ex.
+// `enum Shape { Circle(i32), Empty }`). Values are laid out as the
+// position of their variant (the tag), followed by the fields of
+// their variant, within storage shared by all variants.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnumDecl {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  pub is_public: bool,
+  pub name: String,
+  pub variants: Vec<EnumVariant>,
+}
+
+impl EnumDecl {
+  // Find the variant of the given name, along with its position.
+  pub fn find_variant(&self, name: &str) -> Option<(usize, &EnumVariant)> {
+    self
+      .variants
+      .iter()
+      .enumerate()
+      .find(|(_, variant)| variant.name == name)
+  }
+}
+
+impl node::Node for EnumDecl {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_enum_decl(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    self
+      .variants
+      .iter()
+      .map(|variant| variant as &dyn node::Node)
+      .collect()
+  }
+}
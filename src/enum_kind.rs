@@ -0,0 +1,25 @@
+use crate::node;
+use crate::pass;
+
+// The kind of the values of a user-defined enum. Kinds named by a
+// path are parsed as struct kinds, and those which name an enum are
+// turned into enum kinds once their path is resolved.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnumKind {
+  pub path: Vec<String>,
+}
+
+impl std::fmt::Display for EnumKind {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.path.join("::"))
+  }
+}
+
+impl node::Node for EnumKind {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_enum_kind(self)?;
+
+    Ok(())
+  }
+}
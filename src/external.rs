@@ -1,15 +1,27 @@
-use crate::{node, pass, prototype};
+use crate::{node, pass, prototype, span};
 
 #[derive(Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 
 pub struct External {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  pub is_public: bool,
   pub prototype: prototype::Prototype,
 }
 
 impl node::Node for External {
-  fn accept(&mut self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
     pass.visit_external(self)?;
 
     Ok(())
   }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    vec![&self.prototype]
+  }
 }
@@ -1,17 +1,59 @@
-use crate::{block, node, pass, prototype};
+use crate::{attribute, block, node, pass, prototype, span};
 
-#[derive(Hash, Eq, PartialEq, Debug)]
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 
 pub struct Function {
+  pub id: node::NodeId,
+  pub span: span::Span,
   pub is_public: bool,
+  pub attributes: Vec<attribute::Attribute>,
   pub prototype: prototype::Prototype,
   pub body: block::Block,
 }
 
+impl Function {
+  pub fn find_attribute(&self, name: &str) -> Option<&attribute::Attribute> {
+    self
+      .attributes
+      .iter()
+      .find(|attribute| attribute.name == name)
+  }
+
+  // The symbol which the function is exported under when it opts
+  // out of name mangling, if it does.
+  pub fn find_export_name(&self) -> Option<&str> {
+    if self.find_attribute(attribute::NO_MANGLE).is_some() {
+      return Some(&self.prototype.name);
+    }
+
+    self
+      .find_attribute(attribute::EXPORT_NAME)
+      .and_then(|attribute| attribute.value.as_deref())
+  }
+}
+
 impl node::Node for Function {
-  fn accept(&mut self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
     pass.visit_function(self)?;
 
     Ok(())
   }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    let mut children: Vec<&dyn node::Node> = self
+      .attributes
+      .iter()
+      .map(|attribute| attribute as &dyn node::Node)
+      .collect();
+
+    children.push(&self.prototype);
+    children.push(&self.body);
+
+    children
+  }
 }
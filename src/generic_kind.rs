@@ -0,0 +1,28 @@
+use crate::node;
+use crate::pass;
+
+// The kind named by a generic parameter of a function (ex. `T` in
+// `fn max<T>(a: T, b: T) ~ T`). Kinds named by a path are parsed as
+// struct kinds, and those which name a generic parameter of their
+// function are turned into generic kinds once their path is
+// resolved. Generic kinds are replaced by concrete kinds when the
+// function is instantiated.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenericKind {
+  pub name: String,
+}
+
+impl std::fmt::Display for GenericKind {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.name)
+  }
+}
+
+impl node::Node for GenericKind {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_generic_kind(self)?;
+
+    Ok(())
+  }
+}
@@ -0,0 +1,49 @@
+use crate::{function, node, pass, span};
+
+// The methods of a trait, as implemented by a kind (ex. `impl Shape
+// for Square { ... }`). Within the implementation, `Self` names the
+// implementing kind.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImplDecl {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  pub trait_path: node::Path,
+  pub kind_group: node::KindGroup,
+  pub methods: Vec<function::Function>,
+}
+
+impl ImplDecl {
+  // The name under which the methods of the implementation are
+  // declared (ex. `<Square as Shape>`), which is unique once the
+  // kind and the trait are resolved, since a kind may implement a
+  // trait at most once.
+  pub fn get_name(&self) -> String {
+    format!("<{} as {}>", self.kind_group.kind, self.trait_path)
+  }
+
+  pub fn find_method(&self, name: &str) -> Option<&function::Function> {
+    self
+      .methods
+      .iter()
+      .find(|method| method.prototype.name == name)
+  }
+}
+
+impl node::Node for ImplDecl {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_impl_decl(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    let mut children: Vec<&dyn node::Node> = vec![&self.trait_path, &self.kind_group];
+
+    children.extend(self.methods.iter().map(|method| method as &dyn node::Node));
+
+    children
+  }
+}
@@ -0,0 +1,138 @@
+use crate::{block, function, namespace, node, pass, pass_mut, pass_mut::PassMut, span};
+
+// Makes the implicit `return` at the end of functions returning
+// `void` explicit, so that later passes may assume that every
+// path through such functions ends in a `return` statement.
+pub struct ImplicitReturnPass {
+  namespace_depth: usize,
+  // The allocator of the ids of the tree, taken from the outermost
+  // namespace while it is visited.
+  node_ids: node::NodeIdAllocator,
+}
+
+impl ImplicitReturnPass {
+  pub fn new() -> Self {
+    Self {
+      namespace_depth: 0,
+      node_ids: node::NodeIdAllocator::new(),
+    }
+  }
+}
+
+impl pass_mut::PassMut for ImplicitReturnPass {
+  fn visit_namespace(&mut self, namespace: &mut namespace::Namespace) -> pass::PassResult {
+    if self.namespace_depth > 0 {
+      return pass_mut::walk_namespace(self, namespace);
+    }
+
+    self.node_ids = namespace.node_ids.clone();
+    self.namespace_depth += 1;
+    pass_mut::walk_namespace(self, namespace)?;
+    self.namespace_depth -= 1;
+    namespace.node_ids = self.node_ids.clone();
+
+    Ok(())
+  }
+
+  fn visit_function(&mut self, function: &mut function::Function) -> pass::PassResult {
+    if !matches!(
+      function.prototype.return_kind_group.kind,
+      node::AnyKindNode::VoidKind(_)
+    ) {
+      return Ok(());
+    }
+
+    let body = &mut function.body;
+
+    if !matches!(
+      body.statements.last(),
+      Some(block::AnyStatementNode::ReturnStmt(_))
+    ) {
+      // The implicit return is located at the end of the body.
+      let span = span::Span {
+        start: body.span.end,
+        end: body.span.end,
+      };
+
+      body
+        .statements
+        .push(block::AnyStatementNode::ReturnStmt(block::ReturnStmt {
+          id: self.node_ids.allocate(),
+          span,
+          value: None,
+        }));
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn make_implicit_returns(source: &str) -> namespace::Namespace {
+    let mut namespace = crate::parser::parse_source(source).unwrap();
+
+    assert_eq!(
+      true,
+      ImplicitReturnPass::new()
+        .visit_namespace(&mut namespace)
+        .is_ok()
+    );
+
+    namespace
+  }
+
+  fn get_function<'a>(namespace: &'a namespace::Namespace, name: &str) -> &'a function::Function {
+    match namespace.symbol_table.get(name) {
+      Some(namespace::TopLevelNode::Function(function)) => function,
+      _ => unreachable!(),
+    }
+  }
+
+  #[test]
+  fn implicit_return_pass_empty_body() {
+    let namespace = make_implicit_returns("namespace foo { fn foo() ~ void {} }");
+    let function = get_function(&namespace, "foo");
+
+    assert_eq!(1, function.body.statements.len());
+
+    assert_eq!(
+      true,
+      matches!(
+        function.body.statements[0],
+        block::AnyStatementNode::ReturnStmt(block::ReturnStmt { value: None, .. })
+      )
+    );
+
+    // The return is given the last id of the tree's allocator,
+    // after those of the parsed nodes.
+    let next_id = namespace.node_ids.allocate();
+
+    let return_id = match &function.body.statements[0] {
+      block::AnyStatementNode::ReturnStmt(return_stmt) => return_stmt.id,
+      _ => unreachable!(),
+    };
+
+    assert_eq!(next_id.0 - 1, return_id.0);
+  }
+
+  #[test]
+  fn implicit_return_pass_explicit_return() {
+    let namespace = make_implicit_returns("namespace foo { fn foo() ~ void { return; } }");
+
+    assert_eq!(1, get_function(&namespace, "foo").body.statements.len());
+  }
+
+  #[test]
+  fn implicit_return_pass_non_void() {
+    let namespace = make_implicit_returns("namespace foo { fn foo() ~ bool {} }");
+
+    // Functions returning a value must do so explicitly.
+    assert_eq!(
+      true,
+      get_function(&namespace, "foo").body.statements.is_empty()
+    );
+  }
+}
@@ -2,6 +2,7 @@ use crate::node;
 use crate::pass;
 
 #[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum IntSize {
   Signed8,
   Signed16,
@@ -10,13 +11,26 @@ pub enum IntSize {
   Signed128,
 }
 
+impl IntSize {
+  pub fn get_bit_width(&self) -> u32 {
+    match self {
+      IntSize::Signed8 => 8,
+      IntSize::Signed16 => 16,
+      IntSize::Signed32 => 32,
+      IntSize::Signed64 => 64,
+      IntSize::Signed128 => 128,
+    }
+  }
+}
+
 #[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IntKind {
   pub size: IntSize,
 }
 
 impl node::Node for IntKind {
-  fn accept(&mut self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
     pass.visit_int_kind(self)?;
 
     Ok(())
@@ -1,4 +1,15 @@
-use crate::token;
+use crate::{diagnostic, span, token};
+
+// A line comment, such as `// foo`. Comments are not tokens,
+// and are instead collected separately by the lexer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+  // The text of the comment, including its leading slashes.
+  pub text: String,
+  pub span: span::Span,
+  // Whether the comment follows a token on the same line.
+  pub is_trailing: bool,
+}
 
 pub struct Lexer {
   input: Vec<char>,
@@ -8,6 +19,14 @@ pub struct Lexer {
   // string was empty, or if the read index is out of
   // bounds, it will be [`None`].
   current_char: Option<char>,
+  // The span of the last token produced.
+  token_span: span::Span,
+  comments: Vec<Comment>,
+  // Whether a token was produced since the last line break.
+  is_line_occupied: bool,
+  // The error which stopped the lexer, if any. The lexer produces
+  // no tokens past an error.
+  error: Option<diagnostic::Diagnostic>,
 }
 
 // Determine whether a character is a letter, and within
@@ -34,6 +53,10 @@ impl Lexer {
       index: 0,
       read_index: 0,
       current_char,
+      token_span: span::Span::default(),
+      comments: vec![],
+      is_line_occupied: false,
+      error: None,
     }
   }
 
@@ -55,6 +78,13 @@ impl Lexer {
     self.read_index += 1;
   }
 
+  // Retrieve the character following the current character,
+  // without advancing. Returns [`None`] if there is no such
+  // character.
+  fn peek_char(&self) -> Option<char> {
+    self.input.get(self.read_index).cloned()
+  }
+
   // Determine if the current character is a whitespace character.
   fn is_whitespace(&mut self) -> bool {
     if self.current_char.is_none() {
@@ -66,6 +96,78 @@ impl Lexer {
     current_char == ' ' || current_char == '\t' || current_char == '\n' || current_char == '\r'
   }
 
+  // Retrieve the span of the last token produced.
+  pub fn get_token_span(&self) -> span::Span {
+    self.token_span
+  }
+
+  // Retrieve the comments encountered so far, in the order in
+  // which they appear.
+  pub fn get_comments(&self) -> &[Comment] {
+    &self.comments
+  }
+
+  // Retrieve the error which stopped the lexer, if any. Tokens
+  // collected from a lexer which stopped due to an error do not
+  // cover the whole input.
+  pub fn get_error(&self) -> Option<&diagnostic::Diagnostic> {
+    self.error.as_ref()
+  }
+
+  // Record an error spanning from the start of the current token
+  // up to the current character, which stops the lexer.
+  fn stop(&mut self, message: String) -> Option<token::Token> {
+    self.error = Some(diagnostic::Diagnostic {
+      message,
+      severity: diagnostic::DiagnosticSeverity::Error,
+      span: Some(span::Span {
+        start: self.token_span.start,
+        end: self.index,
+      }),
+      notes: vec![],
+    });
+
+    None
+  }
+
+  // Produce all remaining tokens, along with their spans.
+  pub fn collect_with_spans(&mut self) -> (Vec<token::Token>, Vec<span::Span>) {
+    let mut tokens = vec![];
+    let mut spans = vec![];
+
+    while let Some(token) = self.next() {
+      tokens.push(token);
+      spans.push(self.token_span);
+    }
+
+    (tokens, spans)
+  }
+
+  // Read a line comment, up to (but excluding) the line break
+  // that ends it.
+  fn read_comment(&mut self) {
+    let index = self.index;
+
+    while self.current_char.is_some() && self.current_char != Some('\n') {
+      self.read_char();
+    }
+
+    let end_index = self.index.min(self.input.len());
+
+    self.comments.push(Comment {
+      text: self.input[index..end_index]
+        .iter()
+        .collect::<String>()
+        .trim_end()
+        .to_string(),
+      span: span::Span {
+        start: index,
+        end: end_index,
+      },
+      is_trailing: self.is_line_occupied,
+    });
+  }
+
   // Determine if the current character is unset, and therefore
   // signifies the end of the input string.
   fn is_eof(&self) -> bool {
@@ -77,6 +179,17 @@ impl Iterator for Lexer {
   type Item = token::Token;
 
   fn next(&mut self) -> Option<Self::Item> {
+    let token = self.read_token();
+
+    self.token_span.end = self.index;
+    self.is_line_occupied = true;
+
+    token
+  }
+}
+
+impl Lexer {
+  fn read_token(&mut self) -> Option<token::Token> {
     if self.current_char.is_none() {
       return None;
     }
@@ -84,7 +197,11 @@ impl Iterator for Lexer {
     let read_identifier = |lexer: &mut Lexer| -> String {
       let index = lexer.index;
 
-      while lexer.index < lexer.input.len() && is_letter(lexer.current_char.unwrap()) {
+      // Identifiers start with a letter, but may contain digits
+      // afterwards (ex. `i32`).
+      while lexer.index < lexer.input.len()
+        && (is_letter(lexer.current_char.unwrap()) || is_digit(lexer.current_char.unwrap()))
+      {
         lexer.read_char();
       }
 
@@ -105,11 +222,30 @@ impl Iterator for Lexer {
       lexer.input[index..lexer.index].to_vec()
     };
 
-    // TODO: What if it's EOF + whitespace?
-    while self.is_whitespace() && !self.is_eof() {
-      self.read_char()
+    loop {
+      while self.is_whitespace() && !self.is_eof() {
+        if self.current_char == Some('\n') {
+          self.is_line_occupied = false;
+        }
+
+        self.read_char()
+      }
+
+      if self.current_char != Some('/') || self.peek_char() != Some('/') {
+        break;
+      }
+
+      self.read_comment();
     }
 
+    // Whitespace is only left over at the end of the input, which
+    // it does not extend.
+    if self.is_whitespace() {
+      return None;
+    }
+
+    self.token_span.start = self.index;
+
     // TODO: Is it okay to use '?' here?
 
     let token: token::Token = match self.current_char? {
@@ -119,9 +255,87 @@ impl Iterator for Lexer {
       ')' => token::Token::SymbolParenthesesR,
       '~' => token::Token::SymbolTilde,
       ';' => token::Token::SymbolSemiColon,
-      ':' => token::Token::SymbolColon,
+      ':' => match self.peek_char() {
+        // Path separator (ex. `a::b`).
+        Some(':') => {
+          self.read_char();
+
+          token::Token::SymbolDoubleColon
+        }
+        _ => token::Token::SymbolColon,
+      },
       '&' => token::Token::SymbolAmpersand,
+      '*' => token::Token::SymbolAsterisk,
+      '<' => token::Token::SymbolLessThan,
+      '>' => token::Token::SymbolGreaterThan,
+      '+' => token::Token::SymbolPlus,
       ',' => token::Token::SymbolComma,
+      '#' => token::Token::SymbolHash,
+      '[' => token::Token::SymbolBracketL,
+      ']' => token::Token::SymbolBracketR,
+      '=' => match self.peek_char() {
+        // Separates the pattern of a `match` arm from its body.
+        Some('>') => {
+          self.read_char();
+
+          token::Token::SymbolFatArrow
+        }
+        _ => token::Token::SymbolEqual,
+      },
+      '"' => {
+        self.read_char();
+
+        let index = self.index;
+
+        // There are no escape sequences, so string literals end at
+        // the first quote, and may not span multiple lines.
+        while self.current_char.is_some()
+          && self.current_char != Some('"')
+          && self.current_char != Some('\n')
+        {
+          self.read_char();
+        }
+
+        if self.current_char != Some('"') {
+          return self.stop(String::from("unterminated string literal"));
+        }
+
+        token::Token::LiteralString(self.input[index..self.index].iter().collect())
+      }
+      '.' => {
+        // Ranges (`..` and `..=`), and the variadic symbol
+        // (`...`) all start with two dots, whereas a single dot
+        // accesses a field (ex. `a.b`).
+        if self.peek_char() != Some('.') {
+          token::Token::SymbolDot
+        } else {
+          self.read_char();
+
+          match self.peek_char() {
+            Some('.') => {
+              self.read_char();
+
+              token::Token::SymbolVariadic
+            }
+            Some('=') => {
+              self.read_char();
+
+              token::Token::SymbolRangeInclusive
+            }
+            _ => token::Token::SymbolRange,
+          }
+        }
+      }
+      '\'' => {
+        self.read_char();
+
+        // A label must be immediately followed by its name.
+        if self.current_char.is_none() || !is_letter(self.current_char.unwrap()) {
+          return self.stop(String::from("expected the name of a label after `'`"));
+        }
+
+        return Some(token::Token::Label(read_identifier(self)));
+      }
       _ => {
         if is_letter(self.current_char.unwrap()) {
           let identifier = read_identifier(self);
@@ -137,7 +351,11 @@ impl Iterator for Lexer {
         } else if is_digit(self.current_char.unwrap()) {
           return Some(token::Token::LiteralInt(read_number(self)));
         } else {
-          return None;
+          let character = self.current_char.unwrap();
+
+          self.read_char();
+
+          return self.stop(format!("unexpected character `{}`", character));
         }
       }
     };
@@ -213,6 +431,34 @@ mod tests {
     assert_eq!(None, lexer.next());
   }
 
+  #[test]
+  fn lexer_next_trailing_whitespace() {
+    let mut lexer = Lexer::new("a \n".chars().collect());
+
+    lexer.read_char();
+
+    let (tokens, _) = lexer.collect_with_spans();
+
+    assert_eq!(vec![token::Token::Identifier(String::from("a"))], tokens);
+    assert_eq!(true, lexer.get_error().is_none());
+  }
+
+  #[test]
+  fn lexer_next_unexpected_character() {
+    let mut lexer = Lexer::new("a ?b".chars().collect());
+
+    lexer.read_char();
+
+    let (tokens, _) = lexer.collect_with_spans();
+
+    assert_eq!(vec![token::Token::Identifier(String::from("a"))], tokens);
+
+    let error = lexer.get_error().unwrap();
+
+    assert_eq!("unexpected character `?`", error.message);
+    assert_eq!(Some(span::Span { start: 2, end: 3 }), error.span);
+  }
+
   #[test]
   fn lexer_read_char_single() {
     let mut lexer = Lexer::new(vec!['a']);
@@ -248,6 +494,266 @@ mod tests {
     assert_eq!(false, lexer.is_whitespace());
   }
 
+  #[test]
+  fn lexer_next_label() {
+    let mut lexer = Lexer::new(vec!['\'', 'a']);
+
+    lexer.read_char();
+
+    assert_eq!(Some(token::Token::Label(String::from("a"))), lexer.next());
+  }
+
+  #[test]
+  fn lexer_next_label_without_name() {
+    let mut lexer = Lexer::new(vec!['\'', '?']);
+
+    lexer.read_char();
+
+    assert_eq!(None, lexer.next());
+
+    let error = lexer.get_error().unwrap();
+
+    assert_eq!("expected the name of a label after `'`", error.message);
+    assert_eq!(Some(span::Span { start: 0, end: 1 }), error.span);
+  }
+
+  #[test]
+  fn lexer_next_range() {
+    let mut lexer = Lexer::new(".....=..".chars().collect());
+
+    lexer.read_char();
+
+    assert_eq!(Some(token::Token::SymbolVariadic), lexer.next());
+    assert_eq!(Some(token::Token::SymbolRangeInclusive), lexer.next());
+    assert_eq!(Some(token::Token::SymbolRange), lexer.next());
+  }
+
+  #[test]
+  fn lexer_next_colons() {
+    let mut lexer = Lexer::new("a::b: :::".chars().collect());
+
+    lexer.read_char();
+
+    assert_eq!(
+      Some(token::Token::Identifier(String::from("a"))),
+      lexer.next()
+    );
+    assert_eq!(Some(token::Token::SymbolDoubleColon), lexer.next());
+    assert_eq!(
+      Some(token::Token::Identifier(String::from("b"))),
+      lexer.next()
+    );
+    assert_eq!(Some(token::Token::SymbolColon), lexer.next());
+    assert_eq!(Some(token::Token::SymbolDoubleColon), lexer.next());
+    assert_eq!(Some(token::Token::SymbolColon), lexer.next());
+  }
+
+  #[test]
+  fn lexer_next_attribute() {
+    let mut lexer = Lexer::new("#[export_name = \"foo bar\"]".chars().collect());
+
+    lexer.read_char();
+
+    assert_eq!(Some(token::Token::SymbolHash), lexer.next());
+    assert_eq!(Some(token::Token::SymbolBracketL), lexer.next());
+    assert_eq!(
+      Some(token::Token::Identifier(String::from("export_name"))),
+      lexer.next()
+    );
+    assert_eq!(Some(token::Token::SymbolEqual), lexer.next());
+    assert_eq!(
+      Some(token::Token::LiteralString(String::from("foo bar"))),
+      lexer.next()
+    );
+    assert_eq!(span::Span { start: 16, end: 25 }, lexer.get_token_span());
+    assert_eq!(Some(token::Token::SymbolBracketR), lexer.next());
+  }
+
+  #[test]
+  fn lexer_next_unterminated_string() {
+    let mut lexer = Lexer::new("\"foo\nbar\"".chars().collect());
+
+    lexer.read_char();
+
+    assert_eq!(None, lexer.next());
+
+    let error = lexer.get_error().unwrap();
+
+    assert_eq!("unterminated string literal", error.message);
+    assert_eq!(Some(span::Span { start: 0, end: 4 }), error.span);
+
+    let mut lexer = Lexer::new("\"foo".chars().collect());
+
+    lexer.read_char();
+
+    assert_eq!(None, lexer.next());
+
+    assert_eq!(
+      Some(span::Span { start: 0, end: 4 }),
+      lexer.get_error().unwrap().span
+    );
+  }
+
+  #[test]
+  fn lexer_next_single_dot() {
+    let mut lexer = Lexer::new("a.b".chars().collect());
+
+    lexer.read_char();
+
+    assert_eq!(
+      Some(token::Token::Identifier(String::from("a"))),
+      lexer.next()
+    );
+    assert_eq!(Some(token::Token::SymbolDot), lexer.next());
+    assert_eq!(
+      Some(token::Token::Identifier(String::from("b"))),
+      lexer.next()
+    );
+  }
+
+  #[test]
+  fn lexer_next_fat_arrow() {
+    let mut lexer = Lexer::new("= => =".chars().collect());
+
+    lexer.read_char();
+
+    assert_eq!(Some(token::Token::SymbolEqual), lexer.next());
+    assert_eq!(Some(token::Token::SymbolFatArrow), lexer.next());
+    assert_eq!(Some(token::Token::SymbolEqual), lexer.next());
+  }
+
+  #[test]
+  fn lexer_next_borrow_and_deref() {
+    let mut lexer = Lexer::new("&mut *a".chars().collect());
+
+    lexer.read_char();
+
+    assert_eq!(Some(token::Token::SymbolAmpersand), lexer.next());
+    assert_eq!(Some(token::Token::KeywordMut), lexer.next());
+    assert_eq!(Some(token::Token::SymbolAsterisk), lexer.next());
+    assert_eq!(
+      Some(token::Token::Identifier(String::from("a"))),
+      lexer.next()
+    );
+  }
+
+  #[test]
+  fn lexer_next_generic_parameters() {
+    let mut lexer = Lexer::new("max<T>".chars().collect());
+
+    lexer.read_char();
+
+    assert_eq!(
+      Some(token::Token::Identifier(String::from("max"))),
+      lexer.next()
+    );
+    assert_eq!(Some(token::Token::SymbolLessThan), lexer.next());
+    assert_eq!(
+      Some(token::Token::Identifier(String::from("T"))),
+      lexer.next()
+    );
+    assert_eq!(Some(token::Token::SymbolGreaterThan), lexer.next());
+  }
+
+  #[test]
+  fn lexer_next_trait_bounds() {
+    let mut lexer = Lexer::new("impl T: A + B &dyn A".chars().collect());
+
+    lexer.read_char();
+
+    assert_eq!(Some(token::Token::KeywordImpl), lexer.next());
+    assert_eq!(
+      Some(token::Token::Identifier(String::from("T"))),
+      lexer.next()
+    );
+    assert_eq!(Some(token::Token::SymbolColon), lexer.next());
+    assert_eq!(
+      Some(token::Token::Identifier(String::from("A"))),
+      lexer.next()
+    );
+    assert_eq!(Some(token::Token::SymbolPlus), lexer.next());
+    assert_eq!(
+      Some(token::Token::Identifier(String::from("B"))),
+      lexer.next()
+    );
+    assert_eq!(Some(token::Token::SymbolAmpersand), lexer.next());
+    assert_eq!(Some(token::Token::KeywordDyn), lexer.next());
+  }
+
+  #[test]
+  fn lexer_next_identifier_with_digits() {
+    let mut lexer = Lexer::new("i32 a1".chars().collect());
+
+    lexer.read_char();
+
+    assert_eq!(Some(token::Token::TypeInt32), lexer.next());
+    assert_eq!(
+      Some(token::Token::Identifier(String::from("a1"))),
+      lexer.next()
+    );
+  }
+
+  #[test]
+  fn lexer_next_literal_int() {
+    let mut lexer = Lexer::new("42".chars().collect());
+
+    lexer.read_char();
+
+    assert_eq!(Some(token::Token::LiteralInt(vec!['4', '2'])), lexer.next());
+  }
+
+  #[test]
+  fn lexer_comments() {
+    let mut lexer = Lexer::new("// foo\nfn // bar\n// baz".chars().collect());
+
+    lexer.read_char();
+
+    let (tokens, _) = lexer.collect_with_spans();
+
+    assert_eq!(vec![token::Token::KeywordFn], tokens);
+
+    assert_eq!(
+      vec![
+        Comment {
+          text: String::from("// foo"),
+          span: span::Span { start: 0, end: 6 },
+          is_trailing: false,
+        },
+        Comment {
+          text: String::from("// bar"),
+          span: span::Span { start: 10, end: 16 },
+          is_trailing: true,
+        },
+        Comment {
+          text: String::from("// baz"),
+          span: span::Span { start: 17, end: 23 },
+          is_trailing: false,
+        },
+      ],
+      lexer.get_comments()
+    );
+  }
+
+  #[test]
+  fn lexer_collect_with_spans() {
+    let mut lexer = Lexer::new("fn  foo(".chars().collect());
+
+    lexer.read_char();
+
+    let (tokens, spans) = lexer.collect_with_spans();
+
+    assert_eq!(3, tokens.len());
+
+    assert_eq!(
+      vec![
+        span::Span { start: 0, end: 2 },
+        span::Span { start: 4, end: 7 },
+        span::Span { start: 7, end: 8 },
+      ],
+      spans
+    );
+  }
+
   // TODO:
   // #[test]
   // fn lexer_lex_keywords() {
@@ -1,8 +1,9 @@
 use crate::{
-  block, diagnostic, external, function, int_kind, namespace, node, pass, pass::Pass, prototype,
-  void_kind,
+  array_kind, block, bool_kind, c_abi, diagnostic, dyn_kind, enum_decl, enum_kind, external,
+  function, impl_decl, int_kind, mangling, namespace, node, pass, pass::Pass, pattern, prototype,
+  reference_kind, slice_kind, span, struct_decl, struct_kind, trait_decl, void_kind,
 };
-use inkwell::types::AnyType;
+use inkwell::types::{AnyType, BasicType};
 
 macro_rules! assert {
   ($condition:expr) => {
@@ -10,20 +11,208 @@ macro_rules! assert {
       return Err(diagnostic::Diagnostic {
         message: String::from("internal assertion failed"),
         severity: diagnostic::DiagnosticSeverity::Internal,
+        span: None,
+        notes: vec![],
       });
     }
   };
 }
 
+// Modules target x86-64, and lay out values as its C ABI does (see
+// [`c_abi::find_layout`]). Unlike the data layout LLVM 13 assumes
+// for x86-64, `i128` is aligned to 16 bytes, as C aligns `__int128`.
+const TARGET_TRIPLE: &str = "x86_64-unknown-linux-gnu";
+const DATA_LAYOUT: &str =
+  "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-i128:128-f80:128-n8:16:32:64-S128";
+
+// The basic blocks targeted by `break` and `continue`
+// statements within a loop.
+struct LlvmLoopFrame<'a> {
+  label: Option<String>,
+  llvm_continue_block: inkwell::basic_block::BasicBlock<'a>,
+  llvm_break_block: inkwell::basic_block::BasicBlock<'a>,
+  // Whether the break block has any predecessors. An infinite
+  // loop's exit block is only reachable through a `break`.
+  is_break_block_reachable: bool,
+}
+
+// The LLVM representation of a value bound to a name.
+#[derive(Clone, Copy)]
+enum LlvmNamedValue<'a> {
+  // An immutable SSA value (ex. a `for` loop's iterator).
+  Value(inkwell::values::BasicValueEnum<'a>),
+  // A stack slot holding the value (ex. a parameter), which
+  // makes the value addressable.
+  StackSlot(inkwell::values::PointerValue<'a>),
+}
+
+// The elements of an array or a slice, as a pointer to the first
+// element, along with the amount of elements (as an `i64`).
+type LlvmElements<'a> = (
+  inkwell::values::PointerValue<'a>,
+  inkwell::values::IntValue<'a>,
+);
+
+// How the parameters and return value of an extern are passed
+// under the C calling convention (see the `c_abi` module).
+#[derive(Clone)]
+struct LlvmExternAbi<'a> {
+  parameter_modes: Vec<c_abi::PassingMode>,
+  // The types of the parameters, before they are passed as the
+  // calling convention requires, or [`None`] for parameters which
+  // have no values (ex. `void`).
+  llvm_parameter_types: Vec<Option<inkwell::types::BasicTypeEnum<'a>>>,
+  // How each parameter is extended to fill its register, if at all.
+  parameter_extensions: Vec<Option<c_abi::Extension>>,
+  return_mode: c_abi::PassingMode,
+  return_extension: Option<c_abi::Extension>,
+  // The type of the value returned, before it is passed as the
+  // calling convention requires.
+  llvm_return_type: inkwell::types::AnyTypeEnum<'a>,
+}
+
+impl LlvmExternAbi<'_> {
+  // Whether the value returned is written through a hidden pointer,
+  // given as the first argument.
+  fn has_return_pointer(&self) -> bool {
+    self.return_mode == c_abi::PassingMode::Indirect
+  }
+}
+
+// The methods of an implementation of a trait, in the order of the
+// trait's methods, through which calls on trait objects are
+// dispatched.
+struct LlvmVtable<'a> {
+  trait_path: String,
+  // The type of the values of the implementing kind.
+  llvm_kind_type: inkwell::types::AnyTypeEnum<'a>,
+  llvm_global: inkwell::values::GlobalValue<'a>,
+}
+
+// Retrieve the given type as a type of values, or [`None`] if it
+// has no values (ex. `void`).
+fn as_basic_type(
+  llvm_type: inkwell::types::AnyTypeEnum<'_>,
+) -> Option<inkwell::types::BasicTypeEnum<'_>> {
+  Some(match llvm_type {
+    inkwell::types::AnyTypeEnum::ArrayType(array_type) => {
+      inkwell::types::BasicTypeEnum::ArrayType(array_type)
+    }
+    inkwell::types::AnyTypeEnum::FloatType(float_type) => {
+      inkwell::types::BasicTypeEnum::FloatType(float_type)
+    }
+    inkwell::types::AnyTypeEnum::IntType(int_type) => {
+      inkwell::types::BasicTypeEnum::IntType(int_type)
+    }
+    inkwell::types::AnyTypeEnum::PointerType(pointer_type) => {
+      inkwell::types::BasicTypeEnum::PointerType(pointer_type)
+    }
+    inkwell::types::AnyTypeEnum::StructType(struct_type) => {
+      inkwell::types::BasicTypeEnum::StructType(struct_type)
+    }
+    inkwell::types::AnyTypeEnum::VectorType(vector_type) => {
+      inkwell::types::BasicTypeEnum::VectorType(vector_type)
+    }
+    inkwell::types::AnyTypeEnum::FunctionType(_) | inkwell::types::AnyTypeEnum::VoidType(_) => {
+      return None
+    }
+  })
+}
+
+// Whether the given struct type is that of slices, which are named
+// after their kind (ex. `[i32]`), unlike structs and enums.
+fn is_slice_type(llvm_struct_type: inkwell::types::StructType<'_>) -> bool {
+  llvm_struct_type
+    .get_name()
+    .and_then(|name| name.to_str().ok())
+    .map_or(false, |name| name.starts_with('['))
+}
+
+// Retrieve the path of the trait of the given struct type, if it is
+// that of references to trait objects, which are named after their
+// kind (ex. `dyn Shape`).
+fn find_dyn_trait_path(llvm_struct_type: inkwell::types::StructType<'_>) -> Option<String> {
+  llvm_struct_type
+    .get_name()
+    .and_then(|name| name.to_str().ok())
+    .and_then(|name| name.strip_prefix("dyn "))
+    .map(String::from)
+}
+
+// Public functions may be called from other namespaces, and must
+// be visible to the linker, whereas private functions are local to
+// the module of their namespace. Functions given an export name
+// (ex. `#[no_mangle]`) are meant to be linked against by that name,
+// and are visible to the linker regardless.
+fn find_function_linkage(function: &function::Function) -> inkwell::module::Linkage {
+  match function.is_public || function.find_export_name().is_some() {
+    true => inkwell::module::Linkage::External,
+    false => inkwell::module::Linkage::Private,
+  }
+}
+
+// Locate the diagnostic of the result at the given span, unless it
+// is already located.
+fn with_fallback_span<T>(
+  result: Result<T, diagnostic::Diagnostic>,
+  span: Option<span::Span>,
+) -> Result<T, diagnostic::Diagnostic> {
+  result.map_err(|diagnostic| diagnostic::Diagnostic {
+    span: diagnostic.span.or(span),
+    ..diagnostic
+  })
+}
+
 pub struct LlvmLoweringPass<'a> {
   llvm_context: &'a inkwell::context::Context,
   pub llvm_module: inkwell::module::Module<'a>,
+  // Kinds are keyed by their contents rather than by node id,
+  // since equal kinds always lower into the same LLVM type.
   llvm_type_map: std::collections::HashMap<node::AnyKindNode, inkwell::types::AnyTypeEnum<'a>>,
-  llvm_value_map:
-    std::collections::HashMap<node::AnyLiteralNode, inkwell::values::BasicValueEnum<'a>>,
+  llvm_value_map: node::NodeMap<inkwell::values::BasicValueEnum<'a>>,
   llvm_function_buffer: Option<inkwell::values::FunctionValue<'a>>,
   llvm_basic_block_buffer: Option<inkwell::basic_block::BasicBlock<'a>>,
   llvm_builder_buffer: inkwell::builder::Builder<'a>,
+  // The loops enclosing the statement being lowered, with the
+  // innermost loop last.
+  llvm_loop_stack: Vec<LlvmLoopFrame<'a>>,
+  // The values bound to names (ex. a `for` loop's iterator),
+  // with one map per lexical scope, innermost last.
+  llvm_named_value_scopes: Vec<std::collections::HashMap<String, LlvmNamedValue<'a>>>,
+  // The functions and externs declared so far, by their path
+  // relative to the outermost namespace (ex. `a::b`).
+  llvm_function_map: std::collections::HashMap<String, inkwell::values::FunctionValue<'a>>,
+  // The path of the namespace being lowered, relative to the
+  // outermost namespace.
+  namespace_path: Vec<String>,
+  // The name of the outermost namespace being lowered, which is
+  // part of the symbol of every function within it.
+  outermost_namespace_name: Option<String>,
+  // The symbols of the functions defined so far, which must not be
+  // shared by two functions.
+  llvm_defined_symbols: std::collections::HashSet<String>,
+  // The declarations of all structs, by their path relative to the
+  // outermost namespace, which struct kinds are expected to have
+  // been resolved to.
+  struct_map: c_abi::StructMap,
+  // The declarations of all enums, by their path relative to the
+  // outermost namespace.
+  enum_map: c_abi::EnumMap,
+  // How the parameters and return value of each extern are passed,
+  // by its path relative to the outermost namespace.
+  llvm_extern_abi_map: std::collections::HashMap<String, LlvmExternAbi<'a>>,
+  // The declarations of all traits, by their path relative to the
+  // outermost namespace.
+  trait_map: std::collections::HashMap<String, trait_decl::TraitDecl>,
+  // The vtables of all implementations declared so far.
+  llvm_vtables: Vec<LlvmVtable<'a>>,
+  // The sizes and alignments of LLVM types, following the data
+  // layout of the module.
+  llvm_target_data: inkwell::targets::TargetData,
+  // Whether runtime checks (ex. that indices are within the bounds
+  // of arrays) are emitted, as in debug builds. Release builds omit
+  // them.
+  pub is_debug_build: bool,
 }
 
 impl<'a> LlvmLoweringPass<'a> {
@@ -31,31 +220,75 @@ impl<'a> LlvmLoweringPass<'a> {
     llvm_context: &'a inkwell::context::Context,
     llvm_module: inkwell::module::Module<'a>,
   ) -> Self {
+    let llvm_target_data = inkwell::targets::TargetData::create(DATA_LAYOUT);
+
+    llvm_module.set_triple(&inkwell::targets::TargetTriple::create(TARGET_TRIPLE));
+    llvm_module.set_data_layout(&llvm_target_data.get_data_layout());
+
     Self {
       llvm_context,
       llvm_module,
       llvm_type_map: std::collections::HashMap::new(),
-      llvm_value_map: std::collections::HashMap::new(),
+      llvm_value_map: node::NodeMap::new(),
       llvm_function_buffer: None,
       llvm_basic_block_buffer: None,
       llvm_builder_buffer: llvm_context.create_builder(),
+      llvm_loop_stack: vec![],
+      llvm_named_value_scopes: vec![],
+      llvm_function_map: std::collections::HashMap::new(),
+      namespace_path: vec![],
+      outermost_namespace_name: None,
+      llvm_defined_symbols: std::collections::HashSet::new(),
+      struct_map: c_abi::StructMap::new(),
+      enum_map: c_abi::EnumMap::new(),
+      llvm_extern_abi_map: std::collections::HashMap::new(),
+      trait_map: std::collections::HashMap::new(),
+      llvm_vtables: vec![],
+      llvm_target_data,
+      is_debug_build: true,
     }
   }
 
-  // TODO: Support for parameters.
+  // Determine the path of a top-level node within the namespace
+  // being lowered, relative to the outermost namespace.
+  fn get_qualified_name(&self, name: &str) -> String {
+    let mut path = self.namespace_path.clone();
+
+    path.push(String::from(name));
+    path.join("::")
+  }
+
   fn get_function_type_from(
     llvm_return_type: &inkwell::types::AnyTypeEnum<'a>,
+    llvm_parameter_types: &[inkwell::types::BasicMetadataTypeEnum<'a>],
     is_variadic: bool,
   ) -> Result<inkwell::types::FunctionType<'a>, diagnostic::Diagnostic> {
     Ok(match llvm_return_type {
-      inkwell::types::AnyTypeEnum::IntType(int_type) => int_type.fn_type(&[], is_variadic),
-      inkwell::types::AnyTypeEnum::FloatType(float_type) => float_type.fn_type(&[], is_variadic),
-      inkwell::types::AnyTypeEnum::VoidType(void_type) => void_type.fn_type(&[], is_variadic),
+      inkwell::types::AnyTypeEnum::IntType(int_type) => {
+        int_type.fn_type(llvm_parameter_types, is_variadic)
+      }
+      inkwell::types::AnyTypeEnum::FloatType(float_type) => {
+        float_type.fn_type(llvm_parameter_types, is_variadic)
+      }
+      inkwell::types::AnyTypeEnum::VoidType(void_type) => {
+        void_type.fn_type(llvm_parameter_types, is_variadic)
+      }
+      inkwell::types::AnyTypeEnum::StructType(struct_type) => {
+        struct_type.fn_type(llvm_parameter_types, is_variadic)
+      }
+      inkwell::types::AnyTypeEnum::ArrayType(array_type) => {
+        array_type.fn_type(llvm_parameter_types, is_variadic)
+      }
+      inkwell::types::AnyTypeEnum::PointerType(pointer_type) => {
+        pointer_type.fn_type(llvm_parameter_types, is_variadic)
+      }
       _ => {
         // TODO: Better implementation.
         return Err(diagnostic::Diagnostic {
           message: String::from("unexpected point reached"),
           severity: diagnostic::DiagnosticSeverity::Internal,
+          span: None,
+          notes: vec![],
         });
       }
     })
@@ -76,6 +309,24 @@ impl<'a> LlvmLoweringPass<'a> {
       match node {
         node::AnyKindNode::IntKind(value) => self.visit_int_kind(&value)?,
         node::AnyKindNode::VoidKind(value) => self.visit_void_kind(&value)?,
+        node::AnyKindNode::BoolKind(value) => self.visit_bool_kind(&value)?,
+        node::AnyKindNode::StructKind(value) => self.visit_struct_kind(&value)?,
+        node::AnyKindNode::EnumKind(value) => self.visit_enum_kind(&value)?,
+        node::AnyKindNode::ArrayKind(value) => self.visit_array_kind(&value)?,
+        node::AnyKindNode::SliceKind(value) => self.visit_slice_kind(&value)?,
+        node::AnyKindNode::ReferenceKind(value) => self.visit_reference_kind(&value)?,
+        node::AnyKindNode::DynKind(value) => self.visit_dyn_kind(&value)?,
+        node::AnyKindNode::GenericKind(value) => {
+          return Err(diagnostic::Diagnostic {
+            message: format!(
+              "generic kind `{}` must be substituted before lowering",
+              value
+            ),
+            severity: diagnostic::DiagnosticSeverity::Internal,
+            span: None,
+            notes: vec![],
+          })
+        }
       };
     }
 
@@ -86,255 +337,5169 @@ impl<'a> LlvmLoweringPass<'a> {
   // was already previously visited, simply retrieve and return
   // the result from the LLVM values map.
   //
+  // Integer literals take the integer type expected by their
+  // context, if any, and are thus lowered anew each time rather
+  // than retrieved by their id alone.
+  //
   // Returns [`None`] if visiting the node did not insert a result
   // into the LLVM values map.
   fn visit_or_retrieve_value(
-    &self,
+    &mut self,
     node: &node::AnyLiteralNode,
-  ) -> Result<Option<&inkwell::values::BasicValueEnum<'a>>, diagnostic::Diagnostic> {
-    if !self.llvm_value_map.contains_key(node) {
-      match node {
-        _ => {
-          // TODO: Implement.
-          return Err(diagnostic::Diagnostic {
-            message: String::from("unimplemented"),
-            severity: diagnostic::DiagnosticSeverity::Internal,
-          });
+    llvm_expected_type: Option<inkwell::types::BasicTypeEnum<'a>>,
+  ) -> Result<Option<inkwell::values::BasicValueEnum<'a>>, diagnostic::Diagnostic> {
+    match node {
+      node::AnyLiteralNode::BoolLiteral(bool_literal) => {
+        if !self.llvm_value_map.contains_key(&bool_literal.id) {
+          self.visit_bool_literal(bool_literal)?;
         }
-      };
-    }
 
-    Ok(self.llvm_value_map.get(&node))
+        Ok(self.llvm_value_map.get(&bool_literal.id).cloned())
+      }
+      node::AnyLiteralNode::IntLiteral(int_literal) => self
+        .visit_int_literal_as(int_literal, llvm_expected_type)
+        .map(Some),
+    }
   }
-}
 
-impl<'a> pass::Pass<'a> for LlvmLoweringPass<'a> {
-  fn visit_prototype(&mut self, _prototype: &prototype::Prototype) -> pass::PassResult {
-    // TODO
-    // inkwell::values::GenericValue
-    Ok(())
-  }
+  // Lower an integer literal into a constant of the expected
+  // integer type, or `i32` if no integer type is expected.
+  fn visit_int_literal_as(
+    &self,
+    int_literal: &node::IntLiteral,
+    llvm_expected_type: Option<inkwell::types::BasicTypeEnum<'a>>,
+  ) -> Result<inkwell::values::BasicValueEnum<'a>, diagnostic::Diagnostic> {
+    let llvm_int_type = match llvm_expected_type {
+      // Booleans are also represented as (1-bit) integers.
+      Some(inkwell::types::BasicTypeEnum::IntType(llvm_int_type))
+        if llvm_int_type.get_bit_width() > 1 =>
+      {
+        llvm_int_type
+      }
+      _ => self.llvm_context.i32_type(),
+    };
 
-  fn visit_int_kind(&mut self, int_kind: &int_kind::IntKind) -> pass::PassResult {
-    self.llvm_type_map.insert(
-      node::AnyKindNode::IntKind(*int_kind),
-      match int_kind.size {
-        int_kind::IntSize::Signed8 => self.llvm_context.i8_type().as_any_type_enum(),
-        int_kind::IntSize::Signed16 => self.llvm_context.i16_type().as_any_type_enum(),
-        int_kind::IntSize::Signed32 => self.llvm_context.i32_type().as_any_type_enum(),
-        int_kind::IntSize::Signed64 => self.llvm_context.i64_type().as_any_type_enum(),
-        int_kind::IntSize::Signed128 => self.llvm_context.i128_type().as_any_type_enum(),
-      },
+    if !int_literal.fits_in_bit_width(llvm_int_type.get_bit_width()) {
+      return Err(diagnostic::Diagnostic {
+        message: format!(
+          "integer literal `{}` is out of range for `i{}`",
+          int_literal.value,
+          llvm_int_type.get_bit_width()
+        ),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(int_literal.span),
+        notes: vec![],
+      });
+    }
+
+    let llvm_value = llvm_int_type.const_int_from_string(
+      int_literal.value.as_str(),
+      inkwell::types::StringRadix::Decimal,
     );
-    Ok(())
+
+    assert!(llvm_value.is_some());
+
+    Ok(inkwell::values::BasicValueEnum::IntValue(
+      llvm_value.unwrap(),
+    ))
   }
 
-  fn visit_void_kind(&mut self, void_kind: &void_kind::VoidKind) -> pass::PassResult {
-    self.llvm_type_map.insert(
-      node::AnyKindNode::VoidKind(*void_kind),
-      self.llvm_context.void_type().as_any_type_enum(),
-    );
-    Ok(())
+  // Lower the expression into an LLVM value, at the current
+  // position of the builder.
+  fn visit_expr(
+    &mut self,
+    expr: &node::AnyExprNode,
+  ) -> Result<inkwell::values::BasicValueEnum<'a>, diagnostic::Diagnostic> {
+    self.visit_expr_as(expr, None)
   }
 
-  fn visit_function(&mut self, function: &function::Function) -> pass::PassResult {
-    let llvm_return_type =
-      self.visit_or_retrieve_type(&function.prototype.return_kind_group.kind)?;
+  // Lower the expression into an LLVM value, using the type
+  // expected by its context for literals whose type depends on
+  // it (ex. integer literals).
+  fn visit_expr_as(
+    &mut self,
+    expr: &node::AnyExprNode,
+    llvm_expected_type: Option<inkwell::types::BasicTypeEnum<'a>>,
+  ) -> Result<inkwell::values::BasicValueEnum<'a>, diagnostic::Diagnostic> {
+    let llvm_value = match expr {
+      node::AnyExprNode::Literal(literal) => {
+        self.visit_or_retrieve_value(literal, llvm_expected_type)?
+      }
+      node::AnyExprNode::VariableRef(variable_ref) => {
+        Some(match self.retrieve_named_value(variable_ref)? {
+          LlvmNamedValue::Value(llvm_value) => llvm_value,
+          LlvmNamedValue::StackSlot(llvm_stack_slot) => self
+            .llvm_builder_buffer
+            .build_load(llvm_stack_slot, variable_ref.name.as_str()),
+        })
+      }
+      node::AnyExprNode::CallExpr(call_expr) => {
+        let llvm_value = self.visit_call_expr(call_expr)?;
 
-    assert!(llvm_return_type.is_some());
+        if llvm_value.is_none() {
+          return Err(diagnostic::Diagnostic {
+            message: format!("call to `{}` does not produce a value", call_expr.callee),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(call_expr.span),
+            notes: vec![],
+          });
+        }
 
-    let llvm_function_type = LlvmLoweringPass::get_function_type_from(
-      &llvm_return_type.unwrap(),
-      function.prototype.is_variadic,
-    )?;
+        llvm_value
+      }
+      node::AnyExprNode::FieldAccessExpr(field_access_expr) => {
+        Some(self.visit_field_access_expr(field_access_expr)?)
+      }
+      node::AnyExprNode::StructLiteral(struct_literal) => {
+        Some(self.visit_struct_literal(struct_literal)?)
+      }
+      node::AnyExprNode::VariantLiteral(variant_literal) => {
+        Some(self.visit_variant_literal(variant_literal)?)
+      }
+      node::AnyExprNode::MatchExpr(match_expr) => {
+        let llvm_value = self.visit_match_expr(match_expr, llvm_expected_type)?;
 
-    self.llvm_function_buffer = Some(self.llvm_module.add_function(
-      function.prototype.name.as_str(),
-      llvm_function_type,
-      Some(match function.is_public {
-        true => inkwell::module::Linkage::External,
-        false => inkwell::module::Linkage::Private,
-      }),
-    ));
+        if llvm_value.is_none() {
+          return Err(diagnostic::Diagnostic {
+            message: String::from("match does not produce a value"),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(match_expr.span),
+            notes: vec![],
+          });
+        }
+
+        llvm_value
+      }
+      node::AnyExprNode::ArrayLiteral(array_literal) => {
+        Some(self.visit_array_literal(array_literal, llvm_expected_type)?)
+      }
+      node::AnyExprNode::IndexExpr(index_expr) => {
+        let llvm_element_pointer = self.build_element_pointer(index_expr)?;
+
+        Some(
+          self
+            .llvm_builder_buffer
+            .build_load(llvm_element_pointer, "element"),
+        )
+      }
+      node::AnyExprNode::LenExpr(len_expr) => Some(inkwell::values::BasicValueEnum::IntValue(
+        self.visit_elements(&len_expr.base)?.1,
+      )),
+      node::AnyExprNode::BorrowExpr(borrow_expr) => {
+        Some(inkwell::values::BasicValueEnum::PointerValue(
+          self.visit_borrow_expr(borrow_expr, llvm_expected_type)?,
+        ))
+      }
+      node::AnyExprNode::DerefExpr(deref_expr) => {
+        let llvm_pointer = self.visit_reference(&deref_expr.value)?;
 
-    let empty_body_block = block::Block {
-      statements: vec![block::AnyStatementNode::ReturnStmt(block::ReturnStmt {
-        value: None,
-      })],
+        Some(self.llvm_builder_buffer.build_load(llvm_pointer, "deref"))
+      }
     };
 
-    // If the body block contains no instructions, force
-    // a return void instruction.
-    self.visit_block(if function.body.statements.is_empty() {
-      &empty_body_block
-    } else {
-      &function.body
-    })
+    assert!(llvm_value.is_some());
+
+    // Arrays are turned into slices of their elements where a slice
+    // is expected.
+    match (llvm_value.unwrap(), llvm_expected_type) {
+      (
+        inkwell::values::BasicValueEnum::ArrayValue(llvm_array_value),
+        Some(inkwell::types::BasicTypeEnum::StructType(llvm_slice_type)),
+      ) if is_slice_type(llvm_slice_type) => {
+        let llvm_array_pointer = self.spill_array(llvm_array_value)?;
+        let (llvm_elements_pointer, llvm_length) = self.build_array_elements(llvm_array_pointer)?;
+
+        self.build_slice(llvm_slice_type, llvm_elements_pointer, llvm_length)
+      }
+      // References are turned into references to trait objects where
+      // one is expected.
+      (
+        inkwell::values::BasicValueEnum::PointerValue(llvm_pointer),
+        Some(inkwell::types::BasicTypeEnum::StructType(llvm_dyn_type)),
+      ) if find_dyn_trait_path(llvm_dyn_type).is_some() => {
+        self.build_dyn_reference(llvm_dyn_type, llvm_pointer)
+      }
+      (llvm_value, _) => Ok(llvm_value),
+    }
   }
 
-  fn visit_namespace(&mut self, namespace: &namespace::Namespace) -> pass::PassResult {
-    for top_level_node in namespace.symbol_table.values() {
-      match top_level_node {
-        namespace::TopLevelNode::Function(function) => self.visit_function(function)?,
-        namespace::TopLevelNode::External(external) => self.visit_external(external)?,
-      };
+  // Lower an expression which may produce no value (ex. a call to a
+  // function returning `void`), as allowed for expressions used as
+  // statements, or as the body of a match arm.
+  fn visit_expr_or_void(
+    &mut self,
+    expr: &node::AnyExprNode,
+    llvm_expected_type: Option<inkwell::types::BasicTypeEnum<'a>>,
+  ) -> Result<Option<inkwell::values::BasicValueEnum<'a>>, diagnostic::Diagnostic> {
+    match expr {
+      node::AnyExprNode::CallExpr(call_expr) => self.visit_call_expr(call_expr),
+      node::AnyExprNode::MatchExpr(match_expr) => {
+        self.visit_match_expr(match_expr, llvm_expected_type)
+      }
+      expr => Ok(Some(self.visit_expr_as(expr, llvm_expected_type)?)),
     }
+  }
 
-    Ok(())
+  // Lower the type of a value stored within another (ex. a field of
+  // a struct). References are lowered into pointers to the
+  // referenced kind.
+  //
+  // Returns [`None`] for kinds which have no values (ex. `void`).
+  fn get_stored_type_of(
+    &mut self,
+    kind_group: &node::KindGroup,
+  ) -> Result<Option<inkwell::types::BasicTypeEnum<'a>>, diagnostic::Diagnostic> {
+    let llvm_type = self
+      .visit_or_retrieve_type(&kind_group.to_value_kind())?
+      .copied();
+
+    assert!(llvm_type.is_some());
+
+    Ok(as_basic_type(llvm_type.unwrap()))
   }
 
-  fn visit_external(&mut self, external: &external::External) -> pass::PassResult {
-    let llvm_function_type = LlvmLoweringPass::get_function_type_from(
-      self
-        .visit_or_retrieve_type(&external.prototype.return_kind_group.kind)?
-        .unwrap(),
-      external.prototype.is_variadic,
-    );
+  // Lower the type of a struct's field.
+  fn get_field_type_of(
+    &mut self,
+    field: &struct_decl::StructField,
+  ) -> Result<inkwell::types::BasicTypeEnum<'a>, diagnostic::Diagnostic> {
+    match self.get_stored_type_of(&field.kind_group)? {
+      Some(llvm_type) => Ok(llvm_type),
+      None => Err(diagnostic::Diagnostic {
+        message: format!(
+          "field `{}` cannot be of kind `{}`",
+          field.name, field.kind_group.kind
+        ),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(field.span),
+        notes: vec![],
+      }),
+    }
+  }
 
-    // TODO: Are externs always 'External' linkage?
-    self.llvm_module.add_function(
-      external.prototype.name.as_str(),
-      llvm_function_type?,
-      Some(inkwell::module::Linkage::External),
-    );
+  // Find the position of the accessed field within the given
+  // struct type, which is named after the struct's path.
+  fn find_field_index(
+    &self,
+    llvm_struct_type: inkwell::types::StructType<'a>,
+    field_access_expr: &node::FieldAccessExpr,
+  ) -> Result<u32, diagnostic::Diagnostic> {
+    let field = llvm_struct_type
+      .get_name()
+      .and_then(|name| name.to_str().ok())
+      .and_then(|name| self.struct_map.get(name))
+      .and_then(|struct_decl| struct_decl.find_field(&field_access_expr.field_name));
 
-    Ok(())
+    match field {
+      Some((index, _)) => Ok(index as u32),
+      None => Err(diagnostic::Diagnostic {
+        message: format!("no field `{}` on this value", field_access_expr.field_name),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(field_access_expr.span),
+        notes: vec![],
+      }),
+    }
   }
 
-  fn visit_block(&mut self, block: &block::Block) -> pass::PassResult {
-    assert!(self.llvm_function_buffer.is_some());
+  // Compute a pointer to the accessed field, given a pointer to
+  // the struct containing it.
+  fn build_field_pointer(
+    &self,
+    llvm_struct_pointer: inkwell::values::PointerValue<'a>,
+    field_access_expr: &node::FieldAccessExpr,
+  ) -> Result<inkwell::values::PointerValue<'a>, diagnostic::Diagnostic> {
+    let llvm_struct_type = match llvm_struct_pointer.get_type().get_element_type() {
+      inkwell::types::AnyTypeEnum::StructType(llvm_struct_type) => llvm_struct_type,
+      _ => {
+        return Err(diagnostic::Diagnostic {
+          message: format!(
+            "cannot access field `{}` of a value which is not a struct",
+            field_access_expr.field_name
+          ),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(field_access_expr.span),
+          notes: vec![],
+        })
+      }
+    };
 
-    self.llvm_basic_block_buffer = Some(
-      self
-        .llvm_context
-        // TODO: Name basic block?
-        .append_basic_block(self.llvm_function_buffer.unwrap(), ""),
+    let index = self.find_field_index(llvm_struct_type, field_access_expr)?;
+    let llvm_field_pointer = self.llvm_builder_buffer.build_struct_gep(
+      llvm_struct_pointer,
+      index,
+      format!("{}.addr", field_access_expr.field_name).as_str(),
     );
 
-    self
-      .llvm_builder_buffer
-      .position_at_end(self.llvm_basic_block_buffer.unwrap());
-
-    for statement in &block.statements {
-      match statement {
-        block::AnyStatementNode::ReturnStmt(return_stmt) => self.visit_return_stmt(&return_stmt)?,
-      };
-    }
+    assert!(llvm_field_pointer.is_ok());
 
-    Ok(())
+    Ok(llvm_field_pointer.unwrap())
   }
 
-  fn visit_return_stmt(&mut self, return_stmt: &block::ReturnStmt) -> pass::PassResult {
-    assert!(self.llvm_basic_block_buffer.is_some());
+  // Lower a field access, reading the field of the struct produced
+  // by its base, or of the struct it points to.
+  fn visit_field_access_expr(
+    &mut self,
+    field_access_expr: &node::FieldAccessExpr,
+  ) -> Result<inkwell::values::BasicValueEnum<'a>, diagnostic::Diagnostic> {
+    match self.visit_expr(&field_access_expr.base)? {
+      inkwell::values::BasicValueEnum::StructValue(llvm_struct_value) => {
+        let index = self.find_field_index(llvm_struct_value.get_type(), field_access_expr)?;
 
-    let value = match return_stmt.value {
-      Some(value) => self.visit_or_retrieve_value(&value)?,
-      None => None,
-    };
+        let llvm_value = self.llvm_builder_buffer.build_extract_value(
+          llvm_struct_value,
+          index,
+          field_access_expr.field_name.as_str(),
+        );
 
-    // FIXME: Solve error out.
-    // self.llvm_builder_buffer.build_return(Some(&value));
+        assert!(llvm_value.is_some());
 
-    Ok(())
-  }
+        Ok(llvm_value.unwrap())
+      }
+      inkwell::values::BasicValueEnum::PointerValue(mut llvm_struct_pointer) => {
+        // References to references are followed until the struct
+        // is reached.
+        while let inkwell::types::AnyTypeEnum::PointerType(_) =
+          llvm_struct_pointer.get_type().get_element_type()
+        {
+          llvm_struct_pointer = self
+            .llvm_builder_buffer
+            .build_load(llvm_struct_pointer, "deref")
+            .into_pointer_value();
+        }
 
-  fn visit_bool_literal(&mut self, bool_literal: &node::BoolLiteral) -> pass::PassResult {
-    self.llvm_value_map.insert(
-      node::AnyLiteralNode::BoolLiteral(*bool_literal),
-      inkwell::values::BasicValueEnum::IntValue(
-        self
-          .llvm_context
-          .bool_type()
-          .const_int(bool_literal.value as u64, false),
-      ),
-    );
+        let llvm_field_pointer =
+          self.build_field_pointer(llvm_struct_pointer, field_access_expr)?;
 
-    Ok(())
+        Ok(
+          self
+            .llvm_builder_buffer
+            .build_load(llvm_field_pointer, field_access_expr.field_name.as_str()),
+        )
+      }
+      _ => Err(diagnostic::Diagnostic {
+        message: format!(
+          "cannot access field `{}` of a value which is not a struct",
+          field_access_expr.field_name
+        ),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(field_access_expr.span),
+        notes: vec![],
+      }),
+    }
   }
-}
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+  // Lower a struct literal into a struct value, built up by
+  // inserting the values of its fields in the order they are given.
+  fn visit_struct_literal(
+    &mut self,
+    struct_literal: &node::StructLiteral,
+  ) -> Result<inkwell::values::BasicValueEnum<'a>, diagnostic::Diagnostic> {
+    let struct_kind = node::AnyKindNode::StructKind(struct_kind::StructKind {
+      path: struct_literal.path.segments.clone(),
+    });
 
-  #[test]
-  fn llvm_lowering_pass_proper_initial_values() {
-    let llvm_context = inkwell::context::Context::create();
-    let llvm_module = llvm_context.create_module("test");
+    let llvm_struct_type = match self.visit_or_retrieve_type(&struct_kind)?.copied() {
+      Some(inkwell::types::AnyTypeEnum::StructType(llvm_struct_type)) => llvm_struct_type,
+      _ => {
+        return Err(diagnostic::Diagnostic {
+          message: format!("cannot find struct `{}` in this scope", struct_literal.path),
+          severity: diagnostic::DiagnosticSeverity::Internal,
+          span: Some(struct_literal.span),
+          notes: vec![],
+        })
+      }
+    };
 
-    assert_eq!(
-      true,
-      LlvmLoweringPass::new(&llvm_context, llvm_module)
-        .llvm_type_map
-        .is_empty()
-    );
-  }
+    let struct_decl = self
+      .struct_map
+      .get(&struct_literal.path.to_string())
+      .cloned();
 
-  #[test]
-  fn llvm_lowering_pass_visit_or_retrieve_type() {
-    let llvm_context = inkwell::context::Context::create();
-    let llvm_module = llvm_context.create_module("test");
-    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+    assert!(struct_decl.is_some());
 
-    let int_kind_box = node::AnyKindNode::IntKind(int_kind::IntKind {
-      size: int_kind::IntSize::Signed32,
-    });
+    let struct_decl = struct_decl.unwrap();
+    let mut llvm_struct_value = llvm_struct_type.get_undef();
+    let mut initialized_field_indices = std::collections::HashSet::new();
 
-    let visit_or_retrieve_result = llvm_lowering_pass.visit_or_retrieve_type(&int_kind_box);
+    for (field_name, value) in &struct_literal.fields {
+      let index = match struct_decl.find_field(field_name) {
+        Some((index, _)) if initialized_field_indices.insert(index) => index as u32,
+        _ => {
+          return Err(diagnostic::Diagnostic {
+            message: format!(
+              "field `{}` of `{}` is unknown, or specified more than once",
+              field_name, struct_literal.path
+            ),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(value.get_span()),
+            notes: vec![],
+          })
+        }
+      };
 
-    assert_eq!(true, visit_or_retrieve_result.is_ok());
-    assert_eq!(true, visit_or_retrieve_result.ok().is_some());
-    assert_eq!(1, llvm_lowering_pass.llvm_type_map.len());
-  }
+      let llvm_field_type = llvm_struct_type.get_field_type_at_index(index);
+      let llvm_value = self.visit_expr_as(value, llvm_field_type)?;
 
-  #[test]
-  fn llvm_lowering_pass_visit_void_kind() {
-    let llvm_context = inkwell::context::Context::create();
-    let llvm_module = llvm_context.create_module("test");
-    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+      if Some(llvm_value.get_type()) != llvm_field_type {
+        return Err(diagnostic::Diagnostic {
+          message: format!(
+            "value of field `{}` does not match its kind in `{}`",
+            field_name, struct_literal.path
+          ),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(value.get_span()),
+          notes: vec![],
+        });
+      }
 
-    let visit_void_kind_result = llvm_lowering_pass.visit_void_kind(&void_kind::VoidKind {});
+      let llvm_aggregate_value =
+        self
+          .llvm_builder_buffer
+          .build_insert_value(llvm_struct_value, llvm_value, index, "");
 
-    assert_eq!(true, visit_void_kind_result.is_ok());
-    assert_eq!(llvm_lowering_pass.llvm_type_map.len(), 1);
-  }
+      assert!(llvm_aggregate_value.is_some());
 
-  #[test]
-  fn llvm_lowering_pass_visit_int_kind() {
-    let llvm_context = inkwell::context::Context::create();
-    let llvm_module = llvm_context.create_module("test");
-    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+      llvm_struct_value = llvm_aggregate_value.unwrap().into_struct_value();
+    }
 
-    let visit_int_kind_result = llvm_lowering_pass.visit_int_kind(&int_kind::IntKind {
-      size: int_kind::IntSize::Signed32,
-    });
+    if initialized_field_indices.len() != struct_decl.fields.len() {
+      return Err(diagnostic::Diagnostic {
+        message: format!(
+          "missing field(s) in literal of struct `{}`",
+          struct_literal.path
+        ),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(struct_literal.span),
+        notes: vec![],
+      });
+    }
 
-    assert_eq!(true, visit_int_kind_result.is_ok());
-    assert_eq!(llvm_lowering_pass.llvm_type_map.len(), 1);
+    Ok(inkwell::values::BasicValueEnum::StructValue(
+      llvm_struct_value,
+    ))
   }
 
-  #[test]
-  fn visit_function() {
+  // Lower the type of the elements of an array or a slice.
+  fn get_element_type_of(
+    &mut self,
+    element_kind: &node::AnyKindNode,
+  ) -> Result<inkwell::types::BasicTypeEnum<'a>, diagnostic::Diagnostic> {
+    let llvm_element_type = self.visit_or_retrieve_type(element_kind)?.copied();
+
+    assert!(llvm_element_type.is_some());
+
+    match as_basic_type(llvm_element_type.unwrap()) {
+      Some(llvm_element_type) => Ok(llvm_element_type),
+      None => Err(diagnostic::Diagnostic {
+        message: format!("array elements cannot be of kind `{}`", element_kind),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: None,
+        notes: vec![],
+      }),
+    }
+  }
+
+  // Lower an array literal into an array value, built up by
+  // inserting the values of its elements in order. The type of the
+  // elements is taken from the expected array (or slice) type, if
+  // any, or from the first element.
+  fn visit_array_literal(
+    &mut self,
+    array_literal: &node::ArrayLiteral,
+    llvm_expected_type: Option<inkwell::types::BasicTypeEnum<'a>>,
+  ) -> Result<inkwell::values::BasicValueEnum<'a>, diagnostic::Diagnostic> {
+    let mut llvm_element_type = match llvm_expected_type {
+      Some(inkwell::types::BasicTypeEnum::ArrayType(llvm_array_type)) => {
+        Some(llvm_array_type.get_element_type())
+      }
+      Some(inkwell::types::BasicTypeEnum::StructType(llvm_slice_type))
+        if is_slice_type(llvm_slice_type) =>
+      {
+        llvm_slice_type
+          .get_field_type_at_index(0)
+          .and_then(|llvm_pointer_type| {
+            as_basic_type(llvm_pointer_type.into_pointer_type().get_element_type())
+          })
+      }
+      _ => None,
+    };
+
+    let mut llvm_values = vec![];
+
+    for element in &array_literal.elements {
+      let llvm_value = self.visit_expr_as(element, llvm_element_type)?;
+
+      if *llvm_element_type.get_or_insert(llvm_value.get_type()) != llvm_value.get_type() {
+        return Err(diagnostic::Diagnostic {
+          message: String::from("array elements do not all share the same kind"),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(element.get_span()),
+          notes: vec![],
+        });
+      }
+
+      llvm_values.push(llvm_value);
+    }
+
+    let llvm_element_type = match llvm_element_type {
+      Some(llvm_element_type) => llvm_element_type,
+      None => {
+        return Err(diagnostic::Diagnostic {
+          message: String::from("cannot infer the kind of the elements of an empty array"),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(array_literal.span),
+          notes: vec![],
+        })
+      }
+    };
+
+    let mut llvm_array_value = llvm_element_type
+      .array_type(llvm_values.len() as u32)
+      .get_undef();
+
+    for (index, llvm_value) in llvm_values.into_iter().enumerate() {
+      let llvm_aggregate_value =
+        self
+          .llvm_builder_buffer
+          .build_insert_value(llvm_array_value, llvm_value, index as u32, "");
+
+      assert!(llvm_aggregate_value.is_some());
+
+      llvm_array_value = llvm_aggregate_value.unwrap().into_array_value();
+    }
+
+    Ok(inkwell::values::BasicValueEnum::ArrayValue(
+      llvm_array_value,
+    ))
+  }
+
+  // Store an array value into a stack slot, so that its elements
+  // are addressable.
+  fn spill_array(
+    &self,
+    llvm_array_value: inkwell::values::ArrayValue<'a>,
+  ) -> Result<inkwell::values::PointerValue<'a>, diagnostic::Diagnostic> {
+    let llvm_stack_slot =
+      self.build_entry_alloca(llvm_array_value.get_type().as_basic_type_enum(), "array")?;
+
+    self
+      .llvm_builder_buffer
+      .build_store(llvm_stack_slot, llvm_array_value);
+
+    Ok(llvm_stack_slot)
+  }
+
+  // Retrieve the elements of the array pointed to, whose amount is
+  // constant.
+  fn build_array_elements(
+    &self,
+    llvm_array_pointer: inkwell::values::PointerValue<'a>,
+  ) -> Result<LlvmElements<'a>, diagnostic::Diagnostic> {
+    let llvm_array_type = match llvm_array_pointer.get_type().get_element_type() {
+      inkwell::types::AnyTypeEnum::ArrayType(llvm_array_type) => llvm_array_type,
+      _ => {
+        return Err(diagnostic::Diagnostic {
+          message: String::from("expected a pointer to an array"),
+          severity: diagnostic::DiagnosticSeverity::Internal,
+          span: None,
+          notes: vec![],
+        })
+      }
+    };
+
+    let llvm_zero = self.llvm_context.i64_type().const_zero();
+
+    let llvm_elements_pointer = unsafe {
+      self.llvm_builder_buffer.build_in_bounds_gep(
+        llvm_array_pointer,
+        &[llvm_zero, llvm_zero],
+        "elements",
+      )
+    };
+
+    Ok((
+      llvm_elements_pointer,
+      self
+        .llvm_context
+        .i64_type()
+        .const_int(llvm_array_type.len() as u64, false),
+    ))
+  }
+
+  // Build a slice value from a pointer to its first element, and its
+  // amount of elements.
+  fn build_slice(
+    &self,
+    llvm_slice_type: inkwell::types::StructType<'a>,
+    llvm_elements_pointer: inkwell::values::PointerValue<'a>,
+    llvm_length: inkwell::values::IntValue<'a>,
+  ) -> Result<inkwell::values::BasicValueEnum<'a>, diagnostic::Diagnostic> {
+    let mut llvm_slice_value = llvm_slice_type.get_undef();
+
+    for (index, llvm_value) in [
+      inkwell::values::BasicValueEnum::PointerValue(llvm_elements_pointer),
+      inkwell::values::BasicValueEnum::IntValue(llvm_length),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+      let llvm_aggregate_value = self.llvm_builder_buffer.build_insert_value(
+        llvm_slice_value,
+        llvm_value,
+        index as u32,
+        "slice",
+      );
+
+      assert!(llvm_aggregate_value.is_some());
+
+      llvm_slice_value = llvm_aggregate_value.unwrap().into_struct_value();
+    }
+
+    Ok(inkwell::values::BasicValueEnum::StructValue(
+      llvm_slice_value,
+    ))
+  }
+
+  // Build a reference to a trait object from a pointer to a value
+  // whose kind implements the trait, paired with the vtable of that
+  // implementation.
+  fn build_dyn_reference(
+    &self,
+    llvm_dyn_type: inkwell::types::StructType<'a>,
+    llvm_pointer: inkwell::values::PointerValue<'a>,
+  ) -> Result<inkwell::values::BasicValueEnum<'a>, diagnostic::Diagnostic> {
+    let trait_path = find_dyn_trait_path(llvm_dyn_type);
+
+    assert!(trait_path.is_some());
+
+    let trait_path = trait_path.unwrap();
+    let llvm_kind_type = llvm_pointer.get_type().get_element_type();
+
+    let llvm_vtable = match self.llvm_vtables.iter().find(|llvm_vtable| {
+      llvm_vtable.trait_path == trait_path && llvm_vtable.llvm_kind_type == llvm_kind_type
+    }) {
+      Some(llvm_vtable) => llvm_vtable,
+      None => {
+        return Err(diagnostic::Diagnostic {
+          message: format!(
+            "cannot find the implementation of trait `{}` for a value of type `{}`",
+            trait_path,
+            llvm_kind_type.print_to_string().to_string_lossy()
+          ),
+          severity: diagnostic::DiagnosticSeverity::Internal,
+          span: None,
+          notes: vec![],
+        })
+      }
+    };
+
+    let llvm_byte_pointer_type = self
+      .llvm_context
+      .i8_type()
+      .ptr_type(inkwell::AddressSpace::Generic);
+
+    let llvm_data_pointer =
+      self
+        .llvm_builder_buffer
+        .build_pointer_cast(llvm_pointer, llvm_byte_pointer_type, "dyn.data");
+
+    let llvm_vtable_pointer = self.llvm_builder_buffer.build_pointer_cast(
+      llvm_vtable.llvm_global.as_pointer_value(),
+      llvm_byte_pointer_type.ptr_type(inkwell::AddressSpace::Generic),
+      "dyn.vtable",
+    );
+
+    let mut llvm_dyn_value = llvm_dyn_type.get_undef();
+
+    for (index, llvm_value) in [llvm_data_pointer, llvm_vtable_pointer]
+      .into_iter()
+      .enumerate()
+    {
+      let llvm_aggregate_value = self.llvm_builder_buffer.build_insert_value(
+        llvm_dyn_value,
+        llvm_value,
+        index as u32,
+        "dyn",
+      );
+
+      assert!(llvm_aggregate_value.is_some());
+
+      llvm_dyn_value = llvm_aggregate_value.unwrap().into_struct_value();
+    }
+
+    Ok(inkwell::values::BasicValueEnum::StructValue(llvm_dyn_value))
+  }
+
+  // Lower an expression producing an array or a slice into its
+  // elements. Arrays denoted by places are not copied, so that their
+  // elements may be assigned to.
+  fn visit_elements(
+    &mut self,
+    expr: &node::AnyExprNode,
+  ) -> Result<LlvmElements<'a>, diagnostic::Diagnostic> {
+    let mut llvm_value = match expr.is_place() {
+      true => inkwell::values::BasicValueEnum::PointerValue(self.visit_place(expr)?),
+      false => self.visit_expr(expr)?,
+    };
+
+    if let inkwell::values::BasicValueEnum::ArrayValue(llvm_array_value) = llvm_value {
+      llvm_value =
+        inkwell::values::BasicValueEnum::PointerValue(self.spill_array(llvm_array_value)?);
+    }
+
+    // Pointers (ex. to a slice, or held by a reference field) are
+    // followed until an array or a slice is reached.
+    while let inkwell::values::BasicValueEnum::PointerValue(llvm_pointer) = llvm_value {
+      if let inkwell::types::AnyTypeEnum::ArrayType(_) = llvm_pointer.get_type().get_element_type()
+      {
+        return self.build_array_elements(llvm_pointer);
+      }
+
+      llvm_value = self.llvm_builder_buffer.build_load(llvm_pointer, "deref");
+    }
+
+    let llvm_slice_value = match llvm_value {
+      inkwell::values::BasicValueEnum::StructValue(llvm_struct_value)
+        if is_slice_type(llvm_struct_value.get_type()) =>
+      {
+        llvm_struct_value
+      }
+      _ => {
+        return Err(diagnostic::Diagnostic {
+          message: String::from("expected an array or a slice"),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(expr.get_span()),
+          notes: vec![],
+        })
+      }
+    };
+
+    let llvm_elements_pointer =
+      self
+        .llvm_builder_buffer
+        .build_extract_value(llvm_slice_value, 0, "elements");
+
+    let llvm_length = self
+      .llvm_builder_buffer
+      .build_extract_value(llvm_slice_value, 1, "len");
+
+    assert!(llvm_elements_pointer.is_some() && llvm_length.is_some());
+
+    Ok((
+      llvm_elements_pointer.unwrap().into_pointer_value(),
+      llvm_length.unwrap().into_int_value(),
+    ))
+  }
+
+  // Abort the program if the index is not within the bounds of an
+  // array or a slice of the given length. Negative indices are out
+  // of bounds, since both are compared as unsigned integers.
+  fn build_bounds_check(
+    &mut self,
+    llvm_index: inkwell::values::IntValue<'a>,
+    llvm_length: inkwell::values::IntValue<'a>,
+  ) -> pass::PassResult {
+    assert!(self.llvm_function_buffer.is_some());
+
+    let llvm_function = self.llvm_function_buffer.unwrap();
+
+    let llvm_trap_function = match self.llvm_module.get_function("llvm.trap") {
+      Some(llvm_trap_function) => llvm_trap_function,
+      None => self.llvm_module.add_function(
+        "llvm.trap",
+        self.llvm_context.void_type().fn_type(&[], false),
+        None,
+      ),
+    };
+
+    let llvm_is_in_bounds = self.llvm_builder_buffer.build_int_compare(
+      inkwell::IntPredicate::ULT,
+      llvm_index,
+      llvm_length,
+      "index.check",
+    );
+
+    let llvm_out_of_bounds_block = self
+      .llvm_context
+      .append_basic_block(llvm_function, "index.out_of_bounds");
+
+    let llvm_in_bounds_block = self
+      .llvm_context
+      .append_basic_block(llvm_function, "index.in_bounds");
+
+    self.llvm_builder_buffer.build_conditional_branch(
+      llvm_is_in_bounds,
+      llvm_in_bounds_block,
+      llvm_out_of_bounds_block,
+    );
+
+    self.position_at_end(llvm_out_of_bounds_block);
+    self
+      .llvm_builder_buffer
+      .build_call(llvm_trap_function, &[], "");
+    self.llvm_builder_buffer.build_unreachable();
+
+    self.position_at_end(llvm_in_bounds_block);
+
+    Ok(())
+  }
+
+  // Compute a pointer to the indexed element, whose index is checked
+  // against the bounds of its array or slice in debug builds.
+  fn build_element_pointer(
+    &mut self,
+    index_expr: &node::IndexExpr,
+  ) -> Result<inkwell::values::PointerValue<'a>, diagnostic::Diagnostic> {
+    let (llvm_elements_pointer, llvm_length) = self.visit_elements(&index_expr.base)?;
+    let llvm_i64_type = self.llvm_context.i64_type();
+
+    let llvm_index =
+      match self.visit_expr_as(&index_expr.index, Some(llvm_i64_type.as_basic_type_enum()))? {
+        inkwell::values::BasicValueEnum::IntValue(llvm_index)
+          if llvm_index.get_type().get_bit_width() > 1 =>
+        {
+          llvm_index
+        }
+        _ => {
+          return Err(diagnostic::Diagnostic {
+            message: String::from("arrays and slices can only be indexed by integers"),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(index_expr.index.get_span()),
+            notes: vec![],
+          })
+        }
+      };
+
+    // Indices of any integer kind are sign-extended into an `i64`,
+    // except for wider indices (ex. `i128`), which are checked at
+    // their own width, so that they are not truncated into bounds.
+    if self.is_debug_build {
+      let llvm_checked_type = match llvm_index.get_type().get_bit_width() > 64 {
+        true => llvm_index.get_type(),
+        false => llvm_i64_type,
+      };
+
+      let llvm_checked_index =
+        self
+          .llvm_builder_buffer
+          .build_int_cast(llvm_index, llvm_checked_type, "index.checked");
+
+      let llvm_checked_length = self.llvm_builder_buffer.build_int_z_extend_or_bit_cast(
+        llvm_length,
+        llvm_checked_type,
+        "length.checked",
+      );
+
+      self.build_bounds_check(llvm_checked_index, llvm_checked_length)?;
+    }
+
+    let llvm_index = self
+      .llvm_builder_buffer
+      .build_int_cast(llvm_index, llvm_i64_type, "index");
+
+    Ok(unsafe {
+      self.llvm_builder_buffer.build_in_bounds_gep(
+        llvm_elements_pointer,
+        &[llvm_index],
+        "element.addr",
+      )
+    })
+  }
+
+  // Find the enum declaring the variant which the given path leads
+  // to, along with the position of the variant.
+  fn find_enum_variant(
+    &self,
+    path: &node::Path,
+  ) -> Result<(enum_decl::EnumDecl, usize), diagnostic::Diagnostic> {
+    let (enum_path, variant_name) = path.split_variant();
+
+    let enum_variant = self.enum_map.get(&enum_path).and_then(|enum_decl| {
+      enum_decl
+        .find_variant(variant_name)
+        .map(|(index, _)| (enum_decl.clone(), index))
+    });
+
+    match enum_variant {
+      Some(enum_variant) => Ok(enum_variant),
+      None => Err(diagnostic::Diagnostic {
+        message: format!("cannot find variant `{}` in this scope", path),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(path.span),
+        notes: vec![],
+      }),
+    }
+  }
+
+  // Lower the fields of a variant into a (literal) struct type,
+  // through which the payload of values of the variant is accessed.
+  fn get_payload_type_of(
+    &mut self,
+    enum_path: &str,
+    variant: &enum_decl::EnumVariant,
+  ) -> Result<inkwell::types::StructType<'a>, diagnostic::Diagnostic> {
+    let mut llvm_field_types = vec![];
+
+    for field in &variant.fields {
+      match self.get_stored_type_of(field)? {
+        Some(llvm_field_type) => llvm_field_types.push(llvm_field_type),
+        None => {
+          return Err(diagnostic::Diagnostic {
+            message: format!(
+              "field of variant `{}::{}` cannot be of kind `{}`",
+              enum_path, variant.name, field.kind
+            ),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(field.span),
+            notes: vec![],
+          })
+        }
+      };
+    }
+
+    Ok(
+      self
+        .llvm_context
+        .struct_type(llvm_field_types.as_slice(), false),
+    )
+  }
+
+  // Compute a pointer to the payload of the enum value pointed to,
+  // as seen through the payload type of one of its variants.
+  fn build_payload_pointer(
+    &self,
+    llvm_enum_pointer: inkwell::values::PointerValue<'a>,
+    llvm_payload_type: inkwell::types::StructType<'a>,
+  ) -> Result<inkwell::values::PointerValue<'a>, diagnostic::Diagnostic> {
+    let llvm_payload_pointer =
+      self
+        .llvm_builder_buffer
+        .build_struct_gep(llvm_enum_pointer, 1, "payload.addr");
+
+    assert!(llvm_payload_pointer.is_ok());
+
+    Ok(self.llvm_builder_buffer.build_pointer_cast(
+      llvm_payload_pointer.unwrap(),
+      llvm_payload_type.ptr_type(inkwell::AddressSpace::Generic),
+      "payload.cast",
+    ))
+  }
+
+  // Lower a variant literal into a value of its enum, which is built
+  // up in a stack slot by storing its tag, and then its fields
+  // through the payload type of its variant.
+  fn visit_variant_literal(
+    &mut self,
+    variant_literal: &node::VariantLiteral,
+  ) -> Result<inkwell::values::BasicValueEnum<'a>, diagnostic::Diagnostic> {
+    let (enum_decl, index) = self.find_enum_variant(&variant_literal.path)?;
+    let variant = &enum_decl.variants[index];
+    let segment_count = variant_literal.path.segments.len();
+
+    let enum_kind = node::AnyKindNode::EnumKind(enum_kind::EnumKind {
+      path: variant_literal.path.segments[..segment_count - 1].to_vec(),
+    });
+
+    let llvm_enum_type = self
+      .visit_or_retrieve_type(&enum_kind)?
+      .copied()
+      .and_then(as_basic_type);
+
+    assert!(llvm_enum_type.is_some());
+
+    if variant_literal.arguments.len() != variant.fields.len() {
+      return Err(diagnostic::Diagnostic {
+        message: format!(
+          "variant `{}` takes {} field(s) but {} were supplied",
+          variant_literal.path,
+          variant.fields.len(),
+          variant_literal.arguments.len()
+        ),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(variant_literal.span),
+        notes: vec![],
+      });
+    }
+
+    let llvm_stack_slot = self.build_entry_alloca(llvm_enum_type.unwrap(), "variant")?;
+
+    let llvm_tag_pointer =
+      self
+        .llvm_builder_buffer
+        .build_struct_gep(llvm_stack_slot, 0, "tag.addr");
+
+    assert!(llvm_tag_pointer.is_ok());
+
+    self.llvm_builder_buffer.build_store(
+      llvm_tag_pointer.unwrap(),
+      self.llvm_context.i32_type().const_int(index as u64, false),
+    );
+
+    if !variant.fields.is_empty() {
+      let llvm_payload_type = self.get_payload_type_of(&enum_kind.to_string(), variant)?;
+      let llvm_payload_pointer = self.build_payload_pointer(llvm_stack_slot, llvm_payload_type)?;
+
+      for (field_index, argument) in variant_literal.arguments.iter().enumerate() {
+        let llvm_field_type = llvm_payload_type.get_field_type_at_index(field_index as u32);
+        let llvm_value = self.visit_expr_as(argument, llvm_field_type)?;
+
+        if Some(llvm_value.get_type()) != llvm_field_type {
+          return Err(diagnostic::Diagnostic {
+            message: format!(
+              "value of field {} does not match its kind in `{}`",
+              field_index, variant_literal.path
+            ),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(argument.get_span()),
+            notes: vec![],
+          });
+        }
+
+        let llvm_field_pointer = self.llvm_builder_buffer.build_struct_gep(
+          llvm_payload_pointer,
+          field_index as u32,
+          "field.addr",
+        );
+
+        assert!(llvm_field_pointer.is_ok());
+
+        self
+          .llvm_builder_buffer
+          .build_store(llvm_field_pointer.unwrap(), llvm_value);
+      }
+    }
+
+    Ok(
+      self
+        .llvm_builder_buffer
+        .build_load(llvm_stack_slot, "variant.load"),
+    )
+  }
+
+  // Build the test of whether a value matches the given pattern,
+  // collecting the values bound by the pattern along the way.
+  //
+  // Returns [`None`] if the pattern matches any value.
+  fn build_pattern_test(
+    &mut self,
+    pattern: &pattern::AnyPatternNode,
+    llvm_value: inkwell::values::BasicValueEnum<'a>,
+    llvm_bindings: &mut Vec<(String, inkwell::values::BasicValueEnum<'a>)>,
+  ) -> Result<Option<inkwell::values::IntValue<'a>>, diagnostic::Diagnostic> {
+    let mismatch_error = diagnostic::Diagnostic {
+      message: String::from("pattern does not match the kind of the value"),
+      severity: diagnostic::DiagnosticSeverity::Error,
+      span: Some(pattern.get_span()),
+      notes: vec![],
+    };
+
+    let variant_pattern = match pattern {
+      pattern::AnyPatternNode::WildcardPattern(_) => return Ok(None),
+      pattern::AnyPatternNode::BindingPattern(binding_pattern) => {
+        llvm_bindings.push((binding_pattern.name.clone(), llvm_value));
+
+        return Ok(None);
+      }
+      pattern::AnyPatternNode::LiteralPattern(literal) => {
+        let llvm_literal = self.visit_or_retrieve_value(literal, Some(llvm_value.get_type()))?;
+
+        return match (llvm_value, llvm_literal) {
+          (
+            inkwell::values::BasicValueEnum::IntValue(llvm_int_value),
+            Some(inkwell::values::BasicValueEnum::IntValue(llvm_int_literal)),
+          ) if llvm_int_value.get_type() == llvm_int_literal.get_type() => {
+            Ok(Some(self.llvm_builder_buffer.build_int_compare(
+              inkwell::IntPredicate::EQ,
+              llvm_int_value,
+              llvm_int_literal,
+              "pattern.eq",
+            )))
+          }
+          _ => Err(mismatch_error),
+        };
+      }
+      pattern::AnyPatternNode::VariantPattern(variant_pattern) => variant_pattern,
+    };
+
+    let (enum_decl, index) = self.find_enum_variant(&variant_pattern.path)?;
+    let variant = &enum_decl.variants[index];
+
+    let llvm_enum_value = match llvm_value {
+      inkwell::values::BasicValueEnum::StructValue(llvm_enum_value)
+        if variant_pattern.fields.len() == variant.fields.len() =>
+      {
+        llvm_enum_value
+      }
+      _ => return Err(mismatch_error),
+    };
+
+    let llvm_tag = self
+      .llvm_builder_buffer
+      .build_extract_value(llvm_enum_value, 0, "tag");
+
+    assert!(llvm_tag.is_some());
+
+    let mut llvm_condition = self.llvm_builder_buffer.build_int_compare(
+      inkwell::IntPredicate::EQ,
+      llvm_tag.unwrap().into_int_value(),
+      self.llvm_context.i32_type().const_int(index as u64, false),
+      "tag.eq",
+    );
+
+    if variant.fields.is_empty() {
+      return Ok(Some(llvm_condition));
+    }
+
+    // The payload is read through memory, since its type depends on
+    // the variant. Its fields are read even if the tag differs, but
+    // the results are then never used.
+    let llvm_stack_slot = self.build_entry_alloca(
+      llvm_enum_value.get_type().as_basic_type_enum(),
+      "match.payload",
+    )?;
+
+    self
+      .llvm_builder_buffer
+      .build_store(llvm_stack_slot, llvm_enum_value);
+
+    let (enum_path, _) = variant_pattern.path.split_variant();
+    let llvm_payload_type = self.get_payload_type_of(&enum_path, variant)?;
+    let llvm_payload_pointer = self.build_payload_pointer(llvm_stack_slot, llvm_payload_type)?;
+
+    for (field_index, field_pattern) in variant_pattern.fields.iter().enumerate() {
+      let llvm_field_pointer = self.llvm_builder_buffer.build_struct_gep(
+        llvm_payload_pointer,
+        field_index as u32,
+        "field.addr",
+      );
+
+      assert!(llvm_field_pointer.is_ok());
+
+      let llvm_field_value = self
+        .llvm_builder_buffer
+        .build_load(llvm_field_pointer.unwrap(), "field");
+
+      if let Some(llvm_field_condition) =
+        self.build_pattern_test(field_pattern, llvm_field_value, llvm_bindings)?
+      {
+        llvm_condition =
+          self
+            .llvm_builder_buffer
+            .build_and(llvm_condition, llvm_field_condition, "pattern.and");
+      }
+    }
+
+    Ok(Some(llvm_condition))
+  }
+
+  // Test the given arms of a match in order, branching to the body
+  // of the first one whose pattern matches. The values bound by its
+  // pattern are stored into the stack slots of the arm beforehand.
+  // If no arm matches, the value cannot occur, since matches are
+  // checked to be exhaustive.
+  fn build_arm_tests(
+    &mut self,
+    match_expr: &node::MatchExpr,
+    arm_indices: &[usize],
+    llvm_subject: inkwell::values::BasicValueEnum<'a>,
+    llvm_arm_blocks: &[inkwell::basic_block::BasicBlock<'a>],
+    llvm_arm_binding_slots: &mut [std::collections::HashMap<
+      String,
+      inkwell::values::PointerValue<'a>,
+    >],
+    is_arm_reachable: &mut [bool],
+  ) -> pass::PassResult {
+    assert!(self.llvm_function_buffer.is_some());
+
+    let llvm_function = self.llvm_function_buffer.unwrap();
+
+    for &arm_index in arm_indices {
+      let mut llvm_bindings = vec![];
+
+      let llvm_condition = self.build_pattern_test(
+        &match_expr.arms[arm_index].pattern,
+        llvm_subject,
+        &mut llvm_bindings,
+      )?;
+
+      for (name, llvm_value) in llvm_bindings {
+        let llvm_binding_slots = &mut llvm_arm_binding_slots[arm_index];
+
+        if !llvm_binding_slots.contains_key(&name) {
+          let llvm_stack_slot = self.build_entry_alloca(llvm_value.get_type(), &name)?;
+
+          llvm_binding_slots.insert(name.clone(), llvm_stack_slot);
+        }
+
+        self
+          .llvm_builder_buffer
+          .build_store(llvm_binding_slots[&name], llvm_value);
+      }
+
+      is_arm_reachable[arm_index] = true;
+
+      let llvm_condition = match llvm_condition {
+        Some(llvm_condition) => llvm_condition,
+        None => {
+          self
+            .llvm_builder_buffer
+            .build_unconditional_branch(llvm_arm_blocks[arm_index]);
+
+          return Ok(());
+        }
+      };
+
+      let llvm_next_block = self
+        .llvm_context
+        .append_basic_block(llvm_function, "match.next");
+
+      self.llvm_builder_buffer.build_conditional_branch(
+        llvm_condition,
+        llvm_arm_blocks[arm_index],
+        llvm_next_block,
+      );
+
+      self.position_at_end(llvm_next_block);
+    }
+
+    self.llvm_builder_buffer.build_unreachable();
+
+    Ok(())
+  }
+
+  // Lower a match into a `switch` on the tag of its subject (for
+  // enums), or on its value (for integers and booleans). Each case
+  // tests the arms which may match its values in order, and the
+  // default case tests the arms which match any value.
+  //
+  // Returns [`None`] if the arms do not produce a value (ex. if they
+  // are blocks).
+  fn visit_match_expr(
+    &mut self,
+    match_expr: &node::MatchExpr,
+    llvm_expected_type: Option<inkwell::types::BasicTypeEnum<'a>>,
+  ) -> Result<Option<inkwell::values::BasicValueEnum<'a>>, diagnostic::Diagnostic> {
+    assert!(self.llvm_function_buffer.is_some());
+    assert!(self.llvm_basic_block_buffer.is_some());
+
+    let llvm_function = self.llvm_function_buffer.unwrap();
+    let llvm_subject = self.visit_expr(&match_expr.subject)?;
+
+    let enum_decl = match llvm_subject {
+      inkwell::values::BasicValueEnum::StructValue(llvm_struct_value) => llvm_struct_value
+        .get_type()
+        .get_name()
+        .and_then(|name| name.to_str().ok())
+        .and_then(|name| self.enum_map.get(name))
+        .cloned(),
+      _ => None,
+    };
+
+    // Each case lists the arms which may match its values, in order.
+    let mut cases: Vec<(inkwell::values::IntValue<'a>, Vec<usize>)> = vec![];
+    let mut irrefutable_arm_indices = vec![];
+
+    let llvm_switch_value = match (&enum_decl, llvm_subject) {
+      (Some(enum_decl), inkwell::values::BasicValueEnum::StructValue(llvm_enum_value)) => {
+        for index in 0..enum_decl.variants.len() {
+          cases.push((
+            self.llvm_context.i32_type().const_int(index as u64, false),
+            vec![],
+          ));
+        }
+
+        let llvm_tag = self
+          .llvm_builder_buffer
+          .build_extract_value(llvm_enum_value, 0, "tag");
+
+        assert!(llvm_tag.is_some());
+
+        Some(llvm_tag.unwrap().into_int_value())
+      }
+      (None, inkwell::values::BasicValueEnum::IntValue(llvm_int_value)) => Some(llvm_int_value),
+      _ => None,
+    };
+
+    for (arm_index, arm) in match_expr.arms.iter().enumerate() {
+      let case_index = match (&arm.pattern, llvm_switch_value) {
+        (pattern, _) if pattern.is_irrefutable() => {
+          for (_, arm_indices) in &mut cases {
+            arm_indices.push(arm_index);
+          }
+
+          irrefutable_arm_indices.push(arm_index);
+
+          continue;
+        }
+        (pattern::AnyPatternNode::VariantPattern(variant_pattern), Some(_))
+          if enum_decl.is_some() =>
+        {
+          self.find_enum_variant(&variant_pattern.path)?.1
+        }
+        (pattern::AnyPatternNode::LiteralPattern(literal), Some(llvm_switch_value))
+          if enum_decl.is_none() =>
+        {
+          let llvm_switch_type = llvm_switch_value.get_type().as_basic_type_enum();
+
+          let llvm_literal = self
+            .visit_or_retrieve_value(literal, Some(llvm_switch_type))?
+            .filter(|llvm_literal| llvm_literal.get_type() == llvm_switch_type)
+            .map(|llvm_literal| llvm_literal.into_int_value());
+
+          assert!(llvm_literal.is_some());
+
+          let llvm_literal = llvm_literal.unwrap();
+
+          // Arms of equal literals share their case, which is only
+          // reached by the arms that were found before it otherwise.
+          match cases
+            .iter()
+            .position(|(llvm_case_value, _)| *llvm_case_value == llvm_literal)
+          {
+            Some(case_index) => case_index,
+            None => {
+              cases.push((llvm_literal, irrefutable_arm_indices.clone()));
+              cases.len() - 1
+            }
+          }
+        }
+        (pattern, _) => {
+          return Err(diagnostic::Diagnostic {
+            message: String::from("pattern does not match the kind of the value"),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(pattern.get_span()),
+            notes: vec![],
+          })
+        }
+      };
+
+      assert!(case_index < cases.len());
+
+      cases[case_index].1.push(arm_index);
+    }
+
+    let llvm_case_blocks = cases
+      .iter()
+      .map(|_| {
+        self
+          .llvm_context
+          .append_basic_block(llvm_function, "match.case")
+      })
+      .collect::<Vec<_>>();
+
+    let llvm_default_block = self
+      .llvm_context
+      .append_basic_block(llvm_function, "match.default");
+
+    match llvm_switch_value {
+      Some(llvm_switch_value) => {
+        let llvm_cases = cases
+          .iter()
+          .zip(&llvm_case_blocks)
+          .map(|((llvm_case_value, _), llvm_case_block)| (*llvm_case_value, *llvm_case_block))
+          .collect::<Vec<_>>();
+
+        self.llvm_builder_buffer.build_switch(
+          llvm_switch_value,
+          llvm_default_block,
+          llvm_cases.as_slice(),
+        );
+      }
+      None => {
+        self
+          .llvm_builder_buffer
+          .build_unconditional_branch(llvm_default_block);
+      }
+    };
+
+    let llvm_arm_blocks = match_expr
+      .arms
+      .iter()
+      .map(|_| {
+        self
+          .llvm_context
+          .append_basic_block(llvm_function, "match.arm")
+      })
+      .collect::<Vec<_>>();
+
+    let mut llvm_arm_binding_slots = vec![std::collections::HashMap::new(); match_expr.arms.len()];
+    let mut is_arm_reachable = vec![false; match_expr.arms.len()];
+
+    for ((_, arm_indices), llvm_case_block) in cases.iter().zip(llvm_case_blocks) {
+      self.position_at_end(llvm_case_block);
+
+      self.build_arm_tests(
+        match_expr,
+        arm_indices,
+        llvm_subject,
+        &llvm_arm_blocks,
+        &mut llvm_arm_binding_slots,
+        &mut is_arm_reachable,
+      )?;
+    }
+
+    self.position_at_end(llvm_default_block);
+
+    self.build_arm_tests(
+      match_expr,
+      &irrefutable_arm_indices,
+      llvm_subject,
+      &llvm_arm_blocks,
+      &mut llvm_arm_binding_slots,
+      &mut is_arm_reachable,
+    )?;
+
+    let llvm_merge_block = self
+      .llvm_context
+      .append_basic_block(llvm_function, "match.end");
+
+    let mut llvm_incoming_values = vec![];
+    let mut llvm_value_type = None;
+    let mut fall_through_count = 0;
+
+    for (arm_index, arm) in match_expr.arms.iter().enumerate() {
+      self.position_at_end(llvm_arm_blocks[arm_index]);
+
+      // Arms which no value reaches (ex. after a wildcard) are not
+      // lowered, but their block must still be terminated.
+      if !is_arm_reachable[arm_index] {
+        self.llvm_builder_buffer.build_unreachable();
+
+        continue;
+      }
+
+      self.llvm_named_value_scopes.push(
+        llvm_arm_binding_slots[arm_index]
+          .iter()
+          .map(|(name, llvm_stack_slot)| {
+            (name.clone(), LlvmNamedValue::StackSlot(*llvm_stack_slot))
+          })
+          .collect(),
+      );
+
+      let llvm_value = match &arm.body {
+        node::MatchArmBody::Expr(expr) => {
+          self.visit_expr_or_void(expr, llvm_value_type.or(llvm_expected_type))?
+        }
+        node::MatchArmBody::Block(block) => {
+          self.visit_block(block)?;
+
+          None
+        }
+      };
+
+      self.llvm_named_value_scopes.pop();
+
+      // Only branch to the merge block if the body did not already
+      // terminate (ex. by returning).
+      if self.is_current_block_terminated() {
+        continue;
+      }
+
+      if let Some(llvm_value) = llvm_value {
+        if llvm_value_type.is_some() && llvm_value_type != Some(llvm_value.get_type()) {
+          return Err(diagnostic::Diagnostic {
+            message: String::from("arms of match produce values of different kinds"),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(arm.span),
+            notes: vec![],
+          });
+        }
+
+        llvm_value_type = Some(llvm_value.get_type());
+
+        llvm_incoming_values.push((llvm_value, self.llvm_basic_block_buffer.unwrap()));
+      }
+
+      self
+        .llvm_builder_buffer
+        .build_unconditional_branch(llvm_merge_block);
+
+      fall_through_count += 1;
+    }
+
+    self.position_at_end(llvm_merge_block);
+
+    // If every arm terminated, nothing can reach the merge block, but
+    // it must still be terminated to be well-formed.
+    if fall_through_count == 0 {
+      self.llvm_builder_buffer.build_unreachable();
+
+      return Ok(None);
+    }
+
+    if llvm_incoming_values.is_empty() {
+      return Ok(None);
+    }
+
+    if llvm_incoming_values.len() != fall_through_count {
+      return Err(diagnostic::Diagnostic {
+        message: String::from("some arms of match do not produce a value"),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(match_expr.span),
+        notes: vec![],
+      });
+    }
+
+    assert!(llvm_value_type.is_some());
+
+    let llvm_phi = self
+      .llvm_builder_buffer
+      .build_phi(llvm_value_type.unwrap(), "match.value");
+
+    for (llvm_value, llvm_block) in &llvm_incoming_values {
+      llvm_phi.add_incoming(&[(llvm_value, *llvm_block)]);
+    }
+
+    Ok(Some(llvm_phi.as_basic_value()))
+  }
+
+  // Lower an expression denoting a place (ex. a variable, one of its
+  // fields or one of its elements) into a pointer to it, so that it
+  // may be assigned to.
+  fn visit_place(
+    &mut self,
+    expr: &node::AnyExprNode,
+  ) -> Result<inkwell::values::PointerValue<'a>, diagnostic::Diagnostic> {
+    match expr {
+      node::AnyExprNode::VariableRef(variable_ref) => {
+        match self.retrieve_named_value(variable_ref)? {
+          LlvmNamedValue::StackSlot(llvm_stack_slot) => Ok(llvm_stack_slot),
+          LlvmNamedValue::Value(_) => Err(diagnostic::Diagnostic {
+            message: format!("cannot assign to immutable value `{}`", variable_ref.name),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(variable_ref.span),
+            notes: vec![],
+          }),
+        }
+      }
+      node::AnyExprNode::FieldAccessExpr(field_access_expr) => {
+        let mut llvm_struct_pointer = self.visit_place(&field_access_expr.base)?;
+
+        // A place holding a pointer (ex. a reference field) refers
+        // to the struct it points to, through any amount of
+        // references.
+        while let inkwell::types::AnyTypeEnum::PointerType(_) =
+          llvm_struct_pointer.get_type().get_element_type()
+        {
+          llvm_struct_pointer = self
+            .llvm_builder_buffer
+            .build_load(llvm_struct_pointer, "deref")
+            .into_pointer_value();
+        }
+
+        self.build_field_pointer(llvm_struct_pointer, field_access_expr)
+      }
+      node::AnyExprNode::IndexExpr(index_expr) => self.build_element_pointer(index_expr),
+      node::AnyExprNode::DerefExpr(deref_expr) => self.visit_reference(&deref_expr.value),
+      _ => Err(diagnostic::Diagnostic {
+        message: String::from("invalid assignment target"),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(expr.get_span()),
+        notes: vec![],
+      }),
+    }
+  }
+
+  // Lower an expression producing a reference into the pointer it
+  // holds.
+  fn visit_reference(
+    &mut self,
+    expr: &node::AnyExprNode,
+  ) -> Result<inkwell::values::PointerValue<'a>, diagnostic::Diagnostic> {
+    match self.visit_expr(expr)? {
+      inkwell::values::BasicValueEnum::PointerValue(llvm_pointer) => Ok(llvm_pointer),
+      _ => Err(diagnostic::Diagnostic {
+        message: String::from("cannot dereference a value which is not a reference"),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(expr.get_span()),
+        notes: vec![],
+      }),
+    }
+  }
+
+  // Lower a borrow into a pointer to the borrowed value. Places are
+  // referred to where they are stored, whereas other values (and
+  // values bound without a stack slot, such as a `for` loop's
+  // iterator) are first stored into a temporary stack slot.
+  fn visit_borrow_expr(
+    &mut self,
+    borrow_expr: &node::BorrowExpr,
+    llvm_expected_type: Option<inkwell::types::BasicTypeEnum<'a>>,
+  ) -> Result<inkwell::values::PointerValue<'a>, diagnostic::Diagnostic> {
+    let is_addressable = match borrow_expr.value.as_ref() {
+      node::AnyExprNode::VariableRef(variable_ref) => matches!(
+        self.retrieve_named_value(variable_ref)?,
+        LlvmNamedValue::StackSlot(_)
+      ),
+      value => value.is_place(),
+    };
+
+    if is_addressable {
+      return self.visit_place(&borrow_expr.value);
+    }
+
+    let llvm_expected_value_type = match llvm_expected_type {
+      Some(inkwell::types::BasicTypeEnum::PointerType(llvm_pointer_type)) => {
+        as_basic_type(llvm_pointer_type.get_element_type())
+      }
+      _ => None,
+    };
+
+    let llvm_value = self.visit_expr_as(&borrow_expr.value, llvm_expected_value_type)?;
+    let llvm_stack_slot = self.build_entry_alloca(llvm_value.get_type(), "borrow")?;
+
+    self
+      .llvm_builder_buffer
+      .build_store(llvm_stack_slot, llvm_value);
+
+    Ok(llvm_stack_slot)
+  }
+
+  // Allocate a stack slot in the entry block of the function being
+  // lowered, so that it is only allocated once, even within loops.
+  fn build_entry_alloca(
+    &self,
+    llvm_type: inkwell::types::BasicTypeEnum<'a>,
+    name: &str,
+  ) -> Result<inkwell::values::PointerValue<'a>, diagnostic::Diagnostic> {
+    assert!(self.llvm_function_buffer.is_some());
+
+    let llvm_entry_block = self.llvm_function_buffer.unwrap().get_first_basic_block();
+
+    assert!(llvm_entry_block.is_some());
+
+    let llvm_entry_block = llvm_entry_block.unwrap();
+    let llvm_builder = self.llvm_context.create_builder();
+
+    match llvm_entry_block.get_first_instruction() {
+      Some(llvm_instruction) => llvm_builder.position_before(&llvm_instruction),
+      None => llvm_builder.position_at_end(llvm_entry_block),
+    };
+
+    Ok(llvm_builder.build_alloca(llvm_type, name))
+  }
+
+  // Reinterpret a value as a value of another type of the same size
+  // (ex. a struct as the integer the C calling convention passes it
+  // as), by storing it into memory and loading it back.
+  fn coerce_through_memory(
+    &self,
+    llvm_value: inkwell::values::BasicValueEnum<'a>,
+    llvm_target_type: inkwell::types::BasicTypeEnum<'a>,
+  ) -> Result<inkwell::values::BasicValueEnum<'a>, diagnostic::Diagnostic> {
+    let llvm_value_type = llvm_value.get_type();
+
+    // The coerced type may be larger than the value, once padded
+    // (ex. `{ i64, i32 }` for a struct of three `i32`s), so the slot
+    // must be large and aligned enough for both types.
+    let llvm_slot_type = match self.llvm_target_data.get_abi_size(&llvm_target_type)
+      > self.llvm_target_data.get_abi_size(&llvm_value_type)
+    {
+      true => llvm_target_type,
+      false => llvm_value_type,
+    };
+
+    let llvm_stack_slot = self.build_entry_alloca(llvm_slot_type, "abi.coerce")?;
+
+    let llvm_alignment = self
+      .llvm_target_data
+      .get_abi_alignment(&llvm_value_type)
+      .max(self.llvm_target_data.get_abi_alignment(&llvm_target_type));
+
+    let llvm_alloca = llvm_stack_slot.as_instruction();
+
+    assert!(llvm_alloca.is_some());
+    assert!(llvm_alloca.unwrap().set_alignment(llvm_alignment).is_ok());
+
+    let llvm_value_stack_slot = self.llvm_builder_buffer.build_pointer_cast(
+      llvm_stack_slot,
+      llvm_value_type.ptr_type(inkwell::AddressSpace::Generic),
+      "abi.slot",
+    );
+
+    self
+      .llvm_builder_buffer
+      .build_store(llvm_value_stack_slot, llvm_value);
+
+    let llvm_cast_stack_slot = self.llvm_builder_buffer.build_pointer_cast(
+      llvm_stack_slot,
+      llvm_target_type.ptr_type(inkwell::AddressSpace::Generic),
+      "abi.cast",
+    );
+
+    Ok(
+      self
+        .llvm_builder_buffer
+        .build_load(llvm_cast_stack_slot, "abi.load"),
+    )
+  }
+
+  // Determine the type a value of the given type is passed as.
+  fn get_passed_type(
+    &self,
+    llvm_type: inkwell::types::BasicTypeEnum<'a>,
+    passing_mode: c_abi::PassingMode,
+  ) -> inkwell::types::BasicTypeEnum<'a> {
+    match passing_mode {
+      c_abi::PassingMode::Direct => llvm_type,
+      c_abi::PassingMode::Coerced {
+        low_size,
+        high_size: None,
+      } => self
+        .llvm_context
+        .custom_width_int_type((low_size * 8) as u32)
+        .as_basic_type_enum(),
+      c_abi::PassingMode::Coerced {
+        low_size,
+        high_size: Some(high_size),
+      } => self
+        .llvm_context
+        .struct_type(
+          &[
+            self
+              .llvm_context
+              .custom_width_int_type((low_size * 8) as u32)
+              .as_basic_type_enum(),
+            self
+              .llvm_context
+              .custom_width_int_type((high_size * 8) as u32)
+              .as_basic_type_enum(),
+          ],
+          false,
+        )
+        .as_basic_type_enum(),
+      c_abi::PassingMode::Indirect => llvm_type
+        .ptr_type(inkwell::AddressSpace::Generic)
+        .as_basic_type_enum(),
+    }
+  }
+
+  // Mark the pointers to values passed in memory, so that the values
+  // are copied as C expects (ex. `byval` arguments are copied onto
+  // the stack by the caller), and the values narrower than their
+  // register, so that they are extended as C expects.
+  fn add_abi_attributes(
+    &self,
+    llvm_function: inkwell::values::FunctionValue<'a>,
+    llvm_extern_abi: &LlvmExternAbi<'a>,
+  ) {
+    let parameter_offset = llvm_extern_abi.has_return_pointer() as u32;
+    let mut llvm_attributed_parameters = vec![];
+
+    if llvm_extern_abi.has_return_pointer() {
+      llvm_attributed_parameters.push((0, "sret"));
+    }
+
+    for (index, passing_mode) in llvm_extern_abi.parameter_modes.iter().enumerate() {
+      if *passing_mode == c_abi::PassingMode::Indirect {
+        llvm_attributed_parameters.push((index as u32 + parameter_offset, "byval"));
+      }
+    }
+
+    for (index, attribute_name) in llvm_attributed_parameters {
+      let llvm_pointer_type = llvm_function
+        .get_type()
+        .get_param_types()
+        .remove(index as usize)
+        .into_pointer_type();
+
+      let llvm_attribute = self.llvm_context.create_type_attribute(
+        inkwell::attributes::Attribute::get_named_enum_kind_id(attribute_name),
+        llvm_pointer_type.get_element_type(),
+      );
+
+      llvm_function.add_attribute(
+        inkwell::attributes::AttributeLoc::Param(index),
+        llvm_attribute,
+      );
+    }
+
+    let llvm_extended_values = llvm_extern_abi
+      .parameter_extensions
+      .iter()
+      .enumerate()
+      .map(|(index, extension)| {
+        (
+          inkwell::attributes::AttributeLoc::Param(index as u32 + parameter_offset),
+          extension,
+        )
+      })
+      .chain(std::iter::once((
+        inkwell::attributes::AttributeLoc::Return,
+        &llvm_extern_abi.return_extension,
+      )));
+
+    for (llvm_attribute_location, extension) in llvm_extended_values {
+      let attribute_name = match extension {
+        Some(c_abi::Extension::Zero) => "zeroext",
+        Some(c_abi::Extension::Sign) => "signext",
+        None => continue,
+      };
+
+      let llvm_attribute = self.llvm_context.create_enum_attribute(
+        inkwell::attributes::Attribute::get_named_enum_kind_id(attribute_name),
+        0,
+      );
+
+      llvm_function.add_attribute(llvm_attribute_location, llvm_attribute);
+    }
+  }
+
+  // Lower the expression, and ensure that it results in a
+  // boolean value, so that it can be used as a branch condition.
+  fn visit_condition(
+    &mut self,
+    expr: &node::AnyExprNode,
+  ) -> Result<inkwell::values::IntValue<'a>, diagnostic::Diagnostic> {
+    let llvm_value = self.visit_expr(expr)?;
+
+    if !llvm_value.is_int_value() || llvm_value.into_int_value().get_type().get_bit_width() != 1 {
+      return Err(diagnostic::Diagnostic {
+        message: String::from("condition must be a boolean value"),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(expr.get_span()),
+        notes: vec![],
+      });
+    }
+
+    Ok(llvm_value.into_int_value())
+  }
+
+  // Retrieve the LLVM function for the given prototype, adding
+  // its declaration to the module under the given name if it was
+  // not yet declared.
+  //
+  // Parameters and return values are passed as C expects if the
+  // calling convention of an extern is given.
+  fn declare_function(
+    &mut self,
+    prototype: &prototype::Prototype,
+    llvm_name: &str,
+    llvm_linkage: inkwell::module::Linkage,
+    llvm_extern_abi: Option<&LlvmExternAbi<'a>>,
+  ) -> Result<inkwell::values::FunctionValue<'a>, diagnostic::Diagnostic> {
+    let qualified_name = self.get_qualified_name(&prototype.get_instance_name());
+
+    if let Some(llvm_function) = self.llvm_function_map.get(&qualified_name) {
+      return Ok(*llvm_function);
+    }
+
+    // Externs of the same name (ex. within different namespaces)
+    // refer to the same function.
+    if let Some(llvm_function) = self.llvm_module.get_function(llvm_name) {
+      self.llvm_function_map.insert(qualified_name, llvm_function);
+
+      return Ok(llvm_function);
+    }
+
+    let mut llvm_parameter_types: Vec<inkwell::types::BasicMetadataTypeEnum<'a>> = vec![];
+    let llvm_return_type = self
+      .visit_or_retrieve_type(&prototype.return_kind_group.to_value_kind())?
+      .copied();
+
+    assert!(llvm_return_type.is_some());
+
+    let mut llvm_return_type = llvm_return_type.unwrap();
+
+    if let Some(llvm_extern_abi) = llvm_extern_abi {
+      if llvm_extern_abi.return_mode != c_abi::PassingMode::Direct {
+        let llvm_value_type = as_basic_type(llvm_return_type);
+
+        assert!(llvm_value_type.is_some());
+
+        let llvm_passed_type =
+          self.get_passed_type(llvm_value_type.unwrap(), llvm_extern_abi.return_mode);
+
+        // Values returned in memory are written through a hidden
+        // pointer, given as the first argument.
+        llvm_return_type = match llvm_extern_abi.has_return_pointer() {
+          true => {
+            llvm_parameter_types.push(llvm_passed_type.into());
+            self.llvm_context.void_type().as_any_type_enum()
+          }
+          false => llvm_passed_type.as_any_type_enum(),
+        };
+      }
+    }
+
+    for (index, (parameter_name, parameter_kind_group)) in prototype.parameters.iter().enumerate() {
+      let llvm_parameter_type = self
+        .visit_or_retrieve_type(&parameter_kind_group.to_value_kind())?
+        .copied();
+
+      assert!(llvm_parameter_type.is_some());
+
+      let llvm_parameter_type = match as_basic_type(llvm_parameter_type.unwrap()) {
+        Some(llvm_parameter_type) => llvm_parameter_type,
+        None => {
+          return Err(diagnostic::Diagnostic {
+            message: format!(
+              "parameter `{}` of `{}` cannot be of kind `{}`",
+              parameter_name,
+              prototype.get_instance_name(),
+              parameter_kind_group.kind
+            ),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(parameter_kind_group.span),
+            notes: vec![],
+          })
+        }
+      };
+
+      let passing_mode = llvm_extern_abi.map_or(c_abi::PassingMode::Direct, |llvm_extern_abi| {
+        llvm_extern_abi.parameter_modes[index]
+      });
+
+      llvm_parameter_types.push(
+        self
+          .get_passed_type(llvm_parameter_type, passing_mode)
+          .into(),
+      );
+    }
+
+    let llvm_function_type = LlvmLoweringPass::get_function_type_from(
+      &llvm_return_type,
+      llvm_parameter_types.as_slice(),
+      prototype.is_variadic,
+    )?;
+
+    let llvm_function =
+      self
+        .llvm_module
+        .add_function(llvm_name, llvm_function_type, Some(llvm_linkage));
+
+    let parameter_offset =
+      llvm_extern_abi.map_or(false, LlvmExternAbi::has_return_pointer) as usize;
+
+    for (llvm_parameter, (parameter_name, _)) in llvm_function
+      .get_param_iter()
+      .skip(parameter_offset)
+      .zip(prototype.parameters.iter())
+    {
+      llvm_parameter.set_name(parameter_name.as_str());
+    }
+
+    if let Some(llvm_extern_abi) = llvm_extern_abi {
+      self.add_abi_attributes(llvm_function, llvm_extern_abi);
+    }
+
+    self.llvm_function_map.insert(qualified_name, llvm_function);
+
+    Ok(llvm_function)
+  }
+
+  // Functions are named after their path and signature (see the
+  // `mangling` module), so that functions of the same name within
+  // different namespaces do not collide, unless they opt out of
+  // name mangling (ex. to be called from C through an `extern`).
+  fn find_function_symbol(&self, function: &function::Function) -> String {
+    if let Some(export_name) = function.find_export_name() {
+      return String::from(export_name);
+    }
+
+    let namespace_path = self
+      .outermost_namespace_name
+      .iter()
+      .chain(&self.namespace_path)
+      .cloned()
+      .collect::<Vec<_>>();
+
+    mangling::mangle(&namespace_path, &function.prototype)
+  }
+
+  fn declare_function_of(
+    &mut self,
+    function: &function::Function,
+  ) -> Result<inkwell::values::FunctionValue<'a>, diagnostic::Diagnostic> {
+    // Only instances of generic functions are lowered, which are
+    // created by the monomorphization pass.
+    if function.prototype.is_generic() {
+      return Err(diagnostic::Diagnostic {
+        message: format!(
+          "generic function `{}` must be instantiated before lowering",
+          function.prototype.name
+        ),
+        severity: diagnostic::DiagnosticSeverity::Internal,
+        span: Some(function.span),
+        notes: vec![],
+      });
+    }
+
+    let llvm_name = self.find_function_symbol(function);
+    let is_declared = self
+      .llvm_function_map
+      .contains_key(&self.get_qualified_name(&function.prototype.get_instance_name()));
+
+    if !is_declared && !self.llvm_defined_symbols.insert(llvm_name.clone()) {
+      return Err(diagnostic::Diagnostic {
+        message: format!(
+          "symbol `{}` of function `{}` is already defined by another function",
+          llvm_name,
+          function.prototype.get_instance_name()
+        ),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(function.span),
+        notes: vec![],
+      });
+    }
+
+    let llvm_linkage = find_function_linkage(function);
+    let llvm_function =
+      self.declare_function(&function.prototype, &llvm_name, llvm_linkage, None)?;
+
+    // The function may have been declared by an extern of the same
+    // name beforehand, in which case the definition determines its
+    // linkage.
+    llvm_function.set_linkage(llvm_linkage);
+
+    Ok(llvm_function)
+  }
+
+  // Externs are defined outside of the module, under their own
+  // name, and are always resolved by the linker, regardless of
+  // their visibility. They are expected to follow the C calling
+  // convention.
+  fn declare_external(
+    &mut self,
+    external: &external::External,
+  ) -> Result<inkwell::values::FunctionValue<'a>, diagnostic::Diagnostic> {
+    let llvm_return_type = self
+      .visit_or_retrieve_type(&external.prototype.return_kind_group.to_value_kind())?
+      .copied();
+
+    assert!(llvm_return_type.is_some());
+
+    let mut llvm_parameter_types = vec![];
+
+    for (_, kind_group) in &external.prototype.parameters {
+      let llvm_parameter_type = self
+        .visit_or_retrieve_type(&kind_group.to_value_kind())?
+        .copied();
+
+      assert!(llvm_parameter_type.is_some());
+
+      llvm_parameter_types.push(as_basic_type(llvm_parameter_type.unwrap()));
+    }
+
+    let llvm_extern_abi = LlvmExternAbi {
+      llvm_parameter_types,
+      parameter_modes: external
+        .prototype
+        .parameters
+        .iter()
+        .map(|(_, kind_group)| {
+          c_abi::find_passing_mode(kind_group, &self.struct_map, &self.enum_map)
+        })
+        .collect(),
+      parameter_extensions: external
+        .prototype
+        .parameters
+        .iter()
+        .map(|(_, kind_group)| c_abi::find_extension(kind_group))
+        .collect(),
+      return_mode: c_abi::find_passing_mode(
+        &external.prototype.return_kind_group,
+        &self.struct_map,
+        &self.enum_map,
+      ),
+      return_extension: c_abi::find_extension(&external.prototype.return_kind_group),
+      llvm_return_type: llvm_return_type.unwrap(),
+    };
+
+    let llvm_function = self.declare_function(
+      &external.prototype,
+      &external.prototype.name,
+      inkwell::module::Linkage::External,
+      Some(&llvm_extern_abi),
+    )?;
+
+    self.llvm_extern_abi_map.insert(
+      self.get_qualified_name(&external.prototype.name),
+      llvm_extern_abi,
+    );
+
+    Ok(llvm_function)
+  }
+
+  // Lower the type of references to trait objects, which is named
+  // after their kind (ex. `dyn Shape`), and holds a pointer to the
+  // value, followed by a pointer to the vtable of its kind. Mutable
+  // and immutable references share the same type.
+  fn get_dyn_reference_type_of(
+    &self,
+    dyn_kind: &dyn_kind::DynKind,
+  ) -> inkwell::types::StructType<'a> {
+    let name = dyn_kind.to_string();
+
+    if let Some(llvm_dyn_type) = self.llvm_module.get_struct_type(&name) {
+      return llvm_dyn_type;
+    }
+
+    let llvm_dyn_type = self.llvm_context.opaque_struct_type(&name);
+
+    let llvm_byte_pointer_type = self
+      .llvm_context
+      .i8_type()
+      .ptr_type(inkwell::AddressSpace::Generic);
+
+    llvm_dyn_type.set_body(
+      &[
+        llvm_byte_pointer_type.as_basic_type_enum(),
+        llvm_byte_pointer_type
+          .ptr_type(inkwell::AddressSpace::Generic)
+          .as_basic_type_enum(),
+      ],
+      false,
+    );
+
+    llvm_dyn_type
+  }
+
+  // Build the vtable of an implementation, once its methods are
+  // declared, as a constant array of pointers to its methods (cast
+  // to pointers to bytes), in the order of its trait's methods.
+  fn declare_vtable(&mut self, impl_decl: &impl_decl::ImplDecl) -> pass::PassResult {
+    let trait_path = impl_decl.trait_path.to_string();
+    let trait_decl = self.trait_map.get(&trait_path).cloned();
+
+    assert!(trait_decl.is_some());
+
+    let llvm_byte_pointer_type = self
+      .llvm_context
+      .i8_type()
+      .ptr_type(inkwell::AddressSpace::Generic);
+
+    let mut llvm_method_pointers = vec![];
+
+    for method in &trait_decl.unwrap().methods {
+      let qualified_name =
+        self.get_qualified_name(&format!("{}::{}", impl_decl.get_name(), method.name));
+
+      let llvm_function = match self.llvm_function_map.get(&qualified_name) {
+        Some(llvm_function) => *llvm_function,
+        None => {
+          return Err(diagnostic::Diagnostic {
+            message: format!(
+              "missing method `{}` of trait `{}` in implementation for `{}`",
+              method.name, trait_path, impl_decl.kind_group.kind
+            ),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(impl_decl.span),
+            notes: vec![],
+          })
+        }
+      };
+
+      llvm_method_pointers.push(
+        llvm_function
+          .as_global_value()
+          .as_pointer_value()
+          .const_cast(llvm_byte_pointer_type),
+      );
+    }
+
+    let llvm_vtable_value = llvm_byte_pointer_type.const_array(&llvm_method_pointers);
+
+    let llvm_global = self.llvm_module.add_global(
+      llvm_vtable_value.get_type(),
+      None,
+      &format!("{}::vtable", self.get_qualified_name(&impl_decl.get_name())),
+    );
+
+    llvm_global.set_initializer(&llvm_vtable_value);
+    llvm_global.set_constant(true);
+    llvm_global.set_linkage(inkwell::module::Linkage::Private);
+
+    let llvm_kind_type = self
+      .visit_or_retrieve_type(&impl_decl.kind_group.to_value_kind())?
+      .copied();
+
+    assert!(llvm_kind_type.is_some());
+
+    self.llvm_vtables.push(LlvmVtable {
+      trait_path,
+      llvm_kind_type: llvm_kind_type.unwrap(),
+      llvm_global,
+    });
+
+    Ok(())
+  }
+
+  // Collect the declarations of all structs, enums and traits within
+  // the namespace being lowered, and those nested in it, whose types
+  // are lowered as they are used.
+  fn collect_kind_declarations(&mut self, namespace: &namespace::Namespace) {
+    for (name, top_level_node) in namespace.symbol_table.iter() {
+      match top_level_node {
+        namespace::TopLevelNode::Trait(trait_decl) => {
+          self
+            .trait_map
+            .insert(self.get_qualified_name(name), trait_decl.clone());
+        }
+        namespace::TopLevelNode::Struct(struct_decl) => {
+          self
+            .struct_map
+            .insert(self.get_qualified_name(name), struct_decl.clone());
+        }
+        namespace::TopLevelNode::Enum(enum_decl) => {
+          self
+            .enum_map
+            .insert(self.get_qualified_name(name), enum_decl.clone());
+        }
+        namespace::TopLevelNode::Namespace(namespace) => {
+          self.namespace_path.push(namespace.name.clone());
+          self.collect_kind_declarations(namespace);
+          self.namespace_path.pop();
+        }
+        _ => {}
+      };
+    }
+  }
+
+  // Declare all functions and externs within the namespace being
+  // lowered, and those nested in it. The methods of implementations
+  // are declared within the namespace named after the implementation
+  // (ex. `<Square as Shape>`), along with their vtable.
+  fn declare_namespace(&mut self, namespace: &namespace::Namespace) -> pass::PassResult {
+    for top_level_node in namespace.symbol_table.values() {
+      match top_level_node {
+        namespace::TopLevelNode::Function(function) => {
+          with_fallback_span(self.declare_function_of(function), Some(function.span))?;
+        }
+        namespace::TopLevelNode::External(external) => {
+          with_fallback_span(self.declare_external(external), Some(external.span))?;
+        }
+        namespace::TopLevelNode::Struct(_)
+        | namespace::TopLevelNode::Enum(_)
+        | namespace::TopLevelNode::Trait(_) => {}
+        namespace::TopLevelNode::Namespace(namespace) => {
+          self.namespace_path.push(namespace.name.clone());
+          self.declare_namespace(namespace)?;
+          self.namespace_path.pop();
+        }
+      };
+    }
+
+    for impl_decl in &namespace.impls {
+      self.namespace_path.push(impl_decl.get_name());
+
+      for method in &impl_decl.methods {
+        with_fallback_span(self.declare_function_of(method), Some(method.span))?;
+      }
+
+      self.namespace_path.pop();
+      with_fallback_span(self.declare_vtable(impl_decl), Some(impl_decl.span))?;
+    }
+
+    Ok(())
+  }
+
+  // Lower the bodies of all functions within the namespace being
+  // lowered, and those nested in it.
+  fn lower_namespace(&mut self, namespace: &namespace::Namespace) -> pass::PassResult {
+    for top_level_node in namespace.symbol_table.values() {
+      match top_level_node {
+        namespace::TopLevelNode::Function(function) => self.visit_function(function)?,
+        namespace::TopLevelNode::External(_)
+        | namespace::TopLevelNode::Struct(_)
+        | namespace::TopLevelNode::Enum(_)
+        | namespace::TopLevelNode::Trait(_) => {}
+        namespace::TopLevelNode::Namespace(namespace) => {
+          self.namespace_path.push(namespace.name.clone());
+          self.lower_namespace(namespace)?;
+          self.namespace_path.pop();
+        }
+      };
+    }
+
+    for impl_decl in &namespace.impls {
+      self.namespace_path.push(impl_decl.get_name());
+
+      for method in &impl_decl.methods {
+        self.visit_function(method)?;
+      }
+
+      self.namespace_path.pop();
+    }
+
+    Ok(())
+  }
+
+  // Apply the C default argument promotions to a value passed
+  // as a variadic argument (ex. to `printf`): integers smaller
+  // than `int` are extended to it, and floats become doubles.
+  fn promote_variadic_argument(
+    &self,
+    llvm_value: inkwell::values::BasicValueEnum<'a>,
+  ) -> inkwell::values::BasicValueEnum<'a> {
+    let llvm_c_int_type = self.llvm_context.i32_type();
+
+    match llvm_value {
+      inkwell::values::BasicValueEnum::IntValue(llvm_int_value) => {
+        let bit_width = llvm_int_value.get_type().get_bit_width();
+
+        if bit_width >= llvm_c_int_type.get_bit_width() {
+          return llvm_value;
+        }
+
+        // Booleans are unsigned, while every other integer
+        // kind is signed.
+        inkwell::values::BasicValueEnum::IntValue(match bit_width {
+          1 => self.llvm_builder_buffer.build_int_z_extend(
+            llvm_int_value,
+            llvm_c_int_type,
+            "vararg.zext",
+          ),
+          _ => self.llvm_builder_buffer.build_int_s_extend(
+            llvm_int_value,
+            llvm_c_int_type,
+            "vararg.sext",
+          ),
+        })
+      }
+      inkwell::values::BasicValueEnum::FloatValue(llvm_float_value)
+        if llvm_float_value.get_type() == self.llvm_context.f32_type() =>
+      {
+        inkwell::values::BasicValueEnum::FloatValue(self.llvm_builder_buffer.build_float_ext(
+          llvm_float_value,
+          self.llvm_context.f64_type(),
+          "vararg.fpext",
+        ))
+      }
+      _ => llvm_value,
+    }
+  }
+
+  // Lower a call to a previously declared function or extern.
+  //
+  // Returns [`None`] if the callee does not return a value.
+  fn visit_call_expr(
+    &mut self,
+    call_expr: &node::CallExpr,
+  ) -> Result<Option<inkwell::values::BasicValueEnum<'a>>, diagnostic::Diagnostic> {
+    if let Some((method_name, trait_segments)) = call_expr.callee.segments.split_last() {
+      // Calls through trait objects are left as calls to the trait's
+      // method by the monomorphization pass.
+      if let Some(trait_decl) = self.trait_map.get(&trait_segments.join("::")).cloned() {
+        return self.visit_dyn_call_expr(call_expr, &trait_decl, method_name);
+      }
+    }
+
+    // Calls are expected to have been resolved to the path of
+    // their callee, relative to the outermost namespace.
+    let llvm_function = match self.llvm_function_map.get(&call_expr.callee.to_string()) {
+      Some(value) => *value,
+      None => {
+        return Err(diagnostic::Diagnostic {
+          message: format!("cannot find function `{}` in this scope", call_expr.callee),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(call_expr.span),
+          notes: vec![],
+        })
+      }
+    };
+
+    let llvm_extern_abi = self
+      .llvm_extern_abi_map
+      .get(&call_expr.callee.to_string())
+      .cloned();
+
+    // The hidden pointer to the returned value (if any) is not one
+    // of the callee's parameters.
+    let parameter_offset = llvm_extern_abi
+      .as_ref()
+      .map_or(false, LlvmExternAbi::has_return_pointer) as usize;
+
+    let parameter_count = llvm_function.count_params() as usize - parameter_offset;
+    let argument_count = call_expr.arguments.len();
+
+    if argument_count < parameter_count
+      || (!llvm_function.get_type().is_var_arg() && argument_count > parameter_count)
+    {
+      return Err(diagnostic::Diagnostic {
+        message: format!(
+          "function `{}` takes {} argument(s) but {} were supplied",
+          call_expr.callee, parameter_count, argument_count
+        ),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(call_expr.span),
+        notes: vec![],
+      });
+    }
+
+    let mut llvm_arguments: Vec<inkwell::values::BasicMetadataValueEnum<'a>> = vec![];
+    let mut llvm_return_slot = None;
+
+    if let Some(llvm_extern_abi) = llvm_extern_abi
+      .as_ref()
+      .filter(|abi| abi.has_return_pointer())
+    {
+      let llvm_return_type = as_basic_type(llvm_extern_abi.llvm_return_type);
+
+      assert!(llvm_return_type.is_some());
+
+      let llvm_stack_slot = self.build_entry_alloca(llvm_return_type.unwrap(), "sret")?;
+
+      llvm_return_slot = Some(llvm_stack_slot);
+      llvm_arguments.push(llvm_stack_slot.into());
+    }
+
+    for (index, argument) in call_expr.arguments.iter().enumerate() {
+      let passing_mode = llvm_extern_abi
+        .as_ref()
+        .and_then(|llvm_extern_abi| llvm_extern_abi.parameter_modes.get(index).copied())
+        .unwrap_or(c_abi::PassingMode::Direct);
+
+      let llvm_parameter_type = llvm_function
+        .get_nth_param((index + parameter_offset) as u32)
+        .map(|llvm_parameter| llvm_parameter.get_type());
+
+      // Variadic arguments have no expected type, and arguments
+      // passed as another type are first lowered as their own type.
+      let llvm_argument = self.visit_expr_as(
+        argument,
+        match passing_mode {
+          c_abi::PassingMode::Direct => llvm_parameter_type,
+          _ => llvm_extern_abi
+            .as_ref()
+            .and_then(|llvm_extern_abi| llvm_extern_abi.llvm_parameter_types.get(index).copied())
+            .flatten(),
+        },
+      )?;
+
+      let llvm_argument = match passing_mode {
+        c_abi::PassingMode::Direct => llvm_argument,
+        c_abi::PassingMode::Coerced { .. } => {
+          assert!(llvm_parameter_type.is_some());
+          self.coerce_through_memory(llvm_argument, llvm_parameter_type.unwrap())?
+        }
+        // The callee receives a copy of the argument (`byval`).
+        c_abi::PassingMode::Indirect => {
+          let llvm_stack_slot = self.build_entry_alloca(llvm_argument.get_type(), "byval")?;
+
+          self
+            .llvm_builder_buffer
+            .build_store(llvm_stack_slot, llvm_argument);
+
+          llvm_stack_slot.into()
+        }
+      };
+
+      llvm_arguments.push(
+        match index < parameter_count {
+          true => llvm_argument,
+          false => self.promote_variadic_argument(llvm_argument),
+        }
+        .into(),
+      );
+    }
+
+    let is_void = llvm_function.get_type().get_return_type().is_none();
+
+    // Void values cannot be named.
+    let llvm_call_site = self.llvm_builder_buffer.build_call(
+      llvm_function,
+      llvm_arguments.as_slice(),
+      if is_void { "" } else { "call" },
+    );
+
+    let llvm_value = llvm_call_site.try_as_basic_value().left();
+
+    // Values returned as another type (or in memory) are converted
+    // back into their own type.
+    Ok(match (llvm_extern_abi, llvm_return_slot, llvm_value) {
+      (_, Some(llvm_return_slot), _) => Some(
+        self
+          .llvm_builder_buffer
+          .build_load(llvm_return_slot, "sret.load"),
+      ),
+      (Some(llvm_extern_abi), None, Some(llvm_value))
+        if llvm_extern_abi.return_mode != c_abi::PassingMode::Direct =>
+      {
+        let llvm_return_type = as_basic_type(llvm_extern_abi.llvm_return_type);
+
+        assert!(llvm_return_type.is_some());
+
+        Some(self.coerce_through_memory(llvm_value, llvm_return_type.unwrap())?)
+      }
+      (_, _, llvm_value) => llvm_value,
+    })
+  }
+
+  // Lower a call to a method through a trait object, which calls the
+  // method found in the vtable of its receiver, given the pointer to
+  // the receiver's value as its first argument.
+  //
+  // Returns [`None`] if the method does not return a value.
+  fn visit_dyn_call_expr(
+    &mut self,
+    call_expr: &node::CallExpr,
+    trait_decl: &trait_decl::TraitDecl,
+    method_name: &str,
+  ) -> Result<Option<inkwell::values::BasicValueEnum<'a>>, diagnostic::Diagnostic> {
+    let (method_index, method) = match trait_decl.find_method(method_name) {
+      Some(method) => method,
+      None => {
+        return Err(diagnostic::Diagnostic {
+          message: format!("cannot find function `{}` in this scope", call_expr.callee),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(call_expr.span),
+          notes: vec![],
+        })
+      }
+    };
+
+    if call_expr.arguments.len() != method.parameters.len() {
+      return Err(diagnostic::Diagnostic {
+        message: format!(
+          "function `{}` takes {} argument(s) but {} were supplied",
+          call_expr.callee,
+          method.parameters.len(),
+          call_expr.arguments.len()
+        ),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(call_expr.span),
+        notes: vec![],
+      });
+    }
+
+    let llvm_receiver = self.visit_expr(&call_expr.arguments[0])?;
+
+    assert!(llvm_receiver.is_struct_value());
+
+    let llvm_receiver = llvm_receiver.into_struct_value();
+
+    let llvm_data_pointer =
+      self
+        .llvm_builder_buffer
+        .build_extract_value(llvm_receiver, 0, "dyn.data");
+
+    let llvm_vtable_pointer =
+      self
+        .llvm_builder_buffer
+        .build_extract_value(llvm_receiver, 1, "dyn.vtable");
+
+    assert!(llvm_data_pointer.is_some() && llvm_vtable_pointer.is_some());
+
+    let llvm_method_slot = unsafe {
+      self.llvm_builder_buffer.build_in_bounds_gep(
+        llvm_vtable_pointer.unwrap().into_pointer_value(),
+        &[self
+          .llvm_context
+          .i64_type()
+          .const_int(method_index as u64, false)],
+        "dyn.slot",
+      )
+    };
+
+    let llvm_method_pointer = self
+      .llvm_builder_buffer
+      .build_load(llvm_method_slot, "dyn.method")
+      .into_pointer_value();
+
+    // The receiver is passed as a pointer to bytes, which is passed
+    // like a pointer to the value's kind.
+    let mut llvm_parameter_types: Vec<inkwell::types::BasicMetadataTypeEnum<'a>> = vec![self
+      .llvm_context
+      .i8_type()
+      .ptr_type(inkwell::AddressSpace::Generic)
+      .into()];
+
+    let mut llvm_arguments: Vec<inkwell::values::BasicMetadataValueEnum<'a>> =
+      vec![llvm_data_pointer.unwrap().into()];
+
+    for ((parameter_name, kind_group), argument) in
+      method.parameters.iter().zip(&call_expr.arguments).skip(1)
+    {
+      let llvm_parameter_type = match self.get_stored_type_of(kind_group)? {
+        Some(llvm_parameter_type) => llvm_parameter_type,
+        None => {
+          return Err(diagnostic::Diagnostic {
+            message: format!(
+              "parameter `{}` of `{}` cannot be of kind `{}`",
+              parameter_name, call_expr.callee, kind_group.kind
+            ),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(kind_group.span),
+            notes: vec![],
+          })
+        }
+      };
+
+      llvm_parameter_types.push(llvm_parameter_type.into());
+      llvm_arguments.push(
+        self
+          .visit_expr_as(argument, Some(llvm_parameter_type))?
+          .into(),
+      );
+    }
+
+    let llvm_return_type = self
+      .visit_or_retrieve_type(&method.return_kind_group.to_value_kind())?
+      .copied();
+
+    assert!(llvm_return_type.is_some());
+
+    let llvm_function_type = LlvmLoweringPass::get_function_type_from(
+      &llvm_return_type.unwrap(),
+      llvm_parameter_types.as_slice(),
+      false,
+    )?;
+
+    let llvm_function_pointer = self.llvm_builder_buffer.build_pointer_cast(
+      llvm_method_pointer,
+      llvm_function_type.ptr_type(inkwell::AddressSpace::Generic),
+      "dyn.fn",
+    );
+
+    let llvm_callable = inkwell::values::CallableValue::try_from(llvm_function_pointer);
+
+    assert!(llvm_callable.is_ok());
+
+    let is_void = llvm_function_type.get_return_type().is_none();
+
+    // Void values cannot be named.
+    let llvm_call_site = self.llvm_builder_buffer.build_call(
+      llvm_callable.unwrap(),
+      llvm_arguments.as_slice(),
+      if is_void { "" } else { "call" },
+    );
+
+    Ok(llvm_call_site.try_as_basic_value().left())
+  }
+
+  // Find the value bound to the name of the variable, starting from
+  // the innermost scope.
+  fn retrieve_named_value(
+    &self,
+    variable_ref: &node::VariableRef,
+  ) -> Result<LlvmNamedValue<'a>, diagnostic::Diagnostic> {
+    let name = &variable_ref.name;
+
+    match self
+      .llvm_named_value_scopes
+      .iter()
+      .rev()
+      .find_map(|scope| scope.get(name))
+    {
+      Some(llvm_named_value) => Ok(*llvm_named_value),
+      None => Err(diagnostic::Diagnostic {
+        message: format!("cannot find value `{}` in this scope", name),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(variable_ref.span),
+        notes: vec![],
+      }),
+    }
+  }
+
+  // Bind each of the function's parameters to a stack slot in the
+  // entry block, so that they are addressable and visible by name
+  // within the body. The builder is expected to be positioned at
+  // the end of the entry block.
+  fn spill_parameters(
+    &mut self,
+    function: &function::Function,
+  ) -> Result<std::collections::HashMap<String, LlvmNamedValue<'a>>, diagnostic::Diagnostic> {
+    assert!(self.llvm_function_buffer.is_some());
+
+    let mut llvm_parameter_scope = std::collections::HashMap::new();
+
+    for (llvm_parameter, (parameter_name, _)) in self
+      .llvm_function_buffer
+      .unwrap()
+      .get_param_iter()
+      .zip(function.prototype.parameters.iter())
+    {
+      let llvm_stack_slot = self.llvm_builder_buffer.build_alloca(
+        llvm_parameter.get_type(),
+        format!("{}.addr", parameter_name).as_str(),
+      );
+
+      self
+        .llvm_builder_buffer
+        .build_store(llvm_stack_slot, llvm_parameter);
+
+      llvm_parameter_scope.insert(
+        parameter_name.clone(),
+        LlvmNamedValue::StackSlot(llvm_stack_slot),
+      );
+    }
+
+    Ok(llvm_parameter_scope)
+  }
+
+  // Lower both bounds of a `for` loop's range, ensuring that
+  // they are integers of the same kind.
+  fn visit_range_bounds(
+    &mut self,
+    for_stmt: &block::ForStmt,
+  ) -> Result<(inkwell::values::IntValue<'a>, inkwell::values::IntValue<'a>), diagnostic::Diagnostic>
+  {
+    // A literal bound takes the type of the other bound, which
+    // must therefore be lowered first. Literals have no side
+    // effects, so evaluating the end first is not observable.
+    let (llvm_start_value, llvm_end_value) = match for_stmt.range_start {
+      node::AnyExprNode::Literal(_) => {
+        let llvm_end_value = self.visit_expr(&for_stmt.range_end)?;
+
+        (
+          self.visit_expr_as(&for_stmt.range_start, Some(llvm_end_value.get_type()))?,
+          llvm_end_value,
+        )
+      }
+      _ => {
+        let llvm_start_value = self.visit_expr(&for_stmt.range_start)?;
+
+        (
+          llvm_start_value,
+          self.visit_expr_as(&for_stmt.range_end, Some(llvm_start_value.get_type()))?,
+        )
+      }
+    };
+
+    // Booleans are also represented as (1-bit) integers.
+    let is_integer = |llvm_value: &inkwell::values::BasicValueEnum<'a>| {
+      llvm_value.is_int_value() && llvm_value.into_int_value().get_type().get_bit_width() > 1
+    };
+
+    let range_span = span::Span {
+      start: for_stmt.range_start.get_span().start,
+      end: for_stmt.range_end.get_span().end,
+    };
+
+    if !is_integer(&llvm_start_value) || !is_integer(&llvm_end_value) {
+      return Err(diagnostic::Diagnostic {
+        message: String::from("range bounds must be integers"),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(range_span),
+        notes: vec![],
+      });
+    } else if llvm_start_value.get_type() != llvm_end_value.get_type() {
+      return Err(diagnostic::Diagnostic {
+        message: String::from("range bounds must be of the same integer kind"),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(range_span),
+        notes: vec![],
+      });
+    }
+
+    Ok((
+      llvm_start_value.into_int_value(),
+      llvm_end_value.into_int_value(),
+    ))
+  }
+
+  // Position the builder at the end of the given basic block,
+  // and mark it as the current basic block.
+  fn position_at_end(&mut self, llvm_basic_block: inkwell::basic_block::BasicBlock<'a>) {
+    self.llvm_builder_buffer.position_at_end(llvm_basic_block);
+    self.llvm_basic_block_buffer = Some(llvm_basic_block);
+  }
+
+  // Find the loop targeted by a `break` or `continue` statement,
+  // which is either the innermost loop, or the loop with the
+  // given label.
+  fn find_loop_frame(
+    &mut self,
+    label: &Option<String>,
+  ) -> Result<&mut LlvmLoopFrame<'a>, diagnostic::Diagnostic> {
+    let llvm_loop_frame = match label {
+      Some(_) => self
+        .llvm_loop_stack
+        .iter_mut()
+        .rev()
+        .find(|llvm_loop_frame| llvm_loop_frame.label == *label),
+      None => self.llvm_loop_stack.last_mut(),
+    };
+
+    // Loop control statements should have been validated by
+    // the loop check pass prior to lowering.
+    match llvm_loop_frame {
+      Some(value) => Ok(value),
+      None => Err(diagnostic::Diagnostic {
+        message: String::from("loop control statement does not target any loop"),
+        severity: diagnostic::DiagnosticSeverity::Internal,
+        span: None,
+        notes: vec![],
+      }),
+    }
+  }
+
+  // Determine whether the current basic block already ends with
+  // a terminator instruction (ex. a return or a branch), in which
+  // case no more instructions may be appended to it.
+  fn is_current_block_terminated(&self) -> bool {
+    match self.llvm_basic_block_buffer {
+      Some(llvm_basic_block) => llvm_basic_block.get_terminator().is_some(),
+      None => false,
+    }
+  }
+}
+
+impl<'a> pass::Pass<'a> for LlvmLoweringPass<'a> {
+  fn visit_prototype(&mut self, _prototype: &prototype::Prototype) -> pass::PassResult {
+    // TODO
+    // inkwell::values::GenericValue
+    Ok(())
+  }
+
+  fn visit_int_kind(&mut self, int_kind: &int_kind::IntKind) -> pass::PassResult {
+    self.llvm_type_map.insert(
+      node::AnyKindNode::IntKind(*int_kind),
+      match int_kind.size {
+        int_kind::IntSize::Signed8 => self.llvm_context.i8_type().as_any_type_enum(),
+        int_kind::IntSize::Signed16 => self.llvm_context.i16_type().as_any_type_enum(),
+        int_kind::IntSize::Signed32 => self.llvm_context.i32_type().as_any_type_enum(),
+        int_kind::IntSize::Signed64 => self.llvm_context.i64_type().as_any_type_enum(),
+        int_kind::IntSize::Signed128 => self.llvm_context.i128_type().as_any_type_enum(),
+      },
+    );
+    Ok(())
+  }
+
+  fn visit_void_kind(&mut self, void_kind: &void_kind::VoidKind) -> pass::PassResult {
+    self.llvm_type_map.insert(
+      node::AnyKindNode::VoidKind(*void_kind),
+      self.llvm_context.void_type().as_any_type_enum(),
+    );
+    Ok(())
+  }
+
+  fn visit_bool_kind(&mut self, bool_kind: &bool_kind::BoolKind) -> pass::PassResult {
+    self.llvm_type_map.insert(
+      node::AnyKindNode::BoolKind(*bool_kind),
+      self.llvm_context.bool_type().as_any_type_enum(),
+    );
+    Ok(())
+  }
+
+  fn visit_struct_kind(&mut self, struct_kind: &struct_kind::StructKind) -> pass::PassResult {
+    let path = struct_kind.to_string();
+
+    let struct_decl = match self.struct_map.get(&path) {
+      Some(struct_decl) => struct_decl.clone(),
+      None => {
+        return Err(diagnostic::Diagnostic {
+          message: format!("cannot find struct `{}` in this scope", path),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: None,
+          notes: vec![],
+        })
+      }
+    };
+
+    // Struct types are named after their path. The type is known
+    // before lowering the types of its fields, so that a field
+    // referring to the struct itself (through a reference) lowers
+    // into a pointer to it.
+    let llvm_struct_type = self.llvm_context.opaque_struct_type(path.as_str());
+
+    self.llvm_type_map.insert(
+      node::AnyKindNode::StructKind(struct_kind.clone()),
+      llvm_struct_type.as_any_type_enum(),
+    );
+
+    let mut llvm_field_types = vec![];
+
+    for field in &struct_decl.fields {
+      llvm_field_types.push(self.get_field_type_of(field)?);
+    }
+
+    llvm_struct_type.set_body(llvm_field_types.as_slice(), false);
+
+    Ok(())
+  }
+
+  fn visit_enum_kind(&mut self, enum_kind: &enum_kind::EnumKind) -> pass::PassResult {
+    let path = enum_kind.to_string();
+
+    let enum_decl = match self.enum_map.get(&path) {
+      Some(enum_decl) => enum_decl.clone(),
+      None => {
+        return Err(diagnostic::Diagnostic {
+          message: format!("cannot find enum `{}` in this scope", path),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: None,
+          notes: vec![],
+        })
+      }
+    };
+
+    // Enum types are named after their path, and hold the tag,
+    // followed by storage as large and as aligned as the payload of
+    // any variant, which is accessed through the payload type of
+    // each variant.
+    let llvm_enum_type = self.llvm_context.opaque_struct_type(path.as_str());
+
+    self.llvm_type_map.insert(
+      node::AnyKindNode::EnumKind(enum_kind.clone()),
+      llvm_enum_type.as_any_type_enum(),
+    );
+
+    let payload_layout = c_abi::find_payload_layout(&enum_decl, &self.struct_map, &self.enum_map);
+
+    assert!(payload_layout.is_some());
+
+    let payload_layout = payload_layout.unwrap();
+    let mut llvm_field_types = vec![self.llvm_context.i32_type().as_basic_type_enum()];
+
+    if payload_layout.size > 0 {
+      let llvm_unit_type = self
+        .llvm_context
+        .custom_width_int_type((payload_layout.alignment * 8) as u32);
+
+      llvm_field_types.push(
+        llvm_unit_type
+          .array_type((payload_layout.size / payload_layout.alignment) as u32)
+          .as_basic_type_enum(),
+      );
+    }
+
+    llvm_enum_type.set_body(llvm_field_types.as_slice(), false);
+
+    Ok(())
+  }
+
+  fn visit_array_kind(&mut self, array_kind: &array_kind::ArrayKind) -> pass::PassResult {
+    let llvm_element_type = self.get_element_type_of(&array_kind.element_kind)?;
+
+    if array_kind.length > u32::MAX as u64 {
+      return Err(diagnostic::Diagnostic {
+        message: format!("array kind `{}` is too large", array_kind),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: None,
+        notes: vec![],
+      });
+    }
+
+    self.llvm_type_map.insert(
+      node::AnyKindNode::ArrayKind(array_kind.clone()),
+      llvm_element_type
+        .array_type(array_kind.length as u32)
+        .as_any_type_enum(),
+    );
+
+    Ok(())
+  }
+
+  fn visit_slice_kind(&mut self, slice_kind: &slice_kind::SliceKind) -> pass::PassResult {
+    // Slice types are named after their kind, and hold a pointer to
+    // the first element, followed by the amount of elements. Like
+    // struct types, the type is known before lowering the type of
+    // its elements, which may refer to it (ex. a struct holding a
+    // slice of itself).
+    let llvm_slice_type = self
+      .llvm_context
+      .opaque_struct_type(slice_kind.to_string().as_str());
+
+    self.llvm_type_map.insert(
+      node::AnyKindNode::SliceKind(slice_kind.clone()),
+      llvm_slice_type.as_any_type_enum(),
+    );
+
+    let llvm_element_type = self.get_element_type_of(&slice_kind.element_kind)?;
+
+    llvm_slice_type.set_body(
+      &[
+        llvm_element_type
+          .ptr_type(inkwell::AddressSpace::Generic)
+          .as_basic_type_enum(),
+        self.llvm_context.i64_type().as_basic_type_enum(),
+      ],
+      false,
+    );
+
+    Ok(())
+  }
+
+  fn visit_reference_kind(
+    &mut self,
+    reference_kind: &reference_kind::ReferenceKind,
+  ) -> pass::PassResult {
+    if let node::AnyKindNode::DynKind(dyn_kind) = reference_kind.referenced_kind.as_ref() {
+      self.llvm_type_map.insert(
+        node::AnyKindNode::ReferenceKind(reference_kind.clone()),
+        self.get_dyn_reference_type_of(dyn_kind).as_any_type_enum(),
+      );
+
+      return Ok(());
+    }
+
+    let llvm_referenced_type = self
+      .visit_or_retrieve_type(&reference_kind.referenced_kind)?
+      .copied();
+
+    assert!(llvm_referenced_type.is_some());
+
+    // References to kinds without values (ex. `&void`) point to
+    // bytes, like `void *` in C.
+    let llvm_referenced_type = as_basic_type(llvm_referenced_type.unwrap())
+      .unwrap_or_else(|| self.llvm_context.i8_type().as_basic_type_enum());
+
+    self.llvm_type_map.insert(
+      node::AnyKindNode::ReferenceKind(reference_kind.clone()),
+      llvm_referenced_type
+        .ptr_type(inkwell::AddressSpace::Generic)
+        .as_any_type_enum(),
+    );
+
+    Ok(())
+  }
+
+  fn visit_dyn_kind(&mut self, dyn_kind: &dyn_kind::DynKind) -> pass::PassResult {
+    Err(diagnostic::Diagnostic {
+      message: format!("trait object `{}` must be behind a reference", dyn_kind),
+      severity: diagnostic::DiagnosticSeverity::Error,
+      span: None,
+      notes: vec![],
+    })
+  }
+
+  fn visit_function(&mut self, function: &function::Function) -> pass::PassResult {
+    self.llvm_function_buffer = Some(with_fallback_span(
+      self.declare_function_of(function),
+      Some(function.span),
+    )?);
+
+    let llvm_entry_block = self
+      .llvm_context
+      .append_basic_block(self.llvm_function_buffer.unwrap(), "entry");
+
+    self.position_at_end(llvm_entry_block);
+
+    let llvm_parameter_scope =
+      with_fallback_span(self.spill_parameters(function), Some(function.span))?;
+
+    self.llvm_named_value_scopes.push(llvm_parameter_scope);
+
+    self.visit_block(&function.body)?;
+
+    self.llvm_named_value_scopes.pop();
+
+    // The implicit return of functions returning `void` is
+    // expected to have been made explicit beforehand (by the
+    // implicit return pass), so falling off the end of the body
+    // is never valid.
+    if !self.is_current_block_terminated() {
+      return Err(
+        match self
+          .llvm_function_buffer
+          .unwrap()
+          .get_type()
+          .get_return_type()
+        {
+          Some(_) => diagnostic::Diagnostic {
+            message: format!(
+              "function `{}` must return a value of kind `{}` on every path",
+              function.prototype.get_instance_name(),
+              function.prototype.return_kind_group.to_value_kind()
+            ),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(function.span),
+            notes: vec![],
+          },
+          None => diagnostic::Diagnostic {
+            message: format!(
+              "function `{}` is missing its implicit return",
+              function.prototype.get_instance_name()
+            ),
+            severity: diagnostic::DiagnosticSeverity::Internal,
+            span: Some(function.span),
+            notes: vec![],
+          },
+        },
+      );
+    }
+
+    Ok(())
+  }
+
+  fn visit_namespace(&mut self, namespace: &namespace::Namespace) -> pass::PassResult {
+    // Declare all functions and externs before lowering any
+    // function bodies, so that calls may refer to functions
+    // regardless of their declaration order or namespace.
+    self.outermost_namespace_name = Some(namespace.name.clone());
+    self.collect_kind_declarations(namespace);
+    self.declare_namespace(namespace)?;
+    self.lower_namespace(namespace)
+  }
+
+  fn visit_external(&mut self, external: &external::External) -> pass::PassResult {
+    with_fallback_span(self.declare_external(external), Some(external.span))?;
+
+    Ok(())
+  }
+
+  fn visit_block(&mut self, block: &block::Block) -> pass::PassResult {
+    // The basic block is expected to be created and positioned
+    // by the parent node (ex. a function, or an if statement).
+    assert!(self.llvm_function_buffer.is_some());
+    assert!(self.llvm_basic_block_buffer.is_some());
+
+    // Bindings made by `let` statements are only visible until the
+    // end of the block.
+    self
+      .llvm_named_value_scopes
+      .push(std::collections::HashMap::new());
+
+    for statement in &block.statements {
+      // Any statements after a terminator (ex. a return) are
+      // unreachable, and cannot be lowered into the same basic
+      // block.
+      if self.is_current_block_terminated() {
+        break;
+      }
+
+      let visitation_result = match statement {
+        block::AnyStatementNode::ReturnStmt(return_stmt) => self.visit_return_stmt(&return_stmt),
+        block::AnyStatementNode::IfStmt(if_stmt) => self.visit_if_stmt(&if_stmt),
+        block::AnyStatementNode::WhileStmt(while_stmt) => self.visit_while_stmt(&while_stmt),
+        block::AnyStatementNode::LoopStmt(loop_stmt) => self.visit_loop_stmt(&loop_stmt),
+        block::AnyStatementNode::ForStmt(for_stmt) => self.visit_for_stmt(&for_stmt),
+        block::AnyStatementNode::BreakStmt(break_stmt) => self.visit_break_stmt(&break_stmt),
+        block::AnyStatementNode::ContinueStmt(continue_stmt) => {
+          self.visit_continue_stmt(&continue_stmt)
+        }
+        block::AnyStatementNode::ExprStmt(expr_stmt) => self.visit_expr_stmt(&expr_stmt),
+        block::AnyStatementNode::LetStmt(let_stmt) => self.visit_let_stmt(&let_stmt),
+        block::AnyStatementNode::AssignStmt(assign_stmt) => self.visit_assign_stmt(&assign_stmt),
+      };
+
+      // Diagnostics about parts of the statement which have no span
+      // of their own (ex. kinds) are located at the statement.
+      with_fallback_span(visitation_result, statement.as_node().get_span())?;
+    }
+
+    self.llvm_named_value_scopes.pop();
+
+    Ok(())
+  }
+
+  fn visit_if_stmt(&mut self, if_stmt: &block::IfStmt) -> pass::PassResult {
+    assert!(self.llvm_function_buffer.is_some());
+    assert!(self.llvm_basic_block_buffer.is_some());
+
+    let llvm_function = self.llvm_function_buffer.unwrap();
+
+    let llvm_merge_block = self
+      .llvm_context
+      .append_basic_block(llvm_function, "if.end");
+
+    let branches = std::iter::once((&if_stmt.condition, &if_stmt.then_block)).chain(
+      if_stmt
+        .else_if_branches
+        .iter()
+        .map(|(condition, block)| (condition, block)),
+    );
+
+    let branch_count = if_stmt.else_if_branches.len() + 1;
+    let mut is_merge_block_reachable = false;
+
+    for (index, (condition, block)) in branches.enumerate() {
+      let llvm_condition = self.visit_condition(condition)?;
+
+      let llvm_then_block = self
+        .llvm_context
+        .append_basic_block(llvm_function, "if.then");
+
+      // The last condition falls through directly to the merge
+      // block, unless there is an `else` block.
+      let llvm_else_block = if index == branch_count - 1 && if_stmt.else_block.is_none() {
+        is_merge_block_reachable = true;
+
+        llvm_merge_block
+      } else {
+        self
+          .llvm_context
+          .append_basic_block(llvm_function, "if.else")
+      };
+
+      self.llvm_builder_buffer.build_conditional_branch(
+        llvm_condition,
+        llvm_then_block,
+        llvm_else_block,
+      );
+
+      self.position_at_end(llvm_then_block);
+      self.visit_block(block)?;
+
+      // Only branch to the merge block if the body did not
+      // already terminate (ex. by returning).
+      if !self.is_current_block_terminated() {
+        self
+          .llvm_builder_buffer
+          .build_unconditional_branch(llvm_merge_block);
+
+        is_merge_block_reachable = true;
+      }
+
+      self.position_at_end(llvm_else_block);
+    }
+
+    if let Some(else_block) = &if_stmt.else_block {
+      self.visit_block(else_block)?;
+
+      if !self.is_current_block_terminated() {
+        self
+          .llvm_builder_buffer
+          .build_unconditional_branch(llvm_merge_block);
+
+        is_merge_block_reachable = true;
+      }
+
+      self.position_at_end(llvm_merge_block);
+    }
+
+    // If every branch terminated, nothing can reach the merge
+    // block, but it must still be terminated to be well-formed.
+    if !is_merge_block_reachable {
+      self.llvm_builder_buffer.build_unreachable();
+    }
+
+    Ok(())
+  }
+
+  fn visit_while_stmt(&mut self, while_stmt: &block::WhileStmt) -> pass::PassResult {
+    assert!(self.llvm_function_buffer.is_some());
+    assert!(self.llvm_basic_block_buffer.is_some());
+
+    let llvm_function = self.llvm_function_buffer.unwrap();
+
+    let llvm_condition_block = self
+      .llvm_context
+      .append_basic_block(llvm_function, "while.cond");
+
+    let llvm_body_block = self
+      .llvm_context
+      .append_basic_block(llvm_function, "while.body");
+
+    let llvm_exit_block = self
+      .llvm_context
+      .append_basic_block(llvm_function, "while.end");
+
+    self
+      .llvm_builder_buffer
+      .build_unconditional_branch(llvm_condition_block);
+
+    self.position_at_end(llvm_condition_block);
+
+    let llvm_condition = self.visit_condition(&while_stmt.condition)?;
+
+    self.llvm_builder_buffer.build_conditional_branch(
+      llvm_condition,
+      llvm_body_block,
+      llvm_exit_block,
+    );
+
+    self.position_at_end(llvm_body_block);
+
+    self.llvm_loop_stack.push(LlvmLoopFrame {
+      label: while_stmt.label.clone(),
+      llvm_continue_block: llvm_condition_block,
+      llvm_break_block: llvm_exit_block,
+      is_break_block_reachable: true,
+    });
+
+    self.visit_block(&while_stmt.body)?;
+    self.llvm_loop_stack.pop();
+
+    if !self.is_current_block_terminated() {
+      self
+        .llvm_builder_buffer
+        .build_unconditional_branch(llvm_condition_block);
+    }
+
+    self.position_at_end(llvm_exit_block);
+
+    Ok(())
+  }
+
+  fn visit_loop_stmt(&mut self, loop_stmt: &block::LoopStmt) -> pass::PassResult {
+    assert!(self.llvm_function_buffer.is_some());
+    assert!(self.llvm_basic_block_buffer.is_some());
+
+    let llvm_function = self.llvm_function_buffer.unwrap();
+
+    let llvm_body_block = self
+      .llvm_context
+      .append_basic_block(llvm_function, "loop.body");
+
+    let llvm_exit_block = self
+      .llvm_context
+      .append_basic_block(llvm_function, "loop.end");
+
+    self
+      .llvm_builder_buffer
+      .build_unconditional_branch(llvm_body_block);
+
+    self.position_at_end(llvm_body_block);
+
+    self.llvm_loop_stack.push(LlvmLoopFrame {
+      label: loop_stmt.label.clone(),
+      llvm_continue_block: llvm_body_block,
+      llvm_break_block: llvm_exit_block,
+      is_break_block_reachable: false,
+    });
+
+    self.visit_block(&loop_stmt.body)?;
+
+    let llvm_loop_frame = self.llvm_loop_stack.pop().unwrap();
+
+    if !self.is_current_block_terminated() {
+      self
+        .llvm_builder_buffer
+        .build_unconditional_branch(llvm_body_block);
+    }
+
+    self.position_at_end(llvm_exit_block);
+
+    // Without a `break`, the loop never exits.
+    if !llvm_loop_frame.is_break_block_reachable {
+      self.llvm_builder_buffer.build_unreachable();
+    }
+
+    Ok(())
+  }
+
+  // Lowers into a canonical loop, so that it may be further
+  // optimized (ex. vectorized) by LLVM:
+  //
+  // - The bounds are evaluated once, before entering the loop.
+  // - The header holds the iterator as a `phi` node, and the
+  //   single exit condition.
+  // - The latch is the only block that increments the iterator
+  //   and branches back to the header.
+  fn visit_for_stmt(&mut self, for_stmt: &block::ForStmt) -> pass::PassResult {
+    assert!(self.llvm_function_buffer.is_some());
+    assert!(self.llvm_basic_block_buffer.is_some());
+
+    let llvm_function = self.llvm_function_buffer.unwrap();
+    let (llvm_start_value, llvm_end_value) = self.visit_range_bounds(for_stmt)?;
+    let llvm_preheader_block = self.llvm_basic_block_buffer.unwrap();
+
+    let llvm_header_block = self
+      .llvm_context
+      .append_basic_block(llvm_function, "for.cond");
+
+    let llvm_body_block = self
+      .llvm_context
+      .append_basic_block(llvm_function, "for.body");
+
+    let llvm_latch_block = self
+      .llvm_context
+      .append_basic_block(llvm_function, "for.latch");
+
+    let llvm_exit_block = self
+      .llvm_context
+      .append_basic_block(llvm_function, "for.end");
+
+    self
+      .llvm_builder_buffer
+      .build_unconditional_branch(llvm_header_block);
+
+    self.position_at_end(llvm_header_block);
+
+    let llvm_iterator = self
+      .llvm_builder_buffer
+      .build_phi(llvm_start_value.get_type(), for_stmt.iterator_name.as_str());
+
+    llvm_iterator.add_incoming(&[(&llvm_start_value, llvm_preheader_block)]);
+
+    let llvm_iterator_value = llvm_iterator.as_basic_value().into_int_value();
+
+    let llvm_condition = self.llvm_builder_buffer.build_int_compare(
+      match for_stmt.is_inclusive {
+        true => inkwell::IntPredicate::SLE,
+        false => inkwell::IntPredicate::SLT,
+      },
+      llvm_iterator_value,
+      llvm_end_value,
+      "for.cmp",
+    );
+
+    self.llvm_builder_buffer.build_conditional_branch(
+      llvm_condition,
+      llvm_body_block,
+      llvm_exit_block,
+    );
+
+    self.position_at_end(llvm_body_block);
+
+    self.llvm_loop_stack.push(LlvmLoopFrame {
+      label: for_stmt.label.clone(),
+      llvm_continue_block: llvm_latch_block,
+      llvm_break_block: llvm_exit_block,
+      is_break_block_reachable: true,
+    });
+
+    // The iterator is bound directly to its SSA value, which
+    // leaves no way to mutate it from within the body.
+    self
+      .llvm_named_value_scopes
+      .push(std::collections::HashMap::from([(
+        for_stmt.iterator_name.clone(),
+        LlvmNamedValue::Value(llvm_iterator.as_basic_value()),
+      )]));
+
+    self.visit_block(&for_stmt.body)?;
+    self.llvm_named_value_scopes.pop();
+    self.llvm_loop_stack.pop();
+
+    if !self.is_current_block_terminated() {
+      self
+        .llvm_builder_buffer
+        .build_unconditional_branch(llvm_latch_block);
+    }
+
+    self.position_at_end(llvm_latch_block);
+
+    let llvm_one = llvm_start_value.get_type().const_int(1, false);
+
+    let llvm_next_iterator_value = if for_stmt.is_inclusive {
+      // The end value itself is part of an inclusive range, so
+      // the loop must exit after visiting it instead of relying
+      // on the header's comparison, which would otherwise never
+      // fail if the end value is the kind's maximum value.
+      let llvm_is_last = self.llvm_builder_buffer.build_int_compare(
+        inkwell::IntPredicate::EQ,
+        llvm_iterator_value,
+        llvm_end_value,
+        "for.last",
+      );
+
+      let llvm_next_iterator_value =
+        self
+          .llvm_builder_buffer
+          .build_int_add(llvm_iterator_value, llvm_one, "for.next");
+
+      self.llvm_builder_buffer.build_conditional_branch(
+        llvm_is_last,
+        llvm_exit_block,
+        llvm_header_block,
+      );
+
+      llvm_next_iterator_value
+    } else {
+      // The iterator is always smaller than the end value, so
+      // incrementing it can never overflow.
+      let llvm_next_iterator_value =
+        self
+          .llvm_builder_buffer
+          .build_int_nsw_add(llvm_iterator_value, llvm_one, "for.next");
+
+      self
+        .llvm_builder_buffer
+        .build_unconditional_branch(llvm_header_block);
+
+      llvm_next_iterator_value
+    };
+
+    llvm_iterator.add_incoming(&[(&llvm_next_iterator_value, llvm_latch_block)]);
+    self.position_at_end(llvm_exit_block);
+
+    Ok(())
+  }
+
+  fn visit_break_stmt(&mut self, break_stmt: &block::BreakStmt) -> pass::PassResult {
+    assert!(self.llvm_basic_block_buffer.is_some());
+
+    let llvm_loop_frame = self.find_loop_frame(&break_stmt.label)?;
+
+    llvm_loop_frame.is_break_block_reachable = true;
+
+    let llvm_break_block = llvm_loop_frame.llvm_break_block;
+
+    self
+      .llvm_builder_buffer
+      .build_unconditional_branch(llvm_break_block);
+
+    Ok(())
+  }
+
+  fn visit_continue_stmt(&mut self, continue_stmt: &block::ContinueStmt) -> pass::PassResult {
+    assert!(self.llvm_basic_block_buffer.is_some());
+
+    let llvm_continue_block = self
+      .find_loop_frame(&continue_stmt.label)?
+      .llvm_continue_block;
+
+    self
+      .llvm_builder_buffer
+      .build_unconditional_branch(llvm_continue_block);
+
+    Ok(())
+  }
+
+  fn visit_expr_stmt(&mut self, expr_stmt: &block::ExprStmt) -> pass::PassResult {
+    assert!(self.llvm_basic_block_buffer.is_some());
+
+    // Unlike other expressions, calls and matches used as
+    // statements may produce no value.
+    self.visit_expr_or_void(&expr_stmt.expr, None)?;
+
+    Ok(())
+  }
+
+  fn visit_let_stmt(&mut self, let_stmt: &block::LetStmt) -> pass::PassResult {
+    assert!(self.llvm_basic_block_buffer.is_some());
+
+    let llvm_expected_type = match &let_stmt.kind_group {
+      Some(kind_group) => {
+        let llvm_type = self
+          .visit_or_retrieve_type(&kind_group.to_value_kind())?
+          .copied();
+
+        assert!(llvm_type.is_some());
+
+        match as_basic_type(llvm_type.unwrap()) {
+          Some(llvm_type) => Some(llvm_type),
+          None => {
+            return Err(diagnostic::Diagnostic {
+              message: format!(
+                "binding `{}` cannot be of kind `{}`",
+                let_stmt.name, kind_group.kind
+              ),
+              severity: diagnostic::DiagnosticSeverity::Error,
+              span: Some(kind_group.span),
+              notes: vec![],
+            })
+          }
+        }
+      }
+      None => None,
+    };
+
+    let llvm_value = self.visit_expr_as(&let_stmt.value, llvm_expected_type)?;
+
+    if llvm_expected_type.is_some() && Some(llvm_value.get_type()) != llvm_expected_type {
+      return Err(diagnostic::Diagnostic {
+        message: format!(
+          "value of binding `{}` does not match its kind",
+          let_stmt.name
+        ),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(let_stmt.value.get_span()),
+        notes: vec![],
+      });
+    }
+
+    // Bindings are stored in stack slots, which makes them
+    // addressable (ex. so that their fields may be assigned to).
+    let llvm_stack_slot = self.build_entry_alloca(llvm_value.get_type(), &let_stmt.name)?;
+
+    self
+      .llvm_builder_buffer
+      .build_store(llvm_stack_slot, llvm_value);
+
+    assert!(!self.llvm_named_value_scopes.is_empty());
+
+    self.llvm_named_value_scopes.last_mut().unwrap().insert(
+      let_stmt.name.clone(),
+      LlvmNamedValue::StackSlot(llvm_stack_slot),
+    );
+
+    Ok(())
+  }
+
+  fn visit_assign_stmt(&mut self, assign_stmt: &block::AssignStmt) -> pass::PassResult {
+    assert!(self.llvm_basic_block_buffer.is_some());
+
+    let llvm_place = self.visit_place(&assign_stmt.target)?;
+    let llvm_expected_type = as_basic_type(llvm_place.get_type().get_element_type());
+    let llvm_value = self.visit_expr_as(&assign_stmt.value, llvm_expected_type)?;
+
+    if Some(llvm_value.get_type()) != llvm_expected_type {
+      return Err(diagnostic::Diagnostic {
+        message: String::from("assigned value does not match the kind of its target"),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(assign_stmt.value.get_span()),
+        notes: vec![],
+      });
+    }
+
+    self.llvm_builder_buffer.build_store(llvm_place, llvm_value);
+
+    Ok(())
+  }
+
+  fn visit_return_stmt(&mut self, return_stmt: &block::ReturnStmt) -> pass::PassResult {
+    assert!(self.llvm_basic_block_buffer.is_some());
+
+    assert!(self.llvm_function_buffer.is_some());
+
+    let llvm_return_type = self
+      .llvm_function_buffer
+      .unwrap()
+      .get_type()
+      .get_return_type();
+
+    match (&return_stmt.value, llvm_return_type) {
+      (None, None) => {
+        self.llvm_builder_buffer.build_return(None);
+      }
+      (Some(value), Some(llvm_return_type)) => {
+        let llvm_value = self.visit_expr_as(value, Some(llvm_return_type))?;
+
+        if llvm_value.get_type() != llvm_return_type {
+          return Err(diagnostic::Diagnostic {
+            message: String::from("returned value does not match the function's return kind"),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(value.get_span()),
+            notes: vec![],
+          });
+        }
+
+        self
+          .llvm_builder_buffer
+          .build_return(Some(&llvm_value as &dyn inkwell::values::BasicValue));
+      }
+      (None, Some(_)) => {
+        return Err(diagnostic::Diagnostic {
+          message: String::from("expected a value to be returned"),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(return_stmt.span),
+          notes: vec![],
+        });
+      }
+      (Some(value), None) => {
+        return Err(diagnostic::Diagnostic {
+          message: String::from("cannot return a value from a function returning `void`"),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(value.get_span()),
+          notes: vec![],
+        });
+      }
+    };
+
+    Ok(())
+  }
+
+  fn visit_bool_literal(&mut self, bool_literal: &node::BoolLiteral) -> pass::PassResult {
+    self.llvm_value_map.insert(
+      bool_literal.id,
+      inkwell::values::BasicValueEnum::IntValue(
+        self
+          .llvm_context
+          .bool_type()
+          .const_int(bool_literal.value as u64, false),
+      ),
+    );
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{implicit_return_pass, parser, pass_mut::PassMut, pipeline, span};
+
+  // Lower a function, after making its implicit return explicit.
+  fn lower_function(
+    llvm_lowering_pass: &mut LlvmLoweringPass,
+    mut function: function::Function,
+  ) -> pass::PassResult {
+    implicit_return_pass::ImplicitReturnPass::new().visit_function(&mut function)?;
+    llvm_lowering_pass.visit_function(&function)
+  }
+
+  #[test]
+  fn llvm_lowering_pass_proper_initial_values() {
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+
+    assert_eq!(
+      true,
+      LlvmLoweringPass::new(&llvm_context, llvm_module)
+        .llvm_type_map
+        .is_empty()
+    );
+  }
+
+  #[test]
+  fn llvm_lowering_pass_visit_or_retrieve_type() {
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    let int_kind_box = node::AnyKindNode::IntKind(int_kind::IntKind {
+      size: int_kind::IntSize::Signed32,
+    });
+
+    let visit_or_retrieve_result = llvm_lowering_pass.visit_or_retrieve_type(&int_kind_box);
+
+    assert_eq!(true, visit_or_retrieve_result.is_ok());
+    assert_eq!(true, visit_or_retrieve_result.ok().is_some());
+    assert_eq!(1, llvm_lowering_pass.llvm_type_map.len());
+  }
+
+  #[test]
+  fn llvm_lowering_pass_visit_or_retrieve_value() {
+    let node_ids = node::NodeIdAllocator::new();
+
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    let make_true = || {
+      node::AnyLiteralNode::BoolLiteral(node::BoolLiteral {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        value: true,
+      })
+    };
+
+    let (first, second) = (make_true(), make_true());
+
+    assert_eq!(
+      true,
+      llvm_lowering_pass
+        .visit_or_retrieve_value(&first, None)
+        .ok()
+        .flatten()
+        .is_some()
+    );
+
+    assert_eq!(
+      true,
+      llvm_lowering_pass
+        .visit_or_retrieve_value(&first, None)
+        .is_ok()
+    );
+
+    assert_eq!(1, llvm_lowering_pass.llvm_value_map.len());
+
+    // Equal literals are still distinct nodes.
+    assert_eq!(
+      true,
+      llvm_lowering_pass
+        .visit_or_retrieve_value(&second, None)
+        .is_ok()
+    );
+
+    assert_eq!(2, llvm_lowering_pass.llvm_value_map.len());
+
+    // Integer literals take the type expected each time.
+    let int_literal = node::AnyLiteralNode::IntLiteral(node::IntLiteral {
+      id: node_ids.allocate(),
+      span: span::Span::default(),
+      value: String::from("1"),
+    });
+
+    for llvm_int_type in [llvm_context.i8_type(), llvm_context.i64_type()] {
+      assert_eq!(
+        Some(llvm_int_type.as_basic_type_enum()),
+        llvm_lowering_pass
+          .visit_or_retrieve_value(&int_literal, Some(llvm_int_type.as_basic_type_enum()))
+          .ok()
+          .flatten()
+          .map(|llvm_value| llvm_value.get_type())
+      );
+    }
+  }
+
+  #[test]
+  fn llvm_lowering_pass_visit_void_kind() {
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    let visit_void_kind_result = llvm_lowering_pass.visit_void_kind(&void_kind::VoidKind {});
+
+    assert_eq!(true, visit_void_kind_result.is_ok());
+    assert_eq!(llvm_lowering_pass.llvm_type_map.len(), 1);
+  }
+
+  #[test]
+  fn llvm_lowering_pass_visit_int_kind() {
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    let visit_int_kind_result = llvm_lowering_pass.visit_int_kind(&int_kind::IntKind {
+      size: int_kind::IntSize::Signed32,
+    });
+
+    assert_eq!(true, visit_int_kind_result.is_ok());
+    assert_eq!(llvm_lowering_pass.llvm_type_map.len(), 1);
+  }
+
+  #[test]
+  fn visit_function() {
+    let node_ids = node::NodeIdAllocator::new();
+
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    let visit_function_result = lower_function(
+      &mut llvm_lowering_pass,
+      function::Function {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        is_public: false,
+        attributes: vec![],
+        prototype: prototype::Prototype {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          name: String::from("foo"),
+          return_kind_group: node::KindGroup {
+            id: node_ids.allocate(),
+            span: span::Span::default(),
+            kind: node::AnyKindNode::VoidKind(void_kind::VoidKind {}),
+            is_reference: false,
+            is_mutable: false,
+          },
+          parameters: vec![],
+          is_variadic: false,
+          generic_parameters: vec![],
+          type_arguments: vec![],
+        },
+        body: block::Block {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          statements: vec![],
+        },
+      },
+    );
+
+    assert_eq!(true, visit_function_result.is_ok());
+    assert_eq!(true, llvm_lowering_pass.llvm_function_buffer.is_some());
+    assert_eq!(
+      true,
+      llvm_lowering_pass
+        .llvm_function_buffer
+        .unwrap()
+        .verify(false)
+    );
+  }
+
+  #[test]
+  fn visit_namespace_diagnostic_spans() {
+    // Parts without a span of their own (ex. the loop targeted by a
+    // `break`) are located at their statement.
+    for (source, message, spanned_source) in [
+      (
+        "namespace test { fn f(a: i32) ~ void { if a { return; } return; } }",
+        "condition must be a boolean value",
+        "a",
+      ),
+      (
+        "namespace test { fn f() ~ void { for i in 0..true {} return; } }",
+        "range bounds must be integers",
+        "0..true",
+      ),
+      (
+        "namespace test { fn f() ~ void { break; } }",
+        "loop control statement does not target any loop",
+        "break;",
+      ),
+    ] {
+      let llvm_context = inkwell::context::Context::create();
+      let llvm_module = llvm_context.create_module("test");
+      let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+      let namespace = parser::parse_source(source).unwrap();
+
+      let diagnostic = llvm_lowering_pass.visit_namespace(&namespace).unwrap_err();
+
+      assert_eq!(message, diagnostic.message);
+
+      assert_eq!(
+        Some(spanned_source),
+        diagnostic.span.map(|span| &source[span.start..span.end])
+      );
+    }
+  }
+
+  #[test]
+  fn visit_function_missing_implicit_return() {
+    let node_ids = node::NodeIdAllocator::new();
+
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    // The implicit return pass did not run over the function.
+    let visit_function_result = llvm_lowering_pass.visit_function(&function::Function {
+      id: node_ids.allocate(),
+      span: span::Span::default(),
+      is_public: false,
+      attributes: vec![],
+      prototype: prototype::Prototype {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        name: String::from("foo"),
+        return_kind_group: node::KindGroup {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          kind: node::AnyKindNode::VoidKind(void_kind::VoidKind {}),
+          is_reference: false,
+          is_mutable: false,
+        },
+        parameters: vec![],
+        is_variadic: false,
+        generic_parameters: vec![],
+        type_arguments: vec![],
+      },
+      body: block::Block {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        statements: vec![],
+      },
+    });
+
+    assert_eq!(true, visit_function_result.is_err());
+  }
+
+  #[test]
+  fn visit_function_missing_return_value() {
+    let node_ids = node::NodeIdAllocator::new();
+
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    let visit_function_result = llvm_lowering_pass.visit_function(&function::Function {
+      id: node_ids.allocate(),
+      span: span::Span::default(),
+      is_public: false,
+      attributes: vec![],
+      prototype: prototype::Prototype {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        name: String::from("foo"),
+        return_kind_group: node::KindGroup {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          kind: node::AnyKindNode::BoolKind(bool_kind::BoolKind {}),
+          is_reference: false,
+          is_mutable: false,
+        },
+        parameters: vec![],
+        is_variadic: false,
+        generic_parameters: vec![],
+        type_arguments: vec![],
+      },
+      body: block::Block {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        statements: vec![block::AnyStatementNode::IfStmt(block::IfStmt {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          condition: node::AnyExprNode::Literal(node::AnyLiteralNode::BoolLiteral(
+            node::BoolLiteral {
+              id: node_ids.allocate(),
+              span: span::Span::default(),
+              value: true,
+            },
+          )),
+          then_block: block::Block {
+            id: node_ids.allocate(),
+            span: span::Span::default(),
+            statements: vec![block::AnyStatementNode::ReturnStmt(block::ReturnStmt {
+              id: node_ids.allocate(),
+              span: span::Span::default(),
+              value: Some(node::AnyExprNode::Literal(
+                node::AnyLiteralNode::BoolLiteral(node::BoolLiteral {
+                  id: node_ids.allocate(),
+                  span: span::Span::default(),
+                  value: false,
+                }),
+              )),
+            })],
+          },
+          else_if_branches: vec![],
+          else_block: None,
+        })],
+      },
+    });
+
+    // The path where the condition fails does not return.
+    assert_eq!(true, visit_function_result.is_err());
+  }
+
+  #[test]
+  fn visit_function_return_value() {
+    let node_ids = node::NodeIdAllocator::new();
+
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    let visit_function_result = llvm_lowering_pass.visit_function(&function::Function {
+      id: node_ids.allocate(),
+      span: span::Span::default(),
+      is_public: false,
+      attributes: vec![],
+      prototype: prototype::Prototype {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        name: String::from("foo"),
+        return_kind_group: node::KindGroup {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          kind: node::AnyKindNode::BoolKind(bool_kind::BoolKind {}),
+          is_reference: false,
+          is_mutable: false,
+        },
+        parameters: vec![],
+        is_variadic: false,
+        generic_parameters: vec![],
+        type_arguments: vec![],
+      },
+      body: block::Block {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        statements: vec![block::AnyStatementNode::ReturnStmt(block::ReturnStmt {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          value: Some(node::AnyExprNode::Literal(
+            node::AnyLiteralNode::BoolLiteral(node::BoolLiteral {
+              id: node_ids.allocate(),
+              span: span::Span::default(),
+              value: true,
+            }),
+          )),
+        })],
+      },
+    });
+
+    assert_eq!(true, visit_function_result.is_ok());
+    assert_eq!(
+      true,
+      llvm_lowering_pass
+        .llvm_function_buffer
+        .unwrap()
+        .verify(false)
+    );
+  }
+
+  #[test]
+  fn visit_function_return_int_literal() {
+    let node_ids = node::NodeIdAllocator::new();
+
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    let make_function = |value: &str| function::Function {
+      id: node_ids.allocate(),
+      span: span::Span::default(),
+      is_public: false,
+      attributes: vec![],
+      prototype: prototype::Prototype {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        name: format!("foo_{}", value),
+        return_kind_group: node::KindGroup {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          kind: node::AnyKindNode::IntKind(int_kind::IntKind {
+            size: int_kind::IntSize::Signed8,
+          }),
+          is_reference: false,
+          is_mutable: false,
+        },
+        parameters: vec![],
+        is_variadic: false,
+        generic_parameters: vec![],
+        type_arguments: vec![],
+      },
+      body: block::Block {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        statements: vec![block::AnyStatementNode::ReturnStmt(block::ReturnStmt {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          value: Some(node::AnyExprNode::Literal(
+            node::AnyLiteralNode::IntLiteral(node::IntLiteral {
+              id: node_ids.allocate(),
+              span: span::Span::default(),
+              value: String::from(value),
+            }),
+          )),
+        })],
+      },
+    };
+
+    assert_eq!(
+      true,
+      llvm_lowering_pass
+        .visit_function(&make_function("127"))
+        .is_ok()
+    );
+
+    assert_eq!(
+      true,
+      llvm_lowering_pass
+        .llvm_function_buffer
+        .unwrap()
+        .verify(false)
+    );
+
+    // Out of range for `i8`.
+    assert_eq!(
+      true,
+      llvm_lowering_pass
+        .visit_function(&make_function("128"))
+        .is_err()
+    );
+  }
+
+  #[test]
+  fn visit_function_parameters() {
+    let node_ids = node::NodeIdAllocator::new();
+
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    let make_parameter = |name: &str| {
+      (
+        String::from(name),
+        node::KindGroup {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          kind: node::AnyKindNode::IntKind(int_kind::IntKind {
+            size: int_kind::IntSize::Signed32,
+          }),
+          is_reference: false,
+          is_mutable: false,
+        },
+      )
+    };
+
+    let visit_function_result = lower_function(
+      &mut llvm_lowering_pass,
+      function::Function {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        is_public: false,
+        attributes: vec![],
+        prototype: prototype::Prototype {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          name: String::from("foo"),
+          return_kind_group: node::KindGroup {
+            id: node_ids.allocate(),
+            span: span::Span::default(),
+            kind: node::AnyKindNode::VoidKind(void_kind::VoidKind {}),
+            is_reference: false,
+            is_mutable: false,
+          },
+          parameters: vec![make_parameter("a"), make_parameter("b")],
+          is_variadic: false,
+          generic_parameters: vec![],
+          type_arguments: vec![],
+        },
+        body: block::Block {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          statements: vec![block::AnyStatementNode::ExprStmt(block::ExprStmt {
+            id: node_ids.allocate(),
+            span: span::Span::default(),
+            expr: node::AnyExprNode::VariableRef(node::VariableRef {
+              id: node_ids.allocate(),
+              span: span::Span::default(),
+              name: String::from("b"),
+            }),
+          })],
+        },
+      },
+    );
+
+    assert_eq!(true, visit_function_result.is_ok());
+    assert_eq!(true, llvm_lowering_pass.llvm_named_value_scopes.is_empty());
+
+    let llvm_function = llvm_lowering_pass.llvm_function_buffer.unwrap();
+
+    assert_eq!(2, llvm_function.count_params());
+
+    assert_eq!(
+      "b",
+      llvm_function
+        .get_nth_param(1)
+        .unwrap()
+        .into_int_value()
+        .get_name()
+        .to_str()
+        .unwrap()
+    );
+  }
+
+  #[test]
+  fn visit_function_loop_stmt() {
+    let node_ids = node::NodeIdAllocator::new();
+
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    let visit_function_result = lower_function(
+      &mut llvm_lowering_pass,
+      function::Function {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        is_public: false,
+        attributes: vec![],
+        prototype: prototype::Prototype {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          name: String::from("foo"),
+          return_kind_group: node::KindGroup {
+            id: node_ids.allocate(),
+            span: span::Span::default(),
+            kind: node::AnyKindNode::VoidKind(void_kind::VoidKind {}),
+            is_reference: false,
+            is_mutable: false,
+          },
+          parameters: vec![],
+          is_variadic: false,
+          generic_parameters: vec![],
+          type_arguments: vec![],
+        },
+        body: block::Block {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          statements: vec![block::AnyStatementNode::LoopStmt(block::LoopStmt {
+            id: node_ids.allocate(),
+            span: span::Span::default(),
+            label: Some(String::from("outer")),
+            body: block::Block {
+              id: node_ids.allocate(),
+              span: span::Span::default(),
+              statements: vec![block::AnyStatementNode::LoopStmt(block::LoopStmt {
+                id: node_ids.allocate(),
+                span: span::Span::default(),
+                label: None,
+                body: block::Block {
+                  id: node_ids.allocate(),
+                  span: span::Span::default(),
+                  statements: vec![block::AnyStatementNode::BreakStmt(block::BreakStmt {
+                    id: node_ids.allocate(),
+                    span: span::Span::default(),
+                    label: Some(String::from("outer")),
+                  })],
+                },
+              })],
+            },
+          })],
+        },
+      },
+    );
+
+    assert_eq!(true, visit_function_result.is_ok());
+    assert_eq!(true, llvm_lowering_pass.llvm_loop_stack.is_empty());
+
+    // The entry block, and the body and exit blocks of both loops.
+    assert_eq!(
+      5,
+      llvm_lowering_pass
+        .llvm_function_buffer
+        .unwrap()
+        .count_basic_blocks()
+    );
+  }
+
+  #[test]
+  fn visit_function_for_stmt() {
+    let node_ids = node::NodeIdAllocator::new();
+
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    llvm_lowering_pass
+      .llvm_named_value_scopes
+      .push(std::collections::HashMap::from([(
+        String::from("n"),
+        LlvmNamedValue::Value(inkwell::values::BasicValueEnum::IntValue(
+          llvm_context.i32_type().const_int(10, false),
+        )),
+      )]));
+
+    let make_range_bound = || {
+      node::AnyExprNode::VariableRef(node::VariableRef {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        name: String::from("n"),
+      })
+    };
+
+    let visit_function_result = lower_function(
+      &mut llvm_lowering_pass,
+      function::Function {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        is_public: false,
+        attributes: vec![],
+        prototype: prototype::Prototype {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          name: String::from("foo"),
+          return_kind_group: node::KindGroup {
+            id: node_ids.allocate(),
+            span: span::Span::default(),
+            kind: node::AnyKindNode::VoidKind(void_kind::VoidKind {}),
+            is_reference: false,
+            is_mutable: false,
+          },
+          parameters: vec![],
+          is_variadic: false,
+          generic_parameters: vec![],
+          type_arguments: vec![],
+        },
+        body: block::Block {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          statements: vec![block::AnyStatementNode::ForStmt(block::ForStmt {
+            id: node_ids.allocate(),
+            span: span::Span::default(),
+            label: None,
+            iterator_name: String::from("i"),
+            range_start: make_range_bound(),
+            range_end: make_range_bound(),
+            is_inclusive: true,
+            body: block::Block {
+              id: node_ids.allocate(),
+              span: span::Span::default(),
+              statements: vec![],
+            },
+          })],
+        },
+      },
+    );
+
+    assert_eq!(true, visit_function_result.is_ok());
+
+    // The entry block, and the header, body, latch and exit blocks.
+    assert_eq!(
+      5,
+      llvm_lowering_pass
+        .llvm_function_buffer
+        .unwrap()
+        .count_basic_blocks()
+    );
+  }
+
+  #[test]
+  fn visit_namespace_call_extern() {
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+    let mut namespace = namespace::Namespace::new(String::from("test"));
+    let node_ids = &namespace.node_ids;
+
+    let make_void_kind_group = || node::KindGroup {
+      id: node_ids.allocate(),
+      span: span::Span::default(),
+      kind: node::AnyKindNode::VoidKind(void_kind::VoidKind {}),
+      is_reference: false,
+      is_mutable: false,
+    };
+
+    namespace.symbol_table.insert(
+      String::from("foo"),
+      namespace::TopLevelNode::Function(function::Function {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        is_public: false,
+        attributes: vec![],
+        prototype: prototype::Prototype {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          name: String::from("foo"),
+          return_kind_group: make_void_kind_group(),
+          parameters: vec![],
+          is_variadic: false,
+          generic_parameters: vec![],
+          type_arguments: vec![],
+        },
+        body: block::Block {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          statements: vec![block::AnyStatementNode::ExprStmt(block::ExprStmt {
+            id: node_ids.allocate(),
+            span: span::Span::default(),
+            expr: node::AnyExprNode::CallExpr(node::CallExpr {
+              id: node_ids.allocate(),
+              span: span::Span::default(),
+              callee: node::Path {
+                id: node_ids.allocate(),
+                span: span::Span::default(),
+                segments: vec![String::from("bar")],
+              },
+              arguments: vec![node::AnyExprNode::Literal(
+                node::AnyLiteralNode::BoolLiteral(node::BoolLiteral {
+                  id: node_ids.allocate(),
+                  span: span::Span::default(),
+                  value: true,
+                }),
+              )],
+            }),
+          })],
+        },
+      }),
+    );
+
+    namespace.symbol_table.insert(
+      String::from("bar"),
+      namespace::TopLevelNode::External(external::External {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        is_public: false,
+        prototype: prototype::Prototype {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          name: String::from("bar"),
+          return_kind_group: make_void_kind_group(),
+          parameters: vec![],
+          is_variadic: true,
+          generic_parameters: vec![],
+          type_arguments: vec![],
+        },
+      }),
+    );
+
+    assert_eq!(
+      true,
+      implicit_return_pass::ImplicitReturnPass::new()
+        .visit_namespace(&mut namespace)
+        .is_ok()
+    );
+
+    assert_eq!(true, llvm_lowering_pass.visit_namespace(&namespace).is_ok());
+    assert_eq!(
+      true,
+      llvm_lowering_pass.llvm_module.get_function("bar").is_some()
+    );
+  }
+
+  #[test]
+  fn visit_namespace_linkage() {
     let llvm_context = inkwell::context::Context::create();
     let llvm_module = llvm_context.create_module("test");
     let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+    let mut namespace = namespace::Namespace::new(String::from("test"));
+    let node_ids = &namespace.node_ids;
 
-    let visit_function_result = llvm_lowering_pass.visit_function(&function::Function {
-      is_public: false,
-      prototype: prototype::Prototype {
-        name: String::from("foo"),
-        return_kind_group: node::KindGroup {
-          kind: node::AnyKindNode::VoidKind(void_kind::VoidKind {}),
+    let make_function = |name: &str, is_public: bool| {
+      namespace::TopLevelNode::Function(function::Function {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        is_public,
+        attributes: vec![],
+        prototype: prototype::Prototype {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          name: String::from(name),
+          return_kind_group: node::KindGroup {
+            id: node_ids.allocate(),
+            span: span::Span::default(),
+            kind: node::AnyKindNode::VoidKind(void_kind::VoidKind {}),
+            is_reference: false,
+            is_mutable: false,
+          },
+          parameters: vec![],
+          is_variadic: false,
+          generic_parameters: vec![],
+          type_arguments: vec![],
+        },
+        body: block::Block {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          statements: vec![],
+        },
+      })
+    };
+
+    namespace
+      .symbol_table
+      .insert(String::from("foo"), make_function("foo", true));
+
+    namespace
+      .symbol_table
+      .insert(String::from("bar"), make_function("bar", false));
+
+    assert_eq!(
+      true,
+      implicit_return_pass::ImplicitReturnPass::new()
+        .visit_namespace(&mut namespace)
+        .is_ok()
+    );
+
+    assert_eq!(true, llvm_lowering_pass.visit_namespace(&namespace).is_ok());
+
+    assert_eq!(
+      inkwell::module::Linkage::External,
+      llvm_lowering_pass
+        .llvm_module
+        .get_function("_YN4test3fooE_v")
+        .unwrap()
+        .get_linkage()
+    );
+
+    assert_eq!(
+      inkwell::module::Linkage::Private,
+      llvm_lowering_pass
+        .llvm_module
+        .get_function("_YN4test3barE_v")
+        .unwrap()
+        .get_linkage()
+    );
+  }
+
+  // Lower a namespace through the whole pipeline, after parsing it.
+  fn lower_namespace_source(
+    llvm_lowering_pass: &mut LlvmLoweringPass,
+    source: &str,
+  ) -> Vec<diagnostic::Diagnostic> {
+    match parser::parse_source(source) {
+      Ok(mut namespace) => pipeline::compile_namespace(llvm_lowering_pass, &mut namespace),
+      Err(diagnostic) => vec![diagnostic],
+    }
+  }
+
+  #[test]
+  fn visit_namespace_symbols() {
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    assert_eq!(
+      true,
+      lower_namespace_source(
+        &mut llvm_lowering_pass,
+        "namespace test { namespace a { fn init(b: i32) ~ void {} } \
+         namespace b { fn init() ~ void {} } #[no_mangle] pub fn main() ~ void {} \
+         #[export_name = \"start\"] fn run() ~ void {} }",
+      )
+      .is_empty()
+    );
+
+    // Functions with an export name are visible to the linker, even
+    // if they are private.
+    for (symbol, linkage) in [
+      ("_YN4test1a4initEi_v", inkwell::module::Linkage::Private),
+      ("_YN4test1b4initE_v", inkwell::module::Linkage::Private),
+      ("main", inkwell::module::Linkage::External),
+      ("start", inkwell::module::Linkage::External),
+    ] {
+      assert_eq!(
+        Some(linkage),
+        llvm_lowering_pass
+          .llvm_module
+          .get_function(symbol)
+          .map(|llvm_function| llvm_function.get_linkage()),
+        "{}",
+        symbol
+      );
+    }
+  }
+
+  #[test]
+  fn visit_namespace_generic_instances() {
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    assert_eq!(
+      true,
+      lower_namespace_source(
+        &mut llvm_lowering_pass,
+        "namespace test { fn pick<T>(a: T, b: T) ~ T { return b; } \
+         fn main(a: i64, b: bool) ~ i64 { let c = pick(b, b); let d = pick(1, 2); \
+         return pick(a, a); } }",
+      )
+      .is_empty()
+    );
+
+    // A single instance is lowered for each set of kinds, and the
+    // generic function itself is not lowered.
+    for symbol in [
+      "_YN4test4pickEIbEbb_b",
+      "_YN4test4pickEIiEii_i",
+      "_YN4test4pickEIlEll_l",
+    ] {
+      assert_eq!(
+        true,
+        llvm_lowering_pass
+          .llvm_module
+          .get_function(symbol)
+          .is_some(),
+        "{}",
+        symbol
+      );
+    }
+
+    assert_eq!(4, llvm_lowering_pass.llvm_module.get_functions().count());
+    assert_eq!(true, llvm_lowering_pass.llvm_module.verify().is_ok());
+  }
+
+  #[test]
+  fn visit_namespace_traits() {
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    assert_eq!(
+      true,
+      lower_namespace_source(
+        &mut llvm_lowering_pass,
+        "namespace test { trait Shape { fn area(self: &Self) ~ i32; \
+         fn scale(self: &mut Self, factor: i32) ~ void; } \
+         struct Square { side: i32 } struct Rect { width: i32, height: i32 } \
+         impl Shape for Square { fn area(self: &Self) ~ i32 { return self.side; } \
+         fn scale(self: &mut Self, factor: i32) ~ void { self.side = factor; } } \
+         impl Shape for Rect { fn area(self: &Self) ~ i32 { return self.width; } \
+         fn scale(self: &mut Self, factor: i32) ~ void { self.height = factor; } } \
+         fn measure<T: Shape>(shape: &T) ~ i32 { return Shape::area(shape); } \
+         fn total(shape: &mut dyn Shape) ~ i32 { Shape::scale(shape, 2); return Shape::area(shape); } \
+         fn main() ~ i32 { let mut square = Square { side: 1 }; \
+         let rect = Rect { width: 2, height: 3 }; let a = measure(&rect); \
+         return total(&mut square); } }",
+      )
+      .is_empty()
+    );
+
+    // Methods are declared within the namespace named after their
+    // implementation, and calls on known kinds refer to them
+    // directly.
+    for symbol in [
+      "_YN4test17<Square as Shape>4areaERN6SquareE_i",
+      "_YN4test17<Square as Shape>5scaleEMN6SquareEi_v",
+      "_YN4test15<Rect as Shape>4areaERN4RectE_i",
+      "_YN4test15<Rect as Shape>5scaleEMN4RectEi_v",
+      "_YN4test7measureEIN4RectEERN4RectE_i",
+      "_YN4test5totalEMDN5ShapeE_i",
+    ] {
+      assert_eq!(
+        true,
+        llvm_lowering_pass
+          .llvm_module
+          .get_function(symbol)
+          .is_some(),
+        "{}",
+        symbol
+      );
+    }
+
+    // Calls through trait objects are dispatched through the vtable
+    // of each implementation.
+    for vtable in ["<Square as Shape>::vtable", "<Rect as Shape>::vtable"] {
+      assert_eq!(
+        true,
+        llvm_lowering_pass.llvm_module.get_global(vtable).is_some(),
+        "{}",
+        vtable
+      );
+    }
+
+    assert_eq!(7, llvm_lowering_pass.llvm_module.get_functions().count());
+    assert_eq!(true, llvm_lowering_pass.llvm_module.verify().is_ok());
+  }
+
+  #[test]
+  fn visit_namespace_duplicate_symbols() {
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    let diagnostics = lower_namespace_source(
+      &mut llvm_lowering_pass,
+      "namespace test { #[no_mangle] fn foo() ~ void {} \
+       #[export_name = \"foo\"] fn bar() ~ void {} }",
+    );
+
+    assert_eq!(
+      true,
+      diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.message.contains("`foo`"))
+    );
+  }
+
+  #[test]
+  fn visit_namespace_structs() {
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    assert_eq!(
+      true,
+      lower_namespace_source(
+        &mut llvm_lowering_pass,
+        "namespace test { struct Point { x: i32, y: i32 } struct Line { start: Point, end: Point } \
+         fn make(x: i32) ~ Point { let mut p: Point = Point { y: 0, x: x }; p.y = p.x; return p; } \
+         fn width(l: Line) ~ i32 { let mut a = l.end.x; a = make(a).y; return a; } }",
+      )
+      .is_empty()
+    );
+
+    let llvm_struct_type = llvm_lowering_pass.llvm_module.get_struct_type("Point");
+
+    assert_eq!(true, llvm_struct_type.is_some());
+    assert_eq!(2, llvm_struct_type.unwrap().count_fields());
+    assert_eq!(true, llvm_lowering_pass.llvm_module.verify().is_ok());
+  }
+
+  #[test]
+  fn visit_namespace_extern_struct_abi() {
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    assert_eq!(
+      true,
+      lower_namespace_source(
+        &mut llvm_lowering_pass,
+        "namespace test { struct Small { a: i32, b: i16 } struct Large { a: i64, b: i64, c: i64 } \
+         extern small(a: Small) ~ Small; extern large(a: Large) ~ Large; \
+         fn f(a: Small, b: Large) ~ i32 { let c = large(b).c; return small(a).a; } }",
+      )
+      .is_empty()
+    );
+
+    // Small structs are passed in registers, as integers.
+    let llvm_small_function = llvm_lowering_pass
+      .llvm_module
+      .get_function("small")
+      .unwrap();
+
+    assert_eq!(1, llvm_small_function.count_params());
+
+    assert_eq!(
+      true,
+      llvm_small_function
+        .get_type()
+        .get_return_type()
+        .map_or(false, |llvm_type| llvm_type.is_int_type())
+    );
+
+    // Large structs are passed in memory, and returned through a
+    // hidden pointer.
+    let llvm_large_function = llvm_lowering_pass
+      .llvm_module
+      .get_function("large")
+      .unwrap();
+
+    assert_eq!(2, llvm_large_function.count_params());
+    assert_eq!(None, llvm_large_function.get_type().get_return_type());
+    assert_eq!(true, llvm_lowering_pass.llvm_module.verify().is_ok());
+  }
+
+  #[test]
+  fn visit_namespace_extern_extension_attributes() {
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    assert_eq!(
+      true,
+      lower_namespace_source(
+        &mut llvm_lowering_pass,
+        "namespace test { extern narrow(a: bool, b: i8, c: i16, d: i32) ~ i8; \
+         extern flag() ~ bool; }",
+      )
+      .is_empty()
+    );
+
+    let find_extension = |symbol: &str, llvm_attribute_location| {
+      let llvm_function = llvm_lowering_pass.llvm_module.get_function(symbol).unwrap();
+
+      ["zeroext", "signext"].into_iter().find(|attribute_name| {
+        llvm_function
+          .get_enum_attribute(
+            llvm_attribute_location,
+            inkwell::attributes::Attribute::get_named_enum_kind_id(attribute_name),
+          )
+          .is_some()
+      })
+    };
+
+    // Values narrower than 32 bits are extended to fill their
+    // register, as C expects.
+    for (index, extension) in [
+      (0, Some("zeroext")),
+      (1, Some("signext")),
+      (2, Some("signext")),
+      (3, None),
+    ] {
+      assert_eq!(
+        extension,
+        find_extension("narrow", inkwell::attributes::AttributeLoc::Param(index)),
+        "{}",
+        index
+      );
+    }
+
+    assert_eq!(
+      Some("signext"),
+      find_extension("narrow", inkwell::attributes::AttributeLoc::Return)
+    );
+
+    assert_eq!(
+      Some("zeroext"),
+      find_extension("flag", inkwell::attributes::AttributeLoc::Return)
+    );
+
+    assert_eq!(true, llvm_lowering_pass.llvm_module.verify().is_ok());
+  }
+
+  #[test]
+  fn visit_namespace_c_abi_layouts() {
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    assert_eq!(
+      true,
+      lower_namespace_source(
+        &mut llvm_lowering_pass,
+        "namespace test { struct Wide { a: i8, b: i128 } struct Pair { a: i32, b: Wide, c: i16 } \
+         enum Value { Small(i8), Wide(i128, i8), Empty } \
+         fn f(a: Wide, b: Pair, c: Value) ~ void {} }",
+      )
+      .is_empty()
+    );
+
+    let node_ids = node::NodeIdAllocator::new();
+
+    // LLVM lays out structs and enums as the C ABI does, including
+    // the alignment of `i128`.
+    for (name, kind) in [
+      (
+        "Wide",
+        node::AnyKindNode::StructKind(struct_kind::StructKind {
+          path: vec![String::from("Wide")],
+        }),
+      ),
+      (
+        "Pair",
+        node::AnyKindNode::StructKind(struct_kind::StructKind {
+          path: vec![String::from("Pair")],
+        }),
+      ),
+      (
+        "Value",
+        node::AnyKindNode::EnumKind(enum_kind::EnumKind {
+          path: vec![String::from("Value")],
+        }),
+      ),
+    ] {
+      let layout = c_abi::find_layout(
+        &node::KindGroup {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          kind,
           is_reference: false,
           is_mutable: false,
         },
-        parameters: vec![],
-        is_variadic: false,
+        &llvm_lowering_pass.struct_map,
+        &llvm_lowering_pass.enum_map,
+      )
+      .unwrap();
+
+      let llvm_struct_type = llvm_lowering_pass
+        .llvm_module
+        .get_struct_type(name)
+        .unwrap();
+
+      assert_eq!(
+        layout.size,
+        llvm_lowering_pass
+          .llvm_target_data
+          .get_abi_size(&llvm_struct_type),
+        "{}",
+        name
+      );
+
+      assert_eq!(
+        layout.alignment,
+        llvm_lowering_pass
+          .llvm_target_data
+          .get_abi_alignment(&llvm_struct_type) as u64,
+        "{}",
+        name
+      );
+    }
+
+    assert_eq!(true, llvm_lowering_pass.llvm_module.verify().is_ok());
+  }
+
+  #[test]
+  fn visit_namespace_enums() {
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    assert_eq!(
+      true,
+      lower_namespace_source(
+        &mut llvm_lowering_pass,
+        "namespace test { enum Shape { Circle(i32), Rect(i8, i64), Empty } \
+         enum Option { Some(Shape), None } \
+         fn area(o: Option, b: bool) ~ i32 { \
+           let s = Shape::Rect(1, 2); \
+           match b { true => { return 0; } false => {} } \
+           return match o { \
+             Option::Some(Shape::Circle(r)) => r, \
+             Option::Some(Shape::Rect(0, _)) => 0, \
+             Option::Some(x) => match 1 { 1 => 2, n => n }, \
+             Option::None => 3, \
+           }; \
+         } }",
+      )
+      .is_empty()
+    );
+
+    // The payload follows the tag, as storage of the alignment of
+    // its most aligned field.
+    let llvm_enum_type = llvm_lowering_pass.llvm_module.get_struct_type("Shape");
+
+    assert_eq!(true, llvm_enum_type.is_some());
+    assert_eq!(2, llvm_enum_type.unwrap().count_fields());
+
+    assert_eq!(
+      "[2 x i64]",
+      llvm_enum_type
+        .unwrap()
+        .get_field_type_at_index(1)
+        .unwrap()
+        .print_to_string()
+        .to_string()
+    );
+
+    assert_eq!(true, llvm_lowering_pass.llvm_module.verify().is_ok());
+  }
+
+  #[test]
+  fn visit_namespace_arrays() {
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    assert_eq!(
+      true,
+      lower_namespace_source(
+        &mut llvm_lowering_pass,
+        "namespace test { struct Grid { cells: [[bool; 3]; 2] } \
+         fn first(values: [i64]) ~ i64 { return values[0]; } \
+         fn f(g: Grid, i: i8) ~ i64 { \
+           let mut a = [1, 2, 3]; \
+           let b: [i64; 2] = [4, 5]; \
+           a[i] = a[0]; \
+           g.cells[1][i] = true; \
+           let c = first(b); \
+           return first([c, b.len()]); \
+         } }",
+      )
+      .is_empty()
+    );
+
+    // Slices are made of a pointer to their first element, and their
+    // amount of elements.
+    let llvm_slice_type = llvm_lowering_pass.llvm_module.get_struct_type("[i64]");
+
+    assert_eq!(true, llvm_slice_type.is_some());
+    assert_eq!(2, llvm_slice_type.unwrap().count_fields());
+
+    assert_eq!(
+      "i64*",
+      llvm_slice_type
+        .unwrap()
+        .get_field_type_at_index(0)
+        .unwrap()
+        .print_to_string()
+        .to_string()
+    );
+
+    // Indices are checked against the bounds of their array or slice
+    // in debug builds.
+    assert_eq!(
+      true,
+      llvm_lowering_pass
+        .llvm_module
+        .get_function("llvm.trap")
+        .is_some()
+    );
+
+    assert_eq!(true, llvm_lowering_pass.llvm_module.verify().is_ok());
+
+    // Indices wider than an `i64` are checked before being truncated.
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    assert_eq!(
+      true,
+      lower_namespace_source(
+        &mut llvm_lowering_pass,
+        "namespace test { fn f(i: i128) ~ i32 { let a = [1, 2]; return a[i]; } }",
+      )
+      .is_empty()
+    );
+
+    assert_eq!(
+      true,
+      llvm_lowering_pass
+        .llvm_module
+        .print_to_string()
+        .to_string()
+        .contains("icmp ult i128")
+    );
+
+    assert_eq!(true, llvm_lowering_pass.llvm_module.verify().is_ok());
+
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    llvm_lowering_pass.is_debug_build = false;
+
+    assert_eq!(
+      true,
+      lower_namespace_source(
+        &mut llvm_lowering_pass,
+        "namespace test { fn f() ~ i32 { let a = [1, 2]; return a[1]; } }",
+      )
+      .is_empty()
+    );
+
+    assert_eq!(
+      true,
+      llvm_lowering_pass
+        .llvm_module
+        .get_function("llvm.trap")
+        .is_none()
+    );
+  }
+
+  #[test]
+  fn visit_namespace_references() {
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    assert_eq!(
+      true,
+      lower_namespace_source(
+        &mut llvm_lowering_pass,
+        "namespace test { struct Point { x: i32, next: &Point } \
+         #[no_mangle] fn get(p: &Point) ~ &i32 { return &p.next.x; } \
+         #[no_mangle] fn f(p: &mut Point, values: &mut [i8; 2]) ~ i32 { \
+           let mut a = 1; \
+           let b = &mut a; \
+           *b = *get(p); \
+           p.x = *&2; \
+           values[1] = 3; \
+           for i in 0..2 { let c = &i; a = *c; } \
+           return *b; \
+         } }",
+      )
+      .is_empty()
+    );
+
+    // References are lowered into pointers, both as parameters and
+    // as return values.
+    let llvm_get_function = llvm_lowering_pass.llvm_module.get_function("get");
+
+    assert_eq!(true, llvm_get_function.is_some());
+
+    assert_eq!(
+      true,
+      matches!(
+        llvm_get_function.unwrap().get_type().get_return_type(),
+        Some(inkwell::types::BasicTypeEnum::PointerType(_))
+      )
+    );
+
+    let llvm_f_function = llvm_lowering_pass.llvm_module.get_function("f");
+
+    assert_eq!(true, llvm_f_function.is_some());
+
+    assert_eq!(
+      true,
+      llvm_f_function
+        .unwrap()
+        .get_type()
+        .get_param_types()
+        .iter()
+        .all(|llvm_parameter_type| llvm_parameter_type.is_pointer_type())
+    );
+
+    assert_eq!(true, llvm_lowering_pass.llvm_module.verify().is_ok());
+  }
+
+  #[test]
+  fn visit_function_if_stmt() {
+    let node_ids = node::NodeIdAllocator::new();
+
+    let llvm_context = inkwell::context::Context::create();
+    let llvm_module = llvm_context.create_module("test");
+    let mut llvm_lowering_pass = LlvmLoweringPass::new(&llvm_context, llvm_module);
+
+    let condition =
+      node::AnyExprNode::Literal(node::AnyLiteralNode::BoolLiteral(node::BoolLiteral {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        value: true,
+      }));
+
+    let visit_function_result = lower_function(
+      &mut llvm_lowering_pass,
+      function::Function {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        is_public: false,
+        attributes: vec![],
+        prototype: prototype::Prototype {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          name: String::from("foo"),
+          return_kind_group: node::KindGroup {
+            id: node_ids.allocate(),
+            span: span::Span::default(),
+            kind: node::AnyKindNode::VoidKind(void_kind::VoidKind {}),
+            is_reference: false,
+            is_mutable: false,
+          },
+          parameters: vec![],
+          is_variadic: false,
+          generic_parameters: vec![],
+          type_arguments: vec![],
+        },
+        body: block::Block {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          statements: vec![block::AnyStatementNode::IfStmt(block::IfStmt {
+            id: node_ids.allocate(),
+            span: span::Span::default(),
+            condition,
+            then_block: block::Block {
+              id: node_ids.allocate(),
+              span: span::Span::default(),
+              statements: vec![],
+            },
+            else_if_branches: vec![],
+            else_block: Some(block::Block {
+              id: node_ids.allocate(),
+              span: span::Span::default(),
+              statements: vec![],
+            }),
+          })],
+        },
       },
-      body: block::Block { statements: vec![] },
-    });
+    );
 
     assert_eq!(true, visit_function_result.is_ok());
-    assert_eq!(true, llvm_lowering_pass.llvm_function_buffer.is_some());
+
+    // The entry block, the merge block, and both branches.
+    assert_eq!(
+      4,
+      llvm_lowering_pass
+        .llvm_function_buffer
+        .unwrap()
+        .count_basic_blocks()
+    );
   }
 }
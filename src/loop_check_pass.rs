@@ -0,0 +1,146 @@
+use crate::{block, diagnostic, function, pass, pass::Pass, span};
+
+// Ensures that `break` and `continue` statements only appear
+// inside of loops, and that their labels (if any) refer to an
+// enclosing loop.
+pub struct LoopCheckPass {
+  diagnostics: Vec<diagnostic::Diagnostic>,
+  // The labels of the loops that enclose the statement being
+  // visited, with the innermost loop last. Unlabeled loops are
+  // represented by [`None`].
+  loop_labels: Vec<Option<String>>,
+}
+
+impl LoopCheckPass {
+  pub fn new() -> Self {
+    Self {
+      diagnostics: vec![],
+      loop_labels: vec![],
+    }
+  }
+
+  fn check_loop_control(&mut self, span: span::Span, keyword: &str, label: &Option<String>) {
+    if self.loop_labels.is_empty() {
+      self.diagnostics.push(diagnostic::Diagnostic {
+        message: format!("`{}` outside of a loop", keyword),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(span),
+        notes: vec![],
+      });
+    } else if label.is_some() && !self.loop_labels.contains(label) {
+      self.diagnostics.push(diagnostic::Diagnostic {
+        message: format!(
+          "use of undeclared label `'{}` in `{}`",
+          label.as_ref().unwrap(),
+          keyword
+        ),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(span),
+        notes: vec![],
+      });
+    }
+  }
+
+  fn visit_loop_body(&mut self, label: &Option<String>, body: &block::Block) -> pass::PassResult {
+    self.loop_labels.push(label.clone());
+    self.visit(body)?;
+    self.loop_labels.pop();
+
+    Ok(())
+  }
+}
+
+impl pass::Pass<'_> for LoopCheckPass {
+  fn get_diagnostics(&self) -> Vec<diagnostic::Diagnostic> {
+    self.diagnostics.clone()
+  }
+
+  fn visit_function(&mut self, function: &function::Function) -> pass::PassResult {
+    // Loops cannot cross function boundaries.
+    self.loop_labels.clear();
+
+    self.visit_children(function)
+  }
+
+  fn visit_while_stmt(&mut self, while_stmt: &block::WhileStmt) -> pass::PassResult {
+    self.visit_loop_body(&while_stmt.label, &while_stmt.body)
+  }
+
+  fn visit_loop_stmt(&mut self, loop_stmt: &block::LoopStmt) -> pass::PassResult {
+    self.visit_loop_body(&loop_stmt.label, &loop_stmt.body)
+  }
+
+  fn visit_for_stmt(&mut self, for_stmt: &block::ForStmt) -> pass::PassResult {
+    self.visit_loop_body(&for_stmt.label, &for_stmt.body)
+  }
+
+  fn visit_break_stmt(&mut self, break_stmt: &block::BreakStmt) -> pass::PassResult {
+    self.check_loop_control(break_stmt.span, "break", &break_stmt.label);
+
+    Ok(())
+  }
+
+  fn visit_continue_stmt(&mut self, continue_stmt: &block::ContinueStmt) -> pass::PassResult {
+    self.check_loop_control(continue_stmt.span, "continue", &continue_stmt.label);
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn check(source: &str) -> Vec<String> {
+    let namespace = crate::parser::parse_source(source).unwrap();
+    let mut loop_check_pass = LoopCheckPass::new();
+
+    assert_eq!(true, loop_check_pass.visit(&namespace).is_ok());
+
+    loop_check_pass
+      .get_diagnostics()
+      .into_iter()
+      .map(|diagnostic| diagnostic.message)
+      .collect()
+  }
+
+  #[test]
+  fn loop_check_pass_proper_initial_values() {
+    let loop_check_pass = LoopCheckPass::new();
+
+    assert_eq!(true, loop_check_pass.diagnostics.is_empty());
+    assert_eq!(true, loop_check_pass.loop_labels.is_empty());
+  }
+
+  #[test]
+  fn loop_check_pass_break_inside_loop() {
+    let diagnostics = check("namespace foo { fn f() ~ void { loop { break; } } }");
+
+    assert_eq!(true, diagnostics.is_empty());
+  }
+
+  #[test]
+  fn loop_check_pass_break_outside_loop() {
+    let diagnostics = check("namespace foo { fn f() ~ void { break; continue; } }");
+
+    assert_eq!(
+      vec!["`break` outside of a loop", "`continue` outside of a loop"],
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn loop_check_pass_labeled_break() {
+    let diagnostics = check(
+      "namespace foo {
+        fn f() ~ void { 'outer: loop { loop { break 'outer; break 'inner; } } }
+      }",
+    );
+
+    // Only the undeclared `'inner` label is reported.
+    assert_eq!(
+      vec!["use of undeclared label `'inner` in `break`"],
+      diagnostics
+    );
+  }
+}
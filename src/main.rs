@@ -1,17 +1,331 @@
+pub mod array_kind;
+pub mod attribute;
 pub mod block;
+pub mod bool_kind;
+pub mod borrow_check_pass;
+pub mod c_abi;
+pub mod coherence_check_pass;
 pub mod diagnostic;
+pub mod dyn_kind;
+pub mod enum_decl;
+pub mod enum_kind;
 pub mod external;
 pub mod function;
+pub mod generic_kind;
+pub mod impl_decl;
+pub mod implicit_return_pass;
 pub mod int_kind;
 pub mod lexer;
 pub mod llvm_lowering_pass;
+pub mod loop_check_pass;
+pub mod mangling;
+pub mod match_check_pass;
+pub mod monomorphization_pass;
+pub mod name_resolution_pass;
 pub mod namespace;
 pub mod node;
 pub mod parser;
 pub mod pass;
 pub mod pass_manager;
+pub mod pass_mut;
+pub mod pattern;
+pub mod pipeline;
+pub mod pretty_print_pass;
 pub mod prototype;
+pub mod reference_kind;
+#[cfg(feature = "serde")]
+pub mod serialization;
+pub mod slice_kind;
+pub mod span;
+pub mod struct_decl;
+pub mod struct_kind;
 pub mod token;
+pub mod trait_decl;
+pub mod type_check_pass;
+pub mod use_decl;
+pub mod visibility_check_pass;
 pub mod void_kind;
 
-fn main() {}
+const USAGE: &str = "usage: yamori check <file>...
+       yamori fmt [--check] <file>...
+       yamori demangle [<symbol>...]
+       yamori emit-llvm <file>
+       yamori --dump-ast <file>";
+
+// Report a diagnostic which occurred within the given file.
+fn report_diagnostic(path: &str, source: &str, diagnostic: &diagnostic::Diagnostic) {
+  let severity = match diagnostic.severity {
+    diagnostic::DiagnosticSeverity::Warning => "warning",
+    diagnostic::DiagnosticSeverity::Error => "error",
+    diagnostic::DiagnosticSeverity::Internal => "internal error",
+  };
+
+  let source = source.chars().collect::<Vec<_>>();
+
+  eprintln!("{}: {}", severity, diagnostic.message);
+
+  match diagnostic.span {
+    Some(span) => {
+      let (line, column) = span.get_line_and_column(&source);
+
+      eprintln!("  --> {}:{}:{}", path, line, column);
+    }
+    None => eprintln!("  --> {}", path),
+  };
+
+  for note in &diagnostic.notes {
+    let (line, column) = note.span.get_line_and_column(&source);
+
+    eprintln!("note: {}", note.message);
+    eprintln!("  --> {}:{}:{}", path, line, column);
+  }
+}
+
+// Parse the given files and check them together, reporting the
+// diagnostics of each file. Returns the exit code.
+fn run_check(paths: &[String]) -> i32 {
+  if paths.is_empty() {
+    eprintln!("{}", USAGE);
+
+    return 2;
+  }
+
+  let mut exit_code = 0;
+  let mut sources = vec![];
+  let mut namespaces = vec![];
+
+  for path in paths {
+    let source = match std::fs::read_to_string(path) {
+      Ok(source) => source,
+      Err(error) => {
+        eprintln!("error: could not read `{}`: {}", path, error);
+        exit_code = 1;
+
+        continue;
+      }
+    };
+
+    match parser::parse_source(&source) {
+      Ok(namespace) => {
+        sources.push((path, source));
+        namespaces.push(namespace);
+      }
+      Err(diagnostic) => {
+        report_diagnostic(path, &source, &diagnostic);
+        exit_code = 1;
+      }
+    };
+  }
+
+  if exit_code != 0 {
+    return exit_code;
+  }
+
+  let diagnostics = pipeline::analyze_namespaces(&mut namespaces);
+
+  for ((path, source), diagnostics) in sources.iter().zip(diagnostics) {
+    for diagnostic in diagnostics {
+      report_diagnostic(path, source, &diagnostic);
+
+      if diagnostic.is_error() {
+        exit_code = 1;
+      }
+    }
+  }
+
+  exit_code
+}
+
+// Format the given files in place or, in check mode, report the
+// files which are not formatted. Returns the exit code.
+fn run_fmt(arguments: &[String]) -> i32 {
+  let is_check = arguments.iter().any(|argument| argument == "--check");
+
+  let paths = arguments
+    .iter()
+    .filter(|argument| *argument != "--check")
+    .collect::<Vec<_>>();
+
+  if paths.is_empty() {
+    eprintln!("{}", USAGE);
+
+    return 2;
+  }
+
+  let mut exit_code = 0;
+
+  for path in paths {
+    let source = match std::fs::read_to_string(path) {
+      Ok(source) => source,
+      Err(error) => {
+        eprintln!("error: could not read `{}`: {}", path, error);
+        exit_code = 1;
+
+        continue;
+      }
+    };
+
+    let formatted_source = match pretty_print_pass::format_source(&source) {
+      Ok(formatted_source) => formatted_source,
+      Err(diagnostic) => {
+        report_diagnostic(path, &source, &diagnostic);
+        exit_code = 1;
+
+        continue;
+      }
+    };
+
+    if formatted_source == source {
+      continue;
+    }
+
+    if is_check {
+      println!("{} is not formatted", path);
+      exit_code = 1;
+    } else if let Err(error) = std::fs::write(path, formatted_source) {
+      eprintln!("error: could not write `{}`: {}", path, error);
+      exit_code = 1;
+    }
+  }
+
+  exit_code
+}
+
+// Print the path and signature of each of the given symbols or,
+// if none are given, of each line of the standard input. Symbols
+// which are not mangled are printed unchanged. Returns the exit
+// code.
+fn run_demangle(arguments: &[String]) -> i32 {
+  let symbols = match arguments.is_empty() {
+    true => std::io::stdin()
+      .lines()
+      .map_while(Result::ok)
+      .collect::<Vec<_>>(),
+    false => arguments.to_vec(),
+  };
+
+  for symbol in symbols {
+    match mangling::demangle(symbol.trim()) {
+      Some(demangled_symbol) => println!("{}", demangled_symbol),
+      None => println!("{}", symbol),
+    };
+  }
+
+  0
+}
+
+// Compile the given file and print its LLVM module. Returns the
+// exit code.
+fn run_emit_llvm(arguments: &[String]) -> i32 {
+  let path = match arguments {
+    [path] => path,
+    _ => {
+      eprintln!("{}", USAGE);
+
+      return 2;
+    }
+  };
+
+  let source = match std::fs::read_to_string(path) {
+    Ok(source) => source,
+    Err(error) => {
+      eprintln!("error: could not read `{}`: {}", path, error);
+
+      return 1;
+    }
+  };
+
+  let mut namespace = match parser::parse_source(&source) {
+    Ok(namespace) => namespace,
+    Err(diagnostic) => {
+      report_diagnostic(path, &source, &diagnostic);
+
+      return 1;
+    }
+  };
+
+  let llvm_context = inkwell::context::Context::create();
+  let llvm_module = llvm_context.create_module(&namespace.name);
+  let mut llvm_lowering_pass =
+    llvm_lowering_pass::LlvmLoweringPass::new(&llvm_context, llvm_module);
+  let mut exit_code = 0;
+
+  for diagnostic in pipeline::compile_namespace(&mut llvm_lowering_pass, &mut namespace) {
+    report_diagnostic(path, &source, &diagnostic);
+
+    if diagnostic.is_error() {
+      exit_code = 1;
+    }
+  }
+
+  if exit_code == 0 {
+    print!(
+      "{}",
+      llvm_lowering_pass.llvm_module.print_to_string().to_string()
+    );
+  }
+
+  exit_code
+}
+
+// Print the tree of the given file as JSON, for use by external
+// tools. Returns the exit code.
+#[cfg(feature = "serde")]
+fn run_dump_ast(arguments: &[String]) -> i32 {
+  let path = match arguments {
+    [path] => path,
+    _ => {
+      eprintln!("{}", USAGE);
+
+      return 2;
+    }
+  };
+
+  let source = match std::fs::read_to_string(path) {
+    Ok(source) => source,
+    Err(error) => {
+      eprintln!("error: could not read `{}`: {}", path, error);
+
+      return 1;
+    }
+  };
+
+  match serialization::dump_source(&source) {
+    Ok(json) => {
+      println!("{}", json);
+
+      0
+    }
+    Err(diagnostic) => {
+      report_diagnostic(path, &source, &diagnostic);
+
+      1
+    }
+  }
+}
+
+#[cfg(not(feature = "serde"))]
+fn run_dump_ast(_: &[String]) -> i32 {
+  eprintln!("error: `--dump-ast` requires yamori to be built with the `serde` feature");
+
+  1
+}
+
+fn main() {
+  let arguments = std::env::args().skip(1).collect::<Vec<_>>();
+
+  let exit_code = match arguments.first().map(String::as_str) {
+    Some("check") => run_check(&arguments[1..]),
+    Some("fmt") => run_fmt(&arguments[1..]),
+    Some("demangle") => run_demangle(&arguments[1..]),
+    Some("emit-llvm") => run_emit_llvm(&arguments[1..]),
+    Some("--dump-ast") => run_dump_ast(&arguments[1..]),
+    _ => {
+      eprintln!("{}", USAGE);
+
+      2
+    }
+  };
+
+  std::process::exit(exit_code);
+}
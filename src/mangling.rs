@@ -0,0 +1,497 @@
+use crate::{int_kind, node, prototype};
+
+// Functions are given symbols which encode their path from the
+// outermost namespace, as well as their signature, so that
+// functions of the same name within different namespaces (or of
+// different signatures) never collide at link time:
+//
+//   symbol    := "_Y" path ["I" kind+ "E"] signature
+//   path      := "N" segment+ "E"
+//   segment   := <length of identifier, in decimal> identifier
+//   signature := kind* ["z"] "_" kind
+//   kind      := "R" kind | "M" kind | base
+//   base      := "v" | "b" | "a" | "s" | "i" | "l" | "n" | path
+//              | "A" <length, in decimal> "_" kind | "S" kind
+//              | "D" path
+//
+// Instances of generic functions list the kinds substituted for
+// their generic parameters between `I` and `E`, after their path.
+// The signature lists the kinds of the parameters, followed by `z`
+// if the function is variadic, and by the return kind after `_`.
+// References are prefixed by `R`, and mutable references by `M`.
+// The bases are, in order, `void`, `bool`, `i8`, `i16`, `i32`, `i64`
+// and `i128`, while structs and enums are encoded by their path.
+// Arrays are prefixed by `A` and their length, and slices by `S`.
+// Trait objects are prefixed by `D`, followed by the path of their
+// trait.
+// For example, `fn init(a: i32, b: &mut bool) ~ void` within
+// namespace `bar`, nested within namespace `foo`, is given the
+// symbol `_YN3foo3bar4initEiMb_v`, and the instance of
+// `fn max<T>(a: T, b: T) ~ T` for `i64` within namespace `foo` is
+// given the symbol `_YN3foo3maxEIlEll_l`.
+//
+// Externs are never mangled, since they refer to symbols defined
+// elsewhere under their own name.
+pub const PREFIX: &str = "_Y";
+
+fn mangle_path<'a>(segments: impl Iterator<Item = &'a String>, symbol: &mut String) {
+  symbol.push('N');
+
+  for segment in segments {
+    symbol.push_str(&segment.len().to_string());
+    symbol.push_str(segment);
+  }
+
+  symbol.push('E');
+}
+
+fn mangle_kind(kind: &node::AnyKindNode, symbol: &mut String) {
+  symbol.push(match kind {
+    node::AnyKindNode::VoidKind(_) => 'v',
+    node::AnyKindNode::BoolKind(_) => 'b',
+    node::AnyKindNode::IntKind(int_kind) => match int_kind.size {
+      int_kind::IntSize::Signed8 => 'a',
+      int_kind::IntSize::Signed16 => 's',
+      int_kind::IntSize::Signed32 => 'i',
+      int_kind::IntSize::Signed64 => 'l',
+      int_kind::IntSize::Signed128 => 'n',
+    },
+    node::AnyKindNode::StructKind(struct_kind) => {
+      return mangle_path(struct_kind.path.iter(), symbol);
+    }
+    node::AnyKindNode::EnumKind(enum_kind) => return mangle_path(enum_kind.path.iter(), symbol),
+    node::AnyKindNode::ArrayKind(array_kind) => {
+      symbol.push('A');
+      symbol.push_str(&array_kind.length.to_string());
+      symbol.push('_');
+
+      return mangle_kind(&array_kind.element_kind, symbol);
+    }
+    node::AnyKindNode::SliceKind(slice_kind) => {
+      symbol.push('S');
+
+      return mangle_kind(&slice_kind.element_kind, symbol);
+    }
+    node::AnyKindNode::ReferenceKind(reference_kind) => {
+      symbol.push(match reference_kind.is_mutable {
+        true => 'M',
+        false => 'R',
+      });
+
+      return mangle_kind(&reference_kind.referenced_kind, symbol);
+    }
+    node::AnyKindNode::DynKind(dyn_kind) => {
+      symbol.push('D');
+
+      return mangle_path(dyn_kind.trait_path.iter(), symbol);
+    }
+    // Generic functions are never given symbols, only their
+    // instances are.
+    node::AnyKindNode::GenericKind(_) => {
+      unreachable!("generic kinds are substituted before mangling")
+    }
+  });
+}
+
+fn mangle_kind_group(kind_group: &node::KindGroup, symbol: &mut String) {
+  mangle_kind(&kind_group.to_value_kind(), symbol);
+}
+
+// Produce the symbol of the function with the given prototype,
+// within the namespace of the given path (starting with the
+// outermost namespace).
+pub fn mangle(namespace_path: &[String], prototype: &prototype::Prototype) -> String {
+  let mut symbol = String::from(PREFIX);
+
+  mangle_path(namespace_path.iter().chain([&prototype.name]), &mut symbol);
+
+  if !prototype.type_arguments.is_empty() {
+    symbol.push('I');
+
+    for kind in &prototype.type_arguments {
+      mangle_kind(kind, &mut symbol);
+    }
+
+    symbol.push('E');
+  }
+
+  for (_, kind_group) in &prototype.parameters {
+    mangle_kind_group(kind_group, &mut symbol);
+  }
+
+  if prototype.is_variadic {
+    symbol.push('z');
+  }
+
+  symbol.push('_');
+  mangle_kind_group(&prototype.return_kind_group, &mut symbol);
+
+  symbol
+}
+
+struct Demangler<'a> {
+  symbol: &'a [u8],
+  index: usize,
+}
+
+impl Demangler<'_> {
+  fn peek(&self) -> Option<u8> {
+    self.symbol.get(self.index).cloned()
+  }
+
+  fn skip_past(&mut self, byte: u8) -> Option<()> {
+    if self.peek()? != byte {
+      return None;
+    }
+
+    self.index += 1;
+
+    Some(())
+  }
+
+  fn demangle_segment(&mut self) -> Option<String> {
+    let start_index = self.index;
+
+    while self.peek()?.is_ascii_digit() {
+      self.index += 1;
+    }
+
+    let length = std::str::from_utf8(&self.symbol[start_index..self.index])
+      .ok()?
+      .parse::<usize>()
+      .ok()?;
+
+    let segment = self.symbol.get(self.index..self.index + length)?;
+
+    self.index += length;
+
+    String::from_utf8(segment.to_vec()).ok()
+  }
+
+  fn demangle_path(&mut self) -> Option<Vec<String>> {
+    self.skip_past(b'N')?;
+
+    let mut segments = vec![];
+
+    while self.peek()? != b'E' {
+      segments.push(self.demangle_segment()?);
+    }
+
+    self.skip_past(b'E')?;
+
+    match segments.is_empty() {
+      true => None,
+      false => Some(segments),
+    }
+  }
+
+  fn demangle_base(&mut self) -> Option<String> {
+    match self.peek()? {
+      b'N' => return Some(self.demangle_path()?.join("::")),
+      b'A' => {
+        self.index += 1;
+
+        let start_index = self.index;
+
+        while self.peek()?.is_ascii_digit() {
+          self.index += 1;
+        }
+
+        let length = std::str::from_utf8(&self.symbol[start_index..self.index])
+          .ok()?
+          .parse::<u64>()
+          .ok()?;
+
+        self.skip_past(b'_')?;
+
+        return Some(format!("[{}; {}]", self.demangle_kind()?, length));
+      }
+      b'S' => {
+        self.index += 1;
+
+        return Some(format!("[{}]", self.demangle_kind()?));
+      }
+      b'D' => {
+        self.index += 1;
+
+        return Some(format!("dyn {}", self.demangle_path()?.join("::")));
+      }
+      _ => {}
+    };
+
+    let base = match self.peek()? {
+      b'v' => "void",
+      b'b' => "bool",
+      b'a' => "i8",
+      b's' => "i16",
+      b'i' => "i32",
+      b'l' => "i64",
+      b'n' => "i128",
+      _ => return None,
+    };
+
+    self.index += 1;
+
+    Some(String::from(base))
+  }
+
+  fn demangle_kind(&mut self) -> Option<String> {
+    let prefix = match self.peek()? {
+      b'R' => "&",
+      b'M' => "&mut ",
+      _ => return self.demangle_base(),
+    };
+
+    self.index += 1;
+
+    Some(format!("{}{}", prefix, self.demangle_kind()?))
+  }
+
+  fn demangle(&mut self) -> Option<String> {
+    for byte in PREFIX.bytes() {
+      self.skip_past(byte)?;
+    }
+
+    let segments = self.demangle_path()?;
+    let mut type_arguments = vec![];
+
+    if self.peek()? == b'I' {
+      self.index += 1;
+
+      while self.peek()? != b'E' {
+        type_arguments.push(self.demangle_kind()?);
+      }
+
+      // Instances are given at least one kind.
+      if type_arguments.is_empty() {
+        return None;
+      }
+
+      self.skip_past(b'E')?;
+    }
+
+    let mut name = segments.join("::");
+
+    if !type_arguments.is_empty() {
+      name.push_str(&format!("<{}>", type_arguments.join(", ")));
+    }
+
+    let mut parameters = vec![];
+
+    while !matches!(self.peek()?, b'z' | b'_') {
+      parameters.push(self.demangle_kind()?);
+    }
+
+    if self.peek()? == b'z' {
+      parameters.push(String::from("..."));
+      self.index += 1;
+    }
+
+    self.skip_past(b'_')?;
+
+    let return_kind = self.demangle_kind()?;
+
+    if self.index != self.symbol.len() {
+      return None;
+    }
+
+    Some(format!(
+      "{}({}) ~ {}",
+      name,
+      parameters.join(", "),
+      return_kind
+    ))
+  }
+}
+
+// Recover the path and signature of a function from its symbol
+// (ex. `foo::init(i32) ~ void`). Returns [`None`] if the symbol
+// was not produced by [`mangle`].
+pub fn demangle(symbol: &str) -> Option<String> {
+  Demangler {
+    symbol: symbol.as_bytes(),
+    index: 0,
+  }
+  .demangle()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{array_kind, bool_kind, namespace, parser, reference_kind};
+
+  fn parse_prototype(source: &str) -> prototype::Prototype {
+    let namespace =
+      parser::parse_source(&format!("namespace test {{ extern {}; }}", source)).unwrap();
+
+    let prototype = match namespace.symbol_table.values().next() {
+      Some(namespace::TopLevelNode::External(external)) => external.prototype.clone(),
+      _ => unreachable!(),
+    };
+
+    prototype
+  }
+
+  #[test]
+  fn mangling_mangle() {
+    let namespace_path = vec![String::from("foo"), String::from("bar")];
+
+    assert_eq!(
+      "_YN3foo3bar4initEiMb_v",
+      mangle(
+        &namespace_path,
+        &parse_prototype("init(a: i32, b: &mut bool) ~ void")
+      )
+    );
+
+    assert_eq!(
+      "_YN3foo4mainERnaz_l",
+      mangle(
+        &namespace_path[..1],
+        &parse_prototype("main(a: &i128, b: i8, ...) ~ i64")
+      )
+    );
+
+    assert_eq!(
+      "_YN3foo3sumESiRA16_A2_b_A4_a",
+      mangle(
+        &namespace_path[..1],
+        &parse_prototype("sum(a: [i32], b: &[[bool; 2]; 16]) ~ [i8; 4]")
+      )
+    );
+
+    assert_eq!(
+      "_YN3foo4drawERDN5ShapeE_v",
+      mangle(
+        &namespace_path[..1],
+        &parse_prototype("draw(shape: &dyn Shape) ~ void")
+      )
+    );
+
+    // Instances of a generic function are given distinct symbols.
+    let mut instance = parse_prototype("max(a: i64, b: i64) ~ i64");
+
+    instance.type_arguments = vec![node::AnyKindNode::IntKind(int_kind::IntKind {
+      size: int_kind::IntSize::Signed64,
+    })];
+
+    assert_eq!(
+      "_YN3foo3maxEIlEll_l",
+      mangle(&namespace_path[..1], &instance)
+    );
+
+    // Functions of the same name within different namespaces are
+    // given distinct symbols.
+    assert_eq!(
+      false,
+      mangle(&namespace_path[..1], &parse_prototype("init() ~ void"))
+        == mangle(&namespace_path[1..], &parse_prototype("init() ~ void"))
+    );
+  }
+
+  #[test]
+  fn mangling_demangle() {
+    assert_eq!(
+      Some(String::from("foo::bar::init(i32, &mut bool) ~ void")),
+      demangle("_YN3foo3bar4initEiMb_v")
+    );
+
+    assert_eq!(
+      Some(String::from("foo::main(&i128, i8, ...) ~ i64")),
+      demangle("_YN3foo4mainERnaz_l")
+    );
+
+    assert_eq!(
+      Some(String::from("foo::f(&foo::Point) ~ a::Line")),
+      demangle("_YN3foo1fERN3foo5PointE_N1a4LineE")
+    );
+
+    assert_eq!(
+      Some(String::from("foo::sum([i32], &[[bool; 2]; 16]) ~ [i8; 4]")),
+      demangle(&mangle(
+        &[String::from("foo")],
+        &parse_prototype("sum(a: [i32], b: &[[bool; 2]; 16]) ~ [i8; 4]")
+      ))
+    );
+
+    assert_eq!(
+      Some(String::from("foo::max<i64, [bool]>(i64, i64) ~ i64")),
+      demangle("_YN3foo3maxEIlSbEll_l")
+    );
+
+    assert_eq!(
+      Some(String::from(
+        "foo::<Square as Shape>::area(&dyn foo::Shape) ~ i32"
+      )),
+      demangle("_YN3foo17<Square as Shape>4areaERDN3foo5ShapeE_i")
+    );
+
+    assert_eq!(
+      Some(String::from("a_b::c() ~ bool")),
+      demangle(&mangle(
+        &[String::from("a_b")],
+        &parse_prototype("c() ~ bool")
+      ))
+    );
+  }
+
+  #[test]
+  fn mangling_demangle_nested_references() {
+    // References may only be nested within arrays and slices once
+    // generic parameters are substituted (ex. `[T]` for `T = &i32`).
+    let mut instance = parse_prototype("id(a: &i8) ~ void");
+
+    instance.type_arguments = vec![
+      node::AnyKindNode::ReferenceKind(reference_kind::ReferenceKind {
+        referenced_kind: Box::new(node::AnyKindNode::IntKind(int_kind::IntKind {
+          size: int_kind::IntSize::Signed32,
+        })),
+        is_mutable: false,
+      }),
+      node::AnyKindNode::ArrayKind(array_kind::ArrayKind {
+        element_kind: Box::new(node::AnyKindNode::ReferenceKind(
+          reference_kind::ReferenceKind {
+            referenced_kind: Box::new(node::AnyKindNode::BoolKind(bool_kind::BoolKind {})),
+            is_mutable: true,
+          },
+        )),
+        length: 2,
+      }),
+    ];
+
+    assert_eq!(
+      Some(String::from("foo::id<&i32, [&mut bool; 2]>(&i8) ~ void")),
+      demangle(&mangle(&[String::from("foo")], &instance))
+    );
+
+    assert_eq!(
+      Some(String::from("foo::id<&i32>([&i32]) ~ &i32")),
+      demangle("_YN3foo2idEIRiESRi_Ri")
+    );
+  }
+
+  #[test]
+  fn mangling_demangle_invalid() {
+    for symbol in [
+      "",
+      "main",
+      "_Y",
+      "_YNE_v",
+      "_YN3fooE",
+      "_YN3fooE_",
+      "_YN3fooE_x",
+      "_YN9fooE_v",
+      "_YN3fooEi",
+      "_YN3fooE_vv",
+      "_YN3fooE_A4i",
+      "_YN3fooE_S",
+      "_YN3fooEIE_v",
+      "_YN3fooEI_v",
+      "_YN3fooE_D",
+      "_YN3fooE_DNE",
+      "_YN3fooEIi_v",
+    ] {
+      assert_eq!(None, demangle(symbol), "{}", symbol);
+    }
+  }
+}
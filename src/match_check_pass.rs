@@ -0,0 +1,523 @@
+use crate::{diagnostic, enum_decl, namespace, node, pass, pattern};
+
+// Ensures that the arms of every `match` expression cover all
+// values of its subject (exhaustiveness), and warns about arms
+// which can never be reached because previous arms already cover
+// all the values they match.
+//
+// Both are determined by whether a row of patterns is "useful"
+// with respect to a matrix of previous rows, that is, whether some
+// value is matched by the row but by none of the previous rows.
+// Expects names to have been resolved, and kinds to have been
+// checked.
+pub struct MatchCheckPass<'a> {
+  diagnostics: Vec<diagnostic::Diagnostic>,
+  // The kinds of all expressions, as determined by type checking.
+  expr_kinds: &'a node::NodeMap<node::AnyKindNode>,
+  // The declarations of all enums, by their path relative to the
+  // outermost namespace.
+  enums: std::collections::HashMap<String, enum_decl::EnumDecl>,
+  // The number of namespaces enclosing the node being visited.
+  namespace_depth: usize,
+}
+
+impl<'a> MatchCheckPass<'a> {
+  pub fn new(expr_kinds: &'a node::NodeMap<node::AnyKindNode>) -> Self {
+    Self {
+      diagnostics: vec![],
+      expr_kinds,
+      enums: std::collections::HashMap::new(),
+      namespace_depth: 0,
+    }
+  }
+
+  fn collect_enums(&mut self, namespace: &namespace::Namespace, prefix: &[String]) {
+    for (name, top_level_node) in namespace.symbol_table.iter() {
+      let mut path = prefix.to_vec();
+
+      path.push(name.clone());
+
+      match top_level_node {
+        namespace::TopLevelNode::Enum(enum_decl) => {
+          self.enums.insert(path.join("::"), enum_decl.clone());
+        }
+        namespace::TopLevelNode::Namespace(namespace) => self.collect_enums(namespace, &path),
+        _ => {}
+      };
+    }
+  }
+
+  // Convert a pattern matched against values of the given kind into
+  // its constructor and the patterns of its fields. Returns [`None`]
+  // if the pattern does not fit the kind, which type checking
+  // already reported.
+  fn deconstruct(
+    &self,
+    pattern: &pattern::AnyPatternNode,
+    kind: &node::AnyKindNode,
+  ) -> Option<Pattern> {
+    let constructor = match (pattern, kind) {
+      (pattern::AnyPatternNode::WildcardPattern(_), _)
+      | (pattern::AnyPatternNode::BindingPattern(_), _) => return Some(Pattern::Wild),
+      (
+        pattern::AnyPatternNode::LiteralPattern(node::AnyLiteralNode::BoolLiteral(bool_literal)),
+        node::AnyKindNode::BoolKind(_),
+      ) => Constructor::Bool(bool_literal.value),
+      (
+        pattern::AnyPatternNode::LiteralPattern(node::AnyLiteralNode::IntLiteral(int_literal)),
+        node::AnyKindNode::IntKind(_),
+      ) => Constructor::Int(int_literal.value.clone()),
+      (
+        pattern::AnyPatternNode::VariantPattern(variant_pattern),
+        node::AnyKindNode::EnumKind(_),
+      ) => {
+        let (enum_path, variant_name) = variant_pattern.path.split_variant();
+
+        if enum_path != kind.to_string() {
+          return None;
+        }
+
+        let (index, variant) = self.enums.get(&enum_path)?.find_variant(variant_name)?;
+
+        if variant.fields.len() != variant_pattern.fields.len() {
+          return None;
+        }
+
+        let fields = variant_pattern
+          .fields
+          .iter()
+          .zip(&variant.fields)
+          .map(|(field_pattern, field)| self.deconstruct(field_pattern, &field.kind))
+          .collect::<Option<Vec<_>>>()?;
+
+        return Some(Pattern::Constructor(Constructor::Variant(index), fields));
+      }
+      _ => return None,
+    };
+
+    Some(Pattern::Constructor(constructor, vec![]))
+  }
+
+  // Determine the kinds of the fields of a constructor of the given
+  // kind.
+  fn get_field_kinds(
+    &self,
+    constructor: &Constructor,
+    kind: &node::AnyKindNode,
+  ) -> Vec<node::AnyKindNode> {
+    match constructor {
+      Constructor::Variant(index) => self.enums[&kind.to_string()].variants[*index]
+        .fields
+        .iter()
+        .map(|field| field.kind.clone())
+        .collect(),
+      _ => vec![],
+    }
+  }
+
+  // Determine all constructors of the given kind, if there is a
+  // finite amount of them.
+  fn get_all_constructors(&self, kind: &node::AnyKindNode) -> Option<Vec<Constructor>> {
+    match kind {
+      node::AnyKindNode::BoolKind(_) => {
+        Some(vec![Constructor::Bool(true), Constructor::Bool(false)])
+      }
+      node::AnyKindNode::EnumKind(enum_kind) => Some(
+        (0..self.enums[&enum_kind.to_string()].variants.len())
+          .map(Constructor::Variant)
+          .collect(),
+      ),
+      _ => None,
+    }
+  }
+
+  // Find a row of patterns matching values which no row of the
+  // matrix matches, if any. The columns of the matrix are matched
+  // against values of the given kinds.
+  fn find_witness(
+    &self,
+    matrix: &[Vec<Pattern>],
+    kinds: &[node::AnyKindNode],
+  ) -> Option<Vec<Pattern>> {
+    let kind = match kinds.first() {
+      Some(kind) => kind,
+      // With no columns left, a value is matched by any row.
+      None => {
+        return if matrix.is_empty() {
+          Some(vec![])
+        } else {
+          None
+        }
+      }
+    };
+
+    let used_constructors = get_head_constructors(matrix);
+
+    let missing_constructors = self.get_all_constructors(kind).map(|all_constructors| {
+      all_constructors
+        .into_iter()
+        .filter(|constructor| !used_constructors.contains(constructor))
+        .collect::<Vec<_>>()
+    });
+
+    // If the rows cover all constructors, a witness must be found
+    // within the values of one of them.
+    if let Some(true) = missing_constructors.as_ref().map(Vec::is_empty) {
+      for constructor in self.get_all_constructors(kind).unwrap() {
+        let mut field_kinds = self.get_field_kinds(&constructor, kind);
+        let arity = field_kinds.len();
+
+        field_kinds.extend_from_slice(&kinds[1..]);
+
+        if let Some(witness) =
+          self.find_witness(&specialize(matrix, &constructor, arity), &field_kinds)
+        {
+          let (fields, rest) = witness.split_at(arity);
+          let mut witness = vec![Pattern::Constructor(constructor, fields.to_vec())];
+
+          witness.extend_from_slice(rest);
+
+          return Some(witness);
+        }
+      }
+
+      return None;
+    }
+
+    // Otherwise, any value not covered by the rows starting with a
+    // wildcard is a witness, as long as it uses a missing
+    // constructor.
+    let rest = self.find_witness(&get_default_matrix(matrix), &kinds[1..])?;
+
+    let head = match missing_constructors {
+      Some(missing_constructors) if !used_constructors.is_empty() => {
+        let constructor = missing_constructors[0].clone();
+        let arity = self.get_field_kinds(&constructor, kind).len();
+
+        Pattern::Constructor(constructor, vec![Pattern::Wild; arity])
+      }
+      _ => Pattern::Wild,
+    };
+
+    let mut witness = vec![head];
+
+    witness.extend(rest);
+
+    Some(witness)
+  }
+
+  // Whether some value is matched by the row, but by none of the
+  // rows of the matrix.
+  fn is_useful(
+    &self,
+    matrix: &[Vec<Pattern>],
+    row: &[Pattern],
+    kinds: &[node::AnyKindNode],
+  ) -> bool {
+    let (head, kind) = match (row.first(), kinds.first()) {
+      (Some(head), Some(kind)) => (head, kind),
+      _ => return matrix.is_empty(),
+    };
+
+    let constructors = match head {
+      Pattern::Constructor(constructor, _) => vec![constructor.clone()],
+      Pattern::Wild => {
+        let used_constructors = get_head_constructors(matrix);
+
+        match self.get_all_constructors(kind) {
+          Some(all_constructors)
+            if all_constructors
+              .iter()
+              .all(|constructor| used_constructors.contains(constructor)) =>
+          {
+            all_constructors
+          }
+          // A wildcard is useful if it is useful among the rows
+          // which do not name a constructor.
+          _ => return self.is_useful(&get_default_matrix(matrix), &row[1..], &kinds[1..]),
+        }
+      }
+    };
+
+    constructors.into_iter().any(|constructor| {
+      let mut field_kinds = self.get_field_kinds(&constructor, kind);
+      let arity = field_kinds.len();
+
+      field_kinds.extend_from_slice(&kinds[1..]);
+
+      // The row itself always survives specialization, since its
+      // head is either the constructor or a wildcard.
+      let specialized_rows = specialize(&[row.to_vec()], &constructor, arity);
+
+      self.is_useful(
+        &specialize(matrix, &constructor, arity),
+        &specialized_rows[0],
+        &field_kinds,
+      )
+    })
+  }
+
+  // Describe a pattern within the values of the given kind, for
+  // diagnostics (ex. `Shape::Circle(_)`).
+  fn describe(&self, pattern: &Pattern, kind: &node::AnyKindNode) -> String {
+    match pattern {
+      Pattern::Wild => String::from("_"),
+      Pattern::Constructor(Constructor::Bool(value), _) => value.to_string(),
+      Pattern::Constructor(Constructor::Int(value), _) => value.clone(),
+      Pattern::Constructor(constructor @ Constructor::Variant(index), fields) => {
+        let name = format!(
+          "{}::{}",
+          kind,
+          self.enums[&kind.to_string()].variants[*index].name
+        );
+
+        if fields.is_empty() {
+          return name;
+        }
+
+        let field_kinds = self.get_field_kinds(constructor, kind);
+
+        let fields = fields
+          .iter()
+          .zip(&field_kinds)
+          .map(|(field, field_kind)| self.describe(field, field_kind))
+          .collect::<Vec<_>>();
+
+        format!("{}({})", name, fields.join(", "))
+      }
+    }
+  }
+}
+
+// The constructor of a value, which determines which patterns it
+// may match (ex. a variant of an enum, or a literal).
+#[derive(Clone, PartialEq, Debug)]
+enum Constructor {
+  Variant(usize),
+  Bool(bool),
+  Int(String),
+}
+
+// A pattern reduced to what matters for exhaustiveness.
+#[derive(Clone, Debug)]
+enum Pattern {
+  Wild,
+  Constructor(Constructor, Vec<Pattern>),
+}
+
+// Collect the constructors which the rows of the matrix start
+// with, if any.
+fn get_head_constructors(matrix: &[Vec<Pattern>]) -> Vec<Constructor> {
+  let mut constructors = vec![];
+
+  for row in matrix {
+    if let Some(Pattern::Constructor(constructor, _)) = row.first() {
+      if !constructors.contains(constructor) {
+        constructors.push(constructor.clone());
+      }
+    }
+  }
+
+  constructors
+}
+
+// Keep the rows of the matrix which may match values built with the
+// given constructor, with their first pattern replaced by the
+// patterns of its fields.
+fn specialize(
+  matrix: &[Vec<Pattern>],
+  constructor: &Constructor,
+  arity: usize,
+) -> Vec<Vec<Pattern>> {
+  matrix
+    .iter()
+    .filter_map(|row| {
+      let mut specialized_row = match &row[0] {
+        Pattern::Wild => vec![Pattern::Wild; arity],
+        Pattern::Constructor(row_constructor, fields) if row_constructor == constructor => {
+          fields.clone()
+        }
+        Pattern::Constructor(..) => return None,
+      };
+
+      specialized_row.extend_from_slice(&row[1..]);
+
+      Some(specialized_row)
+    })
+    .collect()
+}
+
+// Keep the rows of the matrix which start with a wildcard, without
+// it.
+fn get_default_matrix(matrix: &[Vec<Pattern>]) -> Vec<Vec<Pattern>> {
+  matrix
+    .iter()
+    .filter(|row| matches!(row[0], Pattern::Wild))
+    .map(|row| row[1..].to_vec())
+    .collect()
+}
+
+impl pass::Pass<'_> for MatchCheckPass<'_> {
+  fn get_diagnostics(&self) -> Vec<diagnostic::Diagnostic> {
+    self.diagnostics.clone()
+  }
+
+  fn visit_namespace(&mut self, namespace: &namespace::Namespace) -> pass::PassResult {
+    if self.namespace_depth == 0 {
+      self.collect_enums(namespace, &[]);
+    }
+
+    self.namespace_depth += 1;
+
+    let result = self.visit_children(namespace);
+
+    self.namespace_depth -= 1;
+
+    result
+  }
+
+  fn visit_match_expr(&mut self, match_expr: &node::MatchExpr) -> pass::PassResult {
+    // Problems with the subject's kind were already reported.
+    let subject_kind = match self.expr_kinds.get(&match_expr.subject.get_id()) {
+      Some(subject_kind) => subject_kind.clone(),
+      None => return self.visit_children(match_expr),
+    };
+
+    let rows = match_expr
+      .arms
+      .iter()
+      .map(|arm| self.deconstruct(&arm.pattern, &subject_kind))
+      .collect::<Option<Vec<_>>>();
+
+    let rows = match rows {
+      Some(rows) => rows,
+      None => return self.visit_children(match_expr),
+    };
+
+    let kinds = [subject_kind.clone()];
+    let mut matrix = vec![];
+
+    for (arm, row) in match_expr.arms.iter().zip(rows) {
+      let row = vec![row];
+
+      if !self.is_useful(&matrix, &row, &kinds) {
+        self.diagnostics.push(diagnostic::Diagnostic {
+          message: String::from("unreachable pattern"),
+          severity: diagnostic::DiagnosticSeverity::Warning,
+          span: Some(arm.pattern.get_span()),
+          notes: vec![],
+        });
+      }
+
+      matrix.push(row);
+    }
+
+    if let Some(witness) = self.find_witness(&matrix, &kinds) {
+      self.diagnostics.push(diagnostic::Diagnostic {
+        message: format!(
+          "non-exhaustive patterns: `{}` not covered",
+          self.describe(&witness[0], &subject_kind)
+        ),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(match_expr.subject.get_span()),
+        notes: vec![],
+      });
+    }
+
+    self.visit_children(match_expr)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{pass::Pass, pipeline};
+
+  fn check_source(source: &str) -> Vec<String> {
+    let (namespace, type_check_pass) = pipeline::type_check_source(source);
+
+    let mut match_check_pass = MatchCheckPass::new(type_check_pass.get_expr_kinds());
+
+    assert_eq!(true, match_check_pass.visit(&namespace).is_ok());
+
+    match_check_pass
+      .get_diagnostics()
+      .into_iter()
+      .map(|diagnostic| diagnostic.message)
+      .collect()
+  }
+
+  #[test]
+  fn match_check_pass_exhaustive() {
+    let diagnostics = check_source(
+      "namespace foo {
+        enum Option { Some(Shape), None }
+        enum Shape { Circle(bool), Empty }
+        fn f(o: Option, b: bool, i: i32) ~ i32 {
+          let a = match o {
+            Option::Some(Shape::Circle(true)) => 1,
+            Option::Some(Shape::Circle(false)) => 2,
+            Option::Some(Shape::Empty) => 3,
+            Option::None => 4,
+          };
+          let c = match b { true => 1, false => 2 };
+          return match i { 0 => 1, x => x };
+        }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+  }
+
+  #[test]
+  fn match_check_pass_non_exhaustive() {
+    let diagnostics = check_source(
+      "namespace foo {
+        enum Option { Some(Shape), None }
+        enum Shape { Circle(bool), Empty }
+        fn f(o: Option, b: bool, i: i32) ~ i32 {
+          let a = match o {
+            Option::Some(Shape::Circle(true)) => 1,
+            Option::Some(Shape::Empty) => 3,
+            Option::None => 4,
+          };
+          let c = match b { true => 1 };
+          let d = match o { Option::Some(_) => 1 };
+          return match i { 0 => 1, 1 => 2 };
+        }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        "non-exhaustive patterns: `Option::Some(Shape::Circle(false))` not covered",
+        "non-exhaustive patterns: `false` not covered",
+        "non-exhaustive patterns: `Option::None` not covered",
+        "non-exhaustive patterns: `_` not covered",
+      ],
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn match_check_pass_unreachable() {
+    let diagnostics = check_source(
+      "namespace foo {
+        enum Shape { Circle(bool), Empty }
+        fn f(s: Shape) ~ i32 {
+          return match s {
+            Shape::Circle(_) => 1,
+            Shape::Circle(true) => 2,
+            _ => 3,
+            Shape::Empty => 4,
+          };
+        }
+      }",
+    );
+
+    assert_eq!(
+      vec!["unreachable pattern", "unreachable pattern"],
+      diagnostics
+    );
+  }
+}
@@ -0,0 +1,675 @@
+use crate::{
+  block, diagnostic, function, namespace, node, pass, pass_mut, pass_mut::PassMut, pattern,
+  prototype,
+};
+
+// The number of instances which may be required in a chain of
+// instances (ex. `f<&i32>` being required by `f<i32>`), after which
+// instantiation is assumed not to terminate.
+const INSTANTIATION_LIMIT: usize = 64;
+
+struct PendingInstance {
+  // The path of the generic function, relative to the outermost
+  // namespace.
+  path: String,
+  type_arguments: Vec<node::AnyKindNode>,
+  // The length of the chain of instances requiring this one.
+  depth: usize,
+}
+
+// Replaces generic functions by one instance for each set of kinds
+// they are called with (ex. `max<i64>`), and rewrites calls to refer
+// to these instances. Instances are declared within the namespace
+// of their generic function, under their instance name, so that
+// later passes may treat them as any other function.
+//
+// Calls to trait methods on known kinds (ex. `Shape::area(&square)`)
+// are rewritten to refer to the method of the matching
+// implementation (ex. `<Square as Shape>::area`), while calls
+// through trait objects are left to be dispatched when called.
+//
+// The kinds given to the generic parameters of each call must have
+// been inferred by the type check pass (see
+// [`type_check_pass::TypeCheckPass::get_call_type_arguments`]).
+pub struct MonomorphizationPass<'a> {
+  diagnostics: Vec<diagnostic::Diagnostic>,
+  call_type_arguments: &'a node::NodeMap<Vec<node::AnyKindNode>>,
+  namespace_depth: usize,
+  // The generic functions removed from the tree, by path.
+  generic_functions: std::collections::HashMap<String, function::Function>,
+  // The paths of the instances created, or about to be created.
+  instances: std::collections::HashSet<String>,
+  pending_instances: std::collections::VecDeque<PendingInstance>,
+  // The paths of all traits.
+  traits: std::collections::HashSet<String>,
+  // The path under which the methods of each implementation are
+  // declared, by the path of the trait and the kind.
+  implementations: std::collections::HashMap<(String, node::AnyKindNode), Vec<String>>,
+  // The kinds bound to the generic parameters of the instance being
+  // created, if any.
+  bindings: std::collections::HashMap<String, node::AnyKindNode>,
+  depth: usize,
+  // The allocator of the ids of the tree, taken from the outermost
+  // namespace while it is visited.
+  node_ids: node::NodeIdAllocator,
+}
+
+impl<'a> MonomorphizationPass<'a> {
+  pub fn new(call_type_arguments: &'a node::NodeMap<Vec<node::AnyKindNode>>) -> Self {
+    Self {
+      diagnostics: vec![],
+      call_type_arguments,
+      namespace_depth: 0,
+      generic_functions: std::collections::HashMap::new(),
+      instances: std::collections::HashSet::new(),
+      pending_instances: std::collections::VecDeque::new(),
+      traits: std::collections::HashSet::new(),
+      implementations: std::collections::HashMap::new(),
+      bindings: std::collections::HashMap::new(),
+      depth: 0,
+      node_ids: node::NodeIdAllocator::new(),
+    }
+  }
+
+  fn collect_declarations(&mut self, namespace: &mut namespace::Namespace, prefix: &[String]) {
+    let mut generic_function_names = vec![];
+
+    for (name, top_level_node) in namespace.symbol_table.iter_mut() {
+      let mut path = prefix.to_vec();
+
+      path.push(name.clone());
+
+      match top_level_node {
+        namespace::TopLevelNode::Function(function) if function.prototype.is_generic() => {
+          generic_function_names.push(name.clone());
+        }
+        namespace::TopLevelNode::Namespace(namespace) => {
+          self.collect_declarations(namespace, &path);
+        }
+        namespace::TopLevelNode::Trait(_) => {
+          self.traits.insert(path.join("::"));
+        }
+        _ => {}
+      };
+    }
+
+    for impl_decl in &namespace.impls {
+      let mut path = prefix.to_vec();
+
+      path.push(impl_decl.get_name());
+
+      self.implementations.insert(
+        (
+          impl_decl.trait_path.to_string(),
+          impl_decl.kind_group.to_value_kind(),
+        ),
+        path,
+      );
+    }
+
+    for name in generic_function_names {
+      if let Some(namespace::TopLevelNode::Function(function)) =
+        namespace.symbol_table.remove(&name)
+      {
+        let mut path = prefix.to_vec();
+
+        path.push(name);
+        self.generic_functions.insert(path.join("::"), function);
+      }
+    }
+  }
+
+  fn instantiate(
+    &mut self,
+    namespace: &mut namespace::Namespace,
+    pending_instance: PendingInstance,
+  ) -> pass::PassResult {
+    let mut function = self.generic_functions[&pending_instance.path].clone();
+
+    if pending_instance.depth > INSTANTIATION_LIMIT {
+      self.diagnostics.push(diagnostic::Diagnostic {
+        message: format!(
+          "reached the instantiation limit of {} while instantiating `{}`",
+          INSTANTIATION_LIMIT, pending_instance.path
+        ),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(function.span),
+        notes: vec![],
+      });
+
+      return Ok(());
+    }
+
+    self.bindings = function
+      .prototype
+      .generic_parameters
+      .iter()
+      .map(|generic_parameter| generic_parameter.name.clone())
+      .zip(pending_instance.type_arguments.iter().cloned())
+      .collect();
+
+    function.prototype.generic_parameters.clear();
+    function.prototype.type_arguments = pending_instance.type_arguments;
+    self.depth = pending_instance.depth;
+    self.visit_function(&mut function)?;
+
+    // Calls are rewritten first, since the kinds given to their
+    // generic parameters are found by their original ids.
+    NodeIdRefreshPass {
+      node_ids: &self.node_ids,
+    }
+    .visit_function(&mut function)?;
+
+    self.bindings.clear();
+    self.depth = 0;
+
+    // Instances are declared next to their generic function.
+    let mut segments = pending_instance.path.split("::").collect::<Vec<_>>();
+
+    segments.pop();
+
+    let mut target_namespace = namespace;
+
+    for segment in segments {
+      target_namespace = match target_namespace.symbol_table.get_mut(segment) {
+        Some(namespace::TopLevelNode::Namespace(namespace)) => namespace,
+        _ => unreachable!("generic functions are declared within namespaces"),
+      };
+    }
+
+    target_namespace.symbol_table.insert(
+      function.prototype.get_instance_name(),
+      namespace::TopLevelNode::Function(function),
+    );
+
+    Ok(())
+  }
+
+  // Rewrite the callee of a call to a trait method into the path of
+  // the method of the implementation for the kind of its receiver,
+  // which the type check pass recorded as the kind bound to `Self`.
+  fn rewrite_method_callee(&mut self, call_expr: &mut node::CallExpr, trait_path: String) {
+    let self_kind = match self.call_type_arguments.get(&call_expr.id) {
+      Some(type_arguments) => type_arguments[0].substitute(&self.bindings),
+      None => return,
+    };
+
+    // Trait objects have no implementation of their own, so calls
+    // through them are left as they are.
+    if let Some(path) = self.implementations.get(&(trait_path, self_kind)) {
+      let method_name = call_expr.callee.segments.pop().unwrap();
+
+      call_expr.callee.segments = path.clone();
+      call_expr.callee.segments.push(method_name);
+    }
+  }
+
+  // Rewrite the callee of a call to a generic function into the path
+  // of its instance, which is created later if it does not exist yet.
+  fn rewrite_callee(&mut self, call_expr: &mut node::CallExpr) {
+    let path = call_expr.callee.to_string();
+
+    if let Some((_, trait_segments)) = call_expr.callee.segments.split_last() {
+      let trait_path = trait_segments.join("::");
+
+      if self.traits.contains(&trait_path) {
+        return self.rewrite_method_callee(call_expr, trait_path);
+      }
+    }
+
+    let generic_function = match self.generic_functions.get(&path) {
+      Some(function) => function,
+      None => return,
+    };
+
+    let type_arguments = match self.call_type_arguments.get(&call_expr.id) {
+      Some(type_arguments) => type_arguments
+        .iter()
+        .map(|kind| kind.substitute(&self.bindings))
+        .collect::<Vec<_>>(),
+      None => return,
+    };
+
+    let mut prototype = generic_function.prototype.clone();
+
+    prototype.type_arguments = type_arguments.clone();
+    *call_expr.callee.segments.last_mut().unwrap() = prototype.get_instance_name();
+
+    if self.instances.insert(call_expr.callee.to_string()) {
+      self.pending_instances.push_back(PendingInstance {
+        path,
+        type_arguments,
+        depth: self.depth + 1,
+      });
+    }
+  }
+}
+
+// Gives every node of a function new ids, so that the instances of
+// a generic function, which are cloned from it, share no ids with
+// one another. Later passes may key what they find by id (ex. the
+// values of expressions), which may differ between instances.
+struct NodeIdRefreshPass<'a> {
+  node_ids: &'a node::NodeIdAllocator,
+}
+
+impl pass_mut::PassMut for NodeIdRefreshPass<'_> {
+  fn visit_function(&mut self, function: &mut function::Function) -> pass::PassResult {
+    function.id = self.node_ids.allocate();
+
+    for attribute in &mut function.attributes {
+      attribute.id = self.node_ids.allocate();
+    }
+
+    pass_mut::walk_function(self, function)
+  }
+
+  fn visit_prototype(&mut self, prototype: &mut prototype::Prototype) -> pass::PassResult {
+    prototype.id = self.node_ids.allocate();
+
+    pass_mut::walk_prototype(self, prototype)
+  }
+
+  fn visit_kind_group(&mut self, kind_group: &mut node::KindGroup) -> pass::PassResult {
+    kind_group.id = self.node_ids.allocate();
+
+    Ok(())
+  }
+
+  fn visit_block(&mut self, block: &mut block::Block) -> pass::PassResult {
+    block.id = self.node_ids.allocate();
+
+    pass_mut::walk_block(self, block)
+  }
+
+  fn visit_statement(&mut self, statement: &mut block::AnyStatementNode) -> pass::PassResult {
+    let id = match statement {
+      block::AnyStatementNode::ReturnStmt(return_stmt) => &mut return_stmt.id,
+      block::AnyStatementNode::IfStmt(if_stmt) => &mut if_stmt.id,
+      block::AnyStatementNode::WhileStmt(while_stmt) => &mut while_stmt.id,
+      block::AnyStatementNode::LoopStmt(loop_stmt) => &mut loop_stmt.id,
+      block::AnyStatementNode::ForStmt(for_stmt) => &mut for_stmt.id,
+      block::AnyStatementNode::BreakStmt(break_stmt) => &mut break_stmt.id,
+      block::AnyStatementNode::ContinueStmt(continue_stmt) => &mut continue_stmt.id,
+      block::AnyStatementNode::ExprStmt(expr_stmt) => &mut expr_stmt.id,
+      block::AnyStatementNode::LetStmt(let_stmt) => &mut let_stmt.id,
+      block::AnyStatementNode::AssignStmt(assign_stmt) => &mut assign_stmt.id,
+    };
+
+    *id = self.node_ids.allocate();
+
+    pass_mut::walk_statement(self, statement)
+  }
+
+  fn visit_expr(&mut self, expr: &mut node::AnyExprNode) -> pass::PassResult {
+    let id = match expr {
+      node::AnyExprNode::Literal(node::AnyLiteralNode::BoolLiteral(bool_literal)) => {
+        &mut bool_literal.id
+      }
+      node::AnyExprNode::Literal(node::AnyLiteralNode::IntLiteral(int_literal)) => {
+        &mut int_literal.id
+      }
+      node::AnyExprNode::VariableRef(variable_ref) => &mut variable_ref.id,
+      node::AnyExprNode::CallExpr(call_expr) => {
+        call_expr.callee.id = self.node_ids.allocate();
+
+        &mut call_expr.id
+      }
+      node::AnyExprNode::FieldAccessExpr(field_access_expr) => &mut field_access_expr.id,
+      node::AnyExprNode::StructLiteral(struct_literal) => {
+        struct_literal.path.id = self.node_ids.allocate();
+
+        &mut struct_literal.id
+      }
+      node::AnyExprNode::VariantLiteral(variant_literal) => {
+        variant_literal.path.id = self.node_ids.allocate();
+
+        &mut variant_literal.id
+      }
+      node::AnyExprNode::MatchExpr(match_expr) => {
+        for arm in &mut match_expr.arms {
+          arm.id = self.node_ids.allocate();
+        }
+
+        &mut match_expr.id
+      }
+      node::AnyExprNode::ArrayLiteral(array_literal) => &mut array_literal.id,
+      node::AnyExprNode::IndexExpr(index_expr) => &mut index_expr.id,
+      node::AnyExprNode::LenExpr(len_expr) => &mut len_expr.id,
+      node::AnyExprNode::BorrowExpr(borrow_expr) => &mut borrow_expr.id,
+      node::AnyExprNode::DerefExpr(deref_expr) => &mut deref_expr.id,
+    };
+
+    *id = self.node_ids.allocate();
+
+    pass_mut::walk_expr(self, expr)
+  }
+
+  fn visit_pattern(&mut self, pattern: &mut pattern::AnyPatternNode) -> pass::PassResult {
+    let id = match pattern {
+      pattern::AnyPatternNode::WildcardPattern(wildcard_pattern) => &mut wildcard_pattern.id,
+      pattern::AnyPatternNode::BindingPattern(binding_pattern) => &mut binding_pattern.id,
+      pattern::AnyPatternNode::LiteralPattern(node::AnyLiteralNode::BoolLiteral(bool_literal)) => {
+        &mut bool_literal.id
+      }
+      pattern::AnyPatternNode::LiteralPattern(node::AnyLiteralNode::IntLiteral(int_literal)) => {
+        &mut int_literal.id
+      }
+      pattern::AnyPatternNode::VariantPattern(variant_pattern) => {
+        variant_pattern.path.id = self.node_ids.allocate();
+
+        &mut variant_pattern.id
+      }
+    };
+
+    *id = self.node_ids.allocate();
+
+    pass_mut::walk_pattern(self, pattern)
+  }
+}
+
+impl pass_mut::PassMut for MonomorphizationPass<'_> {
+  fn get_diagnostics(&self) -> Vec<diagnostic::Diagnostic> {
+    self.diagnostics.clone()
+  }
+
+  fn visit_namespace(&mut self, namespace: &mut namespace::Namespace) -> pass::PassResult {
+    if self.namespace_depth > 0 {
+      return pass_mut::walk_namespace(self, namespace);
+    }
+
+    self.collect_declarations(namespace, &[]);
+    self.node_ids = namespace.node_ids.clone();
+    self.namespace_depth += 1;
+    pass_mut::walk_namespace(self, namespace)?;
+
+    // Instances may require other instances, which are created in
+    // turn.
+    while let Some(pending_instance) = self.pending_instances.pop_front() {
+      self.instantiate(namespace, pending_instance)?;
+    }
+
+    self.namespace_depth -= 1;
+    namespace.node_ids = self.node_ids.clone();
+
+    Ok(())
+  }
+
+  fn visit_kind_group(&mut self, kind_group: &mut node::KindGroup) -> pass::PassResult {
+    if !kind_group.kind.is_generic() {
+      return Ok(());
+    }
+
+    match kind_group.kind.substitute(&self.bindings) {
+      // A parameter bound to a reference kind becomes a reference
+      // (ex. `x: T` becomes `x: &i32`).
+      node::AnyKindNode::ReferenceKind(reference_kind) if !kind_group.is_reference => {
+        kind_group.kind = *reference_kind.referenced_kind;
+        kind_group.is_reference = true;
+        kind_group.is_mutable = reference_kind.is_mutable;
+      }
+      kind => kind_group.kind = kind,
+    };
+
+    Ok(())
+  }
+
+  fn visit_expr(&mut self, expr: &mut node::AnyExprNode) -> pass::PassResult {
+    if let node::AnyExprNode::CallExpr(call_expr) = expr {
+      self.rewrite_callee(call_expr);
+    }
+
+    pass_mut::walk_expr(self, expr)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{block, name_resolution_pass, parser, pass::Pass, type_check_pass};
+
+  fn monomorphize(source: &str) -> (namespace::Namespace, Vec<diagnostic::Diagnostic>) {
+    let mut namespace = parser::parse_source(source).unwrap();
+
+    let mut name_resolution_pass = name_resolution_pass::NameResolutionPass::new();
+
+    assert_eq!(
+      true,
+      name_resolution_pass.visit_namespace(&mut namespace).is_ok()
+    );
+
+    let mut type_check_pass = type_check_pass::TypeCheckPass::new();
+
+    assert_eq!(true, type_check_pass.visit_namespace(&namespace).is_ok());
+    assert_eq!(true, type_check_pass.get_diagnostics().is_empty());
+
+    let mut monomorphization_pass =
+      MonomorphizationPass::new(type_check_pass.get_call_type_arguments());
+
+    assert_eq!(
+      true,
+      monomorphization_pass
+        .visit_namespace(&mut namespace)
+        .is_ok()
+    );
+
+    (namespace, monomorphization_pass.get_diagnostics())
+  }
+
+  fn collect_names(namespace: &namespace::Namespace) -> Vec<&str> {
+    namespace
+      .symbol_table
+      .iter()
+      .map(|(name, _)| name.as_str())
+      .collect()
+  }
+
+  #[test]
+  fn monomorphization_pass_instances() {
+    let (namespace, diagnostics) = monomorphize(
+      "namespace test {
+      fn id<T>(value: T) ~ T {
+        return value;
+      }
+
+      fn main() ~ void {
+        id(1);
+        id(2);
+        id(true);
+      }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+
+    // The generic function is replaced by a single instance for
+    // each set of kinds.
+    assert_eq!(
+      vec!["main", "id<i32>", "id<bool>"],
+      collect_names(&namespace)
+    );
+
+    let instance = match namespace.symbol_table.get("id<bool>") {
+      Some(namespace::TopLevelNode::Function(function)) => function,
+      _ => panic!("expected a function"),
+    };
+
+    assert_eq!(true, instance.prototype.generic_parameters.is_empty());
+    assert_eq!(
+      "bool",
+      instance.prototype.return_kind_group.kind.to_string()
+    );
+  }
+
+  #[test]
+  fn monomorphization_pass_instance_ids() {
+    let (namespace, diagnostics) = monomorphize(
+      "namespace test {
+      fn pick<T>(a: T, b: bool) ~ T {
+        if b { return a; }
+        return a;
+      }
+
+      fn main() ~ void {
+        pick(1, true);
+        pick(true, false);
+      }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+
+    let find_ids = |name| {
+      let function = match namespace.symbol_table.get(name) {
+        Some(namespace::TopLevelNode::Function(function)) => function,
+        _ => panic!("expected a function"),
+      };
+
+      let return_value = match &function.body.statements[1] {
+        block::AnyStatementNode::ReturnStmt(return_stmt) => return_stmt.value.as_ref().unwrap(),
+        _ => panic!("expected a return statement"),
+      };
+
+      vec![
+        function.id,
+        function.prototype.id,
+        function.prototype.return_kind_group.id,
+        function.body.id,
+        return_value.get_id(),
+      ]
+    };
+
+    // Every node of an instance is given a new id, so that no id is
+    // shared between instances.
+    let first_ids = find_ids("pick<i32>");
+    let second_ids = find_ids("pick<bool>");
+
+    assert_eq!(true, first_ids.iter().all(|id| !second_ids.contains(id)));
+  }
+
+  #[test]
+  fn monomorphization_pass_nested_instances() {
+    let (namespace, diagnostics) = monomorphize(
+      "namespace test {
+      fn first<T>(value: T) ~ T {
+        return second(value);
+      }
+
+      fn second<U>(value: U) ~ U {
+        return value;
+      }
+
+      fn main() ~ void {
+        let b: bool = true;
+        let a: &bool = first(&b);
+      }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+
+    assert_eq!(
+      vec!["main", "first<&bool>", "second<&bool>"],
+      collect_names(&namespace)
+    );
+
+    let instance = match namespace.symbol_table.get("first<&bool>") {
+      Some(namespace::TopLevelNode::Function(function)) => function,
+      _ => panic!("expected a function"),
+    };
+
+    // Parameters bound to a reference kind become references.
+    assert_eq!(true, instance.prototype.parameters[0].1.is_reference);
+    assert_eq!("bool", instance.prototype.parameters[0].1.kind.to_string());
+  }
+
+  #[test]
+  fn monomorphization_pass_trait_methods() {
+    let (namespace, diagnostics) = monomorphize(
+      "namespace test {
+      trait Shape { fn area(self: &Self) ~ i32; }
+      struct Square { side: i32 }
+
+      impl Shape for Square {
+        fn area(self: &Self) ~ i32 {
+          return self.side;
+        }
+      }
+
+      fn measure<T: Shape>(shape: &T) ~ i32 {
+        return Shape::area(shape);
+      }
+
+      fn main() ~ void {
+        let square = Square { side: 2 };
+        let shape: &dyn Shape = &square;
+        Shape::area(&square);
+        Shape::area(shape);
+        measure(&square);
+      }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+
+    let collect_callees = |function: &function::Function| {
+      function
+        .body
+        .statements
+        .iter()
+        .filter_map(|statement| match statement {
+          block::AnyStatementNode::ExprStmt(block::ExprStmt {
+            expr: node::AnyExprNode::CallExpr(call_expr),
+            ..
+          }) => Some(call_expr.callee.to_string()),
+          block::AnyStatementNode::ReturnStmt(block::ReturnStmt {
+            value: Some(node::AnyExprNode::CallExpr(call_expr)),
+            ..
+          }) => Some(call_expr.callee.to_string()),
+          _ => None,
+        })
+        .collect::<Vec<_>>()
+    };
+
+    let (main, instance) = match (
+      namespace.symbol_table.get("main"),
+      namespace.symbol_table.get("measure<Square>"),
+    ) {
+      (
+        Some(namespace::TopLevelNode::Function(main)),
+        Some(namespace::TopLevelNode::Function(instance)),
+      ) => (main, instance),
+      _ => panic!("expected functions"),
+    };
+
+    // Calls through trait objects are left to be dispatched when
+    // called.
+    assert_eq!(
+      vec!["<Square as Shape>::area", "Shape::area", "measure<Square>"],
+      collect_callees(main)
+    );
+
+    assert_eq!(vec!["<Square as Shape>::area"], collect_callees(instance));
+  }
+
+  #[test]
+  fn monomorphization_pass_instantiation_limit() {
+    let (_, diagnostics) = monomorphize(
+      "namespace test {
+      fn grow<T>(value: T) ~ void {
+        grow(&value);
+      }
+
+      fn main() ~ void {
+        grow(1);
+      }
+      }",
+    );
+
+    assert_eq!(1, diagnostics.len());
+    assert_eq!(
+      "reached the instantiation limit of 64 while instantiating `grow`",
+      diagnostics[0].message
+    );
+  }
+}
@@ -0,0 +1,1130 @@
+use crate::{
+  diagnostic, enum_kind, function, generic_kind, impl_decl, namespace, node, pass, pass_mut,
+  pattern, prototype, span, trait_decl,
+};
+
+// What a name declared within a namespace refers to.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum DeclarationKind {
+  Function,
+  External,
+  Struct,
+  // An enum, by the index of the scope it is declared in.
+  Enum(usize),
+  // A variant of an enum.
+  Variant,
+  // A trait, by the index of the scope it is declared in.
+  Trait(usize),
+  // A method of a trait.
+  Method,
+  // A nested namespace, by the index of its scope.
+  Namespace(usize),
+}
+
+#[derive(Clone, Copy)]
+struct Declaration {
+  kind: DeclarationKind,
+  is_public: bool,
+  span: span::Span,
+}
+
+// A name brought into scope by a `use` declaration.
+struct Import {
+  path: Vec<String>,
+  span: span::Span,
+}
+
+// The names visible within a namespace. Scopes mirror the tree of
+// namespaces, with the outermost namespace's scope first.
+struct Scope {
+  name: String,
+  // The path of the namespace, relative to the outermost namespace.
+  path: Vec<String>,
+  parent: Option<usize>,
+  declarations: std::collections::HashMap<String, Declaration>,
+  // The names of the variants of each enum declared within the
+  // namespace, by the name of the enum.
+  variant_names: std::collections::HashMap<String, Vec<String>>,
+  // The names of the methods of each trait declared within the
+  // namespace, by the name of the trait.
+  method_names: std::collections::HashMap<String, Vec<String>>,
+  // Names may be imported more than once, in which case they are
+  // ambiguous.
+  imports: std::collections::HashMap<String, Vec<Import>>,
+}
+
+// The declaration a path was resolved to.
+#[derive(Clone, Debug)]
+struct Binding {
+  // The path of the declaration, relative to the outermost
+  // namespace.
+  path: Vec<String>,
+  kind: DeclarationKind,
+}
+
+#[derive(Clone)]
+enum ImportResolution {
+  Pending,
+  Resolved(Binding),
+  Failed,
+}
+
+// Resolves the paths used within function bodies (ex. `a::b()`)
+// to the declarations they refer to, looking names up through the
+// enclosing namespaces, innermost first, and through the names
+// imported into them by `use` declarations.
+//
+// Resolved paths are rewritten to be relative to the outermost
+// namespace, which later passes expect.
+pub struct NameResolutionPass {
+  diagnostics: Vec<diagnostic::Diagnostic>,
+  scopes: Vec<Scope>,
+  // The scope of the namespace being visited.
+  scope_index: usize,
+  // The resolution of each import, by the index of the scope it
+  // belongs to and its name. Imports are only resolved once, so
+  // that problems with them are only reported once.
+  import_resolutions: std::collections::HashMap<(usize, String), ImportResolution>,
+  // The generic parameters of the function being visited, which
+  // kinds may name instead of a struct or enum. Within traits,
+  // `Self` is a generic parameter of every method.
+  generic_parameters: Vec<String>,
+  // The kind named by `Self` within an implementation.
+  self_kind: Option<node::AnyKindNode>,
+}
+
+impl NameResolutionPass {
+  pub fn new() -> Self {
+    Self {
+      diagnostics: vec![],
+      scopes: vec![],
+      scope_index: 0,
+      import_resolutions: std::collections::HashMap::new(),
+      generic_parameters: vec![],
+      self_kind: None,
+    }
+  }
+
+  fn push_error(
+    &mut self,
+    span: span::Span,
+    message: String,
+    notes: Vec<diagnostic::DiagnosticNote>,
+  ) {
+    self.diagnostics.push(diagnostic::Diagnostic {
+      message,
+      severity: diagnostic::DiagnosticSeverity::Error,
+      span: Some(span),
+      notes,
+    });
+  }
+
+  // Create the scope of the given namespace, and those of the
+  // namespaces nested in it. Returns the index of the scope.
+  fn build_scope(
+    &mut self,
+    namespace: &namespace::Namespace,
+    path: Vec<String>,
+    parent: Option<usize>,
+  ) -> usize {
+    let index = self.scopes.len();
+
+    self.scopes.push(Scope {
+      name: namespace.name.clone(),
+      path: path.clone(),
+      parent,
+      declarations: std::collections::HashMap::new(),
+      variant_names: std::collections::HashMap::new(),
+      method_names: std::collections::HashMap::new(),
+      imports: std::collections::HashMap::new(),
+    });
+
+    for (name, top_level_node) in namespace.symbol_table.iter() {
+      let declaration = match top_level_node {
+        namespace::TopLevelNode::Function(function) => Declaration {
+          kind: DeclarationKind::Function,
+          is_public: function.is_public,
+          span: function.span,
+        },
+        namespace::TopLevelNode::External(external) => Declaration {
+          kind: DeclarationKind::External,
+          is_public: external.is_public,
+          span: external.span,
+        },
+        namespace::TopLevelNode::Struct(struct_decl) => Declaration {
+          kind: DeclarationKind::Struct,
+          is_public: struct_decl.is_public,
+          span: struct_decl.span,
+        },
+        namespace::TopLevelNode::Enum(enum_decl) => {
+          self.scopes[index].variant_names.insert(
+            name.clone(),
+            enum_decl
+              .variants
+              .iter()
+              .map(|variant| variant.name.clone())
+              .collect(),
+          );
+
+          Declaration {
+            kind: DeclarationKind::Enum(index),
+            is_public: enum_decl.is_public,
+            span: enum_decl.span,
+          }
+        }
+        namespace::TopLevelNode::Trait(trait_decl) => {
+          self.scopes[index].method_names.insert(
+            name.clone(),
+            trait_decl
+              .methods
+              .iter()
+              .map(|method| method.name.clone())
+              .collect(),
+          );
+
+          Declaration {
+            kind: DeclarationKind::Trait(index),
+            is_public: trait_decl.is_public,
+            span: trait_decl.span,
+          }
+        }
+        // Namespaces are visible wherever the namespace containing
+        // them is.
+        namespace::TopLevelNode::Namespace(nested_namespace) => {
+          let mut nested_path = path.clone();
+
+          nested_path.push(name.clone());
+
+          Declaration {
+            kind: DeclarationKind::Namespace(self.build_scope(
+              nested_namespace,
+              nested_path,
+              Some(index),
+            )),
+            is_public: true,
+            span: nested_namespace.span,
+          }
+        }
+      };
+
+      self.scopes[index]
+        .declarations
+        .insert(name.clone(), declaration);
+    }
+
+    for use_decl in &namespace.uses {
+      for item in &use_decl.items {
+        self.scopes[index]
+          .imports
+          .entry(String::from(item.get_name()))
+          .or_default()
+          .push(Import {
+            path: use_decl.get_item_path(item),
+            span: item.span,
+          });
+      }
+    }
+
+    index
+  }
+
+  // Whether the given scope is, or is nested within, the other.
+  fn is_within(&self, scope_index: usize, other_scope_index: usize) -> bool {
+    let mut current_scope_index = Some(scope_index);
+
+    while let Some(index) = current_scope_index {
+      if index == other_scope_index {
+        return true;
+      }
+
+      current_scope_index = self.scopes[index].parent;
+    }
+
+    false
+  }
+
+  fn resolve_import(&mut self, scope_index: usize, name: &str) -> Option<Binding> {
+    let key = (scope_index, String::from(name));
+
+    match self.import_resolutions.get(&key) {
+      Some(ImportResolution::Resolved(binding)) => return Some(binding.clone()),
+      Some(_) => return None,
+      None => {}
+    };
+
+    self
+      .import_resolutions
+      .insert(key.clone(), ImportResolution::Pending);
+
+    let import = &self.scopes[scope_index].imports[name][0];
+    let (path, span) = (import.path.clone(), import.span);
+    let binding = self.resolve_path(scope_index, &path, span);
+
+    self.import_resolutions.insert(
+      key,
+      match &binding {
+        Some(binding) => ImportResolution::Resolved(binding.clone()),
+        None => ImportResolution::Failed,
+      },
+    );
+
+    binding
+  }
+
+  // Look up the first segment of a path, through the enclosing
+  // namespaces, innermost first.
+  fn lookup_name(&mut self, scope_index: usize, name: &str, span: span::Span) -> Option<Binding> {
+    let mut current_scope_index = Some(scope_index);
+
+    while let Some(index) = current_scope_index {
+      let scope = &self.scopes[index];
+      let declaration = scope.declarations.get(name).copied();
+
+      // Imports being resolved are not candidates, so that an
+      // import may refer to a name it shadows.
+      let imports = match self.import_resolutions.get(&(index, String::from(name))) {
+        Some(ImportResolution::Pending) => &[],
+        _ => scope.imports.get(name).map_or(&[][..], Vec::as_slice),
+      };
+
+      if declaration.is_some() as usize + imports.len() > 1 {
+        let mut notes = vec![];
+
+        if let Some(declaration) = declaration {
+          notes.push(diagnostic::DiagnosticNote {
+            message: format!("`{}` is declared here", name),
+            span: declaration.span,
+          });
+        }
+
+        for import in imports {
+          notes.push(diagnostic::DiagnosticNote {
+            message: format!("`{}` is imported here", name),
+            span: import.span,
+          });
+        }
+
+        self.push_error(span, format!("`{}` is ambiguous", name), notes);
+
+        return None;
+      }
+
+      if let Some(declaration) = declaration {
+        let mut path = scope.path.clone();
+
+        path.push(String::from(name));
+
+        return Some(Binding {
+          path,
+          kind: declaration.kind,
+        });
+      }
+
+      if !imports.is_empty() {
+        return self.resolve_import(index, name);
+      }
+
+      current_scope_index = scope.parent;
+    }
+
+    // The outermost namespace may be referred to by its name.
+    if name == self.scopes[0].name {
+      return Some(Binding {
+        path: vec![],
+        kind: DeclarationKind::Namespace(0),
+      });
+    }
+
+    self.push_error(
+      span,
+      format!("cannot find `{}` in this scope", name),
+      vec![],
+    );
+
+    None
+  }
+
+  // Resolve a path used within the given scope. Problems are
+  // reported, in which case [`None`] is returned.
+  fn resolve_path(
+    &mut self,
+    scope_index: usize,
+    segments: &[String],
+    span: span::Span,
+  ) -> Option<Binding> {
+    let mut binding = self.lookup_name(scope_index, &segments[0], span)?;
+
+    for segment in &segments[1..] {
+      let namespace_scope_index = match binding.kind {
+        DeclarationKind::Namespace(index) => index,
+        // Variants are as visible as their enum.
+        DeclarationKind::Enum(index) => {
+          let enum_name = binding.path.last().unwrap();
+
+          if !self.scopes[index].variant_names[enum_name].contains(segment) {
+            let message = format!("cannot find variant `{}` in enum `{}`", segment, enum_name);
+
+            self.push_error(span, message, vec![]);
+
+            return None;
+          }
+
+          binding.path.push(segment.clone());
+          binding.kind = DeclarationKind::Variant;
+
+          continue;
+        }
+        // Methods are as visible as their trait.
+        DeclarationKind::Trait(index) => {
+          let trait_name = binding.path.last().unwrap();
+
+          if !self.scopes[index].method_names[trait_name].contains(segment) {
+            let message = format!("cannot find method `{}` in trait `{}`", segment, trait_name);
+
+            self.push_error(span, message, vec![]);
+
+            return None;
+          }
+
+          binding.path.push(segment.clone());
+          binding.kind = DeclarationKind::Method;
+
+          continue;
+        }
+        _ => {
+          self.push_error(
+            span,
+            format!("`{}` is not a namespace", binding.path.join("::")),
+            vec![],
+          );
+
+          return None;
+        }
+      };
+
+      let namespace_scope = &self.scopes[namespace_scope_index];
+
+      let declaration = match namespace_scope.declarations.get(segment) {
+        Some(declaration) => *declaration,
+        None => {
+          let message = format!(
+            "cannot find `{}` in namespace `{}`",
+            segment, namespace_scope.name
+          );
+
+          self.push_error(span, message, vec![]);
+
+          return None;
+        }
+      };
+
+      // Private declarations are only visible within their
+      // namespace, and those nested in it.
+      if !declaration.is_public && !self.is_within(scope_index, namespace_scope_index) {
+        let message = format!(
+          "`{}` is private to namespace `{}`",
+          segment, namespace_scope.name
+        );
+
+        self.push_error(
+          span,
+          message,
+          vec![diagnostic::DiagnosticNote {
+            message: format!("`{}` is declared here", segment),
+            span: declaration.span,
+          }],
+        );
+
+        return None;
+      }
+
+      let mut path = self.scopes[namespace_scope_index].path.clone();
+
+      path.push(segment.clone());
+
+      binding = Binding {
+        path,
+        kind: declaration.kind,
+      };
+    }
+
+    Some(binding)
+  }
+
+  // Describe what a binding refers to, for diagnostics (ex.
+  // "struct `a::B`").
+  fn describe(&self, binding: &Binding) -> String {
+    match binding.kind {
+      DeclarationKind::Function => format!("function `{}`", binding.path.join("::")),
+      DeclarationKind::External => format!("extern `{}`", binding.path.join("::")),
+      DeclarationKind::Struct => format!("struct `{}`", binding.path.join("::")),
+      DeclarationKind::Enum(_) => format!("enum `{}`", binding.path.join("::")),
+      DeclarationKind::Variant => format!("variant `{}`", binding.path.join("::")),
+      DeclarationKind::Trait(_) => format!("trait `{}`", binding.path.join("::")),
+      DeclarationKind::Method => format!("method `{}`", binding.path.join("::")),
+      DeclarationKind::Namespace(index) => format!("namespace `{}`", self.scopes[index].name),
+    }
+  }
+
+  // Resolve a path which is expected to refer to a struct, and
+  // rewrite it to be relative to the outermost namespace.
+  fn resolve_struct_path(&mut self, segments: &mut Vec<String>, span: span::Span) {
+    match self.resolve_path(self.scope_index, segments, span) {
+      Some(Binding {
+        path,
+        kind: DeclarationKind::Struct,
+      }) => *segments = path,
+      Some(binding) => {
+        let message = format!("expected a struct, found {}", self.describe(&binding));
+
+        self.push_error(span, message, vec![]);
+      }
+      None => {}
+    };
+  }
+
+  // Resolve a path which is expected to lead to a variant of an
+  // enum, and rewrite it to be relative to the outermost namespace.
+  fn resolve_variant_path(&mut self, path: &mut node::Path) {
+    match self.resolve_path(self.scope_index, &path.segments, path.span) {
+      Some(Binding {
+        path: variant_path,
+        kind: DeclarationKind::Variant,
+      }) => path.segments = variant_path,
+      Some(binding) => {
+        let message = format!("expected a variant, found {}", self.describe(&binding));
+
+        self.push_error(path.span, message, vec![]);
+      }
+      None => {}
+    };
+  }
+
+  // Resolve a path which is expected to refer to a trait, and
+  // rewrite it to be relative to the outermost namespace.
+  fn resolve_trait_path(&mut self, segments: &mut Vec<String>, span: span::Span) {
+    match self.resolve_path(self.scope_index, segments, span) {
+      Some(Binding {
+        path,
+        kind: DeclarationKind::Trait(_),
+      }) => *segments = path,
+      Some(binding) => {
+        let message = format!("expected a trait, found {}", self.describe(&binding));
+
+        self.push_error(span, message, vec![]);
+      }
+      None => {}
+    };
+  }
+
+  // Resolve the traits bounding the given generic parameters, and
+  // make the parameters visible to the kinds which follow.
+  fn resolve_generic_parameters(&mut self, generic_parameters: &mut [prototype::GenericParameter]) {
+    for generic_parameter in generic_parameters {
+      for bound in &mut generic_parameter.bounds {
+        self.resolve_trait_path(&mut bound.segments, bound.span);
+      }
+
+      self.generic_parameters.push(generic_parameter.name.clone());
+    }
+  }
+
+  // Resolve the paths within a kind, including those of the
+  // elements of arrays and slices, and of referenced kinds.
+  fn resolve_kind(&mut self, kind: &mut node::AnyKindNode, span: span::Span) {
+    let struct_kind = match kind {
+      node::AnyKindNode::StructKind(struct_kind) => struct_kind,
+      node::AnyKindNode::ArrayKind(array_kind) => {
+        return self.resolve_kind(&mut array_kind.element_kind, span)
+      }
+      node::AnyKindNode::SliceKind(slice_kind) => {
+        return self.resolve_kind(&mut slice_kind.element_kind, span)
+      }
+      node::AnyKindNode::ReferenceKind(reference_kind) => {
+        return self.resolve_kind(&mut reference_kind.referenced_kind, span)
+      }
+      node::AnyKindNode::DynKind(dyn_kind) => {
+        return self.resolve_trait_path(&mut dyn_kind.trait_path, span)
+      }
+      _ => return,
+    };
+
+    // Generic parameters shadow any struct or enum of the same name.
+    if let [name] = struct_kind.path.as_slice() {
+      if self.generic_parameters.contains(name) {
+        *kind = node::AnyKindNode::GenericKind(generic_kind::GenericKind { name: name.clone() });
+
+        return;
+      }
+
+      if let (Some(self_kind), "Self") = (&self.self_kind, name.as_str()) {
+        *kind = self_kind.clone();
+
+        return;
+      }
+    }
+
+    // Kinds named by a path are parsed as struct kinds, regardless
+    // of whether they name a struct or an enum.
+    match self.resolve_path(self.scope_index, &struct_kind.path, span) {
+      Some(Binding {
+        path,
+        kind: DeclarationKind::Struct,
+      }) => struct_kind.path = path,
+      Some(Binding {
+        path,
+        kind: DeclarationKind::Enum(_),
+      }) => *kind = node::AnyKindNode::EnumKind(enum_kind::EnumKind { path }),
+      Some(binding) => {
+        let message = format!(
+          "expected a struct or enum, found {}",
+          self.describe(&binding)
+        );
+
+        self.push_error(span, message, vec![]);
+      }
+      None => {}
+    };
+  }
+}
+
+impl pass_mut::PassMut for NameResolutionPass {
+  fn get_diagnostics(&self) -> Vec<diagnostic::Diagnostic> {
+    self.diagnostics.clone()
+  }
+
+  fn visit_namespace(&mut self, namespace: &mut namespace::Namespace) -> pass::PassResult {
+    // The scopes of all namespaces must be known before resolving
+    // any path, since a path may refer to any of them.
+    if self.scopes.is_empty() {
+      self.scope_index = self.build_scope(namespace, vec![], None);
+    }
+
+    // Imports are resolved even if they are never used, so that
+    // problems with them are reported regardless.
+    for use_decl in &namespace.uses {
+      for item in &use_decl.items {
+        self.resolve_import(self.scope_index, item.get_name());
+      }
+    }
+
+    let scope_index = self.scope_index;
+
+    for (name, top_level_node) in namespace.symbol_table.iter_mut() {
+      match top_level_node {
+        namespace::TopLevelNode::Function(function) => self.visit_function(function)?,
+        namespace::TopLevelNode::External(external) => self.visit_external(external)?,
+        namespace::TopLevelNode::Struct(struct_decl) => self.visit_struct_decl(struct_decl)?,
+        namespace::TopLevelNode::Enum(enum_decl) => self.visit_enum_decl(enum_decl)?,
+        namespace::TopLevelNode::Trait(trait_decl) => self.visit_trait_decl(trait_decl)?,
+        namespace::TopLevelNode::Namespace(nested_namespace) => {
+          if let Some(Declaration {
+            kind: DeclarationKind::Namespace(index),
+            ..
+          }) = self.scopes[scope_index].declarations.get(name.as_str())
+          {
+            self.scope_index = *index;
+          }
+
+          self.visit_namespace(nested_namespace)?;
+          self.scope_index = scope_index;
+        }
+      };
+    }
+
+    for impl_decl in &mut namespace.impls {
+      self.visit_impl_decl(impl_decl)?;
+    }
+
+    Ok(())
+  }
+
+  fn visit_function(&mut self, function: &mut function::Function) -> pass::PassResult {
+    self.resolve_generic_parameters(&mut function.prototype.generic_parameters);
+
+    let result = pass_mut::walk_function(self, function);
+
+    self.generic_parameters.clear();
+
+    result
+  }
+
+  fn visit_trait_decl(&mut self, trait_decl: &mut trait_decl::TraitDecl) -> pass::PassResult {
+    for method in &mut trait_decl.methods {
+      self.generic_parameters = vec![String::from("Self")];
+      self.resolve_generic_parameters(&mut method.generic_parameters);
+      pass_mut::walk_prototype(self, method)?;
+    }
+
+    self.generic_parameters.clear();
+
+    Ok(())
+  }
+
+  fn visit_impl_decl(&mut self, impl_decl: &mut impl_decl::ImplDecl) -> pass::PassResult {
+    let trait_path = &mut impl_decl.trait_path;
+
+    self.resolve_trait_path(&mut trait_path.segments, trait_path.span);
+    self.visit_kind_group(&mut impl_decl.kind_group)?;
+    self.self_kind = Some(impl_decl.kind_group.kind.clone());
+
+    for method in &mut impl_decl.methods {
+      self.visit_function(method)?;
+    }
+
+    self.self_kind = None;
+
+    Ok(())
+  }
+
+  fn visit_kind_group(&mut self, kind_group: &mut node::KindGroup) -> pass::PassResult {
+    self.resolve_kind(&mut kind_group.kind, kind_group.span);
+
+    Ok(())
+  }
+
+  fn visit_expr(&mut self, expr: &mut node::AnyExprNode) -> pass::PassResult {
+    match expr {
+      node::AnyExprNode::CallExpr(call_expr) => {
+        let callee = &mut call_expr.callee;
+
+        match self.resolve_path(self.scope_index, &callee.segments, callee.span) {
+          // Calls to variants build values of their enum.
+          Some(Binding {
+            path,
+            kind: DeclarationKind::Variant,
+          }) => {
+            callee.segments = path;
+
+            let variant_literal = node::VariantLiteral {
+              id: call_expr.id,
+              span: call_expr.span,
+              path: call_expr.callee.clone(),
+              arguments: std::mem::take(&mut call_expr.arguments),
+            };
+
+            *expr = node::AnyExprNode::VariantLiteral(variant_literal);
+          }
+          Some(
+            binding @ Binding {
+              kind:
+                DeclarationKind::Namespace(_)
+                | DeclarationKind::Struct
+                | DeclarationKind::Enum(_)
+                | DeclarationKind::Trait(_),
+              ..
+            },
+          ) => {
+            let message = format!("expected a function, found {}", self.describe(&binding));
+
+            self.push_error(callee.span, message, vec![]);
+          }
+          Some(binding) => callee.segments = binding.path,
+          None => {}
+        };
+      }
+      node::AnyExprNode::StructLiteral(struct_literal) => {
+        let path = &mut struct_literal.path;
+
+        self.resolve_struct_path(&mut path.segments, path.span);
+      }
+      node::AnyExprNode::VariantLiteral(variant_literal) => {
+        self.resolve_variant_path(&mut variant_literal.path);
+      }
+      _ => {}
+    };
+
+    pass_mut::walk_expr(self, expr)
+  }
+
+  fn visit_pattern(&mut self, pattern: &mut pattern::AnyPatternNode) -> pass::PassResult {
+    if let pattern::AnyPatternNode::VariantPattern(variant_pattern) = pattern {
+      self.resolve_variant_path(&mut variant_pattern.path);
+    }
+
+    pass_mut::walk_pattern(self, pattern)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{parser, pass::Pass, pass_mut::PassMut, type_check_pass};
+
+  // Records the callee of every call, in order.
+  struct TestPassCallees {
+    callees: Vec<String>,
+  }
+
+  impl pass::Pass<'_> for TestPassCallees {
+    fn visit_call_expr(&mut self, call_expr: &node::CallExpr) -> pass::PassResult {
+      self.callees.push(call_expr.callee.to_string());
+      self.visit_children(call_expr)
+    }
+  }
+
+  fn resolve(source: &str) -> (namespace::Namespace, Vec<diagnostic::Diagnostic>) {
+    let mut namespace = parser::parse_source(source).unwrap();
+
+    let mut name_resolution_pass = NameResolutionPass::new();
+
+    assert_eq!(
+      true,
+      name_resolution_pass.visit_namespace(&mut namespace).is_ok()
+    );
+
+    (namespace, name_resolution_pass.get_diagnostics())
+  }
+
+  fn collect_callees(namespace: &namespace::Namespace) -> Vec<String> {
+    let mut test_pass_callees = TestPassCallees { callees: vec![] };
+
+    assert_eq!(true, test_pass_callees.visit(namespace).is_ok());
+
+    test_pass_callees.callees
+  }
+
+  #[test]
+  fn name_resolution_pass_nested_namespaces() {
+    let (namespace, diagnostics) = resolve(
+      "namespace foo {
+        namespace a {
+          pub fn f() ~ void { g(); }
+          fn g() ~ void { h(); foo::h(); }
+        }
+        fn h() ~ void { a::f(); foo::a::f(); }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+
+    assert_eq!(
+      vec!["a::g", "h", "h", "a::f", "a::f"],
+      collect_callees(&namespace)
+    );
+
+    // Calls across namespaces are checked against the prototype
+    // they were resolved to.
+    let mut type_check_pass = type_check_pass::TypeCheckPass::new();
+
+    assert_eq!(true, type_check_pass.visit(&namespace).is_ok());
+    assert_eq!(true, type_check_pass.get_diagnostics().is_empty());
+  }
+
+  #[test]
+  fn name_resolution_pass_use() {
+    let (namespace, diagnostics) = resolve(
+      "namespace foo {
+        use a::{f, b::g as k};
+        use a::b;
+        namespace a {
+          pub fn f() ~ void {}
+          namespace b { pub fn g() ~ void {} }
+        }
+        fn h() ~ void { f(); k(); b::g(); }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+    assert_eq!(
+      vec!["a::f", "a::b::g", "a::b::g"],
+      collect_callees(&namespace)
+    );
+  }
+
+  #[test]
+  fn name_resolution_pass_unresolved() {
+    let (_, diagnostics) = resolve(
+      "namespace foo {
+        use a::missing;
+        namespace a {}
+        fn h() ~ void { nope(); a::nope(); h::nope(); a(); }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        "cannot find `missing` in namespace `a`",
+        "cannot find `nope` in this scope",
+        "cannot find `nope` in namespace `a`",
+        "`h` is not a namespace",
+        "expected a function, found namespace `a`",
+      ],
+      diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect::<Vec<_>>()
+    );
+  }
+
+  #[test]
+  fn name_resolution_pass_private() {
+    let (_, diagnostics) = resolve(
+      "namespace foo {
+        namespace a {
+          fn f() ~ void {}
+          namespace b { fn g() ~ void { a::f(); } }
+        }
+        fn h() ~ void { a::f(); }
+      }",
+    );
+
+    // Private declarations are visible within nested namespaces.
+    assert_eq!(1, diagnostics.len());
+    assert_eq!("`f` is private to namespace `a`", diagnostics[0].message);
+    assert_eq!(1, diagnostics[0].notes.len());
+  }
+
+  #[test]
+  fn name_resolution_pass_ambiguous() {
+    let (_, diagnostics) = resolve(
+      "namespace foo {
+        use a::f;
+        use b::f;
+        namespace a { pub fn f() ~ void {} }
+        namespace b { pub fn f() ~ void {} }
+        fn h() ~ void { f(); }
+      }",
+    );
+
+    assert_eq!(1, diagnostics.len());
+    assert_eq!("`f` is ambiguous", diagnostics[0].message);
+    assert_eq!(2, diagnostics[0].notes.len());
+  }
+
+  #[test]
+  fn name_resolution_pass_structs() {
+    let (namespace, diagnostics) = resolve(
+      "namespace foo {
+        use a::Point;
+        namespace a {
+          pub struct Point { x: i32, y: i32 }
+          pub struct Line { start: Point, end: Point }
+        }
+        fn f(a: &Point) ~ a::Line {
+          return a::Line { start: Point { x: 1, y: 2 }, end: foo::a::Point { x: 3, y: 4 } };
+        }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+
+    let mut type_check_pass = type_check_pass::TypeCheckPass::new();
+
+    // Struct kinds are resolved relative to the outermost namespace,
+    // both within struct declarations and function bodies.
+    assert_eq!(true, type_check_pass.visit(&namespace).is_ok());
+    assert_eq!(true, type_check_pass.get_diagnostics().is_empty());
+
+    let (_, diagnostics) = resolve(
+      "namespace foo {
+        struct Point { x: i32 }
+        fn f() ~ void {}
+        fn g() ~ f {}
+        fn h() ~ void { Point(); f {}; }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        "expected a struct or enum, found function `f`",
+        "expected a function, found struct `Point`",
+        "expected a struct, found function `f`",
+      ],
+      diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect::<Vec<_>>()
+    );
+  }
+
+  #[test]
+  fn name_resolution_pass_enums() {
+    let (namespace, diagnostics) = resolve(
+      "namespace foo {
+        use a::Shape;
+        namespace a {
+          pub enum Shape { Circle(i32), Empty }
+        }
+        fn f(s: Shape) ~ a::Shape {
+          return match s { Shape::Circle(r) => foo::a::Shape::Circle(r), _ => Shape::Empty };
+        }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+
+    // Calls to variants become variant literals, whose kind is
+    // known once struct kinds naming enums become enum kinds.
+    let mut type_check_pass = type_check_pass::TypeCheckPass::new();
+
+    assert_eq!(true, type_check_pass.visit(&namespace).is_ok());
+    assert_eq!(true, type_check_pass.get_diagnostics().is_empty());
+
+    let (_, diagnostics) = resolve(
+      "namespace foo {
+        enum Shape { Circle(i32) }
+        fn f() ~ void {
+          Shape::Square(1);
+          let a = 1;
+          match a { f => {}, Shape(x) => {}, Shape::Circle::r => {} }
+          Shape {};
+        }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        "cannot find variant `Square` in enum `Shape`",
+        "expected a variant, found enum `Shape`",
+        "`Shape::Circle` is not a namespace",
+        "expected a struct, found enum `Shape`",
+      ],
+      diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect::<Vec<_>>()
+    );
+  }
+
+  #[test]
+  fn name_resolution_pass_array_kinds() {
+    let (namespace, diagnostics) = resolve(
+      "namespace foo {
+        use a::Shape;
+        namespace a {
+          pub struct Point { x: i32 }
+          pub enum Shape { Empty }
+        }
+        fn f(points: [a::Point], shapes: [[Shape; 2]; 2]) ~ void {}
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+
+    // Paths within the elements of arrays and slices are resolved.
+    let mut type_check_pass = type_check_pass::TypeCheckPass::new();
+
+    assert_eq!(true, type_check_pass.visit(&namespace).is_ok());
+    assert_eq!(true, type_check_pass.get_diagnostics().is_empty());
+
+    let function = match namespace.symbol_table.get("f") {
+      Some(namespace::TopLevelNode::Function(function)) => function,
+      _ => panic!("expected a function"),
+    };
+
+    assert_eq!(
+      vec!["[a::Point]", "[[a::Shape; 2]; 2]"],
+      function
+        .prototype
+        .parameters
+        .iter()
+        .map(|(_, kind_group)| kind_group.kind.to_string())
+        .collect::<Vec<_>>()
+    );
+
+    let (_, diagnostics) = resolve("namespace foo { fn f(a: [f; 2]) ~ void {} }");
+
+    assert_eq!(1, diagnostics.len());
+  }
+
+  #[test]
+  fn name_resolution_pass_generic_parameters() {
+    let (namespace, diagnostics) = resolve(
+      "namespace foo {
+        struct T { x: i32 }
+        fn f<T>(a: &T, b: [T; 2]) ~ T { let c: T = a; }
+        fn g(a: T) ~ void {}
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+
+    let kinds_of = |name: &str| match namespace.symbol_table.get(name) {
+      Some(namespace::TopLevelNode::Function(function)) => function
+        .prototype
+        .parameters
+        .iter()
+        .map(|(_, kind_group)| kind_group.kind.clone())
+        .collect::<Vec<_>>(),
+      _ => panic!("expected a function"),
+    };
+
+    // Generic parameters shadow structs, but only within their
+    // function.
+    assert_eq!(
+      true,
+      matches!(
+        kinds_of("f").as_slice(),
+        [
+          node::AnyKindNode::GenericKind(_),
+          node::AnyKindNode::ArrayKind(_)
+        ]
+      )
+    );
+
+    assert_eq!(
+      true,
+      matches!(kinds_of("g").as_slice(), [node::AnyKindNode::StructKind(_)])
+    );
+  }
+
+  #[test]
+  fn name_resolution_pass_traits() {
+    let (namespace, diagnostics) = resolve(
+      "namespace foo {
+        namespace a {
+          pub trait Shape { fn area(self: &Self) ~ i32; }
+          pub struct Square { side: i32 }
+        }
+        impl a::Shape for a::Square {
+          fn area(self: &Self) ~ i32 { return 1; }
+        }
+        fn f<T: a::Shape>(s: &T, d: &dyn a::Shape) ~ i32 {
+          return a::Shape::area(s);
+        }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+    assert_eq!(vec!["a::Shape::area"], collect_callees(&namespace));
+
+    let impl_decl = &namespace.impls[0];
+
+    assert_eq!("<a::Square as a::Shape>", impl_decl.get_name());
+
+    // `Self` names the implementing kind within implementations.
+    assert_eq!(
+      "a::Square",
+      impl_decl.methods[0].prototype.parameters[0]
+        .1
+        .kind
+        .to_string()
+    );
+
+    let (_, diagnostics) = resolve(
+      "namespace foo {
+        trait Shape { fn area(self: &Self) ~ i32; }
+        struct Square { side: i32 }
+        impl Square for Shape {}
+        fn f<T: Square>(s: &dyn Shape) ~ i32 { return Shape::perimeter(s); }
+        fn g(s: Shape) ~ void { Shape(); }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        "expected a trait, found struct `Square`",
+        "cannot find method `perimeter` in trait `Shape`",
+        "expected a struct or enum, found trait `Shape`",
+        "expected a function, found trait `Shape`",
+        "expected a trait, found struct `Square`",
+        "expected a struct or enum, found trait `Shape`",
+      ],
+      diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect::<Vec<_>>()
+    );
+  }
+}
@@ -1,30 +1,336 @@
-use crate::{external, function, node, pass};
+use crate::{
+  enum_decl, external, function, impl_decl, node, pass, span, struct_decl, trait_decl, use_decl,
+};
 
 #[derive(Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 
 pub enum TopLevelNode {
   Function(function::Function),
   External(external::External),
+  Namespace(Namespace),
+  Struct(struct_decl::StructDecl),
+  Enum(enum_decl::EnumDecl),
+  Trait(trait_decl::TraitDecl),
 }
 
+impl TopLevelNode {
+  pub fn as_node(&self) -> &dyn node::Node {
+    match self {
+      TopLevelNode::Function(function) => function,
+      TopLevelNode::External(external) => external,
+      TopLevelNode::Namespace(namespace) => namespace,
+      TopLevelNode::Struct(struct_decl) => struct_decl,
+      TopLevelNode::Enum(enum_decl) => enum_decl,
+      TopLevelNode::Trait(trait_decl) => trait_decl,
+    }
+  }
+}
+
+#[derive(Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Namespace {
+  pub id: node::NodeId,
+  pub span: span::Span,
   pub name: String,
-  pub symbol_table: std::collections::HashMap<String, TopLevelNode>,
+  pub uses: Vec<use_decl::UseDecl>,
+  // Implementations are not named, so they are kept apart from the
+  // symbol table, in declaration order.
+  pub impls: Vec<impl_decl::ImplDecl>,
+  pub symbol_table: SymbolTable,
+  // The allocator of the ids of the tree's nodes, from which passes
+  // allocate the ids of the nodes they create. Only the allocator of
+  // the outermost namespace is used.
+  pub node_ids: node::NodeIdAllocator,
+}
+
+// The top-level nodes of a namespace, looked up by name, and
+// iterated over in the order in which they were declared, so that
+// everything produced from the namespace (ex. diagnostics and
+// lowered code) is reproducible.
+#[derive(Hash, Eq, PartialEq, Debug, Default)]
+pub struct SymbolTable {
+  entries: Vec<(String, TopLevelNode)>,
+  // The position of each entry within `entries`, by name.
+  indices: std::collections::BTreeMap<String, usize>,
+}
+
+impl SymbolTable {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  // Insert a node under the given name. A node already declared
+  // under the same name is replaced, and keeps its position.
+  pub fn insert(&mut self, name: String, top_level_node: TopLevelNode) -> Option<TopLevelNode> {
+    if let Some(index) = self.indices.get(&name) {
+      return Some(std::mem::replace(
+        &mut self.entries[*index].1,
+        top_level_node,
+      ));
+    }
+
+    self.indices.insert(name.clone(), self.entries.len());
+    self.entries.push((name, top_level_node));
+
+    None
+  }
+
+  // Remove the node declared under the given name. The remaining
+  // nodes keep their relative order.
+  pub fn remove(&mut self, name: &str) -> Option<TopLevelNode> {
+    let index = self.indices.remove(name)?;
+
+    for other_index in self.indices.values_mut() {
+      if *other_index > index {
+        *other_index -= 1;
+      }
+    }
+
+    Some(self.entries.remove(index).1)
+  }
+
+  pub fn get(&self, name: &str) -> Option<&TopLevelNode> {
+    self.indices.get(name).map(|index| &self.entries[*index].1)
+  }
+
+  pub fn get_mut(&mut self, name: &str) -> Option<&mut TopLevelNode> {
+    self
+      .indices
+      .get(name)
+      .map(|index| &mut self.entries[*index].1)
+  }
+
+  pub fn contains_key(&self, name: &str) -> bool {
+    self.indices.contains_key(name)
+  }
+
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = (&String, &TopLevelNode)> {
+    self
+      .entries
+      .iter()
+      .map(|(name, top_level_node)| (name, top_level_node))
+  }
+
+  pub fn iter_mut(&mut self) -> impl Iterator<Item = (&String, &mut TopLevelNode)> {
+    self
+      .entries
+      .iter_mut()
+      .map(|(name, top_level_node)| (&*name, top_level_node))
+  }
+
+  pub fn values(&self) -> impl Iterator<Item = &TopLevelNode> {
+    self
+      .entries
+      .iter()
+      .map(|(_, top_level_node)| top_level_node)
+  }
+
+  pub fn values_mut(&mut self) -> impl Iterator<Item = &mut TopLevelNode> {
+    self
+      .entries
+      .iter_mut()
+      .map(|(_, top_level_node)| top_level_node)
+  }
+}
+
+// Serialized as a map, with its entries in declaration order.
+#[cfg(feature = "serde")]
+impl serde::Serialize for SymbolTable {
+  fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(self.iter())
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SymbolTable {
+  fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    struct SymbolTableVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for SymbolTableVisitor {
+      type Value = SymbolTable;
+
+      fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a map of top-level nodes")
+      }
+
+      fn visit_map<A: serde::de::MapAccess<'de>>(
+        self,
+        mut map: A,
+      ) -> Result<SymbolTable, A::Error> {
+        let mut symbol_table = SymbolTable::new();
+
+        while let Some((name, top_level_node)) = map.next_entry()? {
+          symbol_table.insert(name, top_level_node);
+        }
+
+        Ok(symbol_table)
+      }
+    }
+
+    deserializer.deserialize_map(SymbolTableVisitor)
+  }
 }
 
 impl Namespace {
+  // Create an empty namespace, as the root of a new tree.
   pub fn new(name: String) -> Self {
+    let node_ids = node::NodeIdAllocator::new();
+
     Self {
+      id: node_ids.allocate(),
+      span: span::Span::default(),
       name,
-      symbol_table: std::collections::HashMap::new(),
+      uses: vec![],
+      impls: vec![],
+      symbol_table: SymbolTable::new(),
+      node_ids,
     }
   }
 }
 
 impl node::Node for Namespace {
-  fn accept(&mut self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
     pass.visit_namespace(self)?;
 
     Ok(())
   }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    let mut children: Vec<&dyn node::Node> = self
+      .uses
+      .iter()
+      .map(|use_decl| use_decl as &dyn node::Node)
+      .collect();
+
+    children.extend(self.symbol_table.values().map(TopLevelNode::as_node));
+    children.extend(
+      self
+        .impls
+        .iter()
+        .map(|impl_decl| impl_decl as &dyn node::Node),
+    );
+
+    // `use` declarations, top-level nodes and implementations are
+    // each in declaration order, but may be interleaved.
+    children.sort_by_key(|child| child.get_span().unwrap_or_default().start);
+
+    children
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{external, prototype, void_kind};
+
+  fn make_external(node_ids: &node::NodeIdAllocator, name: &str) -> TopLevelNode {
+    TopLevelNode::External(external::External {
+      id: node_ids.allocate(),
+      span: span::Span::default(),
+      is_public: false,
+      prototype: prototype::Prototype {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        name: String::from(name),
+        parameters: vec![],
+        is_variadic: false,
+        generic_parameters: vec![],
+        type_arguments: vec![],
+        return_kind_group: node::KindGroup {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          kind: node::AnyKindNode::VoidKind(void_kind::VoidKind {}),
+          is_reference: false,
+          is_mutable: false,
+        },
+      },
+    })
+  }
+
+  #[test]
+  fn symbol_table_declaration_order() {
+    let node_ids = node::NodeIdAllocator::new();
+    let mut symbol_table = SymbolTable::new();
+    let names = ["zeta", "alpha", "mu", "beta", "omega", "gamma"];
+
+    for name in names {
+      assert_eq!(
+        true,
+        symbol_table
+          .insert(String::from(name), make_external(&node_ids, name))
+          .is_none()
+      );
+    }
+
+    assert_eq!(
+      names.to_vec(),
+      symbol_table
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+    );
+
+    assert_eq!(true, symbol_table.contains_key("mu"));
+    assert_eq!(true, symbol_table.get("delta").is_none());
+  }
+
+  #[test]
+  fn symbol_table_insert_existing() {
+    let node_ids = node::NodeIdAllocator::new();
+    let mut symbol_table = SymbolTable::new();
+
+    symbol_table.insert(String::from("foo"), make_external(&node_ids, "foo"));
+    symbol_table.insert(String::from("bar"), make_external(&node_ids, "bar"));
+
+    assert_eq!(
+      true,
+      symbol_table
+        .insert(String::from("foo"), make_external(&node_ids, "foo"))
+        .is_some()
+    );
+
+    // The replaced node keeps its position.
+    assert_eq!(2, symbol_table.len());
+    assert_eq!(
+      vec!["foo", "bar"],
+      symbol_table
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+    );
+  }
+
+  #[test]
+  fn symbol_table_remove() {
+    let node_ids = node::NodeIdAllocator::new();
+    let mut symbol_table = SymbolTable::new();
+
+    for name in ["foo", "bar", "baz"] {
+      symbol_table.insert(String::from(name), make_external(&node_ids, name));
+    }
+
+    assert_eq!(true, symbol_table.remove("bar").is_some());
+    assert_eq!(true, symbol_table.remove("bar").is_none());
+    assert_eq!(true, symbol_table.get("baz").is_some());
+    assert_eq!(
+      vec!["foo", "baz"],
+      symbol_table
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+    );
+  }
 }
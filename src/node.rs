@@ -1,43 +1,746 @@
-use crate::{int_kind, pass, void_kind};
+use crate::{
+  array_kind, block, bool_kind, dyn_kind, enum_kind, generic_kind, int_kind, pass, pattern,
+  reference_kind, slice_kind, span, struct_kind, void_kind,
+};
 
-#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AnyKindNode {
   IntKind(int_kind::IntKind),
   VoidKind(void_kind::VoidKind),
+  BoolKind(bool_kind::BoolKind),
+  StructKind(struct_kind::StructKind),
+  EnumKind(enum_kind::EnumKind),
+  ArrayKind(array_kind::ArrayKind),
+  SliceKind(slice_kind::SliceKind),
+  ReferenceKind(reference_kind::ReferenceKind),
+  GenericKind(generic_kind::GenericKind),
+  DynKind(dyn_kind::DynKind),
 }
 
-#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
+impl std::fmt::Display for AnyKindNode {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      AnyKindNode::IntKind(int_kind) => write!(
+        f,
+        "{}",
+        match int_kind.size {
+          int_kind::IntSize::Signed8 => "i8",
+          int_kind::IntSize::Signed16 => "i16",
+          int_kind::IntSize::Signed32 => "i32",
+          int_kind::IntSize::Signed64 => "i64",
+          int_kind::IntSize::Signed128 => "i128",
+        }
+      ),
+      AnyKindNode::VoidKind(_) => write!(f, "void"),
+      AnyKindNode::BoolKind(_) => write!(f, "bool"),
+      AnyKindNode::StructKind(struct_kind) => write!(f, "{}", struct_kind),
+      AnyKindNode::EnumKind(enum_kind) => write!(f, "{}", enum_kind),
+      AnyKindNode::ArrayKind(array_kind) => write!(f, "{}", array_kind),
+      AnyKindNode::SliceKind(slice_kind) => write!(f, "{}", slice_kind),
+      AnyKindNode::ReferenceKind(reference_kind) => write!(f, "{}", reference_kind),
+      AnyKindNode::GenericKind(generic_kind) => write!(f, "{}", generic_kind),
+      AnyKindNode::DynKind(dyn_kind) => write!(f, "{}", dyn_kind),
+    }
+  }
+}
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AnyLiteralNode {
   BoolLiteral(BoolLiteral),
+  IntLiteral(IntLiteral),
+}
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnyExprNode {
+  Literal(AnyLiteralNode),
+  VariableRef(VariableRef),
+  CallExpr(CallExpr),
+  FieldAccessExpr(FieldAccessExpr),
+  StructLiteral(StructLiteral),
+  VariantLiteral(VariantLiteral),
+  MatchExpr(MatchExpr),
+  ArrayLiteral(ArrayLiteral),
+  IndexExpr(IndexExpr),
+  LenExpr(LenExpr),
+  BorrowExpr(BorrowExpr),
+  DerefExpr(DerefExpr),
+}
+
+impl AnyKindNode {
+  pub fn as_node(&self) -> &dyn Node {
+    match self {
+      AnyKindNode::IntKind(int_kind) => int_kind,
+      AnyKindNode::VoidKind(void_kind) => void_kind,
+      AnyKindNode::BoolKind(bool_kind) => bool_kind,
+      AnyKindNode::StructKind(struct_kind) => struct_kind,
+      AnyKindNode::EnumKind(enum_kind) => enum_kind,
+      AnyKindNode::ArrayKind(array_kind) => array_kind,
+      AnyKindNode::SliceKind(slice_kind) => slice_kind,
+      AnyKindNode::ReferenceKind(reference_kind) => reference_kind,
+      AnyKindNode::GenericKind(generic_kind) => generic_kind,
+      AnyKindNode::DynKind(dyn_kind) => dyn_kind,
+    }
+  }
+
+  // Whether the kind names a generic parameter, or is made of one
+  // (ex. `[T; 2]`).
+  pub fn is_generic(&self) -> bool {
+    match self {
+      AnyKindNode::GenericKind(_) => true,
+      AnyKindNode::ArrayKind(array_kind) => array_kind.element_kind.is_generic(),
+      AnyKindNode::SliceKind(slice_kind) => slice_kind.element_kind.is_generic(),
+      AnyKindNode::ReferenceKind(reference_kind) => reference_kind.referenced_kind.is_generic(),
+      _ => false,
+    }
+  }
+
+  // Replace the generic parameters named within the kind by the
+  // kinds bound to them, if any (ex. `[T; 2]` becomes `[i32; 2]`
+  // when `T` is bound to `i32`).
+  pub fn substitute(
+    &self,
+    bindings: &std::collections::HashMap<String, AnyKindNode>,
+  ) -> AnyKindNode {
+    match self {
+      AnyKindNode::GenericKind(generic_kind) => match bindings.get(&generic_kind.name) {
+        Some(kind) => kind.clone(),
+        None => self.clone(),
+      },
+      AnyKindNode::ArrayKind(array_kind) => AnyKindNode::ArrayKind(array_kind::ArrayKind {
+        element_kind: Box::new(array_kind.element_kind.substitute(bindings)),
+        length: array_kind.length,
+      }),
+      AnyKindNode::SliceKind(slice_kind) => AnyKindNode::SliceKind(slice_kind::SliceKind {
+        element_kind: Box::new(slice_kind.element_kind.substitute(bindings)),
+      }),
+      AnyKindNode::ReferenceKind(reference_kind) => {
+        AnyKindNode::ReferenceKind(reference_kind::ReferenceKind {
+          referenced_kind: Box::new(reference_kind.referenced_kind.substitute(bindings)),
+          is_mutable: reference_kind.is_mutable,
+        })
+      }
+      _ => self.clone(),
+    }
+  }
+}
+
+impl AnyLiteralNode {
+  pub fn as_node(&self) -> &dyn Node {
+    match self {
+      AnyLiteralNode::BoolLiteral(bool_literal) => bool_literal,
+      AnyLiteralNode::IntLiteral(int_literal) => int_literal,
+    }
+  }
+}
+
+impl AnyExprNode {
+  pub fn as_node(&self) -> &dyn Node {
+    match self {
+      AnyExprNode::Literal(literal) => literal.as_node(),
+      AnyExprNode::VariableRef(variable_ref) => variable_ref,
+      AnyExprNode::CallExpr(call_expr) => call_expr,
+      AnyExprNode::FieldAccessExpr(field_access_expr) => field_access_expr,
+      AnyExprNode::StructLiteral(struct_literal) => struct_literal,
+      AnyExprNode::VariantLiteral(variant_literal) => variant_literal,
+      AnyExprNode::MatchExpr(match_expr) => match_expr,
+      AnyExprNode::ArrayLiteral(array_literal) => array_literal,
+      AnyExprNode::IndexExpr(index_expr) => index_expr,
+      AnyExprNode::LenExpr(len_expr) => len_expr,
+      AnyExprNode::BorrowExpr(borrow_expr) => borrow_expr,
+      AnyExprNode::DerefExpr(deref_expr) => deref_expr,
+    }
+  }
+
+  pub fn get_id(&self) -> NodeId {
+    match self {
+      AnyExprNode::Literal(AnyLiteralNode::BoolLiteral(bool_literal)) => bool_literal.id,
+      AnyExprNode::Literal(AnyLiteralNode::IntLiteral(int_literal)) => int_literal.id,
+      AnyExprNode::VariableRef(variable_ref) => variable_ref.id,
+      AnyExprNode::CallExpr(call_expr) => call_expr.id,
+      AnyExprNode::FieldAccessExpr(field_access_expr) => field_access_expr.id,
+      AnyExprNode::StructLiteral(struct_literal) => struct_literal.id,
+      AnyExprNode::VariantLiteral(variant_literal) => variant_literal.id,
+      AnyExprNode::MatchExpr(match_expr) => match_expr.id,
+      AnyExprNode::ArrayLiteral(array_literal) => array_literal.id,
+      AnyExprNode::IndexExpr(index_expr) => index_expr.id,
+      AnyExprNode::LenExpr(len_expr) => len_expr.id,
+      AnyExprNode::BorrowExpr(borrow_expr) => borrow_expr.id,
+      AnyExprNode::DerefExpr(deref_expr) => deref_expr.id,
+    }
+  }
+
+  pub fn get_span(&self) -> span::Span {
+    match self {
+      AnyExprNode::Literal(AnyLiteralNode::BoolLiteral(bool_literal)) => bool_literal.span,
+      AnyExprNode::Literal(AnyLiteralNode::IntLiteral(int_literal)) => int_literal.span,
+      AnyExprNode::VariableRef(variable_ref) => variable_ref.span,
+      AnyExprNode::CallExpr(call_expr) => call_expr.span,
+      AnyExprNode::FieldAccessExpr(field_access_expr) => field_access_expr.span,
+      AnyExprNode::StructLiteral(struct_literal) => struct_literal.span,
+      AnyExprNode::VariantLiteral(variant_literal) => variant_literal.span,
+      AnyExprNode::MatchExpr(match_expr) => match_expr.span,
+      AnyExprNode::ArrayLiteral(array_literal) => array_literal.span,
+      AnyExprNode::IndexExpr(index_expr) => index_expr.span,
+      AnyExprNode::LenExpr(len_expr) => len_expr.span,
+      AnyExprNode::BorrowExpr(borrow_expr) => borrow_expr.span,
+      AnyExprNode::DerefExpr(deref_expr) => deref_expr.span,
+    }
+  }
+
+  // Whether the expression denotes a location which may be
+  // assigned to, such as a variable, one of its fields or one of
+  // its elements, or the value behind a reference.
+  pub fn is_place(&self) -> bool {
+    match self {
+      AnyExprNode::VariableRef(_) | AnyExprNode::DerefExpr(_) => true,
+      AnyExprNode::FieldAccessExpr(field_access_expr) => field_access_expr.base.is_place(),
+      AnyExprNode::IndexExpr(index_expr) => index_expr.base.is_place(),
+      _ => false,
+    }
+  }
+}
+
+// Uniquely identifies a node within the AST, regardless of its
+// contents. Information gathered about a node (ex. its kind, or
+// its LLVM value) is kept in side tables keyed by its id, since
+// nodes with equal contents (ex. two `true` literals) are still
+// distinct nodes.
+//
+// Primitive kinds (ex. `i32`) carry no id, as they are compared
+// by their contents.
+#[derive(Hash, Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeId(pub usize);
+
+// Allocates the ids of the nodes of a single tree. Each tree owns
+// its allocator, so that its ids depend only on its own contents,
+// and not on whatever else was parsed before it.
+#[derive(Eq, PartialEq, Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeIdAllocator {
+  next_id: std::cell::Cell<usize>,
+}
+
+impl NodeIdAllocator {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  // Allocate an id which is distinct from every id allocated
+  // before it by this allocator.
+  pub fn allocate(&self) -> NodeId {
+    let id = self.next_id.get();
+
+    self.next_id.set(id + 1);
+
+    NodeId(id)
+  }
 }
 
+impl std::hash::Hash for NodeIdAllocator {
+  fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+    self.next_id.get().hash(state);
+  }
+}
+
+// A side table, holding information about nodes by their id.
+pub type NodeMap<T> = std::collections::HashMap<NodeId, T>;
+
 pub trait Node {
-  fn accept(&mut self, pass: &mut dyn pass::Pass) -> pass::PassResult;
+  // Dispatch to the pass' `visit_*` method for this node.
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult;
 
+  // The nodes directly contained by this node, in the order in
+  // which they appear in the source.
   fn get_children(&self) -> Vec<&dyn Node> {
     vec![]
   }
+
+  // The location of this node within the source, if it has one.
+  fn get_span(&self) -> Option<span::Span> {
+    None
+  }
 }
 
-#[derive(Hash, Eq, PartialEq, Debug)]
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Identifier {
   pub name: String,
 }
 
+// A reference to a named value, such as the iterator of
+// a `for` loop.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VariableRef {
+  pub id: NodeId,
+  pub span: span::Span,
+  pub name: String,
+}
+
+impl Node for VariableRef {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_variable_ref(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+}
+
+// A name, qualified by the namespaces that lead to it (if any),
+// outermost first (ex. `a::b::c`).
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Path {
+  pub id: NodeId,
+  pub span: span::Span,
+  pub segments: Vec<String>,
+}
+
+impl Path {
+  // Split the path of an enum's variant into the path of the enum,
+  // and the name of the variant.
+  pub fn split_variant(&self) -> (String, &str) {
+    let (variant_name, enum_path) = self.segments.split_last().unwrap();
+
+    (enum_path.join("::"), variant_name)
+  }
+}
+
+impl std::fmt::Display for Path {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.segments.join("::"))
+  }
+}
+
+impl Node for Path {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_path(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+}
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallExpr {
+  pub id: NodeId,
+  pub span: span::Span,
+  pub callee: Path,
+  pub arguments: Vec<AnyExprNode>,
+}
+
+impl Node for CallExpr {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_call_expr(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn Node> {
+    let mut children: Vec<&dyn Node> = vec![&self.callee];
+
+    children.extend(self.arguments.iter().map(|argument| argument.as_node()));
+
+    children
+  }
+}
+
+// The value of a field of a struct (ex. `a.b`). Accessing a field
+// through a reference reads the field of the referenced struct.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldAccessExpr {
+  pub id: NodeId,
+  pub span: span::Span,
+  pub base: Box<AnyExprNode>,
+  pub field_name: String,
+}
+
+impl Node for FieldAccessExpr {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_field_access_expr(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn Node> {
+    vec![self.base.as_node()]
+  }
+}
+
+// A field of a struct literal, paired with its value.
+pub type StructLiteralField = (String, AnyExprNode);
+
+// A value of a struct, given the values of all of its fields (ex.
+// `Point { x: 1, y: 2 }`), in any order.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructLiteral {
+  pub id: NodeId,
+  pub span: span::Span,
+  pub path: Path,
+  pub fields: Vec<StructLiteralField>,
+}
+
+impl Node for StructLiteral {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_struct_literal(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn Node> {
+    let mut children: Vec<&dyn Node> = vec![&self.path];
+
+    children.extend(self.fields.iter().map(|(_, value)| value.as_node()));
+
+    children
+  }
+}
+
+// A value of a variant of an enum, given the values of its fields
+// (ex. `Shape::Circle(1)`, or `Shape::Empty`). The path leads to the
+// variant, through its enum.
+//
+// Variants with fields are parsed as calls, which are turned into
+// variant literals once their callee is resolved.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VariantLiteral {
+  pub id: NodeId,
+  pub span: span::Span,
+  pub path: Path,
+  pub arguments: Vec<AnyExprNode>,
+}
+
+impl Node for VariantLiteral {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_variant_literal(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn Node> {
+    let mut children: Vec<&dyn Node> = vec![&self.path];
+
+    children.extend(self.arguments.iter().map(|argument| argument.as_node()));
+
+    children
+  }
+}
+
+// What an arm of a `match` expression evaluates to once its pattern
+// matched. Blocks produce no value.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MatchArmBody {
+  Expr(AnyExprNode),
+  Block(block::Block),
+}
+
+impl MatchArmBody {
+  pub fn as_node(&self) -> &dyn Node {
+    match self {
+      MatchArmBody::Expr(expr) => expr.as_node(),
+      MatchArmBody::Block(block) => block,
+    }
+  }
+}
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchArm {
+  pub id: NodeId,
+  pub span: span::Span,
+  pub pattern: pattern::AnyPatternNode,
+  pub body: MatchArmBody,
+}
+
+impl Node for MatchArm {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_children(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn Node> {
+    vec![self.pattern.as_node(), self.body.as_node()]
+  }
+}
+
+// Evaluates the first arm whose pattern matches the subject (ex.
+// `match shape { Shape::Circle(r) => r, _ => 0 }`). The arms are
+// expected to cover every value of the subject.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchExpr {
+  pub id: NodeId,
+  pub span: span::Span,
+  pub subject: Box<AnyExprNode>,
+  pub arms: Vec<MatchArm>,
+}
+
+impl Node for MatchExpr {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_match_expr(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn Node> {
+    let mut children: Vec<&dyn Node> = vec![self.subject.as_node()];
+
+    children.extend(self.arms.iter().map(|arm| arm as &dyn Node));
+
+    children
+  }
+}
+
+// A value of an array, given the values of all of its elements
+// (ex. `[1, 2, 3]`).
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArrayLiteral {
+  pub id: NodeId,
+  pub span: span::Span,
+  pub elements: Vec<AnyExprNode>,
+}
+
+impl Node for ArrayLiteral {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_array_literal(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn Node> {
+    self
+      .elements
+      .iter()
+      .map(|element| element.as_node())
+      .collect()
+  }
+}
+
+// The value of an element of an array or a slice (ex. `a[i]`).
+// Indices past the end are caught at runtime in debug builds.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexExpr {
+  pub id: NodeId,
+  pub span: span::Span,
+  pub base: Box<AnyExprNode>,
+  pub index: Box<AnyExprNode>,
+}
+
+impl Node for IndexExpr {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_index_expr(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn Node> {
+    vec![self.base.as_node(), self.index.as_node()]
+  }
+}
+
+// The amount of elements of an array or a slice, as an `i64` (ex.
+// `a.len()`).
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LenExpr {
+  pub id: NodeId,
+  pub span: span::Span,
+  pub base: Box<AnyExprNode>,
+}
+
+impl Node for LenExpr {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_len_expr(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn Node> {
+    vec![self.base.as_node()]
+  }
+}
+
+// A reference to the value of an expression (ex. `&a` or `&mut
+// a.b`). Values which are not places are first stored into a
+// temporary, which the reference refers to.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BorrowExpr {
+  pub id: NodeId,
+  pub span: span::Span,
+  pub is_mutable: bool,
+  pub value: Box<AnyExprNode>,
+}
+
+impl Node for BorrowExpr {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_borrow_expr(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn Node> {
+    vec![self.value.as_node()]
+  }
+}
+
+// The value behind a reference (ex. `*a`).
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DerefExpr {
+  pub id: NodeId,
+  pub span: span::Span,
+  pub value: Box<AnyExprNode>,
+}
+
+impl Node for DerefExpr {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_deref_expr(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn Node> {
+    vec![self.value.as_node()]
+  }
+}
+
 #[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoolLiteral {
+  pub id: NodeId,
+  pub span: span::Span,
   pub value: bool,
 }
 
 impl Node for BoolLiteral {
-  fn accept(&mut self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
     pass.visit_bool_literal(self)
   }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+}
+
+// An integer literal, such as `42`. Its kind is determined by
+// the context in which it is used (ex. the kind of the parameter
+// it is passed to), and defaults to `i32`.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IntLiteral {
+  pub id: NodeId,
+  pub span: span::Span,
+  // The decimal digits of the value, which may be arbitrarily
+  // large. Whether the value fits in its kind is only verified
+  // once the kind is known.
+  pub value: String,
+}
+
+impl IntLiteral {
+  // Determine whether the value can be represented by a signed
+  // integer with the given amount of bits.
+  pub fn fits_in_bit_width(&self, bit_width: u32) -> bool {
+    if bit_width == 0 || bit_width > 128 {
+      return false;
+    }
+
+    // Values larger than `u128` do not fit any integer kind.
+    match self.value.parse::<u128>() {
+      Ok(value) => value <= (1u128 << (bit_width - 1)) - 1,
+      Err(_) => false,
+    }
+  }
+}
+
+impl Node for IntLiteral {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_int_literal(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
 }
 
-#[derive(Hash, Eq, PartialEq, Debug)]
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct KindGroup {
+  pub id: NodeId,
+  pub span: span::Span,
   pub kind: AnyKindNode,
   pub is_reference: bool,
   pub is_mutable: bool,
 }
+
+impl KindGroup {
+  // Whether both groups describe the same kind, regardless of
+  // where they appear.
+  pub fn is_same_as(&self, other: &KindGroup) -> bool {
+    self.kind == other.kind
+      && self.is_reference == other.is_reference
+      && self.is_mutable == other.is_mutable
+  }
+
+  // The kind of the values of this group, which are references to
+  // the group's kind if it is marked as a reference (ex. `&mut i32`).
+  pub fn to_value_kind(&self) -> AnyKindNode {
+    match self.is_reference {
+      true => AnyKindNode::ReferenceKind(reference_kind::ReferenceKind {
+        referenced_kind: Box::new(self.kind.clone()),
+        is_mutable: self.is_mutable,
+      }),
+      false => self.kind.clone(),
+    }
+  }
+}
+
+impl Node for KindGroup {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_kind_group(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn Node> {
+    vec![self.kind.as_node()]
+  }
+}
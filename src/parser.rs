@@ -1,24 +1,20 @@
 use crate::{
-  block, diagnostic, external, function, int_kind, namespace, node, prototype, token, void_kind,
+  array_kind, attribute, block, bool_kind, diagnostic, dyn_kind, enum_decl, external, function,
+  impl_decl, int_kind, lexer, namespace, node, pattern, prototype, slice_kind, span, struct_decl,
+  struct_kind, token, trait_decl, use_decl, void_kind,
 };
 
 macro_rules! skip_past {
   ($self:expr, $token:expr) => {
-    // FIXME: Something's wrong, we get an error when no input is provided (eof at index 0).
-    // if $self.is_eof() {
-    //   return Err(diagnostic::Diagnostic {
-    //     message: format!("expected token `{}` but reached eof", $token),
-    //     severity: diagnostic::DiagnosticSeverity::Error,
-    //   });
-    // }
-
     if !$self.is($token) {
       return Err(diagnostic::Diagnostic {
-        message: format!(
-          "expected token `{}` but got `{}`",
-          $token, $self.tokens[$self.index]
-        ),
+        message: match $self.get_token() {
+          Some(token) => format!("expected token `{}` but got `{}`", $token, token),
+          None => format!("expected token `{}` but reached the end of input", $token),
+        },
         severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some($self.get_span()),
+        notes: vec![],
       });
     }
 
@@ -35,6 +31,8 @@ macro_rules! assert {
         return Err(diagnostic::Diagnostic {
           message: format!("assertion failed: `{}`", stringify!($condition)),
           severity: diagnostic::DiagnosticSeverity::Internal,
+          span: None,
+          notes: vec![],
         });
       }
     }
@@ -45,32 +43,195 @@ type ParserResult<T> = Result<T, diagnostic::Diagnostic>;
 
 pub struct Parser {
   tokens: Vec<token::Token>,
+  // The span of each token, by index. May be empty, in which case
+  // all tokens are given empty spans.
+  spans: Vec<span::Span>,
   index: usize,
+  // The end of the span of the last token that was skipped.
+  previous_span_end: usize,
+  // Allocates the ids of the parsed nodes, so that the ids of a
+  // tree depend only on its source.
+  node_ids: node::NodeIdAllocator,
+  // Whether the last token has been skipped. The index never moves
+  // past the last token, which therefore remains the current token
+  // once skipped.
+  is_exhausted: bool,
+  // Whether a name followed by a brace starts a struct literal.
+  // Struct literals are not allowed where a block follows the
+  // expression (ex. the condition of an `if` statement), since the
+  // block would otherwise be mistaken for the literal's fields.
+  is_struct_literal_allowed: bool,
 }
 
 fn find_top_level_node_name(top_level_node: &namespace::TopLevelNode) -> String {
   match top_level_node {
     namespace::TopLevelNode::Function(function) => function.prototype.name.clone(),
     namespace::TopLevelNode::External(external) => external.prototype.name.clone(),
+    namespace::TopLevelNode::Namespace(namespace) => namespace.name.clone(),
+    namespace::TopLevelNode::Struct(struct_decl) => struct_decl.name.clone(),
+    namespace::TopLevelNode::Enum(enum_decl) => enum_decl.name.clone(),
+    namespace::TopLevelNode::Trait(trait_decl) => trait_decl.name.clone(),
+  }
+}
+
+// Ensure that a top-level node may be declared under the same name
+// as a previous one. Only a single `extern` and a definition with
+// a compatible prototype and the same visibility may share a name.
+fn check_redeclaration(
+  previous: &namespace::TopLevelNode,
+  top_level_node: &namespace::TopLevelNode,
+) -> ParserResult<()> {
+  let name = find_top_level_node_name(top_level_node);
+
+  let (message, note_message) = match (previous, top_level_node) {
+    (namespace::TopLevelNode::External(_), namespace::TopLevelNode::External(_)) => (
+      format!("`{}` is declared more than once", name),
+      format!("previous declaration of `{}` is here", name),
+    ),
+    (namespace::TopLevelNode::Function(function), namespace::TopLevelNode::External(external))
+    | (namespace::TopLevelNode::External(external), namespace::TopLevelNode::Function(function)) => {
+      let message = if !function.prototype.is_compatible_with(&external.prototype) {
+        format!(
+          "prototype of `{}` conflicts with its previous declaration",
+          name
+        )
+      } else if function.is_public != external.is_public {
+        format!(
+          "visibility of `{}` conflicts with its previous declaration",
+          name
+        )
+      } else {
+        return Ok(());
+      };
+
+      (
+        message,
+        format!("previous declaration of `{}` is here", name),
+      )
+    }
+    _ => (
+      format!("`{}` is defined more than once", name),
+      format!("previous definition of `{}` is here", name),
+    ),
+  };
+
+  let find_span = |top_level_node: &namespace::TopLevelNode| match top_level_node {
+    namespace::TopLevelNode::Function(function) => function.span,
+    namespace::TopLevelNode::External(external) => external.span,
+    namespace::TopLevelNode::Namespace(namespace) => namespace.span,
+    namespace::TopLevelNode::Struct(struct_decl) => struct_decl.span,
+    namespace::TopLevelNode::Enum(enum_decl) => enum_decl.span,
+    namespace::TopLevelNode::Trait(trait_decl) => trait_decl.span,
+  };
+
+  Err(diagnostic::Diagnostic {
+    message,
+    severity: diagnostic::DiagnosticSeverity::Error,
+    span: Some(find_span(top_level_node)),
+    notes: vec![diagnostic::DiagnosticNote {
+      message: note_message,
+      span: find_span(previous),
+    }],
+  })
+}
+
+// Ensure that the attributes of a function are known, and are
+// given a value only when they expect one. Both `no_mangle` and
+// `export_name` determine the symbol of the function, so at most
+// one of them may be applied.
+fn check_attributes(attributes: &[attribute::Attribute]) -> ParserResult<()> {
+  let mut symbol_attribute: Option<&attribute::Attribute> = None;
+
+  for attribute in attributes {
+    let message = match (attribute.name.as_str(), &attribute.value) {
+      (attribute::NO_MANGLE, Some(_)) => Some(format!(
+        "attribute `{}` does not take a value",
+        attribute.name
+      )),
+      (attribute::EXPORT_NAME, None) => Some(format!(
+        "attribute `{}` requires a value (ex. `#[{} = \"foo\"]`)",
+        attribute.name, attribute.name
+      )),
+      (attribute::EXPORT_NAME, Some(value)) if value.is_empty() => Some(format!(
+        "the value of attribute `{}` must not be empty",
+        attribute.name
+      )),
+      (attribute::NO_MANGLE | attribute::EXPORT_NAME, _) => None,
+      (name, _) => Some(format!("unknown attribute `{}`", name)),
+    };
+
+    if let Some(message) = message {
+      return Err(diagnostic::Diagnostic {
+        message,
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(attribute.span),
+        notes: vec![],
+      });
+    }
+
+    if let Some(previous) = symbol_attribute {
+      return Err(diagnostic::Diagnostic {
+        message: format!(
+          "attribute `{}` conflicts with attribute `{}`",
+          attribute.name, previous.name
+        ),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(attribute.span),
+        notes: vec![diagnostic::DiagnosticNote {
+          message: format!("attribute `{}` is here", previous.name),
+          span: previous.span,
+        }],
+      });
+    }
+
+    symbol_attribute = Some(attribute);
   }
+
+  Ok(())
 }
 
 impl Parser {
   pub fn new(tokens: Vec<token::Token>) -> Self {
-    Self { tokens, index: 0 }
+    Self::with_spans(tokens, vec![])
   }
 
-  fn is(&self, token: token::Token) -> bool {
-    if self.index >= self.tokens.len() {
-      return false;
+  pub fn with_spans(tokens: Vec<token::Token>, spans: Vec<span::Span>) -> Self {
+    Self {
+      tokens,
+      spans,
+      index: 0,
+      previous_span_end: 0,
+      node_ids: node::NodeIdAllocator::new(),
+      is_exhausted: false,
+      is_struct_literal_allowed: true,
     }
+  }
+
+  // Retrieve the span of the current token.
+  fn get_span(&self) -> span::Span {
+    self.spans.get(self.index).cloned().unwrap_or_default()
+  }
+
+  // Create a span from the start of the given span, up to the
+  // end of the last token that was skipped.
+  fn get_span_from(&self, start_span: span::Span) -> span::Span {
+    span::Span {
+      start: start_span.start,
+      end: self.previous_span_end.max(start_span.start),
+    }
+  }
 
-    self.tokens[self.index] == token
+  fn is(&self, token: token::Token) -> bool {
+    self.get_token() == Some(&token)
   }
 
   fn skip(&mut self) -> bool {
+    self.previous_span_end = self.get_span().end;
+
     // FIXME: Address out of bounds problem.
     if self.index + 1 >= self.tokens.len() {
+      self.is_exhausted = true;
+
       return false;
     }
 
@@ -83,6 +244,15 @@ impl Parser {
     self.tokens.len() == 0 || self.index == self.tokens.len() - 1
   }
 
+  // Retrieve the current token, or [`None`] if every token has
+  // been skipped.
+  fn get_token(&self) -> Option<&token::Token> {
+    match self.is_exhausted {
+      true => None,
+      false => self.tokens.get(self.index),
+    }
+  }
+
   fn peek(&self) -> Option<token::Token> {
     match self.tokens.get(self.index + 1) {
       Some(value) => Some(value.clone()),
@@ -123,6 +293,8 @@ impl Parser {
   }
 
   pub fn parse_block(&mut self) -> ParserResult<block::Block> {
+    let span_start = self.get_span();
+
     skip_past!(self, token::Token::SymbolBraceL);
 
     let mut statements = vec![];
@@ -132,6 +304,39 @@ impl Parser {
         token::Token::KeywordReturn => {
           block::AnyStatementNode::ReturnStmt(self.parse_return_stmt()?)
         }
+        token::Token::KeywordIf => block::AnyStatementNode::IfStmt(self.parse_if_stmt()?),
+        token::Token::KeywordWhile => block::AnyStatementNode::WhileStmt(self.parse_while_stmt()?),
+        token::Token::KeywordLoop => block::AnyStatementNode::LoopStmt(self.parse_loop_stmt()?),
+        token::Token::KeywordFor => block::AnyStatementNode::ForStmt(self.parse_for_stmt()?),
+        token::Token::KeywordBreak => block::AnyStatementNode::BreakStmt(self.parse_break_stmt()?),
+        token::Token::KeywordContinue => {
+          block::AnyStatementNode::ContinueStmt(self.parse_continue_stmt()?)
+        }
+        token::Token::KeywordLet => block::AnyStatementNode::LetStmt(self.parse_let_stmt()?),
+        token::Token::Identifier(_) | token::Token::KeywordMatch | token::Token::SymbolAsterisk => {
+          self.parse_expr_or_assign_stmt()?
+        }
+        // A label may only precede a loop, which is found
+        // after the label and its colon.
+        token::Token::Label(_) => match self.tokens.get(self.index + 2) {
+          Some(token::Token::KeywordWhile) => {
+            block::AnyStatementNode::WhileStmt(self.parse_while_stmt()?)
+          }
+          Some(token::Token::KeywordLoop) => {
+            block::AnyStatementNode::LoopStmt(self.parse_loop_stmt()?)
+          }
+          Some(token::Token::KeywordFor) => {
+            block::AnyStatementNode::ForStmt(self.parse_for_stmt()?)
+          }
+          _ => {
+            return Err(diagnostic::Diagnostic {
+              message: String::from("expected a loop after label"),
+              severity: diagnostic::DiagnosticSeverity::Error,
+              span: Some(self.get_span()),
+              notes: vec![],
+            })
+          }
+        },
         _ => {
           return Err(diagnostic::Diagnostic {
             message: format!(
@@ -139,6 +344,8 @@ impl Parser {
               self.tokens[self.index]
             ),
             severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(self.get_span()),
+            notes: vec![],
           })
         }
       });
@@ -146,7 +353,11 @@ impl Parser {
 
     skip_past!(self, token::Token::SymbolBraceR);
 
-    Ok(block::Block { statements })
+    Ok(block::Block {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      statements,
+    })
   }
 
   pub fn parse_int_kind(&mut self) -> ParserResult<int_kind::IntKind> {
@@ -157,11 +368,17 @@ impl Parser {
     };
 
     let size = match token {
+      token::Token::TypeInt8 => int_kind::IntSize::Signed8,
+      token::Token::TypeInt16 => int_kind::IntSize::Signed16,
       token::Token::TypeInt32 => int_kind::IntSize::Signed32,
+      token::Token::TypeInt64 => int_kind::IntSize::Signed64,
+      token::Token::TypeInt128 => int_kind::IntSize::Signed128,
       _ => {
         return Err(diagnostic::Diagnostic {
           message: format!("not yet implemented"),
           severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(self.get_span()),
+          notes: vec![],
         })
       }
     };
@@ -175,35 +392,114 @@ impl Parser {
     Ok(void_kind::VoidKind {})
   }
 
-  pub fn parse_kind_group(&mut self) -> ParserResult<node::KindGroup> {
-    let mut is_reference = false;
-    let mut is_mutable = false;
+  pub fn parse_bool_kind(&mut self) -> ParserResult<bool_kind::BoolKind> {
+    skip_past!(self, token::Token::TypeBool);
 
-    if self.is(token::Token::SymbolAmpersand) {
-      is_reference = true;
-      self.skip();
-    }
+    Ok(bool_kind::BoolKind {})
+  }
 
-    if self.is(token::Token::KeywordMut) {
-      is_mutable = true;
-      self.skip();
+  pub fn parse_struct_kind(&mut self) -> ParserResult<struct_kind::StructKind> {
+    Ok(struct_kind::StructKind {
+      path: self.parse_path()?.segments,
+    })
+  }
+
+  pub fn parse_dyn_kind(&mut self) -> ParserResult<dyn_kind::DynKind> {
+    skip_past!(self, token::Token::KeywordDyn);
+
+    Ok(dyn_kind::DynKind {
+      trait_path: self.parse_path()?.segments,
+    })
+  }
+
+  // Parse an array kind (ex. `[i32; 4]`), or a slice kind (ex.
+  // `[i32]`) if no length is given.
+  pub fn parse_array_or_slice_kind(&mut self) -> ParserResult<node::AnyKindNode> {
+    skip_past!(self, token::Token::SymbolBracketL);
+
+    let element_kind = Box::new(self.parse_kind()?);
+
+    if !self.is(token::Token::SymbolSemiColon) {
+      skip_past!(self, token::Token::SymbolBracketR);
+
+      return Ok(node::AnyKindNode::SliceKind(slice_kind::SliceKind {
+        element_kind,
+      }));
     }
 
+    self.skip();
+
+    let length_literal = self.parse_int_literal()?;
+
+    let length = match length_literal.value.parse::<u64>() {
+      Ok(length) => length,
+      Err(_) => {
+        return Err(diagnostic::Diagnostic {
+          message: format!("array length `{}` is too large", length_literal.value),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(length_literal.span),
+          notes: vec![],
+        })
+      }
+    };
+
+    skip_past!(self, token::Token::SymbolBracketR);
+
+    Ok(node::AnyKindNode::ArrayKind(array_kind::ArrayKind {
+      element_kind,
+      length,
+    }))
+  }
+
+  // Parse a kind, without the reference and mutability markers of
+  // its group.
+  pub fn parse_kind(&mut self) -> ParserResult<node::AnyKindNode> {
     // TODO: Check if the index is valid?
     // TODO: Support for more types.
-    let kind = match self.tokens[self.index] {
+    Ok(match self.tokens[self.index] {
       token::Token::TypeVoid => node::AnyKindNode::VoidKind(self.parse_void_kind()?),
-      token::Token::TypeInt32 => node::AnyKindNode::IntKind(self.parse_int_kind()?),
+      token::Token::TypeInt8
+      | token::Token::TypeInt16
+      | token::Token::TypeInt32
+      | token::Token::TypeInt64
+      | token::Token::TypeInt128 => node::AnyKindNode::IntKind(self.parse_int_kind()?),
+      token::Token::TypeBool => node::AnyKindNode::BoolKind(self.parse_bool_kind()?),
+      token::Token::Identifier(_) => node::AnyKindNode::StructKind(self.parse_struct_kind()?),
+      token::Token::SymbolBracketL => self.parse_array_or_slice_kind()?,
+      token::Token::KeywordDyn => node::AnyKindNode::DynKind(self.parse_dyn_kind()?),
       _ => {
         return Err(diagnostic::Diagnostic {
           // TODO: Error message.
           message: String::from("foo"),
           severity: diagnostic::DiagnosticSeverity::Internal,
+          span: Some(self.get_span()),
+          notes: vec![],
         });
       }
-    };
+    })
+  }
+
+  pub fn parse_kind_group(&mut self) -> ParserResult<node::KindGroup> {
+    let span_start = self.get_span();
+
+    let mut is_reference = false;
+    let mut is_mutable = false;
+
+    if self.is(token::Token::SymbolAmpersand) {
+      is_reference = true;
+      self.skip();
+    }
+
+    if self.is(token::Token::KeywordMut) {
+      is_mutable = true;
+      self.skip();
+    }
+
+    let kind = self.parse_kind()?;
 
     Ok(node::KindGroup {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
       kind,
       is_reference,
       is_mutable,
@@ -220,8 +516,45 @@ impl Parser {
     Ok((name, kind_group))
   }
 
+  pub fn parse_generic_parameter(&mut self) -> ParserResult<prototype::GenericParameter> {
+    let name = self.parse_name()?;
+    let mut bounds = vec![];
+
+    // Bounds are separated by `+` (ex. `T: Shape + Named`).
+    if self.is(token::Token::SymbolColon) {
+      self.skip();
+      bounds.push(self.parse_path()?);
+
+      while self.is(token::Token::SymbolPlus) {
+        self.skip();
+        bounds.push(self.parse_path()?);
+      }
+    }
+
+    Ok(prototype::GenericParameter { name, bounds })
+  }
+
   pub fn parse_prototype(&mut self) -> ParserResult<prototype::Prototype> {
+    let span_start = self.get_span();
+
     let name = self.parse_name()?;
+    let mut generic_parameters = vec![];
+
+    if self.is(token::Token::SymbolLessThan) {
+      self.skip();
+
+      while !self.is(token::Token::SymbolGreaterThan) && !self.is_eof() {
+        generic_parameters.push(self.parse_generic_parameter()?);
+
+        if !self.is(token::Token::SymbolComma) {
+          break;
+        }
+
+        self.skip();
+      }
+
+      skip_past!(self, token::Token::SymbolGreaterThan);
+    }
 
     skip_past!(self, token::Token::SymbolParenthesesL);
 
@@ -252,14 +585,63 @@ impl Parser {
     let return_kind_group = self.parse_kind_group()?;
 
     Ok(prototype::Prototype {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
       name,
       parameters,
       is_variadic,
+      generic_parameters,
+      type_arguments: vec![],
       return_kind_group,
     })
   }
 
+  pub fn parse_attribute(&mut self) -> ParserResult<attribute::Attribute> {
+    let span_start = self.get_span();
+
+    skip_past!(self, token::Token::SymbolHash);
+    skip_past!(self, token::Token::SymbolBracketL);
+
+    let name = self.parse_name()?;
+    let mut value = None;
+
+    if self.is(token::Token::SymbolEqual) {
+      self.skip();
+
+      value = match &self.tokens[self.index] {
+        token::Token::LiteralString(value) => Some(value.clone()),
+        token => {
+          return Err(diagnostic::Diagnostic {
+            message: format!("expected a string literal but got `{}`", token),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(self.get_span()),
+            notes: vec![],
+          })
+        }
+      };
+
+      self.skip();
+    }
+
+    skip_past!(self, token::Token::SymbolBracketR);
+
+    Ok(attribute::Attribute {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      name,
+      value,
+    })
+  }
+
   pub fn parse_function(&mut self) -> ParserResult<function::Function> {
+    let span_start = self.get_span();
+
+    let mut attributes = vec![];
+
+    while self.is(token::Token::SymbolHash) {
+      attributes.push(self.parse_attribute()?);
+    }
+
     let mut is_public = false;
 
     if self.is(token::Token::KeywordPub) {
@@ -267,207 +649,2076 @@ impl Parser {
       self.skip();
     }
 
+    if !attributes.is_empty() && !self.is(token::Token::KeywordFn) {
+      return Err(diagnostic::Diagnostic {
+        message: String::from("attributes may only be applied to functions"),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(self.get_span()),
+        notes: vec![],
+      });
+    }
+
+    check_attributes(&attributes)?;
+
     skip_past!(self, token::Token::KeywordFn);
 
     let prototype = self.parse_prototype()?;
     let body = self.parse_block()?;
 
     Ok(function::Function {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
       is_public,
+      attributes,
       prototype,
       body,
     })
   }
 
   pub fn parse_external(&mut self) -> ParserResult<external::External> {
+    let span_start = self.get_span();
+
+    let mut is_public = false;
+
+    if self.is(token::Token::KeywordPub) {
+      is_public = true;
+      self.skip();
+    }
+
     skip_past!(self, token::Token::KeywordExtern);
 
     let prototype = self.parse_prototype()?;
 
     skip_past!(self, token::Token::SymbolSemiColon);
 
-    Ok(external::External { prototype })
+    Ok(external::External {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      is_public,
+      prototype,
+    })
   }
 
-  pub fn parse_namespace(&mut self) -> ParserResult<namespace::Namespace> {
-    skip_past!(self, token::Token::KeywordNamespace);
+  pub fn parse_struct_field(&mut self) -> ParserResult<struct_decl::StructField> {
+    let span_start = self.get_span();
+
+    let name = self.parse_name()?;
+
+    skip_past!(self, token::Token::SymbolColon);
+
+    let kind_group = self.parse_kind_group()?;
+
+    Ok(struct_decl::StructField {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      name,
+      kind_group,
+    })
+  }
+
+  pub fn parse_struct_decl(&mut self) -> ParserResult<struct_decl::StructDecl> {
+    let span_start = self.get_span();
+
+    let mut is_public = false;
+
+    if self.is(token::Token::KeywordPub) {
+      is_public = true;
+      self.skip();
+    }
+
+    skip_past!(self, token::Token::KeywordStruct);
 
     let name = self.parse_name()?;
 
     skip_past!(self, token::Token::SymbolBraceL);
 
-    let mut namespace = namespace::Namespace::new(name);
+    let mut fields: Vec<struct_decl::StructField> = vec![];
 
-    // TODO: Verify condition.
+    // Fields are separated by commas, and may be followed by one.
     while !self.is(token::Token::SymbolBraceR) && !self.is_eof() {
-      // TODO: Support for 'pub' visibility modifier.
+      let field = self.parse_struct_field()?;
 
-      let top_level_node = match self.tokens[self.index] {
-        token::Token::KeywordFn => namespace::TopLevelNode::Function(self.parse_function()?),
-        token::Token::KeywordExtern => namespace::TopLevelNode::External(self.parse_external()?),
-        _ => {
-          return Err(diagnostic::Diagnostic {
-            message: format!("unexpected token: {:?}", self.tokens[self.index]),
-            severity: diagnostic::DiagnosticSeverity::Error,
-          })
-        }
-      };
+      if let Some(previous_field) = fields.iter().find(|previous| previous.name == field.name) {
+        return Err(diagnostic::Diagnostic {
+          message: format!("field `{}` is declared more than once", field.name),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(field.span),
+          notes: vec![diagnostic::DiagnosticNote {
+            message: format!("previous declaration of `{}` is here", field.name),
+            span: previous_field.span,
+          }],
+        });
+      }
 
-      namespace
-        .symbol_table
-        .insert(find_top_level_node_name(&top_level_node), top_level_node);
+      fields.push(field);
+
+      if !self.is(token::Token::SymbolComma) {
+        break;
+      }
+
+      self.skip();
     }
 
     skip_past!(self, token::Token::SymbolBraceR);
 
-    Ok(namespace)
+    Ok(struct_decl::StructDecl {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      is_public,
+      name,
+      fields,
+    })
   }
 
-  pub fn parse_return_stmt(&mut self) -> ParserResult<block::ReturnStmt> {
-    skip_past!(self, token::Token::KeywordReturn);
+  pub fn parse_enum_variant(&mut self) -> ParserResult<enum_decl::EnumVariant> {
+    let span_start = self.get_span();
 
-    let mut value = None;
+    let name = self.parse_name()?;
+    let mut fields = vec![];
 
-    if !self.is(token::Token::SymbolSemiColon) {
-      value = Some(self.parse_literal()?);
-    }
+    // Variants without fields are written without parentheses.
+    if self.is(token::Token::SymbolParenthesesL) {
+      self.skip();
 
-    skip_past!(self, token::Token::SymbolSemiColon);
+      while !self.is(token::Token::SymbolParenthesesR) && !self.is_eof() {
+        fields.push(self.parse_kind_group()?);
 
-    Ok(block::ReturnStmt { value })
-  }
+        if !self.is(token::Token::SymbolComma) {
+          break;
+        }
 
-  pub fn parse_bool_literal(&mut self) -> ParserResult<node::BoolLiteral> {
-    Ok(match self.tokens[self.index] {
-      token::Token::LiteralBool(value) => {
         self.skip();
-
-        node::BoolLiteral { value }
-      }
-      // TODO: Better error.
-      _ => {
-        return Err(diagnostic::Diagnostic {
-          message: String::from("unexpected token, expected boolean literal"),
-          severity: diagnostic::DiagnosticSeverity::Error,
-        })
       }
-    })
-  }
 
-  pub fn parse_literal(&mut self) -> ParserResult<node::AnyLiteralNode> {
-    Ok(match self.tokens[self.index] {
-      token::Token::LiteralBool(_) => node::AnyLiteralNode::BoolLiteral(self.parse_bool_literal()?),
-      _ => {
-        return Err(diagnostic::Diagnostic {
-          message: String::from("unexpected token, expected literal"),
-          severity: diagnostic::DiagnosticSeverity::Error,
-        })
-      }
+      skip_past!(self, token::Token::SymbolParenthesesR);
+    }
+
+    Ok(enum_decl::EnumVariant {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      name,
+      fields,
     })
   }
-}
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+  pub fn parse_enum_decl(&mut self) -> ParserResult<enum_decl::EnumDecl> {
+    let span_start = self.get_span();
 
-  #[test]
-  fn parser_proper_initial_values() {
-    let parser = Parser::new(vec![]);
+    let mut is_public = false;
 
-    assert_eq!(0, parser.index);
-  }
+    if self.is(token::Token::KeywordPub) {
+      is_public = true;
+      self.skip();
+    }
+
+    skip_past!(self, token::Token::KeywordEnum);
+
+    let name = self.parse_name()?;
+
+    skip_past!(self, token::Token::SymbolBraceL);
+
+    let mut variants: Vec<enum_decl::EnumVariant> = vec![];
+
+    // Variants are separated by commas, and may be followed by one.
+    while !self.is(token::Token::SymbolBraceR) && !self.is_eof() {
+      let variant = self.parse_enum_variant()?;
+
+      if let Some(previous_variant) = variants
+        .iter()
+        .find(|previous| previous.name == variant.name)
+      {
+        return Err(diagnostic::Diagnostic {
+          message: format!("variant `{}` is declared more than once", variant.name),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(variant.span),
+          notes: vec![diagnostic::DiagnosticNote {
+            message: format!("previous declaration of `{}` is here", variant.name),
+            span: previous_variant.span,
+          }],
+        });
+      }
+
+      variants.push(variant);
+
+      if !self.is(token::Token::SymbolComma) {
+        break;
+      }
+
+      self.skip();
+    }
+
+    skip_past!(self, token::Token::SymbolBraceR);
+
+    Ok(enum_decl::EnumDecl {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      is_public,
+      name,
+      variants,
+    })
+  }
+
+  pub fn parse_trait_decl(&mut self) -> ParserResult<trait_decl::TraitDecl> {
+    let span_start = self.get_span();
+
+    let mut is_public = false;
+
+    if self.is(token::Token::KeywordPub) {
+      is_public = true;
+      self.skip();
+    }
+
+    skip_past!(self, token::Token::KeywordTrait);
+
+    let name = self.parse_name()?;
+
+    skip_past!(self, token::Token::SymbolBraceL);
+
+    let mut methods: Vec<prototype::Prototype> = vec![];
+
+    // Methods are declared without a body.
+    while !self.is(token::Token::SymbolBraceR) && !self.is_eof() {
+      skip_past!(self, token::Token::KeywordFn);
+
+      let method = self.parse_prototype()?;
+
+      skip_past!(self, token::Token::SymbolSemiColon);
+
+      if let Some(previous_method) = methods.iter().find(|previous| previous.name == method.name) {
+        return Err(diagnostic::Diagnostic {
+          message: format!("method `{}` is declared more than once", method.name),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(method.span),
+          notes: vec![diagnostic::DiagnosticNote {
+            message: format!("previous declaration of `{}` is here", method.name),
+            span: previous_method.span,
+          }],
+        });
+      }
+
+      methods.push(method);
+    }
+
+    skip_past!(self, token::Token::SymbolBraceR);
+
+    Ok(trait_decl::TraitDecl {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      is_public,
+      name,
+      methods,
+    })
+  }
+
+  pub fn parse_impl_decl(&mut self) -> ParserResult<impl_decl::ImplDecl> {
+    let span_start = self.get_span();
+
+    skip_past!(self, token::Token::KeywordImpl);
+
+    let trait_path = self.parse_path()?;
+
+    skip_past!(self, token::Token::KeywordFor);
+
+    // Traits are implemented for kinds, rather than for references
+    // to them.
+    let kind_span_start = self.get_span();
+    let kind = self.parse_kind()?;
+
+    let kind_group = node::KindGroup {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(kind_span_start),
+      kind,
+      is_reference: false,
+      is_mutable: false,
+    };
+
+    skip_past!(self, token::Token::SymbolBraceL);
+
+    let mut methods: Vec<function::Function> = vec![];
+
+    while !self.is(token::Token::SymbolBraceR) && !self.is_eof() {
+      let method = self.parse_function()?;
+
+      if let Some(previous_method) = methods
+        .iter()
+        .find(|previous| previous.prototype.name == method.prototype.name)
+      {
+        return Err(diagnostic::Diagnostic {
+          message: format!(
+            "method `{}` is defined more than once",
+            method.prototype.name
+          ),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(method.span),
+          notes: vec![diagnostic::DiagnosticNote {
+            message: format!("previous definition of `{}` is here", method.prototype.name),
+            span: previous_method.span,
+          }],
+        });
+      }
+
+      methods.push(method);
+    }
+
+    skip_past!(self, token::Token::SymbolBraceR);
+
+    Ok(impl_decl::ImplDecl {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      trait_path,
+      kind_group,
+      methods,
+    })
+  }
+
+  pub fn parse_namespace(&mut self) -> ParserResult<namespace::Namespace> {
+    let span_start = self.get_span();
+
+    skip_past!(self, token::Token::KeywordNamespace);
+
+    let name = self.parse_name()?;
+
+    skip_past!(self, token::Token::SymbolBraceL);
+
+    let mut namespace = namespace::Namespace::new(name);
+
+    namespace.id = self.node_ids.allocate();
+
+    // TODO: Verify condition.
+    while !self.is(token::Token::SymbolBraceR) && !self.is_eof() {
+      // The visibility modifier (if any) precedes the keyword which
+      // determines the kind of top-level node.
+      let keyword_token = match self.is(token::Token::KeywordPub) {
+        true => self.peek(),
+        false => Some(self.tokens[self.index].clone()),
+      };
+
+      // Only functions may be preceded by attributes.
+      let top_level_node = match keyword_token {
+        Some(token::Token::KeywordFn | token::Token::SymbolHash) => {
+          namespace::TopLevelNode::Function(self.parse_function()?)
+        }
+        Some(token::Token::KeywordExtern) => {
+          namespace::TopLevelNode::External(self.parse_external()?)
+        }
+        Some(token::Token::KeywordNamespace) => {
+          namespace::TopLevelNode::Namespace(self.parse_namespace()?)
+        }
+        Some(token::Token::KeywordStruct) => {
+          namespace::TopLevelNode::Struct(self.parse_struct_decl()?)
+        }
+        Some(token::Token::KeywordEnum) => namespace::TopLevelNode::Enum(self.parse_enum_decl()?),
+        Some(token::Token::KeywordTrait) => {
+          namespace::TopLevelNode::Trait(self.parse_trait_decl()?)
+        }
+        Some(token::Token::KeywordImpl) => {
+          namespace.impls.push(self.parse_impl_decl()?);
+
+          continue;
+        }
+        Some(token::Token::KeywordUse) => {
+          namespace.uses.push(self.parse_use_decl()?);
+
+          continue;
+        }
+        _ => {
+          return Err(diagnostic::Diagnostic {
+            message: format!("unexpected token: {:?}", self.tokens[self.index]),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(self.get_span()),
+            notes: vec![],
+          })
+        }
+      };
+
+      let name = find_top_level_node_name(&top_level_node);
+
+      if let Some(previous) = namespace.symbol_table.get(&name) {
+        check_redeclaration(previous, &top_level_node)?;
+
+        // The definition is kept over its declaration.
+        if let namespace::TopLevelNode::External(_) = top_level_node {
+          continue;
+        }
+      }
+
+      namespace.symbol_table.insert(name, top_level_node);
+    }
+
+    skip_past!(self, token::Token::SymbolBraceR);
+
+    namespace.span = self.get_span_from(span_start);
+    namespace.node_ids = self.node_ids.clone();
+
+    Ok(namespace)
+  }
+
+  // Ensure that every token has been parsed, so that no part of the
+  // input is silently dropped.
+  pub fn expect_eof(&self) -> ParserResult<()> {
+    match self.get_token() {
+      Some(token) => Err(diagnostic::Diagnostic {
+        message: format!("unexpected token `{}`, expected the end of input", token),
+        severity: diagnostic::DiagnosticSeverity::Error,
+        span: Some(self.get_span()),
+        notes: vec![],
+      }),
+      None => Ok(()),
+    }
+  }
+
+  pub fn parse_path(&mut self) -> ParserResult<node::Path> {
+    let span_start = self.get_span();
+    let mut segments = vec![self.parse_name()?];
+
+    while self.is(token::Token::SymbolDoubleColon) {
+      self.skip();
+      segments.push(self.parse_name()?);
+    }
+
+    Ok(node::Path {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      segments,
+    })
+  }
+
+  pub fn parse_use_item(&mut self) -> ParserResult<use_decl::UseItem> {
+    let span_start = self.get_span();
+    let path = self.parse_path()?;
+    let mut alias = None;
+
+    if self.is(token::Token::KeywordAs) {
+      self.skip();
+      alias = Some(self.parse_name()?);
+    }
+
+    Ok(use_decl::UseItem {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      path,
+      alias,
+    })
+  }
+
+  pub fn parse_use_decl(&mut self) -> ParserResult<use_decl::UseDecl> {
+    let span_start = self.get_span();
+
+    skip_past!(self, token::Token::KeywordUse);
+
+    let path_span_start = self.get_span();
+    let mut segments = vec![self.parse_name()?];
+
+    // The prefix of a group ends with `::{`.
+    while self.is(token::Token::SymbolDoubleColon) {
+      self.skip();
+
+      if self.is(token::Token::SymbolBraceL) {
+        break;
+      }
+
+      segments.push(self.parse_name()?);
+    }
+
+    let is_group = self.is(token::Token::SymbolBraceL);
+    let mut prefix = vec![];
+    let mut items = vec![];
+
+    if is_group {
+      prefix = segments;
+      self.skip();
+
+      while !self.is(token::Token::SymbolBraceR) && !self.is_eof() {
+        items.push(self.parse_use_item()?);
+
+        if !self.is(token::Token::SymbolComma) {
+          break;
+        }
+
+        self.skip();
+      }
+
+      skip_past!(self, token::Token::SymbolBraceR);
+    } else {
+      let path = node::Path {
+        id: self.node_ids.allocate(),
+        span: self.get_span_from(path_span_start),
+        segments,
+      };
+
+      let mut alias = None;
+
+      if self.is(token::Token::KeywordAs) {
+        self.skip();
+        alias = Some(self.parse_name()?);
+      }
+
+      items.push(use_decl::UseItem {
+        id: self.node_ids.allocate(),
+        span: self.get_span_from(path_span_start),
+        path,
+        alias,
+      });
+    }
+
+    skip_past!(self, token::Token::SymbolSemiColon);
+
+    Ok(use_decl::UseDecl {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      prefix,
+      items,
+      is_group,
+    })
+  }
+
+  pub fn parse_return_stmt(&mut self) -> ParserResult<block::ReturnStmt> {
+    let span_start = self.get_span();
+
+    skip_past!(self, token::Token::KeywordReturn);
+
+    let mut value = None;
+
+    if !self.is(token::Token::SymbolSemiColon) {
+      value = Some(self.parse_expr()?);
+    }
+
+    skip_past!(self, token::Token::SymbolSemiColon);
+
+    Ok(block::ReturnStmt {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      value,
+    })
+  }
+
+  pub fn parse_if_stmt(&mut self) -> ParserResult<block::IfStmt> {
+    let span_start = self.get_span();
+
+    skip_past!(self, token::Token::KeywordIf);
+
+    let condition = self.parse_expr_with(false)?;
+    let then_block = self.parse_block()?;
+    let mut else_if_branches = vec![];
+    let mut else_block = None;
+
+    while self.is(token::Token::KeywordElse) {
+      self.skip();
+
+      if !self.is(token::Token::KeywordIf) {
+        else_block = Some(self.parse_block()?);
+
+        break;
+      }
+
+      self.skip();
+      else_if_branches.push((self.parse_expr_with(false)?, self.parse_block()?));
+    }
+
+    Ok(block::IfStmt {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      condition,
+      then_block,
+      else_if_branches,
+      else_block,
+    })
+  }
+
+  pub fn parse_label(&mut self) -> ParserResult<String> {
+    let label = match &self.tokens[self.index] {
+      token::Token::Label(value) => value.clone(),
+      _ => {
+        return Err(diagnostic::Diagnostic {
+          message: format!(
+            "unexpected token `{}`, expected label",
+            self.tokens[self.index]
+          ),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(self.get_span()),
+          notes: vec![],
+        })
+      }
+    };
+
+    self.skip();
+
+    Ok(label)
+  }
+
+  // Parse the optional label that precedes a loop, including
+  // its trailing colon.
+  fn parse_loop_label(&mut self) -> ParserResult<Option<String>> {
+    if !matches!(self.tokens.get(self.index), Some(token::Token::Label(_))) {
+      return Ok(None);
+    }
+
+    let label = self.parse_label()?;
+
+    skip_past!(self, token::Token::SymbolColon);
+
+    Ok(Some(label))
+  }
+
+  // Parse the optional label that follows `break` or `continue`,
+  // which are then terminated by a semi-colon.
+  fn parse_loop_control_label(&mut self) -> ParserResult<Option<String>> {
+    let mut label = None;
+
+    if !self.is(token::Token::SymbolSemiColon) {
+      label = Some(self.parse_label()?);
+    }
+
+    skip_past!(self, token::Token::SymbolSemiColon);
+
+    Ok(label)
+  }
+
+  pub fn parse_while_stmt(&mut self) -> ParserResult<block::WhileStmt> {
+    let span_start = self.get_span();
+
+    let label = self.parse_loop_label()?;
+
+    skip_past!(self, token::Token::KeywordWhile);
+
+    let condition = self.parse_expr_with(false)?;
+    let body = self.parse_block()?;
+
+    Ok(block::WhileStmt {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      label,
+      condition,
+      body,
+    })
+  }
+
+  pub fn parse_loop_stmt(&mut self) -> ParserResult<block::LoopStmt> {
+    let span_start = self.get_span();
+
+    let label = self.parse_loop_label()?;
+
+    skip_past!(self, token::Token::KeywordLoop);
+
+    let body = self.parse_block()?;
+
+    Ok(block::LoopStmt {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      label,
+      body,
+    })
+  }
+
+  pub fn parse_for_stmt(&mut self) -> ParserResult<block::ForStmt> {
+    let span_start = self.get_span();
+
+    let label = self.parse_loop_label()?;
+
+    skip_past!(self, token::Token::KeywordFor);
+
+    let iterator_name = self.parse_name()?;
+
+    skip_past!(self, token::Token::KeywordIn);
+
+    let range_start = self.parse_expr_with(false)?;
+
+    let is_inclusive = match self.get_token() {
+      Some(token::Token::SymbolRange) => false,
+      Some(token::Token::SymbolRangeInclusive) => true,
+      token => {
+        return Err(diagnostic::Diagnostic {
+          message: match token {
+            Some(token) => format!("unexpected token `{}`, expected range", token),
+            None => String::from("unexpected end of input, expected range"),
+          },
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(self.get_span()),
+          notes: vec![],
+        })
+      }
+    };
+
+    self.skip();
+
+    let range_end = self.parse_expr_with(false)?;
+    let body = self.parse_block()?;
+
+    Ok(block::ForStmt {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      label,
+      iterator_name,
+      range_start,
+      range_end,
+      is_inclusive,
+      body,
+    })
+  }
+
+  pub fn parse_break_stmt(&mut self) -> ParserResult<block::BreakStmt> {
+    let span_start = self.get_span();
+
+    skip_past!(self, token::Token::KeywordBreak);
+
+    let label = self.parse_loop_control_label()?;
+
+    Ok(block::BreakStmt {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      label,
+    })
+  }
+
+  pub fn parse_continue_stmt(&mut self) -> ParserResult<block::ContinueStmt> {
+    let span_start = self.get_span();
+
+    skip_past!(self, token::Token::KeywordContinue);
+
+    let label = self.parse_loop_control_label()?;
+
+    Ok(block::ContinueStmt {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      label,
+    })
+  }
+
+  pub fn parse_expr_stmt(&mut self) -> ParserResult<block::ExprStmt> {
+    let span_start = self.get_span();
+
+    let expr = self.parse_expr()?;
+
+    skip_past!(self, token::Token::SymbolSemiColon);
+
+    Ok(block::ExprStmt {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      expr,
+    })
+  }
+
+  // Parse a statement which starts with an expression, which is
+  // either an assignment to that expression, or the expression
+  // alone.
+  fn parse_expr_or_assign_stmt(&mut self) -> ParserResult<block::AnyStatementNode> {
+    let span_start = self.get_span();
+
+    let expr = self.parse_expr()?;
+
+    if !self.is(token::Token::SymbolEqual) {
+      // Expressions ending with a block (ex. `match`) need not be
+      // followed by a semicolon.
+      match expr {
+        node::AnyExprNode::MatchExpr(_) if !self.is(token::Token::SymbolSemiColon) => {}
+        _ => {
+          skip_past!(self, token::Token::SymbolSemiColon);
+        }
+      };
+
+      return Ok(block::AnyStatementNode::ExprStmt(block::ExprStmt {
+        id: self.node_ids.allocate(),
+        span: self.get_span_from(span_start),
+        expr,
+      }));
+    }
+
+    self.skip();
+
+    let value = self.parse_expr()?;
+
+    skip_past!(self, token::Token::SymbolSemiColon);
+
+    Ok(block::AnyStatementNode::AssignStmt(block::AssignStmt {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      target: expr,
+      value,
+    }))
+  }
+
+  pub fn parse_let_stmt(&mut self) -> ParserResult<block::LetStmt> {
+    let span_start = self.get_span();
+
+    skip_past!(self, token::Token::KeywordLet);
+
+    let mut is_mutable = false;
+
+    if self.is(token::Token::KeywordMut) {
+      is_mutable = true;
+      self.skip();
+    }
+
+    let name = self.parse_name()?;
+    let mut kind_group = None;
+
+    if self.is(token::Token::SymbolColon) {
+      self.skip();
+      kind_group = Some(self.parse_kind_group()?);
+    }
+
+    skip_past!(self, token::Token::SymbolEqual);
+
+    let value = self.parse_expr()?;
+
+    skip_past!(self, token::Token::SymbolSemiColon);
+
+    Ok(block::LetStmt {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      name,
+      is_mutable,
+      kind_group,
+      value,
+    })
+  }
+
+  pub fn parse_call_expr(&mut self) -> ParserResult<node::CallExpr> {
+    let callee = self.parse_path()?;
+
+    self.parse_call_expr_with(callee)
+  }
+
+  // Parse the arguments of a call, whose callee was already parsed.
+  fn parse_call_expr_with(&mut self, callee: node::Path) -> ParserResult<node::CallExpr> {
+    skip_past!(self, token::Token::SymbolParenthesesL);
+
+    let mut arguments = vec![];
+
+    while !self.is(token::Token::SymbolParenthesesR) && !self.is_eof() {
+      arguments.push(self.parse_expr_with(true)?);
+
+      if !self.is(token::Token::SymbolComma) {
+        break;
+      }
+
+      self.skip();
+    }
+
+    skip_past!(self, token::Token::SymbolParenthesesR);
+
+    Ok(node::CallExpr {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(callee.span),
+      callee,
+      arguments,
+    })
+  }
+
+  pub fn parse_struct_literal(&mut self) -> ParserResult<node::StructLiteral> {
+    let path = self.parse_path()?;
+
+    self.parse_struct_literal_with(path)
+  }
+
+  // Parse the fields of a struct literal, whose path was already
+  // parsed.
+  fn parse_struct_literal_with(&mut self, path: node::Path) -> ParserResult<node::StructLiteral> {
+    skip_past!(self, token::Token::SymbolBraceL);
+
+    let mut fields = vec![];
+
+    // Fields are separated by commas, and may be followed by one.
+    while !self.is(token::Token::SymbolBraceR) && !self.is_eof() {
+      let name = self.parse_name()?;
+
+      skip_past!(self, token::Token::SymbolColon);
+
+      fields.push((name, self.parse_expr_with(true)?));
+
+      if !self.is(token::Token::SymbolComma) {
+        break;
+      }
+
+      self.skip();
+    }
+
+    skip_past!(self, token::Token::SymbolBraceR);
+
+    Ok(node::StructLiteral {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(path.span),
+      path,
+      fields,
+    })
+  }
+
+  // Parse an expression, allowing or disallowing struct literals
+  // within it (outside of any parentheses or braces).
+  fn parse_expr_with(
+    &mut self,
+    is_struct_literal_allowed: bool,
+  ) -> ParserResult<node::AnyExprNode> {
+    let was_struct_literal_allowed = std::mem::replace(
+      &mut self.is_struct_literal_allowed,
+      is_struct_literal_allowed,
+    );
+
+    let expr = self.parse_expr();
+
+    self.is_struct_literal_allowed = was_struct_literal_allowed;
+
+    expr
+  }
+
+  pub fn parse_expr(&mut self) -> ParserResult<node::AnyExprNode> {
+    let span_start = self.get_span();
+
+    // Borrows and dereferences apply to the whole expression that
+    // follows them, including its field accesses and indexing (ex.
+    // `&a.b` borrows `a.b`).
+    if self.is(token::Token::SymbolAmpersand) {
+      self.skip();
+
+      let is_mutable = self.is(token::Token::KeywordMut);
+
+      if is_mutable {
+        self.skip();
+      }
+
+      let value = self.parse_expr()?;
+
+      return Ok(node::AnyExprNode::BorrowExpr(node::BorrowExpr {
+        id: self.node_ids.allocate(),
+        span: self.get_span_from(span_start),
+        is_mutable,
+        value: Box::new(value),
+      }));
+    } else if self.is(token::Token::SymbolAsterisk) {
+      self.skip();
+
+      let value = self.parse_expr()?;
+
+      return Ok(node::AnyExprNode::DerefExpr(node::DerefExpr {
+        id: self.node_ids.allocate(),
+        span: self.get_span_from(span_start),
+        value: Box::new(value),
+      }));
+    }
+
+    let mut expr = self.parse_primary_expr()?;
+
+    // Field accesses and indexing may be chained (ex. `a.b[0].c`).
+    loop {
+      if self.is(token::Token::SymbolBracketL) {
+        self.skip();
+
+        let index = self.parse_expr_with(true)?;
+
+        skip_past!(self, token::Token::SymbolBracketR);
+
+        expr = node::AnyExprNode::IndexExpr(node::IndexExpr {
+          id: self.node_ids.allocate(),
+          span: self.get_span_from(span_start),
+          base: Box::new(expr),
+          index: Box::new(index),
+        });
+
+        continue;
+      } else if !self.is(token::Token::SymbolDot) {
+        break;
+      }
+
+      self.skip();
+
+      let field_name = self.parse_name()?;
+
+      // The length of an array or a slice reads like a call (ex.
+      // `a.len()`), although there are no methods.
+      if field_name == "len" && self.is(token::Token::SymbolParenthesesL) {
+        self.skip();
+
+        skip_past!(self, token::Token::SymbolParenthesesR);
+
+        expr = node::AnyExprNode::LenExpr(node::LenExpr {
+          id: self.node_ids.allocate(),
+          span: self.get_span_from(span_start),
+          base: Box::new(expr),
+        });
+
+        continue;
+      }
+
+      expr = node::AnyExprNode::FieldAccessExpr(node::FieldAccessExpr {
+        id: self.node_ids.allocate(),
+        span: self.get_span_from(span_start),
+        base: Box::new(expr),
+        field_name,
+      });
+    }
+
+    Ok(expr)
+  }
+
+  // Parse an array literal (ex. `[1, 2, 3]`), whose elements may be
+  // followed by a comma.
+  pub fn parse_array_literal(&mut self) -> ParserResult<node::ArrayLiteral> {
+    let span_start = self.get_span();
+
+    skip_past!(self, token::Token::SymbolBracketL);
+
+    let mut elements = vec![];
+
+    while !self.is(token::Token::SymbolBracketR) && !self.is_eof() {
+      elements.push(self.parse_expr_with(true)?);
+
+      if !self.is(token::Token::SymbolComma) {
+        break;
+      }
+
+      self.skip();
+    }
+
+    skip_past!(self, token::Token::SymbolBracketR);
+
+    Ok(node::ArrayLiteral {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      elements,
+    })
+  }
+
+  fn parse_primary_expr(&mut self) -> ParserResult<node::AnyExprNode> {
+    // TODO: Support for more expressions.
+    Ok(match self.tokens.get(self.index) {
+      Some(token::Token::Identifier(_))
+        if self.peek_is(token::Token::SymbolParenthesesL)
+          || self.peek_is(token::Token::SymbolDoubleColon)
+          || (self.is_struct_literal_allowed && self.peek_is(token::Token::SymbolBraceL)) =>
+      {
+        let path = self.parse_path()?;
+
+        if self.is_struct_literal_allowed && self.is(token::Token::SymbolBraceL) {
+          node::AnyExprNode::StructLiteral(self.parse_struct_literal_with(path)?)
+        } else if self.is(token::Token::SymbolParenthesesL) {
+          node::AnyExprNode::CallExpr(self.parse_call_expr_with(path)?)
+        } else {
+          // A path which is neither called nor followed by fields
+          // leads to a variant without fields (ex. `Shape::Empty`).
+          node::AnyExprNode::VariantLiteral(node::VariantLiteral {
+            id: self.node_ids.allocate(),
+            span: path.span,
+            path,
+            arguments: vec![],
+          })
+        }
+      }
+      Some(token::Token::KeywordMatch) => node::AnyExprNode::MatchExpr(self.parse_match_expr()?),
+      Some(token::Token::SymbolBracketL) => {
+        node::AnyExprNode::ArrayLiteral(self.parse_array_literal()?)
+      }
+      Some(token::Token::Identifier(_)) => {
+        let span = self.get_span();
+
+        node::AnyExprNode::VariableRef(node::VariableRef {
+          id: self.node_ids.allocate(),
+          span,
+          name: self.parse_name()?,
+        })
+      }
+      _ => node::AnyExprNode::Literal(self.parse_literal()?),
+    })
+  }
+
+  pub fn parse_pattern(&mut self) -> ParserResult<pattern::AnyPatternNode> {
+    let span_start = self.get_span();
+
+    Ok(match self.tokens.get(self.index) {
+      Some(token::Token::Identifier(name)) if name == "_" => {
+        self.skip();
+
+        pattern::AnyPatternNode::WildcardPattern(pattern::WildcardPattern {
+          id: self.node_ids.allocate(),
+          span: span_start,
+        })
+      }
+      // A name alone binds the value, unless it leads to a variant
+      // (ex. `Shape::Empty`, or `Circle(r)`).
+      Some(token::Token::Identifier(_))
+        if self.peek_is(token::Token::SymbolDoubleColon)
+          || self.peek_is(token::Token::SymbolParenthesesL) =>
+      {
+        let path = self.parse_path()?;
+        let mut fields = vec![];
+
+        if self.is(token::Token::SymbolParenthesesL) {
+          self.skip();
+
+          while !self.is(token::Token::SymbolParenthesesR) && !self.is_eof() {
+            fields.push(self.parse_pattern()?);
+
+            if !self.is(token::Token::SymbolComma) {
+              break;
+            }
+
+            self.skip();
+          }
+
+          skip_past!(self, token::Token::SymbolParenthesesR);
+        }
+
+        pattern::AnyPatternNode::VariantPattern(pattern::VariantPattern {
+          id: self.node_ids.allocate(),
+          span: self.get_span_from(span_start),
+          path,
+          fields,
+        })
+      }
+      Some(token::Token::Identifier(_)) => {
+        pattern::AnyPatternNode::BindingPattern(pattern::BindingPattern {
+          id: self.node_ids.allocate(),
+          span: span_start,
+          name: self.parse_name()?,
+        })
+      }
+      _ => pattern::AnyPatternNode::LiteralPattern(self.parse_literal()?),
+    })
+  }
+
+  pub fn parse_match_arm(&mut self) -> ParserResult<node::MatchArm> {
+    let span_start = self.get_span();
+
+    let pattern = self.parse_pattern()?;
+
+    skip_past!(self, token::Token::SymbolFatArrow);
+
+    let body = match self.is(token::Token::SymbolBraceL) {
+      true => node::MatchArmBody::Block(self.parse_block()?),
+      false => node::MatchArmBody::Expr(self.parse_expr_with(true)?),
+    };
+
+    Ok(node::MatchArm {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      pattern,
+      body,
+    })
+  }
+
+  pub fn parse_match_expr(&mut self) -> ParserResult<node::MatchExpr> {
+    let span_start = self.get_span();
+
+    skip_past!(self, token::Token::KeywordMatch);
+
+    let subject = self.parse_expr_with(false)?;
+
+    skip_past!(self, token::Token::SymbolBraceL);
+
+    let mut arms = vec![];
+
+    // Arms are separated by commas, which may be omitted after arms
+    // whose body is a block.
+    while !self.is(token::Token::SymbolBraceR) && !self.is_eof() {
+      let arm = self.parse_match_arm()?;
+      let is_block = matches!(arm.body, node::MatchArmBody::Block(_));
+
+      arms.push(arm);
+
+      if self.is(token::Token::SymbolComma) {
+        self.skip();
+      } else if !is_block {
+        break;
+      }
+    }
+
+    skip_past!(self, token::Token::SymbolBraceR);
+
+    Ok(node::MatchExpr {
+      id: self.node_ids.allocate(),
+      span: self.get_span_from(span_start),
+      subject: Box::new(subject),
+      arms,
+    })
+  }
+
+  pub fn parse_bool_literal(&mut self) -> ParserResult<node::BoolLiteral> {
+    Ok(match self.tokens[self.index] {
+      token::Token::LiteralBool(value) => {
+        let span = self.get_span();
+
+        self.skip();
+
+        node::BoolLiteral {
+          id: self.node_ids.allocate(),
+          span,
+          value,
+        }
+      }
+      // TODO: Better error.
+      _ => {
+        return Err(diagnostic::Diagnostic {
+          message: String::from("unexpected token, expected boolean literal"),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(self.get_span()),
+          notes: vec![],
+        })
+      }
+    })
+  }
+
+  pub fn parse_int_literal(&mut self) -> ParserResult<node::IntLiteral> {
+    Ok(match &self.tokens[self.index] {
+      token::Token::LiteralInt(digits) => {
+        let value = digits.iter().collect::<String>();
+        let span = self.get_span();
+
+        self.skip();
+
+        node::IntLiteral {
+          id: self.node_ids.allocate(),
+          span,
+          value,
+        }
+      }
+      _ => {
+        return Err(diagnostic::Diagnostic {
+          message: String::from("unexpected token, expected integer literal"),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(self.get_span()),
+          notes: vec![],
+        })
+      }
+    })
+  }
+
+  pub fn parse_literal(&mut self) -> ParserResult<node::AnyLiteralNode> {
+    Ok(match self.tokens[self.index] {
+      token::Token::LiteralBool(_) => node::AnyLiteralNode::BoolLiteral(self.parse_bool_literal()?),
+      token::Token::LiteralInt(_) => node::AnyLiteralNode::IntLiteral(self.parse_int_literal()?),
+      _ => {
+        return Err(diagnostic::Diagnostic {
+          message: String::from("unexpected token, expected literal"),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(self.get_span()),
+          notes: vec![],
+        })
+      }
+    })
+  }
+}
+
+// Parse the given source, which must consist of a single namespace,
+// along with the comments within it.
+pub fn parse_source_with_comments(
+  source: &str,
+) -> ParserResult<(namespace::Namespace, Vec<lexer::Comment>)> {
+  let mut lexer = lexer::Lexer::new(source.chars().collect());
+
+  lexer.read_char();
+
+  let (tokens, spans) = lexer.collect_with_spans();
+  let mut parser = Parser::with_spans(tokens, spans);
+  let namespace = parser.parse_namespace();
+
+  // The tokens end where the lexer stopped, so the error which
+  // stopped it takes precedence over whatever the parser made of
+  // the truncated input.
+  if let Some(error) = lexer.get_error() {
+    return Err(error.clone());
+  }
+
+  let namespace = namespace?;
+
+  parser.expect_eof()?;
+
+  Ok((namespace, lexer.get_comments().to_vec()))
+}
+
+// Parse the given source, which must consist of a single namespace.
+pub fn parse_source(source: &str) -> ParserResult<namespace::Namespace> {
+  Ok(parse_source_with_comments(source)?.0)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::lexer;
+
+  #[test]
+  fn parser_proper_initial_values() {
+    let parser = Parser::new(vec![]);
+
+    assert_eq!(0, parser.index);
+  }
 
   #[test]
   fn parser_is() {
     let parser = Parser::new(vec![token::Token::KeywordFn]);
 
-    assert_eq!(true, parser.is(token::Token::KeywordFn));
+    assert_eq!(true, parser.is(token::Token::KeywordFn));
+  }
+
+  #[test]
+  fn parser_is_empty() {
+    let parser = Parser::new(vec![]);
+
+    assert_eq!(false, parser.is(token::Token::KeywordFn));
+  }
+
+  #[test]
+  fn parser_skip() {
+    let mut parser = Parser::new(vec![token::Token::KeywordFn, token::Token::KeywordFn]);
+
+    parser.skip();
+    assert_eq!(1, parser.index);
+  }
+
+  #[test]
+  fn parser_skip_out_of_bounds() {
+    let mut parser = Parser::new(vec![token::Token::KeywordFn]);
+
+    parser.skip();
+    assert_eq!(0, parser.index);
+  }
+
+  #[test]
+  fn parser_is_eof() {
+    let mut parser = Parser::new(vec![]);
+
+    assert_eq!(true, parser.is_eof());
+    parser.tokens.push(token::Token::KeywordFn);
+    assert_eq!(true, parser.is_eof());
+    parser.tokens.push(token::Token::KeywordFn);
+    assert_eq!(false, parser.is_eof());
+    parser.skip();
+    assert_eq!(true, parser.is_eof());
+  }
+
+  #[test]
+  fn parser_parse_name() {
+    let mut parser = Parser::new(vec![token::Token::Identifier(String::from("foo"))]);
+    let name = parser.parse_name();
+
+    assert_eq!(true, name.is_ok());
+    assert_eq!("foo", name.ok().unwrap().as_str());
+  }
+
+  #[test]
+  fn parser_parse_block() {
+    let mut parser = Parser::new(vec![token::Token::SymbolBraceL, token::Token::SymbolBraceR]);
+    let block = parser.parse_block();
+
+    assert_eq!(true, block.is_ok());
+  }
+
+  #[test]
+  fn parser_parse_int_kind() {
+    let mut parser = Parser::new(vec![token::Token::TypeInt32]);
+    let int_kind = parser.parse_int_kind();
+
+    assert_eq!(true, int_kind.is_ok());
+    assert_eq!(int_kind.ok().unwrap().size, int_kind::IntSize::Signed32);
+  }
+
+  #[test]
+  fn parse_void_kind() {
+    let mut parser = Parser::new(vec![token::Token::TypeVoid]);
+    let void_kind = parser.parse_void_kind();
+
+    assert_eq!(true, void_kind.is_ok());
+  }
+
+  #[test]
+  fn parser_parse_namespace() {
+    let mut parser = Parser::new(vec![
+      token::Token::KeywordNamespace,
+      token::Token::Identifier(String::from("test")),
+      token::Token::SymbolBraceL,
+      token::Token::SymbolBraceR,
+    ]);
+
+    let namespace = parser.parse_namespace();
+
+    assert_eq!(true, namespace.is_ok());
+    assert_eq!(String::from("test"), namespace.unwrap().name);
+  }
+
+  #[test]
+  fn parser_parse_namespace_declaration_order() {
+    let namespace = parse_source(
+      "namespace test { fn zeta() ~ void {} extern alpha() ~ void; fn mu() ~ void {} }",
+    );
+
+    assert_eq!(true, namespace.is_ok());
+
+    assert_eq!(
+      vec!["zeta", "alpha", "mu"],
+      namespace
+        .unwrap()
+        .symbol_table
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+    );
+  }
+
+  #[test]
+  fn parser_parse_namespace_visibility() {
+    let namespace = parse_source(
+      "namespace test { pub fn foo() ~ void {} pub extern bar() ~ void; extern baz() ~ void; }",
+    );
+
+    assert_eq!(true, namespace.is_ok());
+
+    let namespace = namespace.unwrap();
+
+    assert_eq!(
+      true,
+      matches!(
+        namespace.symbol_table.get("foo"),
+        Some(namespace::TopLevelNode::Function(function::Function {
+          is_public: true,
+          ..
+        }))
+      )
+    );
+
+    assert_eq!(
+      true,
+      matches!(
+        namespace.symbol_table.get("bar"),
+        Some(namespace::TopLevelNode::External(external::External {
+          is_public: true,
+          ..
+        }))
+      )
+    );
+
+    assert_eq!(
+      true,
+      matches!(
+        namespace.symbol_table.get("baz"),
+        Some(namespace::TopLevelNode::External(external::External {
+          is_public: false,
+          ..
+        }))
+      )
+    );
+
+    // The visibility modifier must precede a top-level node.
+    assert_eq!(
+      true,
+      parse_source("namespace test { pub return; }").is_err()
+    );
+  }
+
+  #[test]
+  fn parser_parse_namespace_redefinition() {
+    let namespace =
+      parse_source("namespace test { fn foo() ~ void {} fn bar() ~ void {} fn foo() ~ void {} }");
+
+    let diagnostic = match namespace {
+      Err(diagnostic) => diagnostic,
+      _ => panic!("expected a redefinition error"),
+    };
+
+    assert_eq!("`foo` is defined more than once", diagnostic.message);
+    assert_eq!(Some(span::Span { start: 55, end: 73 }), diagnostic.span);
+    assert_eq!(1, diagnostic.notes.len());
+    assert_eq!(span::Span { start: 17, end: 35 }, diagnostic.notes[0].span);
+  }
+
+  #[test]
+  fn parser_parse_namespace_extern_and_definition() {
+    let namespace = parse_source(
+      "namespace test { extern foo(a: &i32) ~ bool; fn bar() ~ void {} \
+       fn foo(b: &i32) ~ bool { return true; } }",
+    );
+
+    assert_eq!(true, namespace.is_ok());
+
+    let namespace = namespace.unwrap();
+
+    // The definition takes the place of its declaration.
+    assert_eq!(
+      vec!["foo", "bar"],
+      namespace
+        .symbol_table
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+    );
+
+    assert_eq!(
+      true,
+      matches!(
+        namespace.symbol_table.get("foo"),
+        Some(namespace::TopLevelNode::Function(_))
+      )
+    );
+
+    // The order of the declaration and the definition is irrelevant.
+    assert_eq!(
+      true,
+      parse_source("namespace test { fn foo() ~ void {} extern foo() ~ void; }").is_ok()
+    );
+  }
+
+  #[test]
+  fn parser_parse_namespace_conflicting_prototypes() {
+    for source in [
+      "namespace test { extern foo(a: i32) ~ void; fn foo(a: &i32) ~ void {} }",
+      "namespace test { extern foo(a: i32) ~ void; fn foo(a: i32) ~ i8 { return 0; } }",
+      "namespace test { extern foo(a: i32, ...) ~ void; fn foo(a: i32) ~ void {} }",
+      "namespace test { extern foo() ~ void; extern foo() ~ void; }",
+    ] {
+      let namespace = parse_source(source);
+
+      assert_eq!(
+        true,
+        matches!(namespace, Err(diagnostic) if diagnostic.notes.len() == 1),
+        "{}",
+        source
+      );
+    }
+  }
+
+  #[test]
+  fn parser_parse_namespace_conflicting_visibility() {
+    let namespace = parse_source("namespace test { pub extern foo() ~ void; fn foo() ~ void {} }");
+
+    assert_eq!(true, namespace.is_err());
+
+    let diagnostic = namespace.err().unwrap();
+
+    assert_eq!(
+      "visibility of `foo` conflicts with its previous declaration",
+      diagnostic.message
+    );
+
+    assert_eq!(1, diagnostic.notes.len());
+
+    // The order of the declaration and the definition is irrelevant.
+    assert_eq!(
+      true,
+      parse_source("namespace test { fn foo() ~ void {} pub extern foo() ~ void; }").is_err()
+    );
+
+    assert_eq!(
+      true,
+      parse_source("namespace test { pub extern foo() ~ void; pub fn foo() ~ void {} }").is_ok()
+    );
+  }
+
+  #[test]
+  fn parser_parse_namespace_nested() {
+    let namespace = parse_source(
+      "namespace foo { use bar::{baz, qux::quux as corge}; use grault; \
+       namespace bar { pub fn baz() ~ void {} } }",
+    );
+
+    assert_eq!(true, namespace.is_ok());
+
+    let namespace = namespace.unwrap();
+
+    assert_eq!(2, namespace.uses.len());
+    assert_eq!(true, namespace.uses[0].is_group);
+    assert_eq!(vec!["bar"], namespace.uses[0].prefix);
+    assert_eq!("baz", namespace.uses[0].items[0].get_name());
+    assert_eq!("corge", namespace.uses[0].items[1].get_name());
+
+    assert_eq!(
+      vec!["bar", "qux", "quux"],
+      namespace.uses[0].get_item_path(&namespace.uses[0].items[1])
+    );
+
+    assert_eq!(false, namespace.uses[1].is_group);
+    assert_eq!("grault", namespace.uses[1].items[0].get_name());
+
+    assert_eq!(
+      true,
+      matches!(
+        namespace.symbol_table.get("bar"),
+        Some(namespace::TopLevelNode::Namespace(bar)) if bar.symbol_table.contains_key("baz")
+      )
+    );
+
+    // A namespace's name may not be shared by another top-level node.
+    assert_eq!(
+      true,
+      parse_source("namespace foo { namespace bar {} fn bar() ~ void {} }").is_err()
+    );
+  }
+
+  #[test]
+  fn parser_parse_function_attributes() {
+    let namespace = parse_source(
+      "namespace test { #[no_mangle] fn foo() ~ void {} \
+       #[export_name = \"baz\"] pub fn bar() ~ void {} }",
+    );
+
+    assert_eq!(true, namespace.is_ok());
+
+    let namespace = namespace.unwrap();
+
+    assert_eq!(
+      true,
+      matches!(
+        namespace.symbol_table.get("foo"),
+        Some(namespace::TopLevelNode::Function(function)) if function.find_export_name() == Some("foo")
+      )
+    );
+
+    assert_eq!(
+      true,
+      matches!(
+        namespace.symbol_table.get("bar"),
+        Some(namespace::TopLevelNode::Function(function)) if function.find_export_name() == Some("baz")
+      )
+    );
+
+    for (source, message) in [
+      (
+        "namespace test { #[inline] fn foo() ~ void {} }",
+        "unknown attribute `inline`",
+      ),
+      (
+        "namespace test { #[no_mangle = \"foo\"] fn foo() ~ void {} }",
+        "attribute `no_mangle` does not take a value",
+      ),
+      (
+        "namespace test { #[export_name] fn foo() ~ void {} }",
+        "attribute `export_name` requires a value (ex. `#[export_name = \"foo\"]`)",
+      ),
+      (
+        "namespace test { #[export_name = \"\"] fn foo() ~ void {} }",
+        "the value of attribute `export_name` must not be empty",
+      ),
+      (
+        "namespace test { #[no_mangle] #[export_name = \"bar\"] fn foo() ~ void {} }",
+        "attribute `export_name` conflicts with attribute `no_mangle`",
+      ),
+      (
+        "namespace test { #[no_mangle] extern foo() ~ void; }",
+        "attributes may only be applied to functions",
+      ),
+    ] {
+      assert_eq!(
+        true,
+        matches!(parse_source(source), Err(diagnostic) if diagnostic.message == message),
+        "{}",
+        source
+      );
+    }
+  }
+
+  #[test]
+  fn parser_parse_struct_decl() {
+    let namespace = parse_source(
+      "namespace test { pub struct Point { x: i32, y: &mut a::b::C, } struct Empty {} }",
+    );
+
+    assert_eq!(true, namespace.is_ok());
+
+    let namespace = namespace.unwrap();
+
+    match namespace.symbol_table.get("Point") {
+      Some(namespace::TopLevelNode::Struct(struct_decl)) => {
+        assert_eq!(true, struct_decl.is_public);
+        assert_eq!(2, struct_decl.fields.len());
+        assert_eq!(Some(1), struct_decl.find_field("y").map(|(index, _)| index));
+
+        assert_eq!(
+          node::AnyKindNode::StructKind(struct_kind::StructKind {
+            path: vec![String::from("a"), String::from("b"), String::from("C")],
+          }),
+          struct_decl.fields[1].kind_group.kind
+        );
+
+        assert_eq!(true, struct_decl.fields[1].kind_group.is_mutable);
+      }
+      _ => panic!("expected struct `Point`"),
+    };
+
+    assert_eq!(
+      true,
+      matches!(
+        namespace.symbol_table.get("Empty"),
+        Some(namespace::TopLevelNode::Struct(struct_decl)) if struct_decl.fields.is_empty()
+      )
+    );
+
+    assert_eq!(
+      true,
+      matches!(
+        parse_source("namespace test { struct Point { x: i32, x: bool } }"),
+        Err(diagnostic) if diagnostic.message == "field `x` is declared more than once"
+          && diagnostic.notes.len() == 1
+      )
+    );
+  }
+
+  #[test]
+  fn parser_parse_struct_exprs() {
+    let namespace = parse_source(
+      "namespace test { fn f() ~ void { let mut a: Point = Point { x: 1, y: b.c }; \
+       a.x.y = f(Point {}); if a {} } }",
+    );
+
+    assert_eq!(true, namespace.is_ok());
+
+    let namespace = namespace.unwrap();
+
+    let statements = match namespace.symbol_table.get("f") {
+      Some(namespace::TopLevelNode::Function(function)) => &function.body.statements,
+      _ => panic!("expected function `f`"),
+    };
+
+    assert_eq!(3, statements.len());
+
+    assert_eq!(
+      true,
+      matches!(
+        &statements[0],
+        block::AnyStatementNode::LetStmt(block::LetStmt {
+          name,
+          is_mutable: true,
+          kind_group: Some(_),
+          value: node::AnyExprNode::StructLiteral(struct_literal),
+          ..
+        }) if name == "a" && struct_literal.fields.len() == 2
+      )
+    );
+
+    assert_eq!(
+      true,
+      matches!(
+        &statements[1],
+        block::AnyStatementNode::AssignStmt(block::AssignStmt {
+          target: node::AnyExprNode::FieldAccessExpr(node::FieldAccessExpr { base, field_name, .. }),
+          value: node::AnyExprNode::CallExpr(_),
+          ..
+        }) if field_name == "y" && matches!(**base, node::AnyExprNode::FieldAccessExpr(_))
+      )
+    );
+
+    // The brace following a condition starts the block, rather than
+    // a struct literal.
+    assert_eq!(
+      true,
+      matches!(
+        &statements[2],
+        block::AnyStatementNode::IfStmt(block::IfStmt {
+          condition: node::AnyExprNode::VariableRef(_),
+          ..
+        })
+      )
+    );
+  }
+
+  #[test]
+  fn parser_parse_enum_decl() {
+    let namespace = parse_source(
+      "namespace test { pub enum Shape { Circle(i32), Rect(i32, &Point), Empty, } enum Never {} }",
+    );
+
+    assert_eq!(true, namespace.is_ok());
+
+    let namespace = namespace.unwrap();
+
+    match namespace.symbol_table.get("Shape") {
+      Some(namespace::TopLevelNode::Enum(enum_decl)) => {
+        assert_eq!(true, enum_decl.is_public);
+        assert_eq!(3, enum_decl.variants.len());
+        assert_eq!(
+          Some(2),
+          enum_decl.find_variant("Empty").map(|(index, _)| index)
+        );
+        assert_eq!(2, enum_decl.variants[1].fields.len());
+        assert_eq!(true, enum_decl.variants[1].fields[1].is_reference);
+        assert_eq!(true, enum_decl.variants[2].fields.is_empty());
+      }
+      _ => panic!("expected enum `Shape`"),
+    };
+
+    assert_eq!(
+      true,
+      matches!(
+        namespace.symbol_table.get("Never"),
+        Some(namespace::TopLevelNode::Enum(enum_decl)) if enum_decl.variants.is_empty()
+      )
+    );
+
+    assert_eq!(
+      true,
+      matches!(
+        parse_source("namespace test { enum Shape { Empty, Empty(i32) } }"),
+        Err(diagnostic) if diagnostic.message == "variant `Empty` is declared more than once"
+          && diagnostic.notes.len() == 1
+      )
+    );
+  }
+
+  #[test]
+  fn parser_parse_arrays() {
+    let namespace = parse_source(
+      "namespace test { fn f(a: &[[i8; 4]], b: [Point; 2]) ~ void { \
+       let c = [a[0][1], b.len(),]; c[a.len()] = b[1].x; } }",
+    );
+
+    assert_eq!(true, namespace.is_ok());
+
+    let namespace = namespace.unwrap();
+
+    let function = match namespace.symbol_table.get("f") {
+      Some(namespace::TopLevelNode::Function(function)) => function,
+      _ => panic!("expected function `f`"),
+    };
+
+    assert_eq!(
+      vec!["[[i8; 4]]", "[Point; 2]"],
+      function
+        .prototype
+        .parameters
+        .iter()
+        .map(|(_, kind_group)| kind_group.kind.to_string())
+        .collect::<Vec<_>>()
+    );
+
+    assert_eq!(true, function.prototype.parameters[0].1.is_reference);
+
+    let statements = &function.body.statements;
+
+    assert_eq!(
+      true,
+      matches!(
+        &statements[0],
+        block::AnyStatementNode::LetStmt(block::LetStmt {
+          value: node::AnyExprNode::ArrayLiteral(array_literal),
+          ..
+        }) if matches!(array_literal.elements[0], node::AnyExprNode::IndexExpr(_))
+          && matches!(array_literal.elements[1], node::AnyExprNode::LenExpr(_))
+      )
+    );
+
+    assert_eq!(
+      true,
+      matches!(
+        &statements[1],
+        block::AnyStatementNode::AssignStmt(block::AssignStmt {
+          target: node::AnyExprNode::IndexExpr(_),
+          value: node::AnyExprNode::FieldAccessExpr(_),
+          ..
+        })
+      )
+    );
+
+    assert_eq!(
+      true,
+      matches!(
+        parse_source(
+          "namespace test { fn f(a: [i8; 99999999999999999999]) ~ void {} }"
+        ),
+        Err(diagnostic) if diagnostic.message == "array length `99999999999999999999` is too large"
+      )
+    );
+  }
+
+  #[test]
+  fn parser_parse_generic_function() {
+    let namespace = parse_source(
+      "namespace test { fn pick<T, U>(a: T, b: &U) ~ T { return a; } fn f() ~ void {} }",
+    );
+
+    assert_eq!(true, namespace.is_ok());
+
+    let namespace = namespace.unwrap();
+
+    let prototype = match namespace.symbol_table.get("pick") {
+      Some(namespace::TopLevelNode::Function(function)) => &function.prototype,
+      _ => panic!("expected function `pick`"),
+    };
+
+    assert_eq!(
+      vec!["T", "U"],
+      prototype
+        .generic_parameters
+        .iter()
+        .map(|generic_parameter| generic_parameter.name.as_str())
+        .collect::<Vec<_>>()
+    );
+    assert_eq!(2, prototype.parameters.len());
+
+    let prototype = match namespace.symbol_table.get("f") {
+      Some(namespace::TopLevelNode::Function(function)) => &function.prototype,
+      _ => panic!("expected function `f`"),
+    };
+
+    assert_eq!(false, prototype.is_generic());
   }
 
   #[test]
-  fn parser_is_empty() {
-    let parser = Parser::new(vec![]);
+  fn parser_parse_traits() {
+    let namespace = parse_source(
+      "namespace test { pub trait Shape { fn area(self: &Self) ~ i32; } \
+       impl Shape for Square { fn area(self: &Square) ~ i32 { return 1; } } \
+       fn total<T: Shape + a::Named>(s: &T, d: &dyn Shape) ~ void {} }",
+    );
 
-    assert_eq!(false, parser.is(token::Token::KeywordFn));
-  }
+    assert_eq!(true, namespace.is_ok());
 
-  #[test]
-  fn parser_skip() {
-    let mut parser = Parser::new(vec![token::Token::KeywordFn, token::Token::KeywordFn]);
+    let namespace = namespace.unwrap();
 
-    parser.skip();
-    assert_eq!(1, parser.index);
-  }
+    match namespace.symbol_table.get("Shape") {
+      Some(namespace::TopLevelNode::Trait(trait_decl)) => {
+        assert_eq!(true, trait_decl.is_public);
+        assert_eq!(true, trait_decl.find_method("area").is_some());
+      }
+      _ => panic!("expected trait `Shape`"),
+    };
 
-  #[test]
-  fn parser_skip_out_of_bounds() {
-    let mut parser = Parser::new(vec![token::Token::KeywordFn]);
+    // Implementations are kept apart from named top-level nodes.
+    assert_eq!(1, namespace.impls.len());
+    assert_eq!("<Square as Shape>", namespace.impls[0].get_name());
+    assert_eq!(2, namespace.symbol_table.len());
 
-    parser.skip();
-    assert_eq!(0, parser.index);
-  }
+    let prototype = match namespace.symbol_table.get("total") {
+      Some(namespace::TopLevelNode::Function(function)) => &function.prototype,
+      _ => panic!("expected function `total`"),
+    };
 
-  #[test]
-  fn parser_is_eof() {
-    let mut parser = Parser::new(vec![]);
+    assert_eq!(
+      "T: Shape + a::Named",
+      prototype.generic_parameters[0].to_string()
+    );
 
-    assert_eq!(true, parser.is_eof());
-    parser.tokens.push(token::Token::KeywordFn);
-    assert_eq!(true, parser.is_eof());
-    parser.tokens.push(token::Token::KeywordFn);
-    assert_eq!(false, parser.is_eof());
-    parser.skip();
-    assert_eq!(true, parser.is_eof());
+    assert_eq!("dyn Shape", prototype.parameters[1].1.kind.to_string());
+
+    // Methods of traits have no body.
+    assert_eq!(
+      true,
+      parse_source("namespace test { trait Shape { fn area() ~ i32 {} } }").is_err()
+    );
   }
 
   #[test]
-  fn parser_parse_name() {
-    let mut parser = Parser::new(vec![token::Token::Identifier(String::from("foo"))]);
-    let name = parser.parse_name();
+  fn parser_parse_references() {
+    let namespace = parse_source(
+      "namespace test { fn f(a: &mut Point) ~ void { let b = &mut a.x; *b = *&a[0]; } }",
+    );
 
-    assert_eq!(true, name.is_ok());
-    assert_eq!("foo", name.ok().unwrap().as_str());
-  }
+    assert_eq!(true, namespace.is_ok());
 
-  #[test]
-  fn parser_parse_block() {
-    let mut parser = Parser::new(vec![token::Token::SymbolBraceL, token::Token::SymbolBraceR]);
-    let block = parser.parse_block();
+    let namespace = namespace.unwrap();
 
-    assert_eq!(true, block.is_ok());
-  }
+    let function = match namespace.symbol_table.get("f") {
+      Some(namespace::TopLevelNode::Function(function)) => function,
+      _ => panic!("expected function `f`"),
+    };
 
-  #[test]
-  fn parser_parse_int_kind() {
-    let mut parser = Parser::new(vec![token::Token::TypeInt32]);
-    let int_kind = parser.parse_int_kind();
+    let statements = &function.body.statements;
+
+    // The borrow applies to the field access, not to `a` alone.
+    assert_eq!(
+      true,
+      matches!(
+        &statements[0],
+        block::AnyStatementNode::LetStmt(block::LetStmt {
+          value: node::AnyExprNode::BorrowExpr(node::BorrowExpr {
+            is_mutable: true,
+            value,
+            ..
+          }),
+          ..
+        }) if matches!(value.as_ref(), node::AnyExprNode::FieldAccessExpr(_))
+      )
+    );
+
+    let (target, value) = match &statements[1] {
+      block::AnyStatementNode::AssignStmt(assign_stmt) => (&assign_stmt.target, &assign_stmt.value),
+      _ => panic!("expected an assignment"),
+    };
 
-    assert_eq!(true, int_kind.is_ok());
-    assert_eq!(int_kind.ok().unwrap().size, int_kind::IntSize::Signed32);
+    assert_eq!(true, target.is_place());
+    assert_eq!(
+      true,
+      matches!(
+        value,
+        node::AnyExprNode::DerefExpr(node::DerefExpr { value, .. })
+          if matches!(
+            value.as_ref(),
+            node::AnyExprNode::BorrowExpr(node::BorrowExpr { is_mutable: false, .. })
+          )
+      )
+    );
   }
 
   #[test]
-  fn parse_void_kind() {
-    let mut parser = Parser::new(vec![token::Token::TypeVoid]);
-    let void_kind = parser.parse_void_kind();
+  fn parser_parse_match_expr() {
+    let namespace = parse_source(
+      "namespace test { fn f() ~ void { let a = match s { Shape::Circle(Size::Big(_), 1) => r, \
+       Shape::Empty => { return; } b => Shape::Empty, }; match a { true => f(), _ => {} } \
+       g(Shape::Circle(1)); } }",
+    );
 
-    assert_eq!(true, void_kind.is_ok());
-  }
+    assert_eq!(true, namespace.is_ok());
 
-  #[test]
-  fn parser_parse_namespace() {
-    let mut parser = Parser::new(vec![
-      token::Token::KeywordNamespace,
-      token::Token::Identifier(String::from("test")),
-      token::Token::SymbolBraceL,
-      token::Token::SymbolBraceR,
-    ]);
+    let namespace = namespace.unwrap();
 
-    let namespace = parser.parse_namespace();
+    let statements = match namespace.symbol_table.get("f") {
+      Some(namespace::TopLevelNode::Function(function)) => &function.body.statements,
+      _ => panic!("expected function `f`"),
+    };
 
-    assert_eq!(true, namespace.is_ok());
-    assert_eq!(String::from("test"), namespace.unwrap().name);
+    assert_eq!(3, statements.len());
+
+    let match_expr = match &statements[0] {
+      block::AnyStatementNode::LetStmt(block::LetStmt {
+        value: node::AnyExprNode::MatchExpr(match_expr),
+        ..
+      }) => match_expr,
+      _ => panic!("expected a `match` expression"),
+    };
+
+    assert_eq!(3, match_expr.arms.len());
+
+    assert_eq!(
+      true,
+      matches!(
+        &match_expr.arms[0].pattern,
+        pattern::AnyPatternNode::VariantPattern(pattern::VariantPattern { path, fields, .. })
+          if path.to_string() == "Shape::Circle"
+            && matches!(fields[0], pattern::AnyPatternNode::VariantPattern(_))
+            && matches!(fields[1], pattern::AnyPatternNode::LiteralPattern(_))
+      )
+    );
+
+    assert_eq!(
+      true,
+      matches!(
+        &match_expr.arms[1],
+        node::MatchArm {
+          pattern: pattern::AnyPatternNode::VariantPattern(pattern::VariantPattern { fields, .. }),
+          body: node::MatchArmBody::Block(_),
+          ..
+        } if fields.is_empty()
+      )
+    );
+
+    // Paths which are not called lead to variants without fields.
+    assert_eq!(
+      true,
+      matches!(
+        &match_expr.arms[2],
+        node::MatchArm {
+          pattern: pattern::AnyPatternNode::BindingPattern(_),
+          body: node::MatchArmBody::Expr(node::AnyExprNode::VariantLiteral(_)),
+          ..
+        }
+      )
+    );
+
+    // A `match` statement need not be followed by a semicolon.
+    assert_eq!(
+      true,
+      matches!(
+        &statements[1],
+        block::AnyStatementNode::ExprStmt(block::ExprStmt {
+          expr: node::AnyExprNode::MatchExpr(match_expr),
+          ..
+        }) if matches!(match_expr.arms[1].pattern, pattern::AnyPatternNode::WildcardPattern(_))
+      )
+    );
   }
 
   #[test]
@@ -585,5 +2836,406 @@ mod tests {
     assert_eq!(true, kind_group_value.is_mutable);
   }
 
+  #[test]
+  fn parse_if_stmt() {
+    let mut parser = Parser::new(vec![
+      token::Token::KeywordIf,
+      token::Token::LiteralBool(true),
+      token::Token::SymbolBraceL,
+      token::Token::SymbolBraceR,
+    ]);
+
+    let if_stmt_result = parser.parse_if_stmt();
+
+    assert_eq!(true, if_stmt_result.is_ok());
+
+    let if_stmt = if_stmt_result.unwrap();
+
+    assert_eq!(true, if_stmt.else_if_branches.is_empty());
+    assert_eq!(true, if_stmt.else_block.is_none());
+  }
+
+  #[test]
+  fn parse_if_stmt_else_if_else() {
+    let mut parser = Parser::new(vec![
+      token::Token::KeywordIf,
+      token::Token::LiteralBool(true),
+      token::Token::SymbolBraceL,
+      token::Token::SymbolBraceR,
+      token::Token::KeywordElse,
+      token::Token::KeywordIf,
+      token::Token::LiteralBool(false),
+      token::Token::SymbolBraceL,
+      token::Token::SymbolBraceR,
+      token::Token::KeywordElse,
+      token::Token::KeywordIf,
+      token::Token::LiteralBool(true),
+      token::Token::SymbolBraceL,
+      token::Token::SymbolBraceR,
+      token::Token::KeywordElse,
+      token::Token::SymbolBraceL,
+      token::Token::SymbolBraceR,
+    ]);
+
+    let if_stmt_result = parser.parse_if_stmt();
+
+    assert_eq!(true, if_stmt_result.is_ok());
+
+    let if_stmt = if_stmt_result.unwrap();
+
+    assert_eq!(2, if_stmt.else_if_branches.len());
+    assert_eq!(true, if_stmt.else_block.is_some());
+  }
+
+  #[test]
+  fn parse_while_stmt() {
+    let mut parser = Parser::new(vec![
+      token::Token::KeywordWhile,
+      token::Token::LiteralBool(true),
+      token::Token::SymbolBraceL,
+      token::Token::SymbolBraceR,
+    ]);
+
+    let while_stmt_result = parser.parse_while_stmt();
+
+    assert_eq!(true, while_stmt_result.is_ok());
+    assert_eq!(None, while_stmt_result.unwrap().label);
+  }
+
+  #[test]
+  fn parse_loop_stmt_labeled() {
+    let mut parser = Parser::new(vec![
+      token::Token::Label(String::from("outer")),
+      token::Token::SymbolColon,
+      token::Token::KeywordLoop,
+      token::Token::SymbolBraceL,
+      token::Token::KeywordBreak,
+      token::Token::Label(String::from("outer")),
+      token::Token::SymbolSemiColon,
+      token::Token::SymbolBraceR,
+    ]);
+
+    let loop_stmt_result = parser.parse_loop_stmt();
+
+    assert_eq!(true, loop_stmt_result.is_ok());
+
+    let loop_stmt = loop_stmt_result.unwrap();
+
+    assert_eq!(Some(String::from("outer")), loop_stmt.label);
+
+    assert_eq!(
+      true,
+      matches!(
+        loop_stmt.body.statements.as_slice(),
+        [block::AnyStatementNode::BreakStmt(block::BreakStmt {
+          label: Some(label),
+          ..
+        })] if label == "outer"
+      )
+    );
+  }
+
+  #[test]
+  fn parse_continue_stmt() {
+    let mut parser = Parser::new(vec![
+      token::Token::KeywordContinue,
+      token::Token::SymbolSemiColon,
+    ]);
+
+    let continue_stmt_result = parser.parse_continue_stmt();
+
+    assert_eq!(true, continue_stmt_result.is_ok());
+    assert_eq!(None, continue_stmt_result.unwrap().label);
+  }
+
+  #[test]
+  fn parse_for_stmt() {
+    let mut parser = Parser::new(vec![
+      token::Token::KeywordFor,
+      token::Token::Identifier(String::from("i")),
+      token::Token::KeywordIn,
+      token::Token::Identifier(String::from("a")),
+      token::Token::SymbolRange,
+      token::Token::Identifier(String::from("b")),
+      token::Token::SymbolBraceL,
+      token::Token::SymbolBraceR,
+    ]);
+
+    let for_stmt_result = parser.parse_for_stmt();
+
+    assert_eq!(true, for_stmt_result.is_ok());
+
+    let for_stmt = for_stmt_result.unwrap();
+
+    assert_eq!(String::from("i"), for_stmt.iterator_name);
+    assert_eq!(false, for_stmt.is_inclusive);
+
+    assert_eq!(
+      true,
+      matches!(
+        for_stmt.range_end,
+        node::AnyExprNode::VariableRef(node::VariableRef { ref name, .. }) if name == "b"
+      )
+    );
+  }
+
+  #[test]
+  fn parse_for_stmt_inclusive() {
+    let mut parser = Parser::new(vec![
+      token::Token::KeywordFor,
+      token::Token::Identifier(String::from("i")),
+      token::Token::KeywordIn,
+      token::Token::Identifier(String::from("a")),
+      token::Token::SymbolRangeInclusive,
+      token::Token::Identifier(String::from("b")),
+      token::Token::SymbolBraceL,
+      token::Token::SymbolBraceR,
+    ]);
+
+    let for_stmt_result = parser.parse_for_stmt();
+
+    assert_eq!(true, for_stmt_result.is_ok());
+    assert_eq!(true, for_stmt_result.unwrap().is_inclusive);
+  }
+
+  #[test]
+  fn parse_for_stmt_end_of_input() {
+    let mut parser = Parser::new(vec![
+      token::Token::KeywordFor,
+      token::Token::Identifier(String::from("i")),
+      token::Token::KeywordIn,
+      token::Token::LiteralInt(vec!['0']),
+    ]);
+
+    assert_eq!(
+      "unexpected end of input, expected range",
+      parser.parse_for_stmt().err().unwrap().message
+    );
+
+    let mut parser = Parser::new(vec![
+      token::Token::KeywordFor,
+      token::Token::Identifier(String::from("i")),
+      token::Token::KeywordIn,
+      token::Token::LiteralInt(vec!['0']),
+      token::Token::SymbolBraceL,
+    ]);
+
+    assert_eq!(
+      "unexpected token `SymbolBraceL`, expected range",
+      parser.parse_for_stmt().err().unwrap().message
+    );
+  }
+
+  #[test]
+  fn parse_call_expr() {
+    let mut parser = Parser::new(vec![
+      token::Token::Identifier(String::from("foo")),
+      token::Token::SymbolParenthesesL,
+      token::Token::LiteralBool(true),
+      token::Token::SymbolComma,
+      token::Token::Identifier(String::from("bar")),
+      token::Token::SymbolParenthesesL,
+      token::Token::SymbolParenthesesR,
+      token::Token::SymbolParenthesesR,
+    ]);
+
+    let call_expr_result = parser.parse_call_expr();
+
+    assert_eq!(true, call_expr_result.is_ok());
+
+    let call_expr = call_expr_result.unwrap();
+
+    assert_eq!(vec![String::from("foo")], call_expr.callee.segments);
+    assert_eq!(2, call_expr.arguments.len());
+
+    assert_eq!(
+      true,
+      matches!(
+        &call_expr.arguments[1],
+        node::AnyExprNode::CallExpr(node::CallExpr {
+          callee,
+          arguments,
+          ..
+        }) if callee.to_string() == "bar" && arguments.is_empty()
+      )
+    );
+  }
+
+  #[test]
+  fn parse_expr_stmt() {
+    let mut parser = Parser::new(vec![
+      token::Token::Identifier(String::from("foo")),
+      token::Token::SymbolParenthesesL,
+      token::Token::SymbolParenthesesR,
+      token::Token::SymbolSemiColon,
+    ]);
+
+    let expr_stmt_result = parser.parse_expr_stmt();
+
+    assert_eq!(true, expr_stmt_result.is_ok());
+
+    assert_eq!(
+      true,
+      matches!(
+        expr_stmt_result.unwrap().expr,
+        node::AnyExprNode::CallExpr(_)
+      )
+    );
+  }
+
+  #[test]
+  fn parse_expr_distinct_ids() {
+    let mut parser = Parser::new(vec![
+      token::Token::Identifier(String::from("foo")),
+      token::Token::SymbolParenthesesL,
+      token::Token::LiteralBool(true),
+      token::Token::SymbolComma,
+      token::Token::LiteralBool(true),
+      token::Token::SymbolParenthesesR,
+    ]);
+
+    let call_expr_result = parser.parse_call_expr();
+
+    assert_eq!(true, call_expr_result.is_ok());
+
+    let call_expr = call_expr_result.unwrap();
+
+    // Equal literals are still distinct nodes.
+    match call_expr.arguments.as_slice() {
+      [node::AnyExprNode::Literal(node::AnyLiteralNode::BoolLiteral(first)), node::AnyExprNode::Literal(node::AnyLiteralNode::BoolLiteral(second))] =>
+      {
+        assert_ne!(first.id, second.id);
+        assert_ne!(call_expr.id, first.id);
+      }
+      _ => panic!("expected two bool literal arguments"),
+    };
+  }
+
+  #[test]
+  fn parse_function_spans() {
+    let mut lexer = lexer::Lexer::new("fn foo() ~ void {\n  return;\n}".chars().collect());
+
+    lexer.read_char();
+
+    let (tokens, spans) = lexer.collect_with_spans();
+    let mut parser = Parser::with_spans(tokens, spans);
+    let function_result = parser.parse_function();
+
+    assert_eq!(true, function_result.is_ok());
+
+    let function = function_result.unwrap();
+
+    assert_eq!(span::Span { start: 0, end: 29 }, function.span);
+    assert_eq!(span::Span { start: 3, end: 15 }, function.prototype.span);
+    assert_eq!(span::Span { start: 16, end: 29 }, function.body.span);
+
+    assert_eq!(
+      Some(span::Span { start: 20, end: 27 }),
+      function.body.statements[0].as_node().get_span()
+    );
+  }
+
+  #[test]
+  fn parse_error_span() {
+    let mut parser = Parser::with_spans(
+      vec![
+        token::Token::KeywordFn,
+        token::Token::Identifier(String::from("foo")),
+        token::Token::SymbolBraceL,
+      ],
+      vec![
+        span::Span { start: 0, end: 2 },
+        span::Span { start: 3, end: 6 },
+        span::Span { start: 7, end: 8 },
+      ],
+    );
+
+    let function_result = parser.parse_function();
+
+    assert_eq!(true, function_result.is_err());
+
+    // The error points at the unexpected token.
+    assert_eq!(
+      Some(span::Span { start: 7, end: 8 }),
+      function_result.err().unwrap().span
+    );
+  }
+
+  #[test]
+  fn parse_kind_group_bool() {
+    let mut parser = Parser::new(vec![token::Token::TypeBool]);
+
+    let kind_group_result = parser.parse_kind_group();
+
+    assert_eq!(true, kind_group_result.is_ok());
+
+    assert_eq!(
+      node::AnyKindNode::BoolKind(bool_kind::BoolKind {}),
+      kind_group_result.unwrap().kind
+    );
+  }
+
+  #[test]
+  fn parse_return_stmt() {
+    let mut parser = Parser::new(vec![
+      token::Token::KeywordReturn,
+      token::Token::SymbolSemiColon,
+    ]);
+
+    let return_stmt_result = parser.parse_return_stmt();
+
+    assert_eq!(true, return_stmt_result.is_ok());
+    assert_eq!(true, return_stmt_result.unwrap().value.is_none());
+  }
+
+  #[test]
+  fn parse_return_stmt_value() {
+    let mut parser = Parser::new(vec![
+      token::Token::KeywordReturn,
+      token::Token::Identifier(String::from("foo")),
+      token::Token::SymbolParenthesesL,
+      token::Token::SymbolParenthesesR,
+      token::Token::SymbolSemiColon,
+    ]);
+
+    let return_stmt_result = parser.parse_return_stmt();
+
+    assert_eq!(true, return_stmt_result.is_ok());
+
+    assert_eq!(
+      true,
+      matches!(
+        return_stmt_result.unwrap().value,
+        Some(node::AnyExprNode::CallExpr(_))
+      )
+    );
+  }
+
+  #[test]
+  fn parse_int_literal() {
+    let mut parser = Parser::new(vec![token::Token::LiteralInt(vec!['4', '2'])]);
+
+    let int_literal_result = parser.parse_int_literal();
+
+    assert_eq!(true, int_literal_result.is_ok());
+    assert_eq!(String::from("42"), int_literal_result.unwrap().value);
+  }
+
+  #[test]
+  fn parser_parse_int_kind_sizes() {
+    for (token, size) in [
+      (token::Token::TypeInt8, int_kind::IntSize::Signed8),
+      (token::Token::TypeInt16, int_kind::IntSize::Signed16),
+      (token::Token::TypeInt64, int_kind::IntSize::Signed64),
+      (token::Token::TypeInt128, int_kind::IntSize::Signed128),
+    ] {
+      let mut parser = Parser::new(vec![token]);
+      let int_kind = parser.parse_int_kind();
+
+      assert_eq!(true, int_kind.is_ok());
+      assert_eq!(size, int_kind.unwrap().size);
+    }
+  }
+
   // TODO: Add missing tests (is_eof, etc.).
 }
@@ -1,6 +1,7 @@
 use crate::{
-  block, diagnostic, external, function, int_kind, namespace, node, pass_manager, prototype,
-  void_kind,
+  array_kind, block, bool_kind, diagnostic, dyn_kind, enum_decl, enum_kind, external, function,
+  generic_kind, impl_decl, int_kind, namespace, node, pass_manager, pattern, prototype,
+  reference_kind, slice_kind, struct_decl, struct_kind, trait_decl, use_decl, void_kind,
 };
 
 pub struct PassContext {
@@ -9,7 +10,22 @@ pub struct PassContext {
 
 pub type PassResult = Result<(), diagnostic::Diagnostic>;
 
-pub trait Pass<'a> {
+// Allows the provided methods of [`Pass`] to hand the pass over
+// to nodes as a trait object, whatever its concrete type.
+pub trait AsDynPass<'a> {
+  fn as_dyn_pass(&mut self) -> &mut dyn Pass<'a>;
+}
+
+impl<'a, T: Pass<'a>> AsDynPass<'a> for T {
+  fn as_dyn_pass(&mut self) -> &mut dyn Pass<'a> {
+    self
+  }
+}
+
+// By default, visiting a node visits all of its children. Passes
+// that override a `visit_*` method take over the traversal of that
+// node's children, and may resume it with `visit_children`.
+pub trait Pass<'a>: AsDynPass<'a> {
   fn register(&self, _: &pass_manager::PassManager) -> bool {
     return true;
   }
@@ -19,10 +35,18 @@ pub trait Pass<'a> {
   }
 
   fn visit(&mut self, node: &dyn node::Node) -> PassResult {
-    // TODO:
-    // node.accept(&self);
-    self.visit_children(node)?;
+    self.enter_node(node)?;
+    node.accept(self.as_dyn_pass())?;
+    self.leave_node(node)
+  }
 
+  // Invoked before a node and its children are visited.
+  fn enter_node(&mut self, _: &dyn node::Node) -> PassResult {
+    Ok(())
+  }
+
+  // Invoked after a node and its children have been visited.
+  fn leave_node(&mut self, _: &dyn node::Node) -> PassResult {
     Ok(())
   }
 
@@ -34,39 +58,195 @@ pub trait Pass<'a> {
     Ok(())
   }
 
-  fn visit_block(&mut self, _: &block::Block) -> PassResult {
-    Ok(())
+  fn visit_block(&mut self, block: &block::Block) -> PassResult {
+    self.visit_children(block)
   }
 
-  fn visit_function(&mut self, _: &function::Function) -> PassResult {
-    Ok(())
+  fn visit_function(&mut self, function: &function::Function) -> PassResult {
+    self.visit_children(function)
   }
 
-  fn visit_prototype(&mut self, _: &prototype::Prototype) -> PassResult {
-    Ok(())
+  fn visit_prototype(&mut self, prototype: &prototype::Prototype) -> PassResult {
+    self.visit_children(prototype)
   }
 
-  fn visit_int_kind(&mut self, _: &int_kind::IntKind) -> PassResult {
-    Ok(())
+  fn visit_int_kind(&mut self, int_kind: &int_kind::IntKind) -> PassResult {
+    self.visit_children(int_kind)
   }
 
-  fn visit_void_kind(&mut self, _: &void_kind::VoidKind) -> PassResult {
-    Ok(())
+  fn visit_void_kind(&mut self, void_kind: &void_kind::VoidKind) -> PassResult {
+    self.visit_children(void_kind)
   }
 
-  fn visit_namespace(&mut self, _: &namespace::Namespace) -> PassResult {
-    Ok(())
+  fn visit_bool_kind(&mut self, bool_kind: &bool_kind::BoolKind) -> PassResult {
+    self.visit_children(bool_kind)
   }
 
-  fn visit_external(&mut self, _: &external::External) -> PassResult {
-    Ok(())
+  fn visit_struct_kind(&mut self, struct_kind: &struct_kind::StructKind) -> PassResult {
+    self.visit_children(struct_kind)
   }
 
-  fn visit_return_stmt(&mut self, _: &block::ReturnStmt) -> PassResult {
-    Ok(())
+  fn visit_enum_kind(&mut self, enum_kind: &enum_kind::EnumKind) -> PassResult {
+    self.visit_children(enum_kind)
   }
 
-  fn visit_bool_literal(&mut self, _: &node::BoolLiteral) -> PassResult {
-    Ok(())
+  fn visit_array_kind(&mut self, array_kind: &array_kind::ArrayKind) -> PassResult {
+    self.visit_children(array_kind)
+  }
+
+  fn visit_slice_kind(&mut self, slice_kind: &slice_kind::SliceKind) -> PassResult {
+    self.visit_children(slice_kind)
+  }
+
+  fn visit_reference_kind(&mut self, reference_kind: &reference_kind::ReferenceKind) -> PassResult {
+    self.visit_children(reference_kind)
+  }
+
+  fn visit_generic_kind(&mut self, generic_kind: &generic_kind::GenericKind) -> PassResult {
+    self.visit_children(generic_kind)
+  }
+
+  fn visit_dyn_kind(&mut self, dyn_kind: &dyn_kind::DynKind) -> PassResult {
+    self.visit_children(dyn_kind)
+  }
+
+  fn visit_namespace(&mut self, namespace: &namespace::Namespace) -> PassResult {
+    self.visit_children(namespace)
+  }
+
+  fn visit_external(&mut self, external: &external::External) -> PassResult {
+    self.visit_children(external)
+  }
+
+  fn visit_use_decl(&mut self, use_decl: &use_decl::UseDecl) -> PassResult {
+    self.visit_children(use_decl)
+  }
+
+  fn visit_struct_decl(&mut self, struct_decl: &struct_decl::StructDecl) -> PassResult {
+    self.visit_children(struct_decl)
+  }
+
+  fn visit_enum_decl(&mut self, enum_decl: &enum_decl::EnumDecl) -> PassResult {
+    self.visit_children(enum_decl)
+  }
+
+  fn visit_trait_decl(&mut self, trait_decl: &trait_decl::TraitDecl) -> PassResult {
+    self.visit_children(trait_decl)
+  }
+
+  fn visit_impl_decl(&mut self, impl_decl: &impl_decl::ImplDecl) -> PassResult {
+    self.visit_children(impl_decl)
+  }
+
+  fn visit_return_stmt(&mut self, return_stmt: &block::ReturnStmt) -> PassResult {
+    self.visit_children(return_stmt)
+  }
+
+  fn visit_if_stmt(&mut self, if_stmt: &block::IfStmt) -> PassResult {
+    self.visit_children(if_stmt)
+  }
+
+  fn visit_while_stmt(&mut self, while_stmt: &block::WhileStmt) -> PassResult {
+    self.visit_children(while_stmt)
+  }
+
+  fn visit_loop_stmt(&mut self, loop_stmt: &block::LoopStmt) -> PassResult {
+    self.visit_children(loop_stmt)
+  }
+
+  fn visit_for_stmt(&mut self, for_stmt: &block::ForStmt) -> PassResult {
+    self.visit_children(for_stmt)
+  }
+
+  fn visit_break_stmt(&mut self, break_stmt: &block::BreakStmt) -> PassResult {
+    self.visit_children(break_stmt)
+  }
+
+  fn visit_continue_stmt(&mut self, continue_stmt: &block::ContinueStmt) -> PassResult {
+    self.visit_children(continue_stmt)
+  }
+
+  fn visit_expr_stmt(&mut self, expr_stmt: &block::ExprStmt) -> PassResult {
+    self.visit_children(expr_stmt)
+  }
+
+  fn visit_let_stmt(&mut self, let_stmt: &block::LetStmt) -> PassResult {
+    self.visit_children(let_stmt)
+  }
+
+  fn visit_assign_stmt(&mut self, assign_stmt: &block::AssignStmt) -> PassResult {
+    self.visit_children(assign_stmt)
+  }
+
+  fn visit_variable_ref(&mut self, variable_ref: &node::VariableRef) -> PassResult {
+    self.visit_children(variable_ref)
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &node::CallExpr) -> PassResult {
+    self.visit_children(call_expr)
+  }
+
+  fn visit_field_access_expr(&mut self, field_access_expr: &node::FieldAccessExpr) -> PassResult {
+    self.visit_children(field_access_expr)
+  }
+
+  fn visit_struct_literal(&mut self, struct_literal: &node::StructLiteral) -> PassResult {
+    self.visit_children(struct_literal)
+  }
+
+  fn visit_variant_literal(&mut self, variant_literal: &node::VariantLiteral) -> PassResult {
+    self.visit_children(variant_literal)
+  }
+
+  fn visit_match_expr(&mut self, match_expr: &node::MatchExpr) -> PassResult {
+    self.visit_children(match_expr)
+  }
+
+  fn visit_array_literal(&mut self, array_literal: &node::ArrayLiteral) -> PassResult {
+    self.visit_children(array_literal)
+  }
+
+  fn visit_index_expr(&mut self, index_expr: &node::IndexExpr) -> PassResult {
+    self.visit_children(index_expr)
+  }
+
+  fn visit_len_expr(&mut self, len_expr: &node::LenExpr) -> PassResult {
+    self.visit_children(len_expr)
+  }
+
+  fn visit_borrow_expr(&mut self, borrow_expr: &node::BorrowExpr) -> PassResult {
+    self.visit_children(borrow_expr)
+  }
+
+  fn visit_deref_expr(&mut self, deref_expr: &node::DerefExpr) -> PassResult {
+    self.visit_children(deref_expr)
+  }
+
+  fn visit_wildcard_pattern(&mut self, wildcard_pattern: &pattern::WildcardPattern) -> PassResult {
+    self.visit_children(wildcard_pattern)
+  }
+
+  fn visit_binding_pattern(&mut self, binding_pattern: &pattern::BindingPattern) -> PassResult {
+    self.visit_children(binding_pattern)
+  }
+
+  fn visit_variant_pattern(&mut self, variant_pattern: &pattern::VariantPattern) -> PassResult {
+    self.visit_children(variant_pattern)
+  }
+
+  fn visit_path(&mut self, path: &node::Path) -> PassResult {
+    self.visit_children(path)
+  }
+
+  fn visit_bool_literal(&mut self, bool_literal: &node::BoolLiteral) -> PassResult {
+    self.visit_children(bool_literal)
+  }
+
+  fn visit_int_literal(&mut self, int_literal: &node::IntLiteral) -> PassResult {
+    self.visit_children(int_literal)
+  }
+
+  fn visit_kind_group(&mut self, kind_group: &node::KindGroup) -> PassResult {
+    self.visit_children(kind_group)
   }
 }
@@ -1,7 +1,7 @@
 use crate::{diagnostic, node, pass};
 
 pub struct PassManager<'a> {
-  passes: Vec<Box<dyn pass::Pass<'a>>>,
+  passes: Vec<Box<dyn pass::Pass<'a> + 'a>>,
 }
 
 impl<'a> PassManager<'a> {
@@ -11,7 +11,7 @@ impl<'a> PassManager<'a> {
 
   // Register a pass to be run. Returns `true` if the pass'
   // restrictions are met.
-  pub fn add_pass(&mut self, pass: Box<dyn pass::Pass<'a>>) -> bool {
+  pub fn add_pass(&mut self, pass: Box<dyn pass::Pass<'a> + 'a>) -> bool {
     if !pass.register(self) {
       return false;
     }
@@ -46,6 +46,7 @@ impl<'a> PassManager<'a> {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::{block, function, loop_check_pass, namespace, prototype, span, void_kind};
 
   struct TestPassEmpty {
     //
@@ -65,14 +66,59 @@ mod tests {
     }
   }
 
+  // Records the depth at which nodes are entered and left, as
+  // diagnostics.
+  struct TestPassOrder {
+    diagnostics: Vec<diagnostic::Diagnostic>,
+    depth: usize,
+  }
+
+  impl TestPassOrder {
+    fn record(&mut self, event: &str) {
+      self.diagnostics.push(diagnostic::Diagnostic {
+        message: format!("{} {}", event, self.depth),
+        severity: diagnostic::DiagnosticSeverity::Warning,
+        span: None,
+        notes: vec![],
+      });
+    }
+  }
+
+  impl pass::Pass<'_> for TestPassOrder {
+    fn get_diagnostics(&self) -> Vec<diagnostic::Diagnostic> {
+      self.diagnostics.clone()
+    }
+
+    fn enter_node(&mut self, _: &dyn node::Node) -> pass::PassResult {
+      self.record("enter");
+      self.depth += 1;
+
+      Ok(())
+    }
+
+    fn leave_node(&mut self, _: &dyn node::Node) -> pass::PassResult {
+      self.depth -= 1;
+      self.record("leave");
+
+      Ok(())
+    }
+  }
+
   struct TestNode {
-    //
+    children: Vec<TestNode>,
   }
 
   impl node::Node for TestNode {
-    // TODO: Isn't this redundant?
-    fn accept(&mut self, _: &mut dyn pass::Pass) -> pass::PassResult {
-      Ok(())
+    fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+      pass.visit_children(self)
+    }
+
+    fn get_children(&self) -> Vec<&dyn node::Node> {
+      self
+        .children
+        .iter()
+        .map(|child| child as &dyn node::Node)
+        .collect()
     }
   }
 
@@ -98,4 +144,84 @@ mod tests {
 
     assert_eq!(true, pass_manager.passes.is_empty());
   }
+
+  #[test]
+  fn pass_manager_run_order() {
+    let mut pass_manager = PassManager::new();
+
+    pass_manager.add_pass(Box::new(TestPassOrder {
+      diagnostics: vec![],
+      depth: 0,
+    }));
+
+    let root_node = TestNode {
+      children: vec![
+        TestNode {
+          children: vec![TestNode { children: vec![] }],
+        },
+        TestNode { children: vec![] },
+      ],
+    };
+
+    let messages: Vec<String> = pass_manager
+      .run(&root_node)
+      .into_iter()
+      .map(|diagnostic| diagnostic.message)
+      .collect();
+
+    assert_eq!(
+      vec!["enter 0", "enter 1", "enter 2", "leave 2", "leave 1", "enter 1", "leave 1", "leave 0"],
+      messages
+    );
+  }
+
+  #[test]
+  fn pass_manager_run_loop_check_pass() {
+    let mut pass_manager = PassManager::new();
+    let mut namespace = namespace::Namespace::new(String::from("test"));
+    let node_ids = &namespace.node_ids;
+
+    let function = function::Function {
+      id: node_ids.allocate(),
+      span: span::Span::default(),
+      is_public: false,
+      attributes: vec![],
+      prototype: prototype::Prototype {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        name: String::from("foo"),
+        parameters: vec![],
+        is_variadic: false,
+        generic_parameters: vec![],
+        type_arguments: vec![],
+        return_kind_group: node::KindGroup {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          kind: node::AnyKindNode::VoidKind(void_kind::VoidKind {}),
+          is_reference: false,
+          is_mutable: false,
+        },
+      },
+      body: block::Block {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        statements: vec![block::AnyStatementNode::BreakStmt(block::BreakStmt {
+          id: node_ids.allocate(),
+          span: span::Span::default(),
+          label: None,
+        })],
+      },
+    };
+
+    namespace.symbol_table.insert(
+      String::from("foo"),
+      namespace::TopLevelNode::Function(function),
+    );
+
+    pass_manager.add_pass(Box::new(loop_check_pass::LoopCheckPass::new()));
+
+    // The `break` statement is only reached if the pass manager
+    // dispatches into the namespace's children.
+    assert_eq!(1, pass_manager.run(&namespace).len());
+  }
 }
@@ -0,0 +1,265 @@
+use crate::{
+  block, diagnostic, enum_decl, external, function, impl_decl, namespace, node, pass, pattern,
+  prototype, struct_decl, trait_decl,
+};
+
+// A pass which may rewrite the tree as it visits it. Nodes may be
+// modified in place, replaced (ex. by assigning to the statement
+// given to `visit_statement`), or inserted into and removed from
+// their parent (ex. the statements of the block given to
+// `visit_block`).
+//
+// By default, visiting a node visits all of its children. Passes
+// that override a `visit_*` method take over the traversal of that
+// node's children, and may resume it with the corresponding `walk_*`
+// function.
+pub trait PassMut {
+  fn get_diagnostics(&self) -> Vec<diagnostic::Diagnostic> {
+    vec![]
+  }
+
+  fn visit_namespace(&mut self, namespace: &mut namespace::Namespace) -> pass::PassResult {
+    walk_namespace(self, namespace)
+  }
+
+  fn visit_function(&mut self, function: &mut function::Function) -> pass::PassResult {
+    walk_function(self, function)
+  }
+
+  fn visit_external(&mut self, external: &mut external::External) -> pass::PassResult {
+    self.visit_prototype(&mut external.prototype)
+  }
+
+  fn visit_prototype(&mut self, prototype: &mut prototype::Prototype) -> pass::PassResult {
+    walk_prototype(self, prototype)
+  }
+
+  fn visit_struct_decl(&mut self, struct_decl: &mut struct_decl::StructDecl) -> pass::PassResult {
+    for field in &mut struct_decl.fields {
+      self.visit_kind_group(&mut field.kind_group)?;
+    }
+
+    Ok(())
+  }
+
+  fn visit_enum_decl(&mut self, enum_decl: &mut enum_decl::EnumDecl) -> pass::PassResult {
+    for variant in &mut enum_decl.variants {
+      for field in &mut variant.fields {
+        self.visit_kind_group(field)?;
+      }
+    }
+
+    Ok(())
+  }
+
+  fn visit_trait_decl(&mut self, trait_decl: &mut trait_decl::TraitDecl) -> pass::PassResult {
+    for method in &mut trait_decl.methods {
+      self.visit_prototype(method)?;
+    }
+
+    Ok(())
+  }
+
+  fn visit_impl_decl(&mut self, impl_decl: &mut impl_decl::ImplDecl) -> pass::PassResult {
+    walk_impl_decl(self, impl_decl)
+  }
+
+  fn visit_kind_group(&mut self, _: &mut node::KindGroup) -> pass::PassResult {
+    Ok(())
+  }
+
+  fn visit_block(&mut self, block: &mut block::Block) -> pass::PassResult {
+    walk_block(self, block)
+  }
+
+  fn visit_statement(&mut self, statement: &mut block::AnyStatementNode) -> pass::PassResult {
+    walk_statement(self, statement)
+  }
+
+  fn visit_expr(&mut self, expr: &mut node::AnyExprNode) -> pass::PassResult {
+    walk_expr(self, expr)
+  }
+
+  fn visit_pattern(&mut self, pattern: &mut pattern::AnyPatternNode) -> pass::PassResult {
+    walk_pattern(self, pattern)
+  }
+}
+
+pub fn walk_namespace<T: PassMut + ?Sized>(
+  pass: &mut T,
+  namespace: &mut namespace::Namespace,
+) -> pass::PassResult {
+  for top_level_node in namespace.symbol_table.values_mut() {
+    match top_level_node {
+      namespace::TopLevelNode::Function(function) => pass.visit_function(function)?,
+      namespace::TopLevelNode::External(external) => pass.visit_external(external)?,
+      namespace::TopLevelNode::Namespace(namespace) => pass.visit_namespace(namespace)?,
+      namespace::TopLevelNode::Struct(struct_decl) => pass.visit_struct_decl(struct_decl)?,
+      namespace::TopLevelNode::Enum(enum_decl) => pass.visit_enum_decl(enum_decl)?,
+      namespace::TopLevelNode::Trait(trait_decl) => pass.visit_trait_decl(trait_decl)?,
+    };
+  }
+
+  for impl_decl in &mut namespace.impls {
+    pass.visit_impl_decl(impl_decl)?;
+  }
+
+  Ok(())
+}
+
+pub fn walk_impl_decl<T: PassMut + ?Sized>(
+  pass: &mut T,
+  impl_decl: &mut impl_decl::ImplDecl,
+) -> pass::PassResult {
+  pass.visit_kind_group(&mut impl_decl.kind_group)?;
+
+  for method in &mut impl_decl.methods {
+    pass.visit_function(method)?;
+  }
+
+  Ok(())
+}
+
+pub fn walk_function<T: PassMut + ?Sized>(
+  pass: &mut T,
+  function: &mut function::Function,
+) -> pass::PassResult {
+  pass.visit_prototype(&mut function.prototype)?;
+  pass.visit_block(&mut function.body)
+}
+
+pub fn walk_prototype<T: PassMut + ?Sized>(
+  pass: &mut T,
+  prototype: &mut prototype::Prototype,
+) -> pass::PassResult {
+  for (_, kind_group) in &mut prototype.parameters {
+    pass.visit_kind_group(kind_group)?;
+  }
+
+  pass.visit_kind_group(&mut prototype.return_kind_group)
+}
+
+pub fn walk_block<T: PassMut + ?Sized>(pass: &mut T, block: &mut block::Block) -> pass::PassResult {
+  for statement in &mut block.statements {
+    pass.visit_statement(statement)?;
+  }
+
+  Ok(())
+}
+
+pub fn walk_statement<T: PassMut + ?Sized>(
+  pass: &mut T,
+  statement: &mut block::AnyStatementNode,
+) -> pass::PassResult {
+  match statement {
+    block::AnyStatementNode::ReturnStmt(return_stmt) => {
+      if let Some(value) = &mut return_stmt.value {
+        pass.visit_expr(value)?;
+      }
+    }
+    block::AnyStatementNode::IfStmt(if_stmt) => {
+      pass.visit_expr(&mut if_stmt.condition)?;
+      pass.visit_block(&mut if_stmt.then_block)?;
+
+      for (condition, block) in &mut if_stmt.else_if_branches {
+        pass.visit_expr(condition)?;
+        pass.visit_block(block)?;
+      }
+
+      if let Some(else_block) = &mut if_stmt.else_block {
+        pass.visit_block(else_block)?;
+      }
+    }
+    block::AnyStatementNode::WhileStmt(while_stmt) => {
+      pass.visit_expr(&mut while_stmt.condition)?;
+      pass.visit_block(&mut while_stmt.body)?;
+    }
+    block::AnyStatementNode::LoopStmt(loop_stmt) => pass.visit_block(&mut loop_stmt.body)?,
+    block::AnyStatementNode::ForStmt(for_stmt) => {
+      pass.visit_expr(&mut for_stmt.range_start)?;
+      pass.visit_expr(&mut for_stmt.range_end)?;
+      pass.visit_block(&mut for_stmt.body)?;
+    }
+    block::AnyStatementNode::BreakStmt(_) | block::AnyStatementNode::ContinueStmt(_) => {}
+    block::AnyStatementNode::ExprStmt(expr_stmt) => pass.visit_expr(&mut expr_stmt.expr)?,
+    block::AnyStatementNode::LetStmt(let_stmt) => {
+      if let Some(kind_group) = &mut let_stmt.kind_group {
+        pass.visit_kind_group(kind_group)?;
+      }
+
+      pass.visit_expr(&mut let_stmt.value)?;
+    }
+    block::AnyStatementNode::AssignStmt(assign_stmt) => {
+      pass.visit_expr(&mut assign_stmt.target)?;
+      pass.visit_expr(&mut assign_stmt.value)?;
+    }
+  };
+
+  Ok(())
+}
+
+pub fn walk_expr<T: PassMut + ?Sized>(
+  pass: &mut T,
+  expr: &mut node::AnyExprNode,
+) -> pass::PassResult {
+  match expr {
+    node::AnyExprNode::CallExpr(call_expr) => {
+      for argument in &mut call_expr.arguments {
+        pass.visit_expr(argument)?;
+      }
+    }
+    node::AnyExprNode::FieldAccessExpr(field_access_expr) => {
+      pass.visit_expr(&mut field_access_expr.base)?;
+    }
+    node::AnyExprNode::StructLiteral(struct_literal) => {
+      for (_, value) in &mut struct_literal.fields {
+        pass.visit_expr(value)?;
+      }
+    }
+    node::AnyExprNode::VariantLiteral(variant_literal) => {
+      for argument in &mut variant_literal.arguments {
+        pass.visit_expr(argument)?;
+      }
+    }
+    node::AnyExprNode::MatchExpr(match_expr) => {
+      pass.visit_expr(&mut match_expr.subject)?;
+
+      for arm in &mut match_expr.arms {
+        pass.visit_pattern(&mut arm.pattern)?;
+
+        match &mut arm.body {
+          node::MatchArmBody::Expr(expr) => pass.visit_expr(expr)?,
+          node::MatchArmBody::Block(block) => pass.visit_block(block)?,
+        };
+      }
+    }
+    node::AnyExprNode::ArrayLiteral(array_literal) => {
+      for element in &mut array_literal.elements {
+        pass.visit_expr(element)?;
+      }
+    }
+    node::AnyExprNode::IndexExpr(index_expr) => {
+      pass.visit_expr(&mut index_expr.base)?;
+      pass.visit_expr(&mut index_expr.index)?;
+    }
+    node::AnyExprNode::LenExpr(len_expr) => pass.visit_expr(&mut len_expr.base)?,
+    node::AnyExprNode::BorrowExpr(borrow_expr) => pass.visit_expr(&mut borrow_expr.value)?,
+    node::AnyExprNode::DerefExpr(deref_expr) => pass.visit_expr(&mut deref_expr.value)?,
+    node::AnyExprNode::Literal(_) | node::AnyExprNode::VariableRef(_) => {}
+  };
+
+  Ok(())
+}
+
+pub fn walk_pattern<T: PassMut + ?Sized>(
+  pass: &mut T,
+  pattern: &mut pattern::AnyPatternNode,
+) -> pass::PassResult {
+  if let pattern::AnyPatternNode::VariantPattern(variant_pattern) = pattern {
+    for field in &mut variant_pattern.fields {
+      pass.visit_pattern(field)?;
+    }
+  }
+
+  Ok(())
+}
@@ -0,0 +1,114 @@
+use crate::{node, pass, span};
+
+// A pattern, which values are matched against by the arms of a
+// `match` expression.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnyPatternNode {
+  WildcardPattern(WildcardPattern),
+  BindingPattern(BindingPattern),
+  // Matches values equal to the literal (ex. `1` or `true`).
+  LiteralPattern(node::AnyLiteralNode),
+  VariantPattern(VariantPattern),
+}
+
+impl AnyPatternNode {
+  pub fn as_node(&self) -> &dyn node::Node {
+    match self {
+      AnyPatternNode::WildcardPattern(wildcard_pattern) => wildcard_pattern,
+      AnyPatternNode::BindingPattern(binding_pattern) => binding_pattern,
+      AnyPatternNode::LiteralPattern(literal) => literal.as_node(),
+      AnyPatternNode::VariantPattern(variant_pattern) => variant_pattern,
+    }
+  }
+
+  pub fn get_span(&self) -> span::Span {
+    match self {
+      AnyPatternNode::WildcardPattern(wildcard_pattern) => wildcard_pattern.span,
+      AnyPatternNode::BindingPattern(binding_pattern) => binding_pattern.span,
+      AnyPatternNode::LiteralPattern(node::AnyLiteralNode::BoolLiteral(bool_literal)) => {
+        bool_literal.span
+      }
+      AnyPatternNode::LiteralPattern(node::AnyLiteralNode::IntLiteral(int_literal)) => {
+        int_literal.span
+      }
+      AnyPatternNode::VariantPattern(variant_pattern) => variant_pattern.span,
+    }
+  }
+
+  // Whether the pattern matches every value, regardless of its
+  // contents.
+  pub fn is_irrefutable(&self) -> bool {
+    matches!(
+      self,
+      AnyPatternNode::WildcardPattern(_) | AnyPatternNode::BindingPattern(_)
+    )
+  }
+}
+
+// Matches any value, without binding it (ex. `_`).
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WildcardPattern {
+  pub id: node::NodeId,
+  pub span: span::Span,
+}
+
+impl node::Node for WildcardPattern {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_wildcard_pattern(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+}
+
+// Matches any value, and binds it to a name within the arm.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BindingPattern {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  pub name: String,
+}
+
+impl node::Node for BindingPattern {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_binding_pattern(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+}
+
+// Matches values of a variant of an enum whose fields match the
+// given patterns (ex. `Shape::Circle(r)`). The path leads to the
+// variant, through its enum.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VariantPattern {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  pub path: node::Path,
+  pub fields: Vec<AnyPatternNode>,
+}
+
+impl node::Node for VariantPattern {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_variant_pattern(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    let mut children: Vec<&dyn node::Node> = vec![&self.path];
+
+    children.extend(self.fields.iter().map(|field| field.as_node()));
+
+    children
+  }
+}
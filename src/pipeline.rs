@@ -0,0 +1,327 @@
+use crate::{
+  borrow_check_pass, coherence_check_pass, diagnostic, implicit_return_pass, llvm_lowering_pass,
+  loop_check_pass, match_check_pass, monomorphization_pass, name_resolution_pass, namespace, pass,
+  pass::Pass, pass_manager, pass_mut::PassMut, type_check_pass, visibility_check_pass,
+};
+
+// Gather the diagnostics reported by a pass, followed by the error
+// which stopped it (if any).
+fn collect_diagnostics(
+  diagnostics: &mut Vec<diagnostic::Diagnostic>,
+  pass_diagnostics: Vec<diagnostic::Diagnostic>,
+  visitation_result: pass::PassResult,
+) {
+  diagnostics.extend(pass_diagnostics);
+
+  if let Err(diagnostic) = visitation_result {
+    diagnostics.push(diagnostic);
+  }
+}
+
+fn has_errors(diagnostics: &[Vec<diagnostic::Diagnostic>]) -> bool {
+  diagnostics
+    .iter()
+    .flatten()
+    .any(diagnostic::Diagnostic::is_error)
+}
+
+// Resolve and check the given outermost namespaces, which are
+// compiled together (ex. one per source file). Each stage only runs
+// if the previous ones reported no errors, since it relies on their
+// results (ex. the kinds inferred by the type check pass).
+//
+// Returns the diagnostics reported for each namespace, in order,
+// including warnings.
+pub fn analyze_namespaces(
+  namespaces: &mut [namespace::Namespace],
+) -> Vec<Vec<diagnostic::Diagnostic>> {
+  let mut diagnostics = vec![vec![]; namespaces.len()];
+
+  for (namespace, diagnostics) in namespaces.iter_mut().zip(&mut diagnostics) {
+    let mut name_resolution_pass = name_resolution_pass::NameResolutionPass::new();
+    let visitation_result = name_resolution_pass.visit_namespace(namespace);
+
+    collect_diagnostics(
+      diagnostics,
+      name_resolution_pass.get_diagnostics(),
+      visitation_result,
+    );
+  }
+
+  if has_errors(&diagnostics) {
+    return diagnostics;
+  }
+
+  // The kinds inferred for each namespace are kept for the later
+  // stages.
+  let mut type_check_passes = vec![];
+
+  for (namespace, diagnostics) in namespaces.iter().zip(&mut diagnostics) {
+    let mut type_check_pass = type_check_pass::TypeCheckPass::new();
+    let visitation_result = type_check_pass.visit_namespace(namespace);
+
+    collect_diagnostics(
+      diagnostics,
+      type_check_pass.get_diagnostics(),
+      visitation_result,
+    );
+
+    type_check_passes.push(type_check_pass);
+  }
+
+  if has_errors(&diagnostics) {
+    return diagnostics;
+  }
+
+  // The check passes are independent of one another, so all of
+  // their diagnostics are reported at once.
+  for ((namespace, type_check_pass), diagnostics) in namespaces
+    .iter()
+    .zip(&type_check_passes)
+    .zip(&mut diagnostics)
+  {
+    let mut pass_manager = pass_manager::PassManager::new();
+
+    // Items may be used across the namespaces compiled together, so
+    // the visibility check pass is given all of them.
+    pass_manager.add_pass(Box::new(visibility_check_pass::VisibilityCheckPass::new(
+      namespaces,
+    )));
+
+    pass_manager.add_pass(Box::new(loop_check_pass::LoopCheckPass::new()));
+
+    pass_manager.add_pass(Box::new(match_check_pass::MatchCheckPass::new(
+      type_check_pass.get_expr_kinds(),
+    )));
+
+    pass_manager.add_pass(Box::new(borrow_check_pass::BorrowCheckPass::new(
+      type_check_pass.get_expr_kinds(),
+    )));
+
+    pass_manager.add_pass(Box::new(coherence_check_pass::CoherenceCheckPass::new()));
+
+    diagnostics.extend(pass_manager.run(namespace));
+  }
+
+  if has_errors(&diagnostics) {
+    return diagnostics;
+  }
+
+  // Generic functions are only lowered through their instances,
+  // which are created for the kinds inferred by the type check pass.
+  for ((namespace, type_check_pass), diagnostics) in namespaces
+    .iter_mut()
+    .zip(&type_check_passes)
+    .zip(&mut diagnostics)
+  {
+    let mut monomorphization_pass =
+      monomorphization_pass::MonomorphizationPass::new(type_check_pass.get_call_type_arguments());
+
+    let visitation_result = monomorphization_pass.visit_namespace(namespace);
+
+    collect_diagnostics(
+      diagnostics,
+      monomorphization_pass.get_diagnostics(),
+      visitation_result,
+    );
+  }
+
+  if has_errors(&diagnostics) {
+    return diagnostics;
+  }
+
+  // Lowering expects every function to end with an explicit return.
+  for (namespace, diagnostics) in namespaces.iter_mut().zip(&mut diagnostics) {
+    let mut implicit_return_pass = implicit_return_pass::ImplicitReturnPass::new();
+    let visitation_result = implicit_return_pass.visit_namespace(namespace);
+
+    collect_diagnostics(
+      diagnostics,
+      implicit_return_pass.get_diagnostics(),
+      visitation_result,
+    );
+  }
+
+  diagnostics
+}
+
+// Analyze an outermost namespace and lower it into the module of
+// the given lowering pass, unless any errors were reported.
+pub fn compile_namespace(
+  llvm_lowering_pass: &mut llvm_lowering_pass::LlvmLoweringPass,
+  namespace: &mut namespace::Namespace,
+) -> Vec<diagnostic::Diagnostic> {
+  let mut diagnostics = analyze_namespaces(std::slice::from_mut(namespace)).concat();
+
+  if diagnostics.iter().any(diagnostic::Diagnostic::is_error) {
+    return diagnostics;
+  }
+
+  let visitation_result = llvm_lowering_pass.visit_namespace(namespace);
+
+  collect_diagnostics(
+    &mut diagnostics,
+    llvm_lowering_pass.get_diagnostics(),
+    visitation_result,
+  );
+
+  diagnostics
+}
+
+// Parse, resolve and type check the given source, which must not
+// have errors so far. The tests of the check passes run on its
+// result, since those passes rely on the inferred kinds.
+#[cfg(test)]
+pub fn type_check_source(source: &str) -> (namespace::Namespace, type_check_pass::TypeCheckPass) {
+  let mut namespace = crate::parser::parse_source(source).unwrap();
+
+  let mut name_resolution_pass = name_resolution_pass::NameResolutionPass::new();
+
+  assert_eq!(
+    true,
+    name_resolution_pass.visit_namespace(&mut namespace).is_ok()
+  );
+
+  assert_eq!(true, name_resolution_pass.get_diagnostics().is_empty());
+
+  let mut type_check_pass = type_check_pass::TypeCheckPass::new();
+
+  assert_eq!(true, type_check_pass.visit_namespace(&namespace).is_ok());
+  assert_eq!(true, type_check_pass.get_diagnostics().is_empty());
+
+  (namespace, type_check_pass)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{block, parser};
+
+  fn analyze_source(source: &str) -> Vec<String> {
+    let mut namespaces = [parser::parse_source(source).unwrap()];
+
+    analyze_namespaces(&mut namespaces)
+      .concat()
+      .into_iter()
+      .map(|diagnostic| diagnostic.message)
+      .collect()
+  }
+
+  #[test]
+  fn pipeline_analyze_namespaces() {
+    let diagnostics = analyze_source(
+      "namespace foo {
+        fn f(b: bool) ~ i32 {
+          let mut a = 1;
+          while b { a = 2; break; }
+          return a;
+        }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+  }
+
+  #[test]
+  fn pipeline_analyze_namespaces_implicit_return() {
+    let mut namespaces = [parser::parse_source("namespace foo { fn f() ~ void {} }").unwrap()];
+
+    assert_eq!(true, analyze_namespaces(&mut namespaces)[0].is_empty());
+
+    let function = match namespaces[0].symbol_table.get("f") {
+      Some(namespace::TopLevelNode::Function(function)) => function,
+      _ => unreachable!(),
+    };
+
+    assert_eq!(
+      true,
+      matches!(
+        function.body.statements.last(),
+        Some(block::AnyStatementNode::ReturnStmt(_))
+      )
+    );
+  }
+
+  #[test]
+  fn pipeline_analyze_namespaces_instances() {
+    let mut namespaces = [parser::parse_source(
+      "namespace foo { fn id<T>(value: T) ~ T { return value; } fn f() ~ void { id(1); } }",
+    )
+    .unwrap()];
+
+    assert_eq!(true, analyze_namespaces(&mut namespaces)[0].is_empty());
+
+    assert_eq!(
+      vec!["f", "id<i32>"],
+      namespaces[0]
+        .symbol_table
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+    );
+  }
+
+  #[test]
+  fn pipeline_analyze_namespaces_visibility() {
+    let mut namespaces = [
+      parser::parse_source("namespace foo { extern bar() ~ void; }").unwrap(),
+      parser::parse_source("namespace qux { #[no_mangle] fn bar() ~ void {} }").unwrap(),
+    ];
+
+    let diagnostics = analyze_namespaces(&mut namespaces);
+
+    assert_eq!(1, diagnostics[0].len());
+
+    assert_eq!(
+      "function `bar` is private to namespace `qux`",
+      diagnostics[0][0].message
+    );
+
+    assert_eq!(true, diagnostics[1].is_empty());
+  }
+
+  #[test]
+  fn pipeline_analyze_namespaces_check_errors() {
+    let diagnostics = analyze_source(
+      "namespace foo {
+        fn f(b: bool) ~ &i32 {
+          break;
+          continue;
+          match b { true => {} }
+          let a = 1;
+          return &a;
+        }
+        trait Shape { fn area(self: &Self) ~ i32; }
+        impl Shape for i32 { fn area(self: &Self) ~ i32 { return 1; } }
+        impl Shape for i32 { fn area(self: &Self) ~ i32 { return 2; } }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        "`break` outside of a loop",
+        "`continue` outside of a loop",
+        "non-exhaustive patterns: `false` not covered",
+        "cannot return a reference to local variable `a`",
+        "conflicting implementations of trait `Shape` for kind `i32`"
+      ],
+      diagnostics
+    );
+
+    // The check passes rely on the kinds inferred by the type check
+    // pass, and are only run if it reported no errors.
+    let diagnostics = analyze_source(
+      "namespace foo {
+        fn f(b: bool) ~ i32 {
+          break;
+          return false;
+        }
+      }",
+    );
+
+    assert_eq!(
+      vec!["mismatched kinds for returned value: expected `i32`, found `bool`"],
+      diagnostics
+    );
+  }
+}
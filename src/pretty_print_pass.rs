@@ -0,0 +1,1185 @@
+use crate::{
+  block, diagnostic, enum_decl, external, function, impl_decl, lexer, namespace, node, parser,
+  pass, pass::Pass, pattern, prototype, struct_decl, trait_decl, use_decl,
+};
+
+const INDENTATION: &str = "  ";
+
+// Prints the tree back into source, in its canonical format.
+//
+// Comments are not part of the tree, and are instead printed
+// before the first node that follows them, at that node's level
+// of indentation. Trailing comments remain at the end of the line
+// they were found on.
+pub struct PrettyPrintPass {
+  output: String,
+  indentation_level: usize,
+  // The comments which are yet to be printed, with the next one
+  // last.
+  comments: Vec<lexer::Comment>,
+}
+
+impl PrettyPrintPass {
+  pub fn new(mut comments: Vec<lexer::Comment>) -> Self {
+    comments.sort_by_key(|comment| std::cmp::Reverse(comment.span.start));
+
+    Self {
+      output: String::new(),
+      indentation_level: 0,
+      comments,
+    }
+  }
+
+  pub fn get_output(&self) -> &str {
+    &self.output
+  }
+
+  fn write(&mut self, text: &str) {
+    self.output.push_str(text);
+  }
+
+  fn write_indentation(&mut self) {
+    self
+      .output
+      .push_str(&INDENTATION.repeat(self.indentation_level));
+  }
+
+  fn has_comments_before(&self, position: usize) -> bool {
+    matches!(self.comments.last(), Some(comment) if comment.span.start < position)
+  }
+
+  // Print all pending comments which start before the given
+  // position, each on its own line unless it is trailing.
+  fn write_comments_before(&mut self, position: usize) {
+    while self.has_comments_before(position) {
+      let comment = self.comments.pop().unwrap();
+
+      self.write_comment(&comment);
+    }
+  }
+
+  // Print the pending trailing comments which start before the given
+  // position, before the blank line separating two nodes is written,
+  // so that they remain on the last line of the former.
+  fn write_trailing_comments_before(&mut self, position: usize) {
+    while matches!(
+      self.comments.last(),
+      Some(comment) if comment.is_trailing && comment.span.start < position
+    ) {
+      let comment = self.comments.pop().unwrap();
+
+      self.write_comment(&comment);
+    }
+  }
+
+  fn write_comment(&mut self, comment: &lexer::Comment) {
+    if comment.is_trailing && self.output.ends_with('\n') {
+      self.output.pop();
+      self.write(" ");
+    } else {
+      self.write_indentation();
+    }
+
+    self.write(&comment.text);
+    self.write("\n");
+  }
+
+  fn write_label(&mut self, label: &Option<String>) {
+    if let Some(label) = label {
+      self.write(&format!("'{}: ", label));
+    }
+  }
+
+  fn write_kind_group(&mut self, kind_group: &node::KindGroup) {
+    if kind_group.is_reference {
+      self.write("&");
+    }
+
+    if kind_group.is_mutable {
+      self.write("mut ");
+    }
+
+    self.write(&kind_group.kind.to_string());
+  }
+
+  fn write_loop_control(&mut self, keyword: &str, label: &Option<String>) {
+    self.write(keyword);
+
+    if let Some(label) = label {
+      self.write(&format!(" '{}", label));
+    }
+
+    self.write(";\n");
+  }
+
+  // Print any comments following the last node.
+  fn finish(&mut self) -> pass::PassResult {
+    self.write_comments_before(usize::MAX);
+
+    Ok(())
+  }
+}
+
+impl pass::Pass<'_> for PrettyPrintPass {
+  fn visit_namespace(&mut self, namespace: &namespace::Namespace) -> pass::PassResult {
+    // Nested namespaces are preceded by their comments (if any) by
+    // the namespace containing them.
+    let is_outermost = self.indentation_level == 0;
+
+    // Each child is paired with whether it is a `use` declaration,
+    // since consecutive declarations are not separated by a blank
+    // line, unlike other children.
+    let mut children: Vec<(&dyn node::Node, bool)> = namespace
+      .uses
+      .iter()
+      .map(|use_decl| (use_decl as &dyn node::Node, true))
+      .collect();
+
+    children.extend(
+      namespace
+        .symbol_table
+        .values()
+        .map(|top_level_node| (top_level_node.as_node(), false)),
+    );
+
+    children.extend(
+      namespace
+        .impls
+        .iter()
+        .map(|impl_decl| (impl_decl as &dyn node::Node, false)),
+    );
+
+    children.sort_by_key(|(child, _)| child.get_span().unwrap_or_default().start);
+
+    if is_outermost {
+      self.write_comments_before(namespace.span.start);
+    }
+
+    self.write_indentation();
+    self.write(&format!("namespace {} {{", namespace.name));
+
+    if children.is_empty() && !self.has_comments_before(namespace.span.end) {
+      self.write("}\n");
+
+      return match is_outermost {
+        true => self.finish(),
+        false => Ok(()),
+      };
+    }
+
+    self.write("\n");
+    self.indentation_level += 1;
+
+    let mut was_previous_use = None;
+
+    for (child, is_use) in children {
+      let start = child.get_span().unwrap_or_default().start;
+
+      if matches!(was_previous_use, Some(was_use) if !(was_use && is_use)) {
+        self.write_trailing_comments_before(start);
+        self.write("\n");
+      }
+
+      was_previous_use = Some(is_use);
+      self.write_comments_before(start);
+      self.visit(child)?;
+    }
+
+    self.write_comments_before(namespace.span.end);
+    self.indentation_level -= 1;
+    self.write_indentation();
+    self.write("}\n");
+
+    match is_outermost {
+      true => self.finish(),
+      false => Ok(()),
+    }
+  }
+
+  fn visit_use_decl(&mut self, use_decl: &use_decl::UseDecl) -> pass::PassResult {
+    self.write_indentation();
+    self.write("use ");
+
+    if use_decl.is_group {
+      self.write(&format!("{}::{{", use_decl.prefix.join("::")));
+    }
+
+    for (index, item) in use_decl.items.iter().enumerate() {
+      if index > 0 {
+        self.write(", ");
+      }
+
+      self.write(&item.path.to_string());
+
+      if let Some(alias) = &item.alias {
+        self.write(&format!(" as {}", alias));
+      }
+    }
+
+    if use_decl.is_group {
+      self.write("}");
+    }
+
+    self.write(";\n");
+
+    Ok(())
+  }
+
+  fn visit_function(&mut self, function: &function::Function) -> pass::PassResult {
+    // Each attribute is written on its own line.
+    for attribute in &function.attributes {
+      self.write_indentation();
+      self.write(&format!("{}\n", attribute));
+    }
+
+    self.write_indentation();
+
+    if function.is_public {
+      self.write("pub ");
+    }
+
+    self.write("fn ");
+    self.visit_prototype(&function.prototype)?;
+    self.write(" ");
+    self.visit_block(&function.body)?;
+    self.write("\n");
+
+    Ok(())
+  }
+
+  fn visit_external(&mut self, external: &external::External) -> pass::PassResult {
+    self.write_indentation();
+
+    if external.is_public {
+      self.write("pub ");
+    }
+
+    self.write("extern ");
+    self.visit_prototype(&external.prototype)?;
+    self.write(";\n");
+
+    Ok(())
+  }
+
+  fn visit_struct_decl(&mut self, struct_decl: &struct_decl::StructDecl) -> pass::PassResult {
+    self.write_indentation();
+
+    if struct_decl.is_public {
+      self.write("pub ");
+    }
+
+    self.write(&format!("struct {} {{", struct_decl.name));
+
+    if struct_decl.fields.is_empty() && !self.has_comments_before(struct_decl.span.end) {
+      self.write("}\n");
+
+      return Ok(());
+    }
+
+    self.write("\n");
+    self.indentation_level += 1;
+
+    // Each field is written on its own line, followed by a comma.
+    for field in &struct_decl.fields {
+      self.write_comments_before(field.span.start);
+      self.write_indentation();
+      self.write(&format!("{}: ", field.name));
+      self.write_kind_group(&field.kind_group);
+      self.write(",\n");
+    }
+
+    self.write_comments_before(struct_decl.span.end);
+    self.indentation_level -= 1;
+    self.write_indentation();
+    self.write("}\n");
+
+    Ok(())
+  }
+
+  fn visit_enum_decl(&mut self, enum_decl: &enum_decl::EnumDecl) -> pass::PassResult {
+    self.write_indentation();
+
+    if enum_decl.is_public {
+      self.write("pub ");
+    }
+
+    self.write(&format!("enum {} {{", enum_decl.name));
+
+    if enum_decl.variants.is_empty() && !self.has_comments_before(enum_decl.span.end) {
+      self.write("}\n");
+
+      return Ok(());
+    }
+
+    self.write("\n");
+    self.indentation_level += 1;
+
+    // Each variant is written on its own line, followed by a comma.
+    for variant in &enum_decl.variants {
+      self.write_comments_before(variant.span.start);
+      self.write_indentation();
+      self.write(&variant.name);
+
+      if !variant.fields.is_empty() {
+        self.write("(");
+
+        for (index, field) in variant.fields.iter().enumerate() {
+          if index > 0 {
+            self.write(", ");
+          }
+
+          self.write_kind_group(field);
+        }
+
+        self.write(")");
+      }
+
+      self.write(",\n");
+    }
+
+    self.write_comments_before(enum_decl.span.end);
+    self.indentation_level -= 1;
+    self.write_indentation();
+    self.write("}\n");
+
+    Ok(())
+  }
+
+  fn visit_trait_decl(&mut self, trait_decl: &trait_decl::TraitDecl) -> pass::PassResult {
+    self.write_indentation();
+
+    if trait_decl.is_public {
+      self.write("pub ");
+    }
+
+    self.write(&format!("trait {} {{", trait_decl.name));
+
+    if trait_decl.methods.is_empty() && !self.has_comments_before(trait_decl.span.end) {
+      self.write("}\n");
+
+      return Ok(());
+    }
+
+    self.write("\n");
+    self.indentation_level += 1;
+
+    // Each method is written on its own line.
+    for method in &trait_decl.methods {
+      self.write_comments_before(method.span.start);
+      self.write_indentation();
+      self.write("fn ");
+      self.visit_prototype(method)?;
+      self.write(";\n");
+    }
+
+    self.write_comments_before(trait_decl.span.end);
+    self.indentation_level -= 1;
+    self.write_indentation();
+    self.write("}\n");
+
+    Ok(())
+  }
+
+  fn visit_impl_decl(&mut self, impl_decl: &impl_decl::ImplDecl) -> pass::PassResult {
+    self.write_indentation();
+    self.write(&format!("impl {} for ", impl_decl.trait_path));
+    self.write_kind_group(&impl_decl.kind_group);
+    self.write(" {");
+
+    if impl_decl.methods.is_empty() && !self.has_comments_before(impl_decl.span.end) {
+      self.write("}\n");
+
+      return Ok(());
+    }
+
+    self.write("\n");
+    self.indentation_level += 1;
+
+    // Methods are separated by a blank line, as within namespaces.
+    for (index, method) in impl_decl.methods.iter().enumerate() {
+      if index > 0 {
+        self.write_trailing_comments_before(method.span.start);
+        self.write("\n");
+      }
+
+      self.write_comments_before(method.span.start);
+      self.visit_function(method)?;
+    }
+
+    self.write_comments_before(impl_decl.span.end);
+    self.indentation_level -= 1;
+    self.write_indentation();
+    self.write("}\n");
+
+    Ok(())
+  }
+
+  fn visit_prototype(&mut self, prototype: &prototype::Prototype) -> pass::PassResult {
+    self.write(&prototype.name);
+
+    if prototype.is_generic() {
+      let generic_parameters = prototype
+        .generic_parameters
+        .iter()
+        .map(|generic_parameter| generic_parameter.to_string())
+        .collect::<Vec<_>>();
+
+      self.write(&format!("<{}>", generic_parameters.join(", ")));
+    }
+
+    self.write("(");
+
+    for (index, (parameter_name, parameter_kind_group)) in prototype.parameters.iter().enumerate() {
+      if index > 0 {
+        self.write(", ");
+      }
+
+      self.write(&format!("{}: ", parameter_name));
+      self.write_kind_group(parameter_kind_group);
+    }
+
+    if prototype.is_variadic {
+      if !prototype.parameters.is_empty() {
+        self.write(", ");
+      }
+
+      self.write("...");
+    }
+
+    self.write(") ~ ");
+    self.write_kind_group(&prototype.return_kind_group);
+
+    Ok(())
+  }
+
+  fn visit_block(&mut self, block: &block::Block) -> pass::PassResult {
+    if block.statements.is_empty() && !self.has_comments_before(block.span.end) {
+      self.write("{}");
+
+      return Ok(());
+    }
+
+    self.write("{\n");
+    self.indentation_level += 1;
+
+    for statement in &block.statements {
+      let statement = statement.as_node();
+
+      self.write_comments_before(statement.get_span().unwrap_or_default().start);
+      self.write_indentation();
+      self.visit(statement)?;
+    }
+
+    self.write_comments_before(block.span.end);
+    self.indentation_level -= 1;
+    self.write_indentation();
+    self.write("}");
+
+    Ok(())
+  }
+
+  fn visit_return_stmt(&mut self, return_stmt: &block::ReturnStmt) -> pass::PassResult {
+    self.write("return");
+
+    if let Some(value) = &return_stmt.value {
+      self.write(" ");
+      self.visit(value.as_node())?;
+    }
+
+    self.write(";\n");
+
+    Ok(())
+  }
+
+  fn visit_if_stmt(&mut self, if_stmt: &block::IfStmt) -> pass::PassResult {
+    self.write("if ");
+    self.visit(if_stmt.condition.as_node())?;
+    self.write(" ");
+    self.visit_block(&if_stmt.then_block)?;
+
+    for (condition, block) in &if_stmt.else_if_branches {
+      self.write(" else if ");
+      self.visit(condition.as_node())?;
+      self.write(" ");
+      self.visit_block(block)?;
+    }
+
+    if let Some(else_block) = &if_stmt.else_block {
+      self.write(" else ");
+      self.visit_block(else_block)?;
+    }
+
+    self.write("\n");
+
+    Ok(())
+  }
+
+  fn visit_while_stmt(&mut self, while_stmt: &block::WhileStmt) -> pass::PassResult {
+    self.write_label(&while_stmt.label);
+    self.write("while ");
+    self.visit(while_stmt.condition.as_node())?;
+    self.write(" ");
+    self.visit_block(&while_stmt.body)?;
+    self.write("\n");
+
+    Ok(())
+  }
+
+  fn visit_loop_stmt(&mut self, loop_stmt: &block::LoopStmt) -> pass::PassResult {
+    self.write_label(&loop_stmt.label);
+    self.write("loop ");
+    self.visit_block(&loop_stmt.body)?;
+    self.write("\n");
+
+    Ok(())
+  }
+
+  fn visit_for_stmt(&mut self, for_stmt: &block::ForStmt) -> pass::PassResult {
+    self.write_label(&for_stmt.label);
+    self.write(&format!("for {} in ", for_stmt.iterator_name));
+    self.visit(for_stmt.range_start.as_node())?;
+    self.write(if for_stmt.is_inclusive { "..=" } else { ".." });
+    self.visit(for_stmt.range_end.as_node())?;
+    self.write(" ");
+    self.visit_block(&for_stmt.body)?;
+    self.write("\n");
+
+    Ok(())
+  }
+
+  fn visit_break_stmt(&mut self, break_stmt: &block::BreakStmt) -> pass::PassResult {
+    self.write_loop_control("break", &break_stmt.label);
+
+    Ok(())
+  }
+
+  fn visit_continue_stmt(&mut self, continue_stmt: &block::ContinueStmt) -> pass::PassResult {
+    self.write_loop_control("continue", &continue_stmt.label);
+
+    Ok(())
+  }
+
+  fn visit_expr_stmt(&mut self, expr_stmt: &block::ExprStmt) -> pass::PassResult {
+    self.visit(expr_stmt.expr.as_node())?;
+
+    // Like blocks, matches used as statements need no semicolon.
+    self.write(match expr_stmt.expr {
+      node::AnyExprNode::MatchExpr(_) => "\n",
+      _ => ";\n",
+    });
+
+    Ok(())
+  }
+
+  fn visit_let_stmt(&mut self, let_stmt: &block::LetStmt) -> pass::PassResult {
+    self.write("let ");
+
+    if let_stmt.is_mutable {
+      self.write("mut ");
+    }
+
+    self.write(&let_stmt.name);
+
+    if let Some(kind_group) = &let_stmt.kind_group {
+      self.write(": ");
+      self.write_kind_group(kind_group);
+    }
+
+    self.write(" = ");
+    self.visit(let_stmt.value.as_node())?;
+    self.write(";\n");
+
+    Ok(())
+  }
+
+  fn visit_assign_stmt(&mut self, assign_stmt: &block::AssignStmt) -> pass::PassResult {
+    self.visit(assign_stmt.target.as_node())?;
+    self.write(" = ");
+    self.visit(assign_stmt.value.as_node())?;
+    self.write(";\n");
+
+    Ok(())
+  }
+
+  fn visit_variable_ref(&mut self, variable_ref: &node::VariableRef) -> pass::PassResult {
+    self.write(&variable_ref.name);
+
+    Ok(())
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &node::CallExpr) -> pass::PassResult {
+    self.write(&format!("{}(", call_expr.callee));
+
+    for (index, argument) in call_expr.arguments.iter().enumerate() {
+      if index > 0 {
+        self.write(", ");
+      }
+
+      self.visit(argument.as_node())?;
+    }
+
+    self.write(")");
+
+    Ok(())
+  }
+
+  fn visit_field_access_expr(
+    &mut self,
+    field_access_expr: &node::FieldAccessExpr,
+  ) -> pass::PassResult {
+    self.visit(field_access_expr.base.as_node())?;
+    self.write(&format!(".{}", field_access_expr.field_name));
+
+    Ok(())
+  }
+
+  fn visit_index_expr(&mut self, index_expr: &node::IndexExpr) -> pass::PassResult {
+    self.visit(index_expr.base.as_node())?;
+    self.write("[");
+    self.visit(index_expr.index.as_node())?;
+    self.write("]");
+
+    Ok(())
+  }
+
+  fn visit_len_expr(&mut self, len_expr: &node::LenExpr) -> pass::PassResult {
+    self.visit(len_expr.base.as_node())?;
+    self.write(".len()");
+
+    Ok(())
+  }
+
+  fn visit_borrow_expr(&mut self, borrow_expr: &node::BorrowExpr) -> pass::PassResult {
+    self.write(match borrow_expr.is_mutable {
+      true => "&mut ",
+      false => "&",
+    });
+
+    self.visit(borrow_expr.value.as_node())
+  }
+
+  fn visit_deref_expr(&mut self, deref_expr: &node::DerefExpr) -> pass::PassResult {
+    self.write("*");
+
+    self.visit(deref_expr.value.as_node())
+  }
+
+  fn visit_array_literal(&mut self, array_literal: &node::ArrayLiteral) -> pass::PassResult {
+    self.write("[");
+
+    for (index, element) in array_literal.elements.iter().enumerate() {
+      if index > 0 {
+        self.write(", ");
+      }
+
+      self.visit(element.as_node())?;
+    }
+
+    self.write("]");
+
+    Ok(())
+  }
+
+  fn visit_struct_literal(&mut self, struct_literal: &node::StructLiteral) -> pass::PassResult {
+    self.write(&format!("{} {{", struct_literal.path));
+
+    for (index, (field_name, value)) in struct_literal.fields.iter().enumerate() {
+      self.write(match index {
+        0 => " ",
+        _ => ", ",
+      });
+
+      self.write(&format!("{}: ", field_name));
+      self.visit(value.as_node())?;
+    }
+
+    self.write(match struct_literal.fields.is_empty() {
+      true => "}",
+      false => " }",
+    });
+
+    Ok(())
+  }
+
+  fn visit_variant_literal(&mut self, variant_literal: &node::VariantLiteral) -> pass::PassResult {
+    self.write(&variant_literal.path.to_string());
+
+    if variant_literal.arguments.is_empty() {
+      return Ok(());
+    }
+
+    self.write("(");
+
+    for (index, argument) in variant_literal.arguments.iter().enumerate() {
+      if index > 0 {
+        self.write(", ");
+      }
+
+      self.visit(argument.as_node())?;
+    }
+
+    self.write(")");
+
+    Ok(())
+  }
+
+  fn visit_match_expr(&mut self, match_expr: &node::MatchExpr) -> pass::PassResult {
+    self.write("match ");
+    self.visit(match_expr.subject.as_node())?;
+
+    if match_expr.arms.is_empty() && !self.has_comments_before(match_expr.span.end) {
+      self.write(" {}");
+
+      return Ok(());
+    }
+
+    self.write(" {\n");
+    self.indentation_level += 1;
+
+    // Each arm is written on its own line, and those whose body is
+    // an expression are followed by a comma.
+    for arm in &match_expr.arms {
+      self.write_comments_before(arm.span.start);
+      self.write_indentation();
+      self.visit(arm.pattern.as_node())?;
+      self.write(" => ");
+      self.visit(arm.body.as_node())?;
+
+      self.write(match arm.body {
+        node::MatchArmBody::Expr(_) => ",\n",
+        node::MatchArmBody::Block(_) => "\n",
+      });
+    }
+
+    self.write_comments_before(match_expr.span.end);
+    self.indentation_level -= 1;
+    self.write_indentation();
+    self.write("}");
+
+    Ok(())
+  }
+
+  fn visit_wildcard_pattern(&mut self, _: &pattern::WildcardPattern) -> pass::PassResult {
+    self.write("_");
+
+    Ok(())
+  }
+
+  fn visit_binding_pattern(
+    &mut self,
+    binding_pattern: &pattern::BindingPattern,
+  ) -> pass::PassResult {
+    self.write(&binding_pattern.name);
+
+    Ok(())
+  }
+
+  fn visit_variant_pattern(
+    &mut self,
+    variant_pattern: &pattern::VariantPattern,
+  ) -> pass::PassResult {
+    self.write(&variant_pattern.path.to_string());
+
+    if variant_pattern.fields.is_empty() {
+      return Ok(());
+    }
+
+    self.write("(");
+
+    for (index, field) in variant_pattern.fields.iter().enumerate() {
+      if index > 0 {
+        self.write(", ");
+      }
+
+      self.visit(field.as_node())?;
+    }
+
+    self.write(")");
+
+    Ok(())
+  }
+
+  fn visit_bool_literal(&mut self, bool_literal: &node::BoolLiteral) -> pass::PassResult {
+    self.write(&bool_literal.value.to_string());
+
+    Ok(())
+  }
+
+  fn visit_int_literal(&mut self, int_literal: &node::IntLiteral) -> pass::PassResult {
+    self.write(&int_literal.value);
+
+    Ok(())
+  }
+}
+
+// Format the given source, which must consist of a single
+// namespace, into its canonical format.
+pub fn format_source(source: &str) -> Result<String, diagnostic::Diagnostic> {
+  let (namespace, comments) = parser::parse_source_with_comments(source)?;
+  let mut pretty_print_pass = PrettyPrintPass::new(comments);
+
+  pretty_print_pass.visit_namespace(&namespace)?;
+
+  Ok(pretty_print_pass.output)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::span;
+
+  #[test]
+  fn pretty_print_pass_empty_namespace() {
+    assert_eq!(
+      "namespace foo {}\n",
+      format_source("namespace   foo\n{ }").unwrap()
+    );
+  }
+
+  #[test]
+  fn pretty_print_pass_functions() {
+    let source = "namespace foo { pub extern puts(s: i32, ...) ~ i32; \
+                  #[export_name=\"foo\"] pub fn main(a: &mut i8) ~ void { if a { return; } else if true { loop { break; } } \
+                  'outer: for i in 0..=10 { puts(i, bar(), 1); continue 'outer; } } }";
+
+    assert_eq!(
+      "namespace foo {
+  pub extern puts(s: i32, ...) ~ i32;
+
+  #[export_name = \"foo\"]
+  pub fn main(a: &mut i8) ~ void {
+    if a {
+      return;
+    } else if true {
+      loop {
+        break;
+      }
+    }
+    'outer: for i in 0..=10 {
+      puts(i, bar(), 1);
+      continue 'outer;
+    }
+  }
+}
+",
+      format_source(source).unwrap()
+    );
+  }
+
+  #[test]
+  fn pretty_print_pass_comments() {
+    let source = "// header
+namespace foo {
+    // leading
+    fn main() ~ void { // trailing
+        // inner
+        return;
+        // last
+    }
+}
+// footer";
+
+    let formatted_source = format_source(source).unwrap();
+
+    assert_eq!(
+      "// header
+namespace foo {
+  // leading
+  fn main() ~ void { // trailing
+    // inner
+    return;
+    // last
+  }
+}
+// footer
+",
+      formatted_source
+    );
+
+    // Formatting is idempotent.
+    assert_eq!(formatted_source, format_source(&formatted_source).unwrap());
+  }
+
+  #[test]
+  fn pretty_print_pass_trailing_comments_between_items() {
+    let source = "namespace foo {
+  fn a() ~ void {} // a
+  fn b() ~ void {}
+  trait Shape { fn area(self: &Self) ~ i32; }
+  impl Shape for i32 {
+    fn area(self: &Self) ~ i32 { return 1; } // area
+    fn id(self: &Self) ~ i32 { return 2; }
+  }
+}";
+
+    let formatted_source = format_source(source).unwrap();
+
+    assert_eq!(
+      "namespace foo {
+  fn a() ~ void {} // a
+
+  fn b() ~ void {}
+
+  trait Shape {
+    fn area(self: &Self) ~ i32;
+  }
+
+  impl Shape for i32 {
+    fn area(self: &Self) ~ i32 {
+      return 1;
+    } // area
+
+    fn id(self: &Self) ~ i32 {
+      return 2;
+    }
+  }
+}
+",
+      formatted_source
+    );
+
+    assert_eq!(formatted_source, format_source(&formatted_source).unwrap());
+  }
+
+  #[test]
+  fn pretty_print_pass_nested_namespaces() {
+    let source = "namespace foo { use bar::{baz,qux as quux}; use bar::baz as corge; \
+                  namespace bar { pub fn baz() ~ void { bar::qux(); } fn qux() ~ void {} } }";
+
+    let formatted_source = format_source(source).unwrap();
+
+    assert_eq!(
+      "namespace foo {
+  use bar::{baz, qux as quux};
+  use bar::baz as corge;
+
+  namespace bar {
+    pub fn baz() ~ void {
+      bar::qux();
+    }
+
+    fn qux() ~ void {}
+  }
+}
+",
+      formatted_source
+    );
+
+    assert_eq!(formatted_source, format_source(&formatted_source).unwrap());
+  }
+
+  #[test]
+  fn pretty_print_pass_structs() {
+    let source = "namespace foo { pub struct Point { x: i32, y: &mut bar::Unit } struct Empty {} \
+                  fn main(p: Point) ~ void { let mut a: Point = Point { x: p.y.z, y: Empty{} }; \
+                  a.x = 1; let b = f(Point{x:1}); if a.x { } } }";
+
+    let formatted_source = format_source(source).unwrap();
+
+    assert_eq!(
+      "namespace foo {
+  pub struct Point {
+    x: i32,
+    y: &mut bar::Unit,
+  }
+
+  struct Empty {}
+
+  fn main(p: Point) ~ void {
+    let mut a: Point = Point { x: p.y.z, y: Empty {} };
+    a.x = 1;
+    let b = f(Point { x: 1 });
+    if a.x {}
+  }
+}
+",
+      formatted_source
+    );
+
+    assert_eq!(formatted_source, format_source(&formatted_source).unwrap());
+  }
+
+  #[test]
+  fn pretty_print_pass_arrays() {
+    let source = "namespace foo { struct Grid { cells: [[bool;3];3] } \
+                  fn sum(values: &[i32]) ~ i64 { return values.len(); } \
+                  fn main(g: Grid) ~ void { let a: [i8; 2] = [1,2,]; g.cells[a[0]][1] = true; sum([]); } }";
+
+    let formatted_source = format_source(source).unwrap();
+
+    assert_eq!(
+      "namespace foo {
+  struct Grid {
+    cells: [[bool; 3]; 3],
+  }
+
+  fn sum(values: &[i32]) ~ i64 {
+    return values.len();
+  }
+
+  fn main(g: Grid) ~ void {
+    let a: [i8; 2] = [1, 2];
+    g.cells[a[0]][1] = true;
+    sum([]);
+  }
+}
+",
+      formatted_source
+    );
+
+    assert_eq!(formatted_source, format_source(&formatted_source).unwrap());
+  }
+
+  #[test]
+  fn pretty_print_pass_references() {
+    let source =
+      "namespace foo { fn f(p: &mut i32) ~ &i32 { let a = & mut *p; *a = * & 1; return &*p; } }";
+
+    let formatted_source = format_source(source).unwrap();
+
+    assert_eq!(
+      "namespace foo {
+  fn f(p: &mut i32) ~ &i32 {
+    let a = &mut *p;
+    *a = *&1;
+    return &*p;
+  }
+}
+",
+      formatted_source
+    );
+
+    assert_eq!(formatted_source, format_source(&formatted_source).unwrap());
+  }
+
+  #[test]
+  fn pretty_print_pass_generics() {
+    let source = "namespace foo { fn pick< T,U >(a: T, b: &U) ~ T { return pick(a, b); } }";
+
+    let formatted_source = format_source(source).unwrap();
+
+    assert_eq!(
+      "namespace foo {
+  fn pick<T, U>(a: T, b: &U) ~ T {
+    return pick(a, b);
+  }
+}
+",
+      formatted_source
+    );
+
+    assert_eq!(formatted_source, format_source(&formatted_source).unwrap());
+  }
+
+  #[test]
+  fn pretty_print_pass_traits() {
+    let source = "namespace foo { pub trait Shape { fn area(self: &Self) ~ i32; } \
+                  impl Shape for Square { fn area(self: &Self) ~ i32 { return 1; } fn f() ~ void {} } \
+                  impl Shape for i32 {} \
+                  fn total< T:Shape+Other >(a: &T, b: &dyn Shape) ~ i32 { return Shape::area(a); } }";
+
+    let formatted_source = format_source(source).unwrap();
+
+    assert_eq!(
+      "namespace foo {
+  pub trait Shape {
+    fn area(self: &Self) ~ i32;
+  }
+
+  impl Shape for Square {
+    fn area(self: &Self) ~ i32 {
+      return 1;
+    }
+
+    fn f() ~ void {}
+  }
+
+  impl Shape for i32 {}
+
+  fn total<T: Shape + Other>(a: &T, b: &dyn Shape) ~ i32 {
+    return Shape::area(a);
+  }
+}
+",
+      formatted_source
+    );
+
+    assert_eq!(formatted_source, format_source(&formatted_source).unwrap());
+  }
+
+  #[test]
+  fn pretty_print_pass_enums() {
+    let source =
+      "namespace foo { pub enum Shape { Circle(i32), Rect(&Point, bool), Empty } enum Never {} \
+                  fn main(s: Shape) ~ i32 { match s { Shape::Empty => {} _ => { f(); } } \
+                  let a = Shape::Circle(1); return match a { Shape::Circle(1) => 0, \
+                  Shape::Rect(_, true) => 2, // trailing
+                  x => 3 }; } }";
+
+    let formatted_source = format_source(source).unwrap();
+
+    assert_eq!(
+      "namespace foo {
+  pub enum Shape {
+    Circle(i32),
+    Rect(&Point, bool),
+    Empty,
+  }
+
+  enum Never {}
+
+  fn main(s: Shape) ~ i32 {
+    match s {
+      Shape::Empty => {}
+      _ => {
+        f();
+      }
+    }
+    let a = Shape::Circle(1);
+    return match a {
+      Shape::Circle(1) => 0,
+      Shape::Rect(_, true) => 2, // trailing
+      x => 3,
+    };
+  }
+}
+",
+      formatted_source
+    );
+
+    assert_eq!(formatted_source, format_source(&formatted_source).unwrap());
+  }
+
+  #[test]
+  fn pretty_print_pass_parse_error() {
+    assert_eq!(true, format_source("namespace foo { fn }").is_err());
+  }
+
+  #[test]
+  fn pretty_print_pass_trailing_input() {
+    // Nothing past the namespace may be dropped from the output.
+    let diagnostic = format_source("namespace foo {}\nnamespace bar { fn baz() ~ void {} }")
+      .err()
+      .unwrap();
+
+    assert_eq!(
+      "unexpected token `KeywordNamespace`, expected the end of input",
+      diagnostic.message
+    );
+    assert_eq!(Some(span::Span { start: 17, end: 26 }), diagnostic.span);
+
+    assert_eq!(
+      "unexpected character `?`",
+      format_source("namespace foo { fn bar() ~ void {} ? fn baz() ~ void {} }")
+        .err()
+        .unwrap()
+        .message
+    );
+
+    // The closing brace of the namespace may not be omitted.
+    assert_eq!(
+      true,
+      format_source("namespace foo { fn bar() ~ void {}").is_err()
+    );
+  }
+}
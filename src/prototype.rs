@@ -1,20 +1,136 @@
 use crate::node;
 use crate::pass;
+use crate::span;
 
 pub type Parameter = (String, node::KindGroup);
 
-#[derive(Hash, Eq, PartialEq, Debug)]
+// A kind a function is generic over, along with the traits the
+// kinds substituted for it must implement (ex. `T: Shape`).
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenericParameter {
+  pub name: String,
+  pub bounds: Vec<node::Path>,
+}
+
+impl GenericParameter {
+  pub fn is_same_as(&self, other: &GenericParameter) -> bool {
+    self.name == other.name
+      && self.bounds.len() == other.bounds.len()
+      && self
+        .bounds
+        .iter()
+        .zip(&other.bounds)
+        .all(|(bound, other_bound)| bound.segments == other_bound.segments)
+  }
+}
+
+impl std::fmt::Display for GenericParameter {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.name)?;
+
+    if !self.bounds.is_empty() {
+      let bounds = self
+        .bounds
+        .iter()
+        .map(|bound| bound.to_string())
+        .collect::<Vec<_>>();
+
+      write!(f, ": {}", bounds.join(" + "))?;
+    }
+
+    Ok(())
+  }
+}
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Prototype {
+  pub id: node::NodeId,
+  pub span: span::Span,
   pub name: String,
   pub parameters: Vec<Parameter>,
   pub is_variadic: bool,
+  // The kinds the function is generic over (ex. `T` for
+  // `fn max<T>(a: T, b: T) ~ T`), which are replaced by concrete
+  // kinds when the function is instantiated.
+  pub generic_parameters: Vec<GenericParameter>,
+  // The kinds substituted for the generic parameters of the
+  // function this prototype was instantiated from, if any.
+  pub type_arguments: Vec<node::AnyKindNode>,
   pub return_kind_group: node::KindGroup,
 }
 
+impl Prototype {
+  pub fn is_generic(&self) -> bool {
+    !self.generic_parameters.is_empty()
+  }
+
+  pub fn find_generic_parameter(&self, name: &str) -> Option<&GenericParameter> {
+    self
+      .generic_parameters
+      .iter()
+      .find(|generic_parameter| generic_parameter.name == name)
+  }
+
+  // The name under which the function is declared, which includes
+  // the type arguments of instances of generic functions (ex.
+  // `max<i32>`), so that each instance is declared separately.
+  pub fn get_instance_name(&self) -> String {
+    if self.type_arguments.is_empty() {
+      return self.name.clone();
+    }
+
+    let type_arguments = self
+      .type_arguments
+      .iter()
+      .map(|kind| kind.to_string())
+      .collect::<Vec<_>>();
+
+    format!("{}<{}>", self.name, type_arguments.join(", "))
+  }
+
+  // Whether both prototypes accept and return the same kinds, in
+  // which case they describe the same function. Parameter names
+  // are irrelevant.
+  pub fn is_compatible_with(&self, other: &Prototype) -> bool {
+    self.parameters.len() == other.parameters.len()
+      && self
+        .parameters
+        .iter()
+        .zip(&other.parameters)
+        .all(|((_, kind_group), (_, other_kind_group))| kind_group.is_same_as(other_kind_group))
+      && self.is_variadic == other.is_variadic
+      && self.generic_parameters.len() == other.generic_parameters.len()
+      && self
+        .generic_parameters
+        .iter()
+        .zip(&other.generic_parameters)
+        .all(|(generic_parameter, other)| generic_parameter.is_same_as(other))
+      && self.return_kind_group.is_same_as(&other.return_kind_group)
+  }
+}
+
 impl node::Node for Prototype {
-  fn accept(&mut self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
     pass.visit_prototype(self)?;
 
     Ok(())
   }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    let mut children: Vec<&dyn node::Node> = self
+      .parameters
+      .iter()
+      .map(|(_, kind_group)| kind_group as &dyn node::Node)
+      .collect();
+
+    children.push(&self.return_kind_group);
+
+    children
+  }
 }
@@ -0,0 +1,38 @@
+use crate::node;
+use crate::pass;
+
+// The kind of a value which refers to another value, stored
+// elsewhere (ex. `&mut Point`). References are made of a pointer
+// to the referenced value, and only allow it to be modified if
+// they are mutable.
+//
+// References are written as part of a kind group, whose markers
+// are turned into this kind for the values of the group (see
+// [`node::KindGroup::to_value_kind`]).
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReferenceKind {
+  pub referenced_kind: Box<node::AnyKindNode>,
+  pub is_mutable: bool,
+}
+
+impl std::fmt::Display for ReferenceKind {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self.is_mutable {
+      true => write!(f, "&mut {}", self.referenced_kind),
+      false => write!(f, "&{}", self.referenced_kind),
+    }
+  }
+}
+
+impl node::Node for ReferenceKind {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_reference_kind(self)?;
+
+    Ok(())
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    vec![self.referenced_kind.as_node()]
+  }
+}
@@ -0,0 +1,160 @@
+use crate::{diagnostic, namespace, parser};
+
+// The version of the schema which trees are serialized with. It
+// must be incremented whenever the shape of a node changes, so
+// that external tools can reject trees they do not understand.
+pub const SCHEMA_VERSION: u32 = 11;
+
+#[derive(serde::Serialize)]
+struct Document<'a> {
+  version: u32,
+  namespace: &'a namespace::Namespace,
+}
+
+// Read ahead of the rest of the document, so that trees of another
+// version are reported as such, rather than as malformed.
+#[derive(serde::Deserialize)]
+struct DocumentVersion {
+  version: u32,
+}
+
+#[derive(serde::Deserialize)]
+struct OwnedDocument {
+  namespace: namespace::Namespace,
+}
+
+fn make_error(message: String) -> diagnostic::Diagnostic {
+  diagnostic::Diagnostic {
+    message,
+    severity: diagnostic::DiagnosticSeverity::Error,
+    span: None,
+    notes: vec![],
+  }
+}
+
+pub fn to_json(namespace: &namespace::Namespace) -> String {
+  serde_json::to_string_pretty(&Document {
+    version: SCHEMA_VERSION,
+    namespace,
+  })
+  .unwrap()
+}
+
+pub fn from_json(json: &str) -> Result<namespace::Namespace, diagnostic::Diagnostic> {
+  let document_version = serde_json::from_str::<DocumentVersion>(json)
+    .map_err(|error| make_error(format!("malformed tree: {}", error)))?;
+
+  if document_version.version != SCHEMA_VERSION {
+    return Err(make_error(format!(
+      "unsupported tree version `{}`, expected `{}`",
+      document_version.version, SCHEMA_VERSION
+    )));
+  }
+
+  let document = serde_json::from_str::<OwnedDocument>(json)
+    .map_err(|error| make_error(format!("malformed tree: {}", error)))?;
+
+  Ok(document.namespace)
+}
+
+// Parse the given source, which must consist of a single
+// namespace, and serialize the resulting tree.
+pub fn dump_source(source: &str) -> Result<String, diagnostic::Diagnostic> {
+  Ok(to_json(&parser::parse_source(source)?))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{node, pass::Pass, pretty_print_pass};
+
+  const SOURCE: &str = "namespace foo {
+  extern puts(s: &i32, ...) ~ i32;
+
+  fn main(a: i8) ~ bool {
+    'outer: for i in 0..=10 {
+      puts(i, true);
+      break 'outer;
+    }
+    return false;
+  }
+}
+";
+
+  #[test]
+  fn serialization_round_trip() {
+    let json = dump_source(SOURCE).unwrap();
+    let namespace = from_json(&json).unwrap();
+    let mut pretty_print_pass = pretty_print_pass::PrettyPrintPass::new(vec![]);
+
+    assert_eq!(true, pretty_print_pass.visit_namespace(&namespace).is_ok());
+    assert_eq!(SOURCE, pretty_print_pass.get_output());
+
+    // Top-level nodes are serialized in declaration order.
+    assert_eq!(json, to_json(&namespace));
+  }
+
+  #[test]
+  fn serialization_reserves_ids() {
+    let namespace = from_json(&dump_source(SOURCE).unwrap()).unwrap();
+
+    // Ids allocated for the deserialized tree (ex. by rewriting
+    // passes) never collide with those read back.
+    let id = namespace.node_ids.allocate();
+
+    assert_eq!(true, id > namespace.id);
+
+    for top_level_node in namespace.symbol_table.values() {
+      let top_level_node_id = match top_level_node {
+        namespace::TopLevelNode::Function(function) => function.body.id,
+        namespace::TopLevelNode::External(external) => external.prototype.id,
+        namespace::TopLevelNode::Namespace(namespace) => namespace.id,
+        namespace::TopLevelNode::Struct(struct_decl) => struct_decl.id,
+        namespace::TopLevelNode::Enum(enum_decl) => enum_decl.id,
+        namespace::TopLevelNode::Trait(trait_decl) => trait_decl.id,
+      };
+
+      assert_eq!(true, id > top_level_node_id);
+    }
+  }
+
+  #[test]
+  fn serialization_reproducible_ids() {
+    let json = dump_source(SOURCE).unwrap();
+
+    // Ids do not depend on what was parsed before.
+    assert_eq!(
+      true,
+      dump_source("namespace bar { fn baz() ~ void {} }").is_ok()
+    );
+    assert_eq!(json, dump_source(SOURCE).unwrap());
+  }
+
+  #[test]
+  fn serialization_trailing_input() {
+    assert_eq!(
+      true,
+      dump_source(&format!("{}namespace bar {{}}", SOURCE)).is_err()
+    );
+  }
+
+  #[test]
+  fn serialization_version_mismatch() {
+    let json = dump_source(SOURCE).unwrap().replacen(
+      &format!("\"version\": {}", SCHEMA_VERSION),
+      "\"version\": 0",
+      1,
+    );
+
+    assert_eq!(
+      true,
+      matches!(from_json(&json), Err(diagnostic) if diagnostic.message.contains("version `0`"))
+    );
+  }
+
+  #[test]
+  fn serialization_malformed() {
+    assert_eq!(true, from_json("{\"version\": 1}").is_err());
+    assert_eq!(true, from_json("[]").is_err());
+  }
+}
@@ -0,0 +1,30 @@
+use crate::node;
+use crate::pass;
+
+// The kind of a view into any amount of values of the same kind,
+// stored next to each other (ex. `[i32]`). Slices are made of a
+// pointer to their first value, and the amount of values. Arrays
+// are turned into slices where a slice is expected.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SliceKind {
+  pub element_kind: Box<node::AnyKindNode>,
+}
+
+impl std::fmt::Display for SliceKind {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "[{}]", self.element_kind)
+  }
+}
+
+impl node::Node for SliceKind {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_slice_kind(self)?;
+
+    Ok(())
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    vec![self.element_kind.as_node()]
+  }
+}
@@ -0,0 +1,63 @@
+// A range of characters within the source, from `start`
+// (inclusive) to `end` (exclusive).
+#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span {
+  pub start: usize,
+  pub end: usize,
+}
+
+impl Span {
+  // Create a span covering both this and the other span, as
+  // well as anything in between.
+  pub fn to(&self, other: Span) -> Span {
+    Span {
+      start: self.start.min(other.start),
+      end: self.end.max(other.end),
+    }
+  }
+
+  // Determine the line and column (both starting at one) on
+  // which the span starts within the source.
+  pub fn get_line_and_column(&self, source: &[char]) -> (usize, usize) {
+    let mut line = 1;
+    let mut column = 1;
+
+    for character in source.iter().take(self.start) {
+      if *character == '\n' {
+        line += 1;
+        column = 1;
+      } else {
+        column += 1;
+      }
+    }
+
+    (line, column)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn span_to() {
+    let span = Span { start: 4, end: 6 }.to(Span { start: 1, end: 2 });
+
+    assert_eq!(Span { start: 1, end: 6 }, span);
+  }
+
+  #[test]
+  fn span_get_line_and_column() {
+    let source = "fn\n  foo".chars().collect::<Vec<_>>();
+
+    assert_eq!(
+      (1, 1),
+      Span { start: 0, end: 2 }.get_line_and_column(&source)
+    );
+    assert_eq!(
+      (2, 3),
+      Span { start: 5, end: 8 }.get_line_and_column(&source)
+    );
+  }
+}
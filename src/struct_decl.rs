@@ -0,0 +1,65 @@
+use crate::{node, pass, span};
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructField {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  pub name: String,
+  pub kind_group: node::KindGroup,
+}
+
+impl node::Node for StructField {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_children(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    vec![&self.kind_group]
+  }
+}
+
+// A user-defined kind, made up of named fields (ex. `struct Point
+// { x: i32, y: i32 }`). Fields are laid out in declaration order.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructDecl {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  pub is_public: bool,
+  pub name: String,
+  pub fields: Vec<StructField>,
+}
+
+impl StructDecl {
+  // Find the field of the given name, along with its position.
+  pub fn find_field(&self, name: &str) -> Option<(usize, &StructField)> {
+    self
+      .fields
+      .iter()
+      .enumerate()
+      .find(|(_, field)| field.name == name)
+  }
+}
+
+impl node::Node for StructDecl {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_struct_decl(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    self
+      .fields
+      .iter()
+      .map(|field| field as &dyn node::Node)
+      .collect()
+  }
+}
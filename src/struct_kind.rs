@@ -0,0 +1,26 @@
+use crate::node;
+use crate::pass;
+
+// The kind of the values of a user-defined struct. Like primitive
+// kinds, struct kinds are compared by their contents, so the path
+// is expected to be resolved (relative to the outermost namespace)
+// before kinds are compared.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructKind {
+  pub path: Vec<String>,
+}
+
+impl std::fmt::Display for StructKind {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.path.join("::"))
+  }
+}
+
+impl node::Node for StructKind {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_struct_kind(self)?;
+
+    Ok(())
+  }
+}
@@ -4,17 +4,41 @@ use crate::diagnostic;
 
 pub enum Token {
   Identifier(String),
+  Label(String),
   Integer(Vec<char>),
   LiteralInt(Vec<char>),
   LiteralBool(bool),
+  LiteralString(String),
   KeywordPub,
   KeywordFn,
   KeywordExtern,
   KeywordNamespace,
   KeywordReturn,
   KeywordMut,
+  KeywordIf,
+  KeywordElse,
+  KeywordWhile,
+  KeywordLoop,
+  KeywordBreak,
+  KeywordContinue,
+  KeywordFor,
+  KeywordIn,
+  KeywordUse,
+  KeywordAs,
+  KeywordStruct,
+  KeywordLet,
+  KeywordEnum,
+  KeywordMatch,
+  KeywordTrait,
+  KeywordImpl,
+  KeywordDyn,
   TypeVoid,
+  TypeBool,
+  TypeInt8,
+  TypeInt16,
   TypeInt32,
+  TypeInt64,
+  TypeInt128,
   SymbolBraceL,
   SymbolBraceR,
   SymbolParenthesesL,
@@ -22,10 +46,23 @@ pub enum Token {
   SymbolTilde,
   SymbolSemiColon,
   SymbolColon,
+  SymbolDoubleColon,
   SymbolAmpersand,
+  SymbolAsterisk,
   SymbolComma,
   SymbolVariadic,
+  SymbolRange,
+  SymbolRangeInclusive,
   SymbolArrow,
+  SymbolHash,
+  SymbolBracketL,
+  SymbolBracketR,
+  SymbolEqual,
+  SymbolDot,
+  SymbolFatArrow,
+  SymbolLessThan,
+  SymbolGreaterThan,
+  SymbolPlus,
 }
 
 impl std::fmt::Display for Token {
@@ -40,18 +77,42 @@ pub fn get_keyword_or_type_token(identifier_str: &str) -> Result<Token, diagnost
     "fn" => Token::KeywordFn,
     "extern" => Token::KeywordExtern,
     "void" => Token::TypeVoid,
+    "bool" => Token::TypeBool,
+    "i8" => Token::TypeInt8,
+    "i16" => Token::TypeInt16,
     "i32" => Token::TypeInt32,
+    "i64" => Token::TypeInt64,
+    "i128" => Token::TypeInt128,
     "namespace" => Token::KeywordNamespace,
     "return" => Token::KeywordReturn,
     "true" => Token::LiteralBool(true),
     "false" => Token::LiteralBool(false),
     "mut" => Token::KeywordMut,
+    "if" => Token::KeywordIf,
+    "else" => Token::KeywordElse,
+    "while" => Token::KeywordWhile,
+    "loop" => Token::KeywordLoop,
+    "break" => Token::KeywordBreak,
+    "continue" => Token::KeywordContinue,
+    "for" => Token::KeywordFor,
+    "in" => Token::KeywordIn,
+    "use" => Token::KeywordUse,
+    "as" => Token::KeywordAs,
+    "struct" => Token::KeywordStruct,
+    "let" => Token::KeywordLet,
+    "enum" => Token::KeywordEnum,
+    "match" => Token::KeywordMatch,
+    "trait" => Token::KeywordTrait,
+    "impl" => Token::KeywordImpl,
+    "dyn" => Token::KeywordDyn,
     "..." => Token::SymbolVariadic,
     "->" => Token::SymbolArrow,
     _ => {
       return Err(diagnostic::Diagnostic {
         message: format!("identifier `{}` is not a keyword", identifier_str),
         severity: diagnostic::DiagnosticSeverity::Error,
+        span: None,
+        notes: vec![],
       })
     }
   })
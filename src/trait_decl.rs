@@ -0,0 +1,45 @@
+use crate::{node, pass, prototype, span};
+
+// A set of methods which kinds may implement (ex. `trait Shape {
+// fn area(self: &Self) ~ i32; }`). Within the trait, `Self` names
+// the kind implementing it. Methods are called through the trait
+// (ex. `Shape::area(&square)`), and are dispatched on the kind of
+// their first parameter, which must be a reference to `Self`.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TraitDecl {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  pub is_public: bool,
+  pub name: String,
+  pub methods: Vec<prototype::Prototype>,
+}
+
+impl TraitDecl {
+  // Find the method of the given name, along with its position.
+  pub fn find_method(&self, name: &str) -> Option<(usize, &prototype::Prototype)> {
+    self
+      .methods
+      .iter()
+      .enumerate()
+      .find(|(_, method)| method.name == name)
+  }
+}
+
+impl node::Node for TraitDecl {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_trait_decl(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    self
+      .methods
+      .iter()
+      .map(|method| method as &dyn node::Node)
+      .collect()
+  }
+}
@@ -0,0 +1,2560 @@
+use crate::{
+  array_kind, block, bool_kind, diagnostic, enum_decl, enum_kind, external, function, impl_decl,
+  int_kind, namespace, node, pass, pass::Pass, pattern, prototype, reference_kind, span,
+  struct_decl, struct_kind, trait_decl, void_kind,
+};
+
+// A value bound to a name within the body of a function.
+#[derive(Clone)]
+struct Variable {
+  // The kind of the value, or [`None`] if it could not be
+  // determined.
+  kind: Option<node::AnyKindNode>,
+  // Only `let` bindings may be declared without `mut`, in which
+  // case they may be neither assigned to nor borrowed mutably.
+  is_mutable: bool,
+}
+
+// Resolves the names used within function bodies, and ensures
+// that values are used according to their kinds (ex. that calls
+// provide the arguments expected by their callee).
+pub struct TypeCheckPass {
+  diagnostics: Vec<diagnostic::Diagnostic>,
+  // The prototypes of all functions and externs that may be
+  // called, by their path relative to the outermost namespace (ex.
+  // `a::b`), which calls are expected to have been resolved to.
+  prototypes: std::collections::HashMap<String, prototype::Prototype>,
+  // The declarations of all structs, by their path relative to the
+  // outermost namespace, which struct kinds are expected to have
+  // been resolved to.
+  structs: std::collections::HashMap<String, struct_decl::StructDecl>,
+  // The declarations of all enums, by their path relative to the
+  // outermost namespace.
+  enums: std::collections::HashMap<String, enum_decl::EnumDecl>,
+  // The declarations of all traits, by their path relative to the
+  // outermost namespace.
+  traits: std::collections::HashMap<String, trait_decl::TraitDecl>,
+  // The path of the trait and the kind of every implementation.
+  implementations: std::collections::HashSet<(String, node::AnyKindNode)>,
+  // The paths of the traits bounding each generic parameter of the
+  // function being visited, by name.
+  generic_bounds: std::collections::HashMap<String, Vec<String>>,
+  // The number of namespaces enclosing the node being visited.
+  namespace_depth: usize,
+  // The values bound to names, with one map per lexical scope,
+  // innermost last.
+  variable_scopes: Vec<std::collections::HashMap<String, Variable>>,
+  // The return kind of the function being visited.
+  return_kind: Option<node::AnyKindNode>,
+  // The kinds of all expressions whose kind could be determined.
+  expr_kinds: node::NodeMap<node::AnyKindNode>,
+  // The kinds inferred for the generic parameters of the callee of
+  // each call to a generic function, in order.
+  call_type_arguments: node::NodeMap<Vec<node::AnyKindNode>>,
+}
+
+impl TypeCheckPass {
+  pub fn new() -> Self {
+    Self {
+      diagnostics: vec![],
+      prototypes: std::collections::HashMap::new(),
+      structs: std::collections::HashMap::new(),
+      enums: std::collections::HashMap::new(),
+      traits: std::collections::HashMap::new(),
+      implementations: std::collections::HashSet::new(),
+      generic_bounds: std::collections::HashMap::new(),
+      namespace_depth: 0,
+      variable_scopes: vec![],
+      return_kind: None,
+      expr_kinds: node::NodeMap::new(),
+      call_type_arguments: node::NodeMap::new(),
+    }
+  }
+
+  pub fn get_expr_kinds(&self) -> &node::NodeMap<node::AnyKindNode> {
+    &self.expr_kinds
+  }
+
+  pub fn get_call_type_arguments(&self) -> &node::NodeMap<Vec<node::AnyKindNode>> {
+    &self.call_type_arguments
+  }
+
+  fn push_error(&mut self, span: span::Span, message: String) {
+    self.diagnostics.push(diagnostic::Diagnostic {
+      message,
+      severity: diagnostic::DiagnosticSeverity::Error,
+      span: Some(span),
+      notes: vec![],
+    });
+  }
+
+  // Whether values of the given kind implement the trait of the
+  // given path. Generic kinds implement the traits they are bound
+  // by, and trait objects implement their own trait.
+  fn implements(&self, kind: &node::AnyKindNode, trait_path: &str) -> bool {
+    match kind {
+      node::AnyKindNode::GenericKind(generic_kind) => self
+        .generic_bounds
+        .get(&generic_kind.name)
+        .is_some_and(|bounds| bounds.iter().any(|bound| bound == trait_path)),
+      node::AnyKindNode::DynKind(dyn_kind) => dyn_kind.trait_path.join("::") == trait_path,
+      _ => self
+        .implementations
+        .contains(&(String::from(trait_path), kind.clone())),
+    }
+  }
+
+  // Whether a value of one kind may be used where a value of
+  // another kind is expected. Arrays may be used as slices of the
+  // same elements, mutable references as immutable ones, and
+  // references to a kind implementing a trait as trait objects.
+  fn can_coerce(&self, from_kind: &node::AnyKindNode, to_kind: &node::AnyKindNode) -> bool {
+    match (from_kind, to_kind) {
+      (node::AnyKindNode::ArrayKind(array_kind), node::AnyKindNode::SliceKind(slice_kind)) => {
+        array_kind.element_kind == slice_kind.element_kind
+      }
+      (
+        node::AnyKindNode::ReferenceKind(from_reference_kind),
+        node::AnyKindNode::ReferenceKind(to_reference_kind),
+      ) => {
+        let is_coercible_value = match to_reference_kind.referenced_kind.as_ref() {
+          node::AnyKindNode::DynKind(dyn_kind)
+            if !matches!(
+              *from_reference_kind.referenced_kind,
+              node::AnyKindNode::DynKind(_)
+            ) =>
+          {
+            self.implements(
+              &from_reference_kind.referenced_kind,
+              &dyn_kind.trait_path.join("::"),
+            )
+          }
+          to_referenced_kind => from_reference_kind.referenced_kind.as_ref() == to_referenced_kind,
+        };
+
+        (from_reference_kind.is_mutable || !to_reference_kind.is_mutable) && is_coercible_value
+      }
+      _ => from_kind == to_kind,
+    }
+  }
+
+  // Follow references until a kind which is not a reference is
+  // reached, as field accesses and indexing do (ex. `a.x` where `a`
+  // is of kind `&Point`).
+  fn auto_deref(kind: node::AnyKindNode) -> node::AnyKindNode {
+    match kind {
+      node::AnyKindNode::ReferenceKind(reference_kind) => {
+        Self::auto_deref(*reference_kind.referenced_kind)
+      }
+      kind => kind,
+    }
+  }
+
+  // Find the innermost variable declared under the given name.
+  fn find_variable(&self, name: &str) -> Option<&Variable> {
+    self
+      .variable_scopes
+      .iter()
+      .rev()
+      .find_map(|scope| scope.get(name))
+  }
+
+  // Find the name of the immutable binding which holds the given
+  // place, if any (ex. `a` for `a.b[0]`, if `a` is not declared
+  // with `mut`). Places reached through a reference or a slice are
+  // instead governed by the mutability of that reference.
+  fn find_immutable_binding<'a>(&self, place: &'a node::AnyExprNode) -> Option<&'a str> {
+    let base = match place {
+      node::AnyExprNode::VariableRef(variable_ref) => {
+        return match self.find_variable(&variable_ref.name) {
+          Some(variable) if !variable.is_mutable => Some(&variable_ref.name),
+          _ => None,
+        };
+      }
+      node::AnyExprNode::FieldAccessExpr(field_access_expr) => &field_access_expr.base,
+      node::AnyExprNode::IndexExpr(index_expr) => &index_expr.base,
+      _ => return None,
+    };
+
+    match self.expr_kinds.get(&base.get_id()) {
+      Some(node::AnyKindNode::ReferenceKind(_)) | Some(node::AnyKindNode::SliceKind(_)) => None,
+      _ => self.find_immutable_binding(base),
+    }
+  }
+
+  // Find the immutable reference through which the place would be
+  // modified, if any (ex. `a` of kind `&Point`, for `a.x`). Places
+  // not reached through references are left unchecked.
+  fn find_immutable_reference(&self, place: &node::AnyExprNode) -> Option<node::AnyKindNode> {
+    // Field accesses and indexing follow any amount of references,
+    // whereas a dereference follows a single one.
+    let (base, is_auto_deref) = match place {
+      node::AnyExprNode::FieldAccessExpr(field_access_expr) => (&field_access_expr.base, true),
+      node::AnyExprNode::IndexExpr(index_expr) => (&index_expr.base, true),
+      node::AnyExprNode::DerefExpr(deref_expr) => (&deref_expr.value, false),
+      _ => return None,
+    };
+
+    let mut base_kind = self.expr_kinds.get(&base.get_id());
+
+    while let Some(node::AnyKindNode::ReferenceKind(reference_kind)) = base_kind {
+      if !reference_kind.is_mutable {
+        return base_kind.cloned();
+      }
+
+      if !is_auto_deref {
+        break;
+      }
+
+      base_kind = Some(&reference_kind.referenced_kind);
+    }
+
+    // The base may itself be reached through a reference (ex. `a.b`
+    // of kind `&mut Point`, for `a.b.x`).
+    match base.is_place() {
+      true => self.find_immutable_reference(base),
+      false => None,
+    }
+  }
+
+  // Report a mismatch if the kind of a value is known, and it
+  // cannot be used as the expected kind.
+  fn expect_kind(
+    &mut self,
+    span: span::Span,
+    expected_kind: &node::AnyKindNode,
+    actual_kind: &Option<node::AnyKindNode>,
+    context: &str,
+  ) {
+    if let Some(actual_kind) = actual_kind {
+      if !self.can_coerce(actual_kind, expected_kind) {
+        self.push_error(
+          span,
+          format!(
+            "mismatched kinds for {}: expected `{}`, found `{}`",
+            context, expected_kind, actual_kind
+          ),
+        );
+      }
+    }
+  }
+
+  // Determine the kind of the value produced by an expression,
+  // while checking the expression itself.
+  //
+  // Returns [`None`] if the kind could not be determined, in
+  // which case the problem (if any) was already reported.
+  fn infer_expr_kind(&mut self, expr: &node::AnyExprNode) -> Option<node::AnyKindNode> {
+    self.infer_expr_kind_as(expr, None)
+  }
+
+  // Determine the kind of the value produced by an expression,
+  // given the kind expected by its context. The expected kind
+  // is only used to type literals whose kind depends on their
+  // context (ex. integer literals), and mismatches are left to
+  // the caller to report.
+  fn infer_expr_kind_as(
+    &mut self,
+    expr: &node::AnyExprNode,
+    expected_kind: Option<&node::AnyKindNode>,
+  ) -> Option<node::AnyKindNode> {
+    let expr_kind = match expr {
+      node::AnyExprNode::Literal(literal) => match literal {
+        node::AnyLiteralNode::BoolLiteral(_) => {
+          Some(node::AnyKindNode::BoolKind(bool_kind::BoolKind {}))
+        }
+        node::AnyLiteralNode::IntLiteral(int_literal) => Some(node::AnyKindNode::IntKind(
+          self.check_int_literal(int_literal, expected_kind),
+        )),
+      },
+      node::AnyExprNode::VariableRef(variable_ref) => {
+        let variable = self.find_variable(&variable_ref.name).cloned();
+
+        if variable.is_none() {
+          self.push_error(
+            variable_ref.span,
+            format!("cannot find value `{}` in this scope", variable_ref.name),
+          );
+        }
+
+        variable.and_then(|variable| variable.kind)
+      }
+      node::AnyExprNode::CallExpr(call_expr) => self.check_call_expr(call_expr),
+      node::AnyExprNode::FieldAccessExpr(field_access_expr) => {
+        self.check_field_access_expr(field_access_expr)
+      }
+      node::AnyExprNode::StructLiteral(struct_literal) => self.check_struct_literal(struct_literal),
+      node::AnyExprNode::VariantLiteral(variant_literal) => {
+        self.check_variant_literal(variant_literal)
+      }
+      node::AnyExprNode::MatchExpr(match_expr) => self.check_match_expr(match_expr),
+      node::AnyExprNode::ArrayLiteral(array_literal) => {
+        self.check_array_literal(array_literal, expected_kind)
+      }
+      node::AnyExprNode::IndexExpr(index_expr) => self.check_index_expr(index_expr),
+      node::AnyExprNode::LenExpr(len_expr) => self.check_len_expr(len_expr),
+      node::AnyExprNode::BorrowExpr(borrow_expr) => {
+        self.check_borrow_expr(borrow_expr, expected_kind)
+      }
+      node::AnyExprNode::DerefExpr(deref_expr) => self.check_deref_expr(deref_expr),
+    };
+
+    if let Some(expr_kind) = &expr_kind {
+      self.expr_kinds.insert(expr.get_id(), expr_kind.clone());
+    }
+
+    expr_kind
+  }
+
+  // Determine the kind of an integer literal, which is either
+  // the integer kind expected by its context, or `i32`, and
+  // ensure that its value fits in that kind.
+  fn check_int_literal(
+    &mut self,
+    int_literal: &node::IntLiteral,
+    expected_kind: Option<&node::AnyKindNode>,
+  ) -> int_kind::IntKind {
+    let int_kind = match expected_kind {
+      Some(node::AnyKindNode::IntKind(int_kind)) => *int_kind,
+      _ => int_kind::IntKind {
+        size: int_kind::IntSize::Signed32,
+      },
+    };
+
+    if !int_literal.fits_in_bit_width(int_kind.size.get_bit_width()) {
+      self.push_error(
+        int_literal.span,
+        format!(
+          "integer literal `{}` is out of range for `{}`",
+          int_literal.value,
+          node::AnyKindNode::IntKind(int_kind)
+        ),
+      );
+    }
+
+    int_kind
+  }
+
+  fn collect_declarations(&mut self, namespace: &namespace::Namespace, prefix: &[String]) {
+    for (name, top_level_node) in namespace.symbol_table.iter() {
+      let mut path = prefix.to_vec();
+
+      path.push(name.clone());
+
+      let prototype = match top_level_node {
+        namespace::TopLevelNode::Function(function) => &function.prototype,
+        namespace::TopLevelNode::External(external) => &external.prototype,
+        namespace::TopLevelNode::Namespace(namespace) => {
+          self.collect_declarations(namespace, &path);
+
+          continue;
+        }
+        namespace::TopLevelNode::Struct(struct_decl) => {
+          self.structs.insert(path.join("::"), struct_decl.clone());
+
+          continue;
+        }
+        namespace::TopLevelNode::Enum(enum_decl) => {
+          self.enums.insert(path.join("::"), enum_decl.clone());
+
+          continue;
+        }
+        namespace::TopLevelNode::Trait(trait_decl) => {
+          self.traits.insert(path.join("::"), trait_decl.clone());
+
+          continue;
+        }
+      };
+
+      self.prototypes.insert(path.join("::"), prototype.clone());
+    }
+
+    // Conflicting implementations are reported by the coherence
+    // check pass.
+    for impl_decl in &namespace.impls {
+      self.implementations.insert((
+        impl_decl.trait_path.to_string(),
+        impl_decl.kind_group.to_value_kind(),
+      ));
+    }
+  }
+
+  // Report trait objects which are not behind a reference, since
+  // their size is unknown (ex. `dyn Shape`, or `&[dyn Shape]`).
+  fn check_dyn_kind(
+    &mut self,
+    kind: &node::AnyKindNode,
+    is_behind_reference: bool,
+    span: span::Span,
+  ) {
+    match kind {
+      node::AnyKindNode::DynKind(_) if !is_behind_reference => self.push_error(
+        span,
+        format!("trait object `{}` must be behind a reference", kind),
+      ),
+      node::AnyKindNode::ReferenceKind(reference_kind) => {
+        self.check_dyn_kind(&reference_kind.referenced_kind, true, span)
+      }
+      node::AnyKindNode::ArrayKind(array_kind) => {
+        self.check_dyn_kind(&array_kind.element_kind, false, span)
+      }
+      node::AnyKindNode::SliceKind(slice_kind) => {
+        self.check_dyn_kind(&slice_kind.element_kind, false, span)
+      }
+      _ => {}
+    };
+  }
+
+  fn check_dyn_kind_group(&mut self, kind_group: &node::KindGroup, span: span::Span) {
+    self.check_dyn_kind(&kind_group.kind, kind_group.is_reference, span);
+  }
+
+  fn check_prototype_kinds(&mut self, prototype: &prototype::Prototype) {
+    for (_, kind_group) in &prototype.parameters {
+      self.check_dyn_kind_group(kind_group, prototype.span);
+    }
+
+    self.check_dyn_kind_group(&prototype.return_kind_group, prototype.span);
+  }
+
+  // Find the method called through a trait (ex. `Shape::area`),
+  // whose prototype is made generic over the kind implementing the
+  // trait, so that it is inferred from the receiver as any other
+  // generic parameter.
+  fn find_trait_method(&self, callee: &node::Path) -> Option<prototype::Prototype> {
+    let (method_name, trait_segments) = callee.segments.split_last()?;
+    let (_, method) = self
+      .traits
+      .get(&trait_segments.join("::"))?
+      .find_method(method_name)?;
+
+    let mut prototype = method.clone();
+
+    prototype.generic_parameters.insert(
+      0,
+      prototype::GenericParameter {
+        name: String::from("Self"),
+        bounds: vec![node::Path {
+          id: callee.id,
+          span: callee.span,
+          segments: trait_segments.to_vec(),
+        }],
+      },
+    );
+
+    Some(prototype)
+  }
+
+  // Whether a value of the given struct or enum would need to
+  // contain a value of the target struct or enum, directly or
+  // through other fields. References are not followed, since they
+  // are of a fixed size.
+  fn contains_struct(
+    &self,
+    struct_path: &str,
+    target_path: &str,
+    visited_paths: &mut std::collections::HashSet<String>,
+  ) -> bool {
+    let field_kind_groups = match (self.structs.get(struct_path), self.enums.get(struct_path)) {
+      (Some(struct_decl), _) => struct_decl
+        .fields
+        .iter()
+        .map(|field| &field.kind_group)
+        .collect::<Vec<_>>(),
+      (_, Some(enum_decl)) => enum_decl
+        .variants
+        .iter()
+        .flat_map(|variant| variant.fields.iter())
+        .collect(),
+      _ => return false,
+    };
+
+    for kind_group in field_kind_groups {
+      if kind_group.is_reference {
+        continue;
+      }
+
+      // Arrays contain their elements, unlike slices.
+      let mut field_kind = &kind_group.kind;
+
+      while let node::AnyKindNode::ArrayKind(array_kind) = field_kind {
+        field_kind = &array_kind.element_kind;
+      }
+
+      let field_struct_path = match field_kind {
+        node::AnyKindNode::StructKind(field_struct_kind) => field_struct_kind.to_string(),
+        node::AnyKindNode::EnumKind(field_enum_kind) => field_enum_kind.to_string(),
+        _ => continue,
+      };
+
+      if field_struct_path == target_path
+        || (visited_paths.insert(field_struct_path.clone())
+          && self.contains_struct(&field_struct_path, target_path, visited_paths))
+      {
+        return true;
+      }
+    }
+
+    false
+  }
+
+  fn check_struct_decls(&mut self) {
+    let mut struct_paths = self.structs.keys().cloned().collect::<Vec<_>>();
+
+    // Diagnostics are reported in a stable order.
+    struct_paths.sort();
+
+    for struct_path in struct_paths {
+      let struct_decl = self.structs[&struct_path].clone();
+
+      for field in &struct_decl.fields {
+        if let node::AnyKindNode::VoidKind(_) = field.kind_group.kind {
+          if !field.kind_group.is_reference {
+            self.push_error(
+              field.span,
+              format!(
+                "field `{}` of struct `{}` cannot be of kind `void`",
+                field.name, struct_path
+              ),
+            );
+          }
+        }
+
+        self.check_dyn_kind_group(&field.kind_group, field.span);
+      }
+
+      if self.contains_struct(
+        &struct_path,
+        &struct_path,
+        &mut std::collections::HashSet::new(),
+      ) {
+        self.diagnostics.push(diagnostic::Diagnostic {
+          message: format!("recursive struct `{}` has infinite size", struct_path),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(struct_decl.span),
+          notes: vec![diagnostic::DiagnosticNote {
+            message: String::from("use a reference to break the cycle"),
+            span: struct_decl.span,
+          }],
+        });
+      }
+    }
+  }
+
+  fn check_enum_decls(&mut self) {
+    let mut enum_paths = self.enums.keys().cloned().collect::<Vec<_>>();
+
+    // Diagnostics are reported in a stable order.
+    enum_paths.sort();
+
+    for enum_path in enum_paths {
+      let enum_decl = self.enums[&enum_path].clone();
+
+      for variant in &enum_decl.variants {
+        for field in &variant.fields {
+          if let node::AnyKindNode::VoidKind(_) = field.kind {
+            if !field.is_reference {
+              self.push_error(
+                field.span,
+                format!(
+                  "field of variant `{}::{}` cannot be of kind `void`",
+                  enum_path, variant.name
+                ),
+              );
+            }
+          }
+
+          self.check_dyn_kind_group(field, field.span);
+        }
+      }
+
+      if self.contains_struct(
+        &enum_path,
+        &enum_path,
+        &mut std::collections::HashSet::new(),
+      ) {
+        self.diagnostics.push(diagnostic::Diagnostic {
+          message: format!("recursive enum `{}` has infinite size", enum_path),
+          severity: diagnostic::DiagnosticSeverity::Error,
+          span: Some(enum_decl.span),
+          notes: vec![diagnostic::DiagnosticNote {
+            message: String::from("use a reference to break the cycle"),
+            span: enum_decl.span,
+          }],
+        });
+      }
+    }
+  }
+
+  fn check_field_access_expr(
+    &mut self,
+    field_access_expr: &node::FieldAccessExpr,
+  ) -> Option<node::AnyKindNode> {
+    let base_kind = Self::auto_deref(self.infer_expr_kind(&field_access_expr.base)?);
+
+    let struct_kind = match &base_kind {
+      node::AnyKindNode::StructKind(struct_kind) => struct_kind,
+      _ => {
+        self.push_error(
+          field_access_expr.span,
+          format!(
+            "cannot access field `{}` of non-struct kind `{}`",
+            field_access_expr.field_name, base_kind
+          ),
+        );
+
+        return None;
+      }
+    };
+
+    let field_kind = self
+      .structs
+      .get(&struct_kind.to_string())?
+      .find_field(&field_access_expr.field_name)
+      .map(|(_, field)| field.kind_group.to_value_kind());
+
+    if field_kind.is_none() {
+      self.push_error(
+        field_access_expr.span,
+        format!(
+          "no field `{}` on kind `{}`",
+          field_access_expr.field_name, struct_kind
+        ),
+      );
+    }
+
+    field_kind
+  }
+
+  fn check_struct_literal(
+    &mut self,
+    struct_literal: &node::StructLiteral,
+  ) -> Option<node::AnyKindNode> {
+    let struct_decl = match self.structs.get(&struct_literal.path.to_string()) {
+      Some(struct_decl) => struct_decl.clone(),
+      None => {
+        self.push_error(
+          struct_literal.path.span,
+          format!("cannot find struct `{}` in this scope", struct_literal.path),
+        );
+
+        for (_, value) in &struct_literal.fields {
+          self.infer_expr_kind(value);
+        }
+
+        return None;
+      }
+    };
+
+    let mut initialized_field_names = std::collections::HashSet::new();
+
+    for (field_name, value) in &struct_literal.fields {
+      let field = struct_decl.find_field(field_name).map(|(_, field)| field);
+      let field_kind = field.map(|field| field.kind_group.to_value_kind());
+      let value_kind = self.infer_expr_kind_as(value, field_kind.as_ref());
+
+      let field = match field {
+        Some(field) => field,
+        None => {
+          self.push_error(
+            value.get_span(),
+            format!(
+              "struct `{}` has no field named `{}`",
+              struct_literal.path, field_name
+            ),
+          );
+
+          continue;
+        }
+      };
+
+      if !initialized_field_names.insert(field_name.clone()) {
+        self.push_error(
+          value.get_span(),
+          format!("field `{}` is specified more than once", field_name),
+        );
+      }
+
+      self.expect_kind(
+        value.get_span(),
+        &field.kind_group.to_value_kind(),
+        &value_kind,
+        format!("field `{}` of `{}`", field_name, struct_literal.path).as_str(),
+      );
+    }
+
+    let missing_field_names = struct_decl
+      .fields
+      .iter()
+      .filter(|field| !initialized_field_names.contains(&field.name))
+      .map(|field| format!("`{}`", field.name))
+      .collect::<Vec<_>>();
+
+    if !missing_field_names.is_empty() {
+      self.push_error(
+        struct_literal.span,
+        format!(
+          "missing field(s) {} in literal of struct `{}`",
+          missing_field_names.join(", "),
+          struct_literal.path
+        ),
+      );
+    }
+
+    Some(node::AnyKindNode::StructKind(struct_kind::StructKind {
+      path: struct_literal.path.segments.clone(),
+    }))
+  }
+
+  fn check_variant_literal(
+    &mut self,
+    variant_literal: &node::VariantLiteral,
+  ) -> Option<node::AnyKindNode> {
+    let (enum_path, variant_name) = variant_literal.path.split_variant();
+
+    let variant = self
+      .enums
+      .get(&enum_path)
+      .and_then(|enum_decl| enum_decl.find_variant(variant_name))
+      .map(|(_, variant)| variant.clone());
+
+    let variant = match variant {
+      Some(variant) => variant,
+      None => {
+        self.push_error(
+          variant_literal.path.span,
+          format!(
+            "cannot find variant `{}` in this scope",
+            variant_literal.path
+          ),
+        );
+
+        for argument in &variant_literal.arguments {
+          self.infer_expr_kind(argument);
+        }
+
+        return None;
+      }
+    };
+
+    if variant.fields.len() != variant_literal.arguments.len() {
+      self.push_error(
+        variant_literal.span,
+        format!(
+          "variant `{}` takes {} field(s) but {} were supplied",
+          variant_literal.path,
+          variant.fields.len(),
+          variant_literal.arguments.len()
+        ),
+      );
+    }
+
+    for (index, argument) in variant_literal.arguments.iter().enumerate() {
+      let field_kind = variant
+        .fields
+        .get(index)
+        .map(node::KindGroup::to_value_kind);
+      let argument_kind = self.infer_expr_kind_as(argument, field_kind.as_ref());
+
+      if let Some(field_kind) = &field_kind {
+        self.expect_kind(
+          argument.get_span(),
+          field_kind,
+          &argument_kind,
+          format!("field {} of `{}`", index, variant_literal.path).as_str(),
+        );
+      }
+    }
+
+    let segment_count = variant_literal.path.segments.len();
+
+    Some(node::AnyKindNode::EnumKind(enum_kind::EnumKind {
+      path: variant_literal.path.segments[..segment_count - 1].to_vec(),
+    }))
+  }
+
+  // Check a pattern against the kind of the values it is matched
+  // against, if known, and collect the kinds of the names it binds.
+  fn check_pattern(
+    &mut self,
+    pattern: &pattern::AnyPatternNode,
+    expected_kind: Option<&node::AnyKindNode>,
+    bindings: &mut std::collections::HashMap<String, Option<node::AnyKindNode>>,
+  ) {
+    match pattern {
+      pattern::AnyPatternNode::WildcardPattern(_) => {}
+      pattern::AnyPatternNode::BindingPattern(binding_pattern) => {
+        if bindings
+          .insert(binding_pattern.name.clone(), expected_kind.cloned())
+          .is_some()
+        {
+          self.push_error(
+            binding_pattern.span,
+            format!(
+              "identifier `{}` is bound more than once in the same pattern",
+              binding_pattern.name
+            ),
+          );
+        }
+      }
+      pattern::AnyPatternNode::LiteralPattern(literal) => {
+        let literal_kind = match literal {
+          node::AnyLiteralNode::BoolLiteral(_) => {
+            node::AnyKindNode::BoolKind(bool_kind::BoolKind {})
+          }
+          node::AnyLiteralNode::IntLiteral(int_literal) => {
+            node::AnyKindNode::IntKind(self.check_int_literal(int_literal, expected_kind))
+          }
+        };
+
+        if let Some(expected_kind) = expected_kind {
+          self.expect_kind(
+            pattern.get_span(),
+            expected_kind,
+            &Some(literal_kind),
+            "pattern",
+          );
+        }
+      }
+      pattern::AnyPatternNode::VariantPattern(variant_pattern) => {
+        let (enum_path, variant_name) = variant_pattern.path.split_variant();
+
+        let variant = self
+          .enums
+          .get(&enum_path)
+          .and_then(|enum_decl| enum_decl.find_variant(variant_name))
+          .map(|(_, variant)| variant.clone());
+
+        let variant = match variant {
+          Some(variant) => variant,
+          None => {
+            self.push_error(
+              variant_pattern.path.span,
+              format!(
+                "cannot find variant `{}` in this scope",
+                variant_pattern.path
+              ),
+            );
+
+            return;
+          }
+        };
+
+        if let Some(expected_kind) = expected_kind {
+          let segment_count = variant_pattern.path.segments.len();
+
+          let enum_kind = node::AnyKindNode::EnumKind(enum_kind::EnumKind {
+            path: variant_pattern.path.segments[..segment_count - 1].to_vec(),
+          });
+
+          self.expect_kind(
+            variant_pattern.span,
+            expected_kind,
+            &Some(enum_kind),
+            "pattern",
+          );
+        }
+
+        if variant.fields.len() != variant_pattern.fields.len() {
+          self.push_error(
+            variant_pattern.span,
+            format!(
+              "variant `{}` has {} field(s) but the pattern has {}",
+              variant_pattern.path,
+              variant.fields.len(),
+              variant_pattern.fields.len()
+            ),
+          );
+        }
+
+        for (index, field_pattern) in variant_pattern.fields.iter().enumerate() {
+          let field_kind = variant
+            .fields
+            .get(index)
+            .map(node::KindGroup::to_value_kind);
+
+          self.check_pattern(field_pattern, field_kind.as_ref(), bindings);
+        }
+      }
+    };
+  }
+
+  fn check_match_expr(&mut self, match_expr: &node::MatchExpr) -> Option<node::AnyKindNode> {
+    let subject_kind = self.infer_expr_kind(&match_expr.subject);
+
+    // The kind of the first arm whose kind is known, which all
+    // other arms must agree with.
+    let mut match_kind: Option<node::AnyKindNode> = None;
+
+    for arm in &match_expr.arms {
+      let mut bindings = std::collections::HashMap::new();
+
+      self.check_pattern(&arm.pattern, subject_kind.as_ref(), &mut bindings);
+
+      // Bindings are only visible within their arm.
+      self.variable_scopes.push(
+        bindings
+          .into_iter()
+          .map(|(name, kind)| {
+            (
+              name,
+              Variable {
+                kind,
+                is_mutable: true,
+              },
+            )
+          })
+          .collect(),
+      );
+
+      let arm_kind = match &arm.body {
+        node::MatchArmBody::Expr(expr) => self.infer_expr_kind_as(expr, match_kind.as_ref()),
+        // Blocks do not produce a value.
+        node::MatchArmBody::Block(block) => {
+          self.visit_block(block).ok();
+
+          Some(node::AnyKindNode::VoidKind(void_kind::VoidKind {}))
+        }
+      };
+
+      self.variable_scopes.pop();
+
+      match &match_kind {
+        Some(match_kind) => self.expect_kind(arm.span, match_kind, &arm_kind, "match arm"),
+        None => match_kind = arm_kind,
+      };
+    }
+
+    // Matches without arms may only be used as statements.
+    if match_expr.arms.is_empty() {
+      return Some(node::AnyKindNode::VoidKind(void_kind::VoidKind {}));
+    }
+
+    match_kind
+  }
+
+  // Determine the kind of an array literal, whose elements must
+  // all be of the same kind. The kind of the elements is taken
+  // from the expected kind if possible, or from the first element.
+  fn check_array_literal(
+    &mut self,
+    array_literal: &node::ArrayLiteral,
+    expected_kind: Option<&node::AnyKindNode>,
+  ) -> Option<node::AnyKindNode> {
+    let mut element_kind = match expected_kind {
+      Some(node::AnyKindNode::ArrayKind(array_kind)) => Some(*array_kind.element_kind.clone()),
+      Some(node::AnyKindNode::SliceKind(slice_kind)) => Some(*slice_kind.element_kind.clone()),
+      _ => None,
+    };
+
+    if array_literal.elements.is_empty() && element_kind.is_none() {
+      self.push_error(
+        array_literal.span,
+        String::from("cannot infer the kind of the elements of an empty array"),
+      );
+
+      return None;
+    }
+
+    for element in &array_literal.elements {
+      let actual_kind = self.infer_expr_kind_as(element, element_kind.as_ref());
+
+      match &element_kind {
+        Some(element_kind) => self.expect_kind(
+          element.get_span(),
+          element_kind,
+          &actual_kind,
+          "array element",
+        ),
+        None => element_kind = actual_kind,
+      };
+    }
+
+    if let Some(node::AnyKindNode::VoidKind(_)) = element_kind {
+      self.push_error(
+        array_literal.span,
+        String::from("array elements cannot be of kind `void`"),
+      );
+
+      return None;
+    }
+
+    Some(node::AnyKindNode::ArrayKind(array_kind::ArrayKind {
+      element_kind: Box::new(element_kind?),
+      length: array_literal.elements.len() as u64,
+    }))
+  }
+
+  fn check_index_expr(&mut self, index_expr: &node::IndexExpr) -> Option<node::AnyKindNode> {
+    let base_kind = self.infer_expr_kind(&index_expr.base).map(Self::auto_deref);
+
+    let index_kind = self.infer_expr_kind_as(
+      &index_expr.index,
+      Some(&node::AnyKindNode::IntKind(int_kind::IntKind {
+        size: int_kind::IntSize::Signed64,
+      })),
+    );
+
+    match index_kind {
+      Some(node::AnyKindNode::IntKind(_)) | None => {}
+      Some(index_kind) => self.push_error(
+        index_expr.index.get_span(),
+        format!(
+          "arrays and slices cannot be indexed by a value of kind `{}`",
+          index_kind
+        ),
+      ),
+    };
+
+    match base_kind? {
+      node::AnyKindNode::ArrayKind(array_kind) => {
+        // Indices known before running the program are verified
+        // right away.
+        if let node::AnyExprNode::Literal(node::AnyLiteralNode::IntLiteral(int_literal)) =
+          index_expr.index.as_ref()
+        {
+          if int_literal
+            .value
+            .parse::<u64>()
+            .map_or(true, |index| index >= array_kind.length)
+          {
+            self.push_error(
+              index_expr.span,
+              format!(
+                "index out of bounds: the length is {} but the index is {}",
+                array_kind.length, int_literal.value
+              ),
+            );
+          }
+        }
+
+        Some(*array_kind.element_kind)
+      }
+      node::AnyKindNode::SliceKind(slice_kind) => Some(*slice_kind.element_kind),
+      base_kind => {
+        self.push_error(
+          index_expr.span,
+          format!("cannot index into a value of kind `{}`", base_kind),
+        );
+
+        None
+      }
+    }
+  }
+
+  fn check_len_expr(&mut self, len_expr: &node::LenExpr) -> Option<node::AnyKindNode> {
+    match Self::auto_deref(self.infer_expr_kind(&len_expr.base)?) {
+      node::AnyKindNode::ArrayKind(_) | node::AnyKindNode::SliceKind(_) => {
+        Some(node::AnyKindNode::IntKind(int_kind::IntKind {
+          size: int_kind::IntSize::Signed64,
+        }))
+      }
+      base_kind => {
+        self.push_error(
+          len_expr.span,
+          format!("cannot take the length of a value of kind `{}`", base_kind),
+        );
+
+        None
+      }
+    }
+  }
+
+  // Determine the kind of a borrow, which refers to a value of the
+  // kind of its operand. Places may only be borrowed mutably if they
+  // are not reached through an immutable reference.
+  fn check_borrow_expr(
+    &mut self,
+    borrow_expr: &node::BorrowExpr,
+    expected_kind: Option<&node::AnyKindNode>,
+  ) -> Option<node::AnyKindNode> {
+    let expected_value_kind = match expected_kind {
+      Some(node::AnyKindNode::ReferenceKind(reference_kind)) => {
+        Some(reference_kind.referenced_kind.as_ref())
+      }
+      _ => None,
+    };
+
+    let value_kind = self.infer_expr_kind_as(&borrow_expr.value, expected_value_kind)?;
+
+    if let node::AnyKindNode::VoidKind(_) = value_kind {
+      self.push_error(
+        borrow_expr.span,
+        String::from("cannot borrow a `void` value"),
+      );
+
+      return None;
+    }
+
+    if borrow_expr.is_mutable && borrow_expr.value.is_place() {
+      if let Some(reference_kind) = self.find_immutable_reference(&borrow_expr.value) {
+        self.push_error(
+          borrow_expr.span,
+          format!(
+            "cannot borrow as mutable through an immutable reference of kind `{}`",
+            reference_kind
+          ),
+        );
+      } else if let Some(name) = self.find_immutable_binding(&borrow_expr.value) {
+        self.push_error(
+          borrow_expr.span,
+          format!(
+            "cannot borrow `{}` as mutable, as it is not declared with `mut`",
+            name
+          ),
+        );
+      }
+    }
+
+    Some(node::AnyKindNode::ReferenceKind(
+      reference_kind::ReferenceKind {
+        referenced_kind: Box::new(value_kind),
+        is_mutable: borrow_expr.is_mutable,
+      },
+    ))
+  }
+
+  fn check_deref_expr(&mut self, deref_expr: &node::DerefExpr) -> Option<node::AnyKindNode> {
+    match self.infer_expr_kind(&deref_expr.value)? {
+      node::AnyKindNode::ReferenceKind(reference_kind)
+        if !matches!(
+          *reference_kind.referenced_kind,
+          node::AnyKindNode::VoidKind(_) | node::AnyKindNode::DynKind(_)
+        ) =>
+      {
+        Some(*reference_kind.referenced_kind)
+      }
+      value_kind => {
+        self.push_error(
+          deref_expr.span,
+          format!("cannot dereference a value of kind `{}`", value_kind),
+        );
+
+        None
+      }
+    }
+  }
+
+  // Bind the generic parameters named within the kind of a
+  // parameter to the matching parts of the kind of its argument
+  // (ex. `T` to `i32`, for a parameter of kind `&[T]` given a `&[i32;
+  // 2]`). Parameters which are already bound are left as they are,
+  // and mismatches are left to be reported once all bindings are
+  // known.
+  fn infer_type_arguments(
+    parameter_kind: &node::AnyKindNode,
+    argument_kind: &node::AnyKindNode,
+    bindings: &mut std::collections::HashMap<String, node::AnyKindNode>,
+  ) {
+    match (parameter_kind, argument_kind) {
+      (node::AnyKindNode::GenericKind(generic_kind), _) => {
+        bindings
+          .entry(generic_kind.name.clone())
+          .or_insert_with(|| argument_kind.clone());
+      }
+      (
+        node::AnyKindNode::ArrayKind(parameter_kind),
+        node::AnyKindNode::ArrayKind(argument_kind),
+      ) => Self::infer_type_arguments(
+        &parameter_kind.element_kind,
+        &argument_kind.element_kind,
+        bindings,
+      ),
+      (
+        node::AnyKindNode::SliceKind(parameter_kind),
+        node::AnyKindNode::ArrayKind(argument_kind),
+      ) => Self::infer_type_arguments(
+        &parameter_kind.element_kind,
+        &argument_kind.element_kind,
+        bindings,
+      ),
+      (
+        node::AnyKindNode::SliceKind(parameter_kind),
+        node::AnyKindNode::SliceKind(argument_kind),
+      ) => Self::infer_type_arguments(
+        &parameter_kind.element_kind,
+        &argument_kind.element_kind,
+        bindings,
+      ),
+      (
+        node::AnyKindNode::ReferenceKind(parameter_kind),
+        node::AnyKindNode::ReferenceKind(argument_kind),
+      ) => Self::infer_type_arguments(
+        &parameter_kind.referenced_kind,
+        &argument_kind.referenced_kind,
+        bindings,
+      ),
+      _ => {}
+    };
+  }
+
+  fn check_call_expr(&mut self, call_expr: &node::CallExpr) -> Option<node::AnyKindNode> {
+    let prototype = match self
+      .prototypes
+      .get(&call_expr.callee.to_string())
+      .cloned()
+      .or_else(|| self.find_trait_method(&call_expr.callee))
+    {
+      Some(prototype) => prototype,
+      None => {
+        self.push_error(
+          call_expr.span,
+          format!("cannot find function `{}` in this scope", call_expr.callee),
+        );
+
+        // The arguments are still checked, so that any problems
+        // within them are not left unreported.
+        for argument in &call_expr.arguments {
+          self.infer_expr_kind(argument);
+        }
+
+        return None;
+      }
+    };
+
+    let parameter_count = prototype.parameters.len();
+    let argument_count = call_expr.arguments.len();
+
+    if argument_count < parameter_count
+      || (!prototype.is_variadic && argument_count > parameter_count)
+    {
+      self.push_error(
+        call_expr.span,
+        format!(
+          "function `{}` takes {}{} argument(s) but {} were supplied",
+          call_expr.callee,
+          if prototype.is_variadic {
+            "at least "
+          } else {
+            ""
+          },
+          parameter_count,
+          argument_count
+        ),
+      );
+    }
+
+    let is_generic_parameter = |index: usize| {
+      prototype
+        .parameters
+        .get(index)
+        .is_some_and(|(_, kind_group)| kind_group.to_value_kind().is_generic())
+    };
+
+    // Integer literals given for generic parameters take the kind
+    // inferred from the other arguments (if any), so they are
+    // checked last.
+    let (literal_indices, indices): (Vec<_>, Vec<_>) = (0..argument_count).partition(|index| {
+      is_generic_parameter(*index)
+        && matches!(
+          call_expr.arguments[*index],
+          node::AnyExprNode::Literal(node::AnyLiteralNode::IntLiteral(_))
+        )
+    });
+
+    let mut bindings = std::collections::HashMap::new();
+    let mut argument_kinds = vec![None; argument_count];
+
+    for index in indices.into_iter().chain(literal_indices) {
+      let argument = &call_expr.arguments[index];
+      let parameter = prototype.parameters.get(index);
+      let parameter_kind = parameter.map(|(_, kind_group)| kind_group.to_value_kind());
+
+      let expected_kind = parameter_kind
+        .as_ref()
+        .map(|parameter_kind| parameter_kind.substitute(&bindings))
+        .filter(|expected_kind| !expected_kind.is_generic());
+
+      let argument_kind = self.infer_expr_kind_as(argument, expected_kind.as_ref());
+
+      match parameter.zip(parameter_kind.as_ref()) {
+        // Arguments given for generic parameters are checked once
+        // all generic parameters are bound.
+        Some((_, parameter_kind)) if parameter_kind.is_generic() => {
+          if let Some(argument_kind) = &argument_kind {
+            Self::infer_type_arguments(parameter_kind, argument_kind, &mut bindings);
+          }
+        }
+        Some(((parameter_name, _), parameter_kind)) => self.expect_kind(
+          argument.get_span(),
+          parameter_kind,
+          &argument_kind,
+          format!("argument `{}` of `{}`", parameter_name, call_expr.callee).as_str(),
+        ),
+        // Variadic arguments may be of any kind that produces
+        // a value.
+        None => {
+          if let Some(node::AnyKindNode::VoidKind(_)) = argument_kind {
+            self.push_error(
+              argument.get_span(),
+              format!(
+                "cannot pass a `void` value as a variadic argument of `{}`",
+                call_expr.callee
+              ),
+            );
+          }
+        }
+      };
+
+      argument_kinds[index] = argument_kind;
+    }
+
+    if !prototype.is_generic() {
+      return Some(prototype.return_kind_group.to_value_kind());
+    }
+
+    let mut type_arguments = vec![];
+
+    for generic_parameter in &prototype.generic_parameters {
+      match bindings.get(&generic_parameter.name) {
+        Some(node::AnyKindNode::VoidKind(_)) => self.push_error(
+          call_expr.span,
+          format!(
+            "cannot use `void` for generic parameter `{}` of `{}`",
+            generic_parameter.name, call_expr.callee
+          ),
+        ),
+        Some(kind) => type_arguments.push(kind.clone()),
+        None => self.push_error(
+          call_expr.span,
+          format!(
+            "cannot infer the kind of generic parameter `{}` of `{}`",
+            generic_parameter.name, call_expr.callee
+          ),
+        ),
+      };
+    }
+
+    if type_arguments.len() != prototype.generic_parameters.len() {
+      return None;
+    }
+
+    for (generic_parameter, kind) in prototype.generic_parameters.iter().zip(&type_arguments) {
+      for bound in &generic_parameter.bounds {
+        if !self.implements(kind, &bound.to_string()) {
+          self.diagnostics.push(diagnostic::Diagnostic {
+            message: format!("kind `{}` does not implement trait `{}`", kind, bound),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(call_expr.span),
+            notes: vec![diagnostic::DiagnosticNote {
+              message: format!(
+                "required by generic parameter `{}` of `{}`",
+                generic_parameter.name, call_expr.callee
+              ),
+              span: call_expr.span,
+            }],
+          });
+        }
+      }
+    }
+
+    for ((parameter_name, kind_group), (argument, argument_kind)) in prototype
+      .parameters
+      .iter()
+      .zip(call_expr.arguments.iter().zip(&argument_kinds))
+    {
+      let parameter_kind = kind_group.to_value_kind();
+
+      if parameter_kind.is_generic() {
+        self.expect_kind(
+          argument.get_span(),
+          &parameter_kind.substitute(&bindings),
+          argument_kind,
+          format!("argument `{}` of `{}`", parameter_name, call_expr.callee).as_str(),
+        );
+      }
+    }
+
+    self
+      .call_type_arguments
+      .insert(call_expr.id, type_arguments);
+
+    Some(
+      prototype
+        .return_kind_group
+        .to_value_kind()
+        .substitute(&bindings),
+    )
+  }
+
+  fn check_condition(&mut self, condition: &node::AnyExprNode) {
+    let condition_kind = self.infer_expr_kind(condition);
+
+    self.expect_kind(
+      condition.get_span(),
+      &node::AnyKindNode::BoolKind(bool_kind::BoolKind {}),
+      &condition_kind,
+      "condition",
+    );
+  }
+}
+
+impl pass::Pass<'_> for TypeCheckPass {
+  fn get_diagnostics(&self) -> Vec<diagnostic::Diagnostic> {
+    self.diagnostics.clone()
+  }
+
+  fn visit_namespace(&mut self, namespace: &namespace::Namespace) -> pass::PassResult {
+    // All prototypes must be known before visiting any function,
+    // since a function may call others declared after it, or
+    // within another namespace.
+    if self.namespace_depth == 0 {
+      self.collect_declarations(namespace, &[]);
+      self.check_struct_decls();
+      self.check_enum_decls();
+    }
+
+    self.namespace_depth += 1;
+
+    let result = self.visit_children(namespace);
+
+    self.namespace_depth -= 1;
+
+    result
+  }
+
+  fn visit_function(&mut self, function: &function::Function) -> pass::PassResult {
+    let generic_parameters = &function.prototype.generic_parameters;
+
+    for (index, generic_parameter) in generic_parameters.iter().enumerate() {
+      if generic_parameters[..index]
+        .iter()
+        .any(|previous| previous.name == generic_parameter.name)
+      {
+        self.push_error(
+          function.prototype.span,
+          format!(
+            "generic parameter `{}` of `{}` is declared more than once",
+            generic_parameter.name, function.prototype.name
+          ),
+        );
+      }
+    }
+
+    self.check_prototype_kinds(&function.prototype);
+
+    self.generic_bounds = generic_parameters
+      .iter()
+      .map(|generic_parameter| {
+        let bounds = generic_parameter
+          .bounds
+          .iter()
+          .map(|bound| bound.to_string())
+          .collect();
+
+        (generic_parameter.name.clone(), bounds)
+      })
+      .collect();
+
+    let mut parameter_scope = std::collections::HashMap::new();
+
+    for (parameter_name, parameter_kind_group) in &function.prototype.parameters {
+      if parameter_scope
+        .insert(
+          parameter_name.clone(),
+          Variable {
+            kind: Some(parameter_kind_group.to_value_kind()),
+            is_mutable: true,
+          },
+        )
+        .is_some()
+      {
+        self.push_error(
+          function.prototype.span,
+          format!(
+            "parameter `{}` of `{}` is bound more than once",
+            parameter_name, function.prototype.name
+          ),
+        );
+      }
+    }
+
+    self.return_kind = Some(function.prototype.return_kind_group.to_value_kind());
+
+    // Parameters are visible throughout the function's body.
+    self.variable_scopes.clear();
+    self.variable_scopes.push(parameter_scope);
+    self.visit_block(&function.body)?;
+    self.variable_scopes.pop();
+    self.generic_bounds.clear();
+
+    Ok(())
+  }
+
+  fn visit_trait_decl(&mut self, trait_decl: &trait_decl::TraitDecl) -> pass::PassResult {
+    for method in &trait_decl.methods {
+      // Methods are dispatched on their receiver, which must be the
+      // only use of `Self` so that methods may be called through
+      // trait objects.
+      let has_receiver = method.parameters.first().is_some_and(|(_, kind_group)| {
+        kind_group.is_reference
+          && matches!(
+            &kind_group.kind,
+            node::AnyKindNode::GenericKind(generic_kind) if generic_kind.name == "Self"
+          )
+      });
+
+      if !has_receiver {
+        self.push_error(
+          method.span,
+          format!(
+            "the first parameter of method `{}` of trait `{}` must be of kind `&Self` or `&mut Self`",
+            method.name, trait_decl.name
+          ),
+        );
+      }
+
+      let is_self_used_elsewhere = method
+        .parameters
+        .iter()
+        .skip(1)
+        .map(|(_, kind_group)| kind_group)
+        .chain([&method.return_kind_group])
+        .any(|kind_group| kind_group.to_value_kind().is_generic());
+
+      if is_self_used_elsewhere {
+        self.push_error(
+          method.span,
+          format!(
+            "`Self` may only be used as the first parameter of method `{}` of trait `{}`",
+            method.name, trait_decl.name
+          ),
+        );
+      }
+
+      if method.is_generic() {
+        self.push_error(
+          method.span,
+          format!(
+            "method `{}` of trait `{}` cannot be generic",
+            method.name, trait_decl.name
+          ),
+        );
+      }
+
+      if method.is_variadic {
+        self.push_error(
+          method.span,
+          format!(
+            "method `{}` of trait `{}` cannot be variadic",
+            method.name, trait_decl.name
+          ),
+        );
+      }
+
+      self.check_prototype_kinds(method);
+    }
+
+    Ok(())
+  }
+
+  fn visit_impl_decl(&mut self, impl_decl: &impl_decl::ImplDecl) -> pass::PassResult {
+    let trait_path = impl_decl.trait_path.to_string();
+    let kind = impl_decl.kind_group.to_value_kind();
+
+    self.check_dyn_kind_group(&impl_decl.kind_group, impl_decl.span);
+
+    let trait_decl = match kind {
+      node::AnyKindNode::VoidKind(_) => {
+        self.push_error(
+          impl_decl.span,
+          format!("cannot implement trait `{}` for `void`", trait_path),
+        );
+
+        None
+      }
+      // Unknown traits were already reported by name resolution.
+      _ => self.traits.get(&trait_path).cloned(),
+    };
+
+    if let Some(trait_decl) = trait_decl {
+      let bindings = std::collections::HashMap::from([(String::from("Self"), kind.clone())]);
+
+      for method in &trait_decl.methods {
+        let function = match impl_decl.find_method(&method.name) {
+          Some(function) => function,
+          None => {
+            self.push_error(
+              impl_decl.span,
+              format!(
+                "missing method `{}` of trait `{}` in implementation for `{}`",
+                method.name, trait_path, kind
+              ),
+            );
+
+            continue;
+          }
+        };
+
+        let mut expected_prototype = method.clone();
+
+        for (_, kind_group) in expected_prototype.parameters.iter_mut() {
+          kind_group.kind = kind_group.kind.substitute(&bindings);
+        }
+
+        if !function.prototype.is_compatible_with(&expected_prototype) {
+          self.diagnostics.push(diagnostic::Diagnostic {
+            message: format!(
+              "method `{}` of implementation for `{}` does not match its declaration in trait `{}`",
+              method.name, kind, trait_path
+            ),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(function.prototype.span),
+            notes: vec![diagnostic::DiagnosticNote {
+              message: String::from("declared here"),
+              span: method.span,
+            }],
+          });
+        }
+      }
+
+      for function in &impl_decl.methods {
+        if trait_decl.find_method(&function.prototype.name).is_none() {
+          self.push_error(
+            function.prototype.span,
+            format!(
+              "method `{}` is not a member of trait `{}`",
+              function.prototype.name, trait_path
+            ),
+          );
+        }
+      }
+    }
+
+    for function in &impl_decl.methods {
+      self.visit_function(function)?;
+    }
+
+    Ok(())
+  }
+
+  fn visit_external(&mut self, external: &external::External) -> pass::PassResult {
+    // Externs refer to symbols defined elsewhere, which cannot be
+    // instantiated for each kind they are called with.
+    if external.prototype.is_generic() {
+      self.push_error(
+        external.prototype.span,
+        format!("extern `{}` cannot be generic", external.prototype.name),
+      );
+    }
+
+    self.check_prototype_kinds(&external.prototype);
+
+    Ok(())
+  }
+
+  fn visit_block(&mut self, block: &block::Block) -> pass::PassResult {
+    self.variable_scopes.push(std::collections::HashMap::new());
+
+    self.visit_children(block)?;
+    self.variable_scopes.pop();
+
+    Ok(())
+  }
+
+  fn visit_return_stmt(&mut self, return_stmt: &block::ReturnStmt) -> pass::PassResult {
+    let return_kind = self.return_kind.clone();
+
+    let value_kind = match &return_stmt.value {
+      Some(value) => self.infer_expr_kind_as(value, return_kind.as_ref()),
+      None => Some(node::AnyKindNode::VoidKind(void_kind::VoidKind {})),
+    };
+
+    if let Some(return_kind) = return_kind {
+      let span = match &return_stmt.value {
+        Some(value) => value.get_span(),
+        None => return_stmt.span,
+      };
+
+      self.expect_kind(span, &return_kind, &value_kind, "returned value");
+    }
+
+    Ok(())
+  }
+
+  fn visit_if_stmt(&mut self, if_stmt: &block::IfStmt) -> pass::PassResult {
+    self.check_condition(&if_stmt.condition);
+    self.visit_block(&if_stmt.then_block)?;
+
+    for (condition, block) in &if_stmt.else_if_branches {
+      self.check_condition(condition);
+      self.visit_block(block)?;
+    }
+
+    if let Some(else_block) = &if_stmt.else_block {
+      self.visit_block(else_block)?;
+    }
+
+    Ok(())
+  }
+
+  fn visit_while_stmt(&mut self, while_stmt: &block::WhileStmt) -> pass::PassResult {
+    self.check_condition(&while_stmt.condition);
+
+    self.visit_block(&while_stmt.body)
+  }
+
+  fn visit_loop_stmt(&mut self, loop_stmt: &block::LoopStmt) -> pass::PassResult {
+    self.visit_block(&loop_stmt.body)
+  }
+
+  fn visit_for_stmt(&mut self, for_stmt: &block::ForStmt) -> pass::PassResult {
+    // A literal bound takes the kind of the other bound.
+    let (range_start_kind, range_end_kind) = match for_stmt.range_start {
+      node::AnyExprNode::Literal(_) => {
+        let range_end_kind = self.infer_expr_kind(&for_stmt.range_end);
+
+        (
+          self.infer_expr_kind_as(&for_stmt.range_start, range_end_kind.as_ref()),
+          range_end_kind,
+        )
+      }
+      _ => {
+        let range_start_kind = self.infer_expr_kind(&for_stmt.range_start);
+
+        (
+          range_start_kind.clone(),
+          self.infer_expr_kind_as(&for_stmt.range_end, range_start_kind.as_ref()),
+        )
+      }
+    };
+
+    for (range_bound, range_bound_kind) in [
+      (&for_stmt.range_start, &range_start_kind),
+      (&for_stmt.range_end, &range_end_kind),
+    ] {
+      if let Some(kind) = range_bound_kind {
+        if !matches!(kind, node::AnyKindNode::IntKind(_)) {
+          self.push_error(
+            range_bound.get_span(),
+            format!("range bounds must be integers, found `{}`", kind),
+          );
+        }
+      }
+    }
+
+    if range_start_kind.is_some() && range_end_kind.is_some() {
+      self.expect_kind(
+        for_stmt.range_end.get_span(),
+        range_start_kind.as_ref().unwrap(),
+        &range_end_kind,
+        "range end",
+      );
+    }
+
+    // The iterator is only visible inside of the loop's body.
+    self.variable_scopes.push(std::collections::HashMap::from([(
+      for_stmt.iterator_name.clone(),
+      Variable {
+        kind: range_start_kind.or(range_end_kind),
+        is_mutable: false,
+      },
+    )]));
+
+    self.visit_block(&for_stmt.body)?;
+    self.variable_scopes.pop();
+
+    Ok(())
+  }
+
+  fn visit_expr_stmt(&mut self, expr_stmt: &block::ExprStmt) -> pass::PassResult {
+    self.infer_expr_kind(&expr_stmt.expr);
+
+    Ok(())
+  }
+
+  fn visit_let_stmt(&mut self, let_stmt: &block::LetStmt) -> pass::PassResult {
+    if let Some(kind_group) = &let_stmt.kind_group {
+      self.check_dyn_kind_group(kind_group, let_stmt.span);
+    }
+
+    let expected_kind = let_stmt
+      .kind_group
+      .as_ref()
+      .map(node::KindGroup::to_value_kind);
+
+    let value_kind = self.infer_expr_kind_as(&let_stmt.value, expected_kind.as_ref());
+
+    match &expected_kind {
+      Some(expected_kind) => self.expect_kind(
+        let_stmt.value.get_span(),
+        expected_kind,
+        &value_kind,
+        format!("binding `{}`", let_stmt.name).as_str(),
+      ),
+      None => {
+        if let Some(node::AnyKindNode::VoidKind(_)) = value_kind {
+          self.push_error(
+            let_stmt.value.get_span(),
+            format!("cannot bind a `void` value to `{}`", let_stmt.name),
+          );
+        }
+      }
+    };
+
+    // The binding is only visible after its own statement, so that
+    // its value may refer to a previous binding of the same name.
+    if let Some(scope) = self.variable_scopes.last_mut() {
+      scope.insert(
+        let_stmt.name.clone(),
+        Variable {
+          kind: expected_kind.or(value_kind),
+          is_mutable: let_stmt.is_mutable,
+        },
+      );
+    }
+
+    Ok(())
+  }
+
+  fn visit_assign_stmt(&mut self, assign_stmt: &block::AssignStmt) -> pass::PassResult {
+    if !assign_stmt.target.is_place() {
+      self.push_error(
+        assign_stmt.target.get_span(),
+        String::from("invalid assignment target"),
+      );
+
+      // Both sides are still checked, but not against each other.
+      self.infer_expr_kind(&assign_stmt.target);
+      self.infer_expr_kind(&assign_stmt.value);
+
+      return Ok(());
+    }
+
+    let target_kind = self.infer_expr_kind(&assign_stmt.target);
+    let value_kind = self.infer_expr_kind_as(&assign_stmt.value, target_kind.as_ref());
+
+    if let Some(reference_kind) = self.find_immutable_reference(&assign_stmt.target) {
+      self.push_error(
+        assign_stmt.target.get_span(),
+        format!(
+          "cannot assign through an immutable reference of kind `{}`",
+          reference_kind
+        ),
+      );
+    } else if let Some(name) = self.find_immutable_binding(&assign_stmt.target) {
+      self.push_error(
+        assign_stmt.target.get_span(),
+        format!(
+          "cannot assign to `{}`, as it is not declared with `mut`",
+          name
+        ),
+      );
+    }
+
+    if let Some(target_kind) = &target_kind {
+      self.expect_kind(
+        assign_stmt.value.get_span(),
+        target_kind,
+        &value_kind,
+        "assigned value",
+      );
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::pass_mut::PassMut;
+
+  #[test]
+  fn type_check_pass_proper_initial_values() {
+    let type_check_pass = TypeCheckPass::new();
+
+    assert_eq!(true, type_check_pass.diagnostics.is_empty());
+    assert_eq!(true, type_check_pass.prototypes.is_empty());
+    assert_eq!(true, type_check_pass.expr_kinds.is_empty());
+  }
+
+  #[test]
+  fn type_check_pass_call() {
+    let diagnostics = check_source(
+      "namespace foo {
+        extern foo(a: bool) ~ bool;
+        fn f() ~ bool { let a: bool = foo(true); return foo(a); }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+  }
+
+  #[test]
+  fn type_check_pass_expr_kinds() {
+    let node_ids = node::NodeIdAllocator::new();
+
+    let mut type_check_pass = TypeCheckPass::new();
+
+    let make_one = || {
+      node::AnyExprNode::Literal(node::AnyLiteralNode::IntLiteral(node::IntLiteral {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        value: String::from("1"),
+      }))
+    };
+
+    let int8_kind = node::AnyKindNode::IntKind(int_kind::IntKind {
+      size: int_kind::IntSize::Signed8,
+    });
+
+    let (first, second) = (make_one(), make_one());
+
+    type_check_pass.infer_expr_kind_as(&first, Some(&int8_kind));
+    type_check_pass.infer_expr_kind(&second);
+
+    // Equal literals are recorded separately, by their id.
+    assert_eq!(
+      Some(&int8_kind),
+      type_check_pass.get_expr_kinds().get(&first.get_id())
+    );
+
+    assert_eq!(
+      Some(&node::AnyKindNode::IntKind(int_kind::IntKind {
+        size: int_kind::IntSize::Signed32,
+      })),
+      type_check_pass.get_expr_kinds().get(&second.get_id())
+    );
+  }
+
+  #[test]
+  fn type_check_pass_call_unknown_callee() {
+    let diagnostics = check_source(
+      "namespace foo {
+        extern foo() ~ bool;
+        fn f() ~ void { bar(); }
+      }",
+    );
+
+    assert_eq!(
+      vec!["cannot find function `bar` in this scope"],
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn type_check_pass_call_arity() {
+    let diagnostics = check_source(
+      "namespace foo {
+        extern foo(a: bool) ~ bool;
+        fn f() ~ void { foo(); foo(true, true); }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        "function `foo` takes 1 argument(s) but 0 were supplied",
+        "function `foo` takes 1 argument(s) but 2 were supplied",
+      ],
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn type_check_pass_call_variadic() {
+    let diagnostics = check_source(
+      "namespace foo {
+        extern foo(a: bool, ...) ~ bool;
+        fn f() ~ void { foo(true, true); foo(true, 1, false); }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+
+    let diagnostics = check_source(
+      "namespace foo {
+        extern foo(a: bool, ...) ~ bool;
+        fn f() ~ void { foo(); }
+      }",
+    );
+
+    assert_eq!(
+      vec!["function `foo` takes at least 1 argument(s) but 0 were supplied"],
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn type_check_pass_function_parameters() {
+    let diagnostics = check_source(
+      "namespace foo {
+        extern foo(a: bool) ~ bool;
+        fn bar(a: bool, a: bool) ~ void { foo(a); }
+      }",
+    );
+
+    // Only the duplicate parameter is reported.
+    assert_eq!(
+      vec!["parameter `a` of `bar` is bound more than once"],
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn type_check_pass_return_kind() {
+    let diagnostics = check_source(
+      "namespace foo {
+        fn foo() ~ bool { return true; return; }
+      }",
+    );
+
+    // Only the return statement without a value is reported.
+    assert_eq!(
+      vec!["mismatched kinds for returned value: expected `bool`, found `void`"],
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn type_check_pass_int_literal() {
+    let node_ids = node::NodeIdAllocator::new();
+
+    let mut type_check_pass = TypeCheckPass::new();
+
+    let make_int_literal = |value: &str| {
+      node::AnyExprNode::Literal(node::AnyLiteralNode::IntLiteral(node::IntLiteral {
+        id: node_ids.allocate(),
+        span: span::Span::default(),
+        value: String::from(value),
+      }))
+    };
+
+    let i8_kind = node::AnyKindNode::IntKind(int_kind::IntKind {
+      size: int_kind::IntSize::Signed8,
+    });
+
+    assert_eq!(
+      Some(i8_kind.clone()),
+      type_check_pass.infer_expr_kind_as(&make_int_literal("127"), Some(&i8_kind))
+    );
+
+    assert_eq!(true, type_check_pass.get_diagnostics().is_empty());
+
+    // Without an expected integer kind, literals are `i32`.
+    assert_eq!(
+      Some(node::AnyKindNode::IntKind(int_kind::IntKind {
+        size: int_kind::IntSize::Signed32,
+      })),
+      type_check_pass.infer_expr_kind(&make_int_literal("128"))
+    );
+
+    assert_eq!(true, type_check_pass.get_diagnostics().is_empty());
+    type_check_pass.infer_expr_kind_as(&make_int_literal("128"), Some(&i8_kind));
+    assert_eq!(1, type_check_pass.get_diagnostics().len());
+  }
+
+  #[test]
+  fn type_check_pass_call_argument_kind() {
+    let diagnostics = check_source(
+      "namespace foo {
+        extern foo(a: i32) ~ bool;
+        fn f() ~ void { foo(true); }
+      }",
+    );
+
+    assert_eq!(
+      vec!["mismatched kinds for argument `a` of `foo`: expected `i32`, found `bool`"],
+      diagnostics
+    );
+  }
+
+  fn check_source(source: &str) -> Vec<String> {
+    let mut namespace = crate::parser::parse_source(source).unwrap();
+
+    // Calls to variants are only known as such once resolved.
+    let mut name_resolution_pass = crate::name_resolution_pass::NameResolutionPass::new();
+
+    assert_eq!(
+      true,
+      name_resolution_pass.visit_namespace(&mut namespace).is_ok()
+    );
+
+    let mut type_check_pass = TypeCheckPass::new();
+
+    assert_eq!(true, type_check_pass.visit(&namespace).is_ok());
+
+    type_check_pass
+      .get_diagnostics()
+      .into_iter()
+      .map(|diagnostic| diagnostic.message)
+      .collect()
+  }
+
+  #[test]
+  fn type_check_pass_structs() {
+    let diagnostics = check_source(
+      "namespace foo {
+        struct Point { x: i32, y: bool }
+        fn f(p: &mut Point) ~ i32 {
+          let mut a = Point { y: true, x: 1 };
+          let b: i8 = 2;
+          a.x = p.x;
+          p.y = a.y;
+          return a.x;
+        }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+
+    let diagnostics = check_source(
+      "namespace foo {
+        struct Point { x: i32, y: bool }
+        fn f(p: Point) ~ void {
+          let a: bool = p.x;
+          let b = Point { x: 1, x: 2, z: 3 };
+          p.z = 1;
+          p.x.y = 1;
+          f(p) = 1;
+          p.y = 1;
+        }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        "mismatched kinds for binding `a`: expected `bool`, found `i32`",
+        "field `x` is specified more than once",
+        "struct `Point` has no field named `z`",
+        "missing field(s) `y` in literal of struct `Point`",
+        "no field `z` on kind `Point`",
+        "cannot access field `y` of non-struct kind `i32`",
+        "invalid assignment target",
+        "mismatched kinds for assigned value: expected `bool`, found `i32`",
+      ],
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn type_check_pass_struct_decls() {
+    let diagnostics = check_source(
+      "namespace foo {
+        struct A { b: B, c: &A }
+        struct B { a: A, v: void }
+        struct C { c: &C }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        "recursive struct `A` has infinite size",
+        "field `v` of struct `B` cannot be of kind `void`",
+        "recursive struct `B` has infinite size",
+      ],
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn type_check_pass_enums() {
+    let diagnostics = check_source(
+      "namespace foo {
+        enum Shape { Circle(i32), Rect(i32, i8), Empty }
+        fn area(s: Shape) ~ i32 {
+          let t = Shape::Rect(1, 2);
+          return match s {
+            Shape::Circle(r) => r,
+            Shape::Rect(w, 0) => w,
+            Shape::Rect(_, h) => 1,
+            Shape::Empty => { f(t); },
+          };
+        }
+        fn f(s: Shape) ~ void {
+          match s { Shape::Empty => {}, _ => {} }
+        }
+      }",
+    );
+
+    assert_eq!(
+      vec!["mismatched kinds for match arm: expected `i32`, found `void`"],
+      diagnostics
+    );
+
+    let diagnostics = check_source(
+      "namespace foo {
+        enum A { B(A), C(&A) }
+        enum E { V(i32, void) }
+        fn f(a: A) ~ i32 {
+          let b = A::C(true);
+          let c = E::V(1);
+          return match a {
+            A::B(x, x) => 1,
+            A::C(true) => 2,
+            _ => false,
+          };
+        }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        "recursive enum `A` has infinite size",
+        "field of variant `E::V` cannot be of kind `void`",
+        "mismatched kinds for field 0 of `A::C`: expected `&A`, found `bool`",
+        "variant `E::V` takes 2 field(s) but 1 were supplied",
+        "variant `A::B` has 1 field(s) but the pattern has 2",
+        "identifier `x` is bound more than once in the same pattern",
+        "mismatched kinds for pattern: expected `&A`, found `bool`",
+        "mismatched kinds for match arm: expected `i32`, found `bool`",
+      ],
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn type_check_pass_arrays() {
+    let diagnostics = check_source(
+      "namespace foo {
+        struct Grid { cells: [[bool; 3]; 3] }
+        fn first(values: [i32]) ~ i32 {
+          return values[0];
+        }
+        fn f(g: Grid) ~ i64 {
+          let mut a = [1, 2, 3];
+          let b: [i8; 2] = [4, 5];
+          a[2] = first(a);
+          g.cells[1][2] = true;
+          return b.len();
+        }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+
+    let diagnostics = check_source(
+      "namespace foo {
+        struct Chain { links: [Chain; 2] }
+        fn first(values: [i8]) ~ i8 {
+          return values[true];
+        }
+        fn f(i: i64) ~ void {
+          let a = [1, false];
+          let b: [i32; 3] = [1, 2];
+          let c = [];
+          let d = b[3];
+          first(b);
+          i[0];
+          i.len();
+        }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        "recursive struct `Chain` has infinite size",
+        "arrays and slices cannot be indexed by a value of kind `bool`",
+        "mismatched kinds for array element: expected `i32`, found `bool`",
+        "mismatched kinds for binding `b`: expected `[i32; 3]`, found `[i32; 2]`",
+        "cannot infer the kind of the elements of an empty array",
+        "index out of bounds: the length is 3 but the index is 3",
+        "mismatched kinds for argument `values` of `first`: expected `[i8]`, found `[i32; 3]`",
+        "cannot index into a value of kind `i64`",
+        "cannot take the length of a value of kind `i64`",
+      ],
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn type_check_pass_references() {
+    let diagnostics = check_source(
+      "namespace foo {
+        struct Point { x: i32, y: bool }
+        struct Line { start: &mut Point, end: &Point }
+        fn get(p: &Point) ~ i32 {
+          return p.x;
+        }
+        fn f(l: &mut Line, values: &mut [i8; 2]) ~ &i32 {
+          let mut a = Point { x: 1, y: true };
+          let b: &mut Point = &mut a;
+          let c: &i64 = &5;
+          let d = *c;
+          *b = Point { x: get(b), y: false };
+          b.x = *&mut 2;
+          l.start.y = l.end.y;
+          values[values.len()] = 0;
+          return &l.start.x;
+        }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+
+    let diagnostics = check_source(
+      "namespace foo {
+        struct Point { x: i32, y: bool }
+        struct Line { start: &mut Point, end: &Point }
+        fn f(p: &Point, l: &Line, values: &[i8], v: &void) ~ void {
+          let a: &mut Point = p;
+          let b: &i32 = &true;
+          let c = &mut p.x;
+          let d = *l.start.x;
+          let e = *v;
+          let g = &f(p, l, values, v);
+          p.x = 1;
+          *p = Point { x: 1, y: true };
+          l.start.y = true;
+          l.end.y = true;
+          values[0] = 1;
+        }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        "mismatched kinds for binding `a`: expected `&mut Point`, found `&Point`",
+        "mismatched kinds for binding `b`: expected `&i32`, found `&bool`",
+        "cannot borrow as mutable through an immutable reference of kind `&Point`",
+        "cannot dereference a value of kind `i32`",
+        "cannot dereference a value of kind `&void`",
+        "cannot borrow a `void` value",
+        "cannot assign through an immutable reference of kind `&Point`",
+        "cannot assign through an immutable reference of kind `&Point`",
+        "cannot assign through an immutable reference of kind `&Line`",
+        "cannot assign through an immutable reference of kind `&Point`",
+        "cannot assign through an immutable reference of kind `&[i8]`",
+      ],
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn type_check_pass_immutable_bindings() {
+    let diagnostics = check_source(
+      "namespace foo {
+        struct Point { x: i32, y: i32 }
+        fn f(p: &mut Point, values: [i32]) ~ void {
+          let a = 1;
+          let mut b = Point { x: 1, y: 2 };
+          let c = Point { x: 1, y: 2 };
+          let d = [1, 2];
+          let e = p;
+          a = 2;
+          b.x = 3;
+          c.y = 4;
+          d[0] = 5;
+          e.x = 6;
+          values[0] = 7;
+          let a = 8;
+          let mut a = a;
+          a = 9;
+          if true {
+            let b = 10;
+            b = 11;
+          }
+          b = Point { x: 12, y: 13 };
+        }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        "cannot assign to `a`, as it is not declared with `mut`",
+        "cannot assign to `c`, as it is not declared with `mut`",
+        "cannot assign to `d`, as it is not declared with `mut`",
+        "cannot assign to `b`, as it is not declared with `mut`",
+      ],
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn type_check_pass_immutable_borrows() {
+    let diagnostics = check_source(
+      "namespace foo {
+        struct Point { x: i32, y: i32 }
+        fn f(p: &mut Point) ~ void {
+          let a = 1;
+          let mut b = Point { x: 1, y: 2 };
+          let c = Point { x: 1, y: 2 };
+          let d = [1, 2];
+          let e = p;
+          let g = &mut a;
+          let h = &mut b.x;
+          let i = &mut c.y;
+          let j = &mut d[0];
+          let k = &mut e.x;
+          let l = &a;
+        }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        "cannot borrow `a` as mutable, as it is not declared with `mut`",
+        "cannot borrow `c` as mutable, as it is not declared with `mut`",
+        "cannot borrow `d` as mutable, as it is not declared with `mut`",
+      ],
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn type_check_pass_immutable_iterator() {
+    let diagnostics = check_source(
+      "namespace foo {
+        fn f() ~ void {
+          for i in 0..2 {
+            i = 1;
+            let a = &mut i;
+            let b = &i;
+          }
+        }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        "cannot assign to `i`, as it is not declared with `mut`",
+        "cannot borrow `i` as mutable, as it is not declared with `mut`",
+      ],
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn type_check_pass_generics() {
+    let diagnostics = check_source(
+      "namespace foo {
+        struct Point { x: i32 }
+        fn pick<T>(a: T, b: T) ~ T {
+          let c: T = b;
+          return a;
+        }
+        fn first<T>(values: [T]) ~ &T {
+          return &values[0];
+        }
+        fn wrap<U>(a: &U) ~ &U {
+          return pick(a, a);
+        }
+        fn f(p: Point, values: [i64; 2]) ~ i64 {
+          let a = pick(1, 2);
+          let b: i64 = pick(values[0], 2);
+          let c: Point = pick(p, p);
+          let d: &i64 = first(values);
+          let e: &i64 = wrap(d);
+          return b;
+        }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+
+    let diagnostics = check_source(
+      "namespace foo {
+        fn pick<T>(a: T, b: T) ~ T {
+          return a.x;
+        }
+        fn none<T>() ~ i32 {
+          return 1;
+        }
+        fn twice<T, T>() ~ void {}
+        extern ext<T>() ~ void;
+        fn f(a: i32, b: bool) ~ void {
+          let c = pick(a, b);
+          let d = none();
+          let e = pick(f(a, b), f(a, b));
+          let g: bool = pick(a, a);
+        }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        "cannot access field `x` of non-struct kind `T`",
+        "generic parameter `T` of `twice` is declared more than once",
+        "extern `ext` cannot be generic",
+        "mismatched kinds for argument `b` of `pick`: expected `i32`, found `bool`",
+        "cannot infer the kind of generic parameter `T` of `none`",
+        "cannot use `void` for generic parameter `T` of `pick`",
+        "mismatched kinds for binding `g`: expected `bool`, found `i32`",
+      ],
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn type_check_pass_traits() {
+    let diagnostics = check_source(
+      "namespace foo {
+        trait Shape {
+          fn area(self: &Self) ~ i32;
+          fn grow(self: &mut Self, by: i32) ~ void;
+        }
+        struct Square { side: i32 }
+        impl Shape for Square {
+          fn area(self: &Self) ~ i32 { return self.side; }
+          fn grow(self: &mut Square, by: i32) ~ void { self.side = by; }
+        }
+        fn measure<T: Shape>(shape: &T) ~ i32 {
+          let d: &dyn Shape = shape;
+          return Shape::area(shape);
+        }
+        fn total(shape: &dyn Shape) ~ i32 {
+          return measure(shape);
+        }
+        fn f() ~ i32 {
+          let mut square = Square { side: 1 };
+          Shape::grow(&mut square, 2);
+          let shape: &dyn Shape = &square;
+          let a: i32 = Shape::area(shape);
+          return total(&square);
+        }
+      }",
+    );
+
+    assert_eq!(true, diagnostics.is_empty());
+
+    let diagnostics = check_source(
+      "namespace foo {
+        trait Shape {
+          fn area(self: &Self) ~ i32;
+          fn scale(self: &Self, other: &Self) ~ void;
+        }
+        trait Empty {
+          fn make() ~ i32;
+          fn pick<T>(self: &Self) ~ void;
+        }
+        struct Square { side: i32 }
+        struct Circle { radius: i32 }
+        impl Shape for Square {
+          fn area(self: &Self) ~ bool { return true; }
+          fn perimeter(self: &Self) ~ i32 { return 1; }
+        }
+        impl Shape for void {}
+        fn measure<T: Shape>(shape: &T) ~ i32 {
+          return 1;
+        }
+        fn f(shape: dyn Shape) ~ void {
+          let circle = Circle { radius: 1 };
+          measure(&circle);
+          Shape::area(&circle);
+          let d: &dyn Shape = &circle;
+          let e = *d;
+        }
+      }",
+    );
+
+    assert_eq!(
+      vec![
+        "`Self` may only be used as the first parameter of method `scale` of trait `Shape`",
+        "the first parameter of method `make` of trait `Empty` must be of kind `&Self` or \
+         `&mut Self`",
+        "method `pick` of trait `Empty` cannot be generic",
+        "method `area` of implementation for `Square` does not match its declaration in trait \
+         `Shape`",
+        "missing method `scale` of trait `Shape` in implementation for `Square`",
+        "method `perimeter` is not a member of trait `Shape`",
+        "cannot implement trait `Shape` for `void`",
+        "trait object `dyn Shape` must be behind a reference",
+        "kind `Circle` does not implement trait `Shape`",
+        "kind `Circle` does not implement trait `Shape`",
+        "mismatched kinds for binding `d`: expected `&dyn Shape`, found `&Circle`",
+        "cannot dereference a value of kind `&dyn Shape`",
+      ],
+      diagnostics
+    );
+  }
+}
@@ -0,0 +1,81 @@
+use crate::{node, pass, span};
+
+// A single name brought into scope by a `use` declaration,
+// optionally under another name (ex. `d as e`).
+#[derive(Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UseItem {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  pub path: node::Path,
+  pub alias: Option<String>,
+}
+
+impl UseItem {
+  // The name under which the item is brought into scope.
+  pub fn get_name(&self) -> &str {
+    match &self.alias {
+      Some(alias) => alias,
+      None => self.path.segments.last().unwrap(),
+    }
+  }
+}
+
+impl node::Node for UseItem {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_children(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    vec![&self.path]
+  }
+}
+
+// Brings names from other namespaces into the scope of the
+// namespace containing it, either one at a time (ex. `use a::b;`)
+// or as a group sharing a common prefix (ex. `use a::{b, c};`).
+#[derive(Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UseDecl {
+  pub id: node::NodeId,
+  pub span: span::Span,
+  // The segments shared by all items of a group. Empty unless the
+  // declaration is a group.
+  pub prefix: Vec<String>,
+  pub items: Vec<UseItem>,
+  pub is_group: bool,
+}
+
+impl UseDecl {
+  // The full path of the given item, including the prefix of the
+  // group it belongs to (if any).
+  pub fn get_item_path(&self, item: &UseItem) -> Vec<String> {
+    let mut path = self.prefix.clone();
+
+    path.extend(item.path.segments.iter().cloned());
+
+    path
+  }
+}
+
+impl node::Node for UseDecl {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+    pass.visit_use_decl(self)
+  }
+
+  fn get_span(&self) -> Option<span::Span> {
+    Some(self.span)
+  }
+
+  fn get_children(&self) -> Vec<&dyn node::Node> {
+    self
+      .items
+      .iter()
+      .map(|item| item as &dyn node::Node)
+      .collect()
+  }
+}
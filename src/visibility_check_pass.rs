@@ -0,0 +1,160 @@
+use crate::{diagnostic, external, function, namespace, pass};
+
+// Ensures that externs which refer to functions defined by other
+// namespaces (compiled alongside the visited one) only refer to
+// public functions, since private functions are not visible
+// outside of their namespace. Externs refer to functions by their
+// symbol, so only functions which opt out of name mangling (ex.
+// with `#[no_mangle]`) may be referred to.
+pub struct VisibilityCheckPass<'a> {
+  diagnostics: Vec<diagnostic::Diagnostic>,
+  // All namespaces being compiled, including the visited one.
+  namespaces: &'a [namespace::Namespace],
+  // The outermost namespace being visited, which is only compared
+  // by address. Ids are allocated per tree, so they do not tell
+  // namespaces of different trees apart.
+  namespace: Option<*const namespace::Namespace>,
+  // The number of namespaces enclosing the node being visited.
+  namespace_depth: usize,
+}
+
+impl<'a> VisibilityCheckPass<'a> {
+  pub fn new(namespaces: &'a [namespace::Namespace]) -> Self {
+    Self {
+      diagnostics: vec![],
+      namespaces,
+      namespace: None,
+      namespace_depth: 0,
+    }
+  }
+}
+
+// Find the function exported under the given symbol within the
+// given namespace, or those nested in it, along with the namespace
+// which directly contains it.
+fn find_exported_function<'a>(
+  namespace: &'a namespace::Namespace,
+  symbol: &str,
+) -> Option<(&'a namespace::Namespace, &'a function::Function)> {
+  namespace
+    .symbol_table
+    .values()
+    .find_map(|top_level_node| match top_level_node {
+      namespace::TopLevelNode::Function(function)
+        if function.find_export_name() == Some(symbol) =>
+      {
+        Some((namespace, function))
+      }
+      namespace::TopLevelNode::Namespace(namespace) => find_exported_function(namespace, symbol),
+      _ => None,
+    })
+}
+
+impl pass::Pass<'_> for VisibilityCheckPass<'_> {
+  fn get_diagnostics(&self) -> Vec<diagnostic::Diagnostic> {
+    self.diagnostics.clone()
+  }
+
+  fn visit_namespace(&mut self, namespace: &namespace::Namespace) -> pass::PassResult {
+    if self.namespace_depth == 0 {
+      self.namespace = Some(namespace);
+    }
+
+    self.namespace_depth += 1;
+
+    let result = self.visit_children(namespace);
+
+    self.namespace_depth -= 1;
+
+    result
+  }
+
+  fn visit_external(&mut self, external: &external::External) -> pass::PassResult {
+    let name = &external.prototype.name;
+
+    for namespace in self.namespaces {
+      if self.namespace == Some(namespace as *const _) {
+        continue;
+      }
+
+      if let Some((namespace, function)) = find_exported_function(namespace, name) {
+        if !function.is_public {
+          self.diagnostics.push(diagnostic::Diagnostic {
+            message: format!(
+              "function `{}` is private to namespace `{}`",
+              name, namespace.name
+            ),
+            severity: diagnostic::DiagnosticSeverity::Error,
+            span: Some(external.span),
+            notes: vec![],
+          });
+        }
+      }
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{parser, pass::Pass};
+
+  fn parse_namespace(source: &str) -> namespace::Namespace {
+    parser::parse_source(source).unwrap()
+  }
+
+  #[test]
+  fn visibility_check_pass_private_function() {
+    let namespaces = [
+      parse_namespace(
+        "namespace foo { extern bar() ~ void; extern baz() ~ void; extern quux() ~ void; }",
+      ),
+      // Mangled functions (ex. `quux`) cannot be referred to by
+      // externs at all.
+      parse_namespace(
+        "namespace qux { #[no_mangle] fn bar() ~ void {} #[no_mangle] pub fn baz() ~ void {} \
+         fn quux() ~ void {} }",
+      ),
+    ];
+
+    let mut visibility_check_pass = VisibilityCheckPass::new(&namespaces);
+
+    assert_eq!(
+      true,
+      visibility_check_pass
+        .visit_namespace(&namespaces[0])
+        .is_ok()
+    );
+
+    let diagnostics = visibility_check_pass.get_diagnostics();
+
+    assert_eq!(1, diagnostics.len());
+
+    assert_eq!(
+      "function `bar` is private to namespace `qux`",
+      diagnostics[0].message
+    );
+  }
+
+  #[test]
+  fn visibility_check_pass_same_namespace() {
+    // The definition takes the place of the extern, and is visible
+    // within its own namespace regardless.
+    let namespaces = [parse_namespace(
+      "namespace foo { extern bar() ~ void; fn bar() ~ void {} }",
+    )];
+
+    let mut visibility_check_pass = VisibilityCheckPass::new(&namespaces);
+
+    assert_eq!(
+      true,
+      visibility_check_pass
+        .visit_namespace(&namespaces[0])
+        .is_ok()
+    );
+
+    assert_eq!(true, visibility_check_pass.get_diagnostics().is_empty());
+  }
+}
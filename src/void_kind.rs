@@ -2,12 +2,13 @@ use crate::node;
 use crate::pass;
 
 #[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VoidKind {
   //
 }
 
 impl node::Node for VoidKind {
-  fn accept(&mut self, pass: &mut dyn pass::Pass) -> pass::PassResult {
+  fn accept(&self, pass: &mut dyn pass::Pass) -> pass::PassResult {
     pass.visit_void_kind(self)?;
 
     Ok(())